@@ -0,0 +1,8 @@
+CREATE TABLE notification_events (
+    id BIGSERIAL PRIMARY KEY,
+    notification_type VARCHAR NOT NULL,
+    data JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+
+CREATE INDEX idx_notification_events_created_at ON notification_events (created_at);
@@ -0,0 +1 @@
+ALTER TABLE notification_events DROP COLUMN IF EXISTS list;
@@ -0,0 +1 @@
+ALTER TABLE notification_events ADD COLUMN list VARCHAR;
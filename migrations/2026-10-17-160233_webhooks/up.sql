@@ -0,0 +1,33 @@
+CREATE TYPE webhook_delivery_status AS ENUM (
+    'Pending',
+    'Succeeded',
+    'Failed'
+);
+
+CREATE TABLE webhooks (
+    id UUID NOT NULL DEFAULT gen_random_uuid() PRIMARY KEY,
+    url VARCHAR NOT NULL,
+    secret VARCHAR NOT NULL,
+    event_types TEXT[] NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    consecutive_failures INTEGER NOT NULL DEFAULT 0,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL ON UPDATE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+
+CREATE TABLE webhook_deliveries (
+    id UUID NOT NULL DEFAULT gen_random_uuid() PRIMARY KEY,
+    webhook_id UUID NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    event_id BIGINT NOT NULL REFERENCES notification_events(id) ON DELETE CASCADE,
+    status webhook_delivery_status NOT NULL DEFAULT 'Pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    response_status INTEGER,
+    error VARCHAR,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+
+CREATE INDEX idx_webhook_deliveries_webhook_created_at ON webhook_deliveries (webhook_id, created_at DESC);
+CREATE INDEX idx_webhook_deliveries_pending ON webhook_deliveries (next_attempt_at) WHERE status = 'Pending';
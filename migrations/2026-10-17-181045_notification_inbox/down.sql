@@ -0,0 +1,11 @@
+DROP TABLE IF EXISTS notification_preferences;
+
+DROP INDEX IF EXISTS idx_notifications_user_unread;
+DROP INDEX IF EXISTS idx_notifications_user_created_at;
+
+ALTER TABLE notifications
+    DROP COLUMN IF EXISTS read_at,
+    DROP COLUMN IF EXISTS payload,
+    DROP COLUMN IF EXISTS category;
+
+DROP TYPE IF EXISTS notification_category;
@@ -0,0 +1,23 @@
+CREATE TYPE notification_category AS ENUM (
+    'General',
+    'SubmissionStatus',
+    'ClanInvite',
+    'MergeRequest',
+    'Bounty'
+);
+
+ALTER TABLE notifications
+    ADD COLUMN category notification_category NOT NULL DEFAULT 'General',
+    ADD COLUMN payload JSONB,
+    ADD COLUMN read_at TIMESTAMPTZ;
+
+CREATE INDEX idx_notifications_user_created_at ON notifications (user_id, created_at DESC);
+CREATE INDEX idx_notifications_user_unread ON notifications (user_id) WHERE read_at IS NULL;
+
+CREATE TABLE notification_preferences (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    category notification_category NOT NULL,
+    muted BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    PRIMARY KEY (user_id, category)
+);
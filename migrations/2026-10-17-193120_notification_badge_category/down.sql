@@ -0,0 +1,22 @@
+DELETE FROM notifications WHERE category = 'Badge';
+DELETE FROM notification_preferences WHERE category = 'Badge';
+
+ALTER TYPE notification_category RENAME TO notification_category_old;
+
+CREATE TYPE notification_category AS ENUM (
+    'General',
+    'SubmissionStatus',
+    'ClanInvite',
+    'MergeRequest',
+    'Bounty'
+);
+
+ALTER TABLE notifications
+    ALTER COLUMN category DROP DEFAULT,
+    ALTER COLUMN category TYPE notification_category USING category::text::notification_category,
+    ALTER COLUMN category SET DEFAULT 'General';
+
+ALTER TABLE notification_preferences
+    ALTER COLUMN category TYPE notification_category USING category::text::notification_category;
+
+DROP TYPE notification_category_old;
@@ -0,0 +1 @@
+ALTER TYPE notification_category ADD VALUE IF NOT EXISTS 'Badge';
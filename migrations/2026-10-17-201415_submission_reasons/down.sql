@@ -0,0 +1,67 @@
+DROP MATERIALIZED VIEW IF EXISTS aredl.submission_reason_stats;
+DROP MATERIALIZED VIEW IF EXISTS arepl.submission_reason_stats;
+
+CREATE OR REPLACE FUNCTION aredl.submission_log_history()
+RETURNS TRIGGER AS
+$$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        INSERT INTO aredl.submission_history (id, submission_id, status, user_notes, reviewer_id, reviewer_notes, private_reviewer_notes, locked, mobile, custom_copy_id, video_url, raw_url, mod_menu, priority, timestamp)
+        VALUES (uuid_generate_v4(), NEW.id, NEW.status, NEW.user_notes, NEW.reviewer_id, NEW.reviewer_notes, NEW.private_reviewer_notes, NEW.locked, NEW.mobile, NEW.custom_copy_id, NEW.video_url, NEW.raw_url, NEW.mod_menu, NEW.priority, CLOCK_TIMESTAMP());
+        RETURN NEW;
+    END IF;
+
+    IF TG_OP = 'UPDATE' THEN
+        IF NEW IS NOT DISTINCT FROM OLD THEN
+            RETURN NEW;
+        END IF;
+
+        IF aredl.submission_is_only_claim_toggle(OLD, NEW) THEN
+            RETURN NEW;
+        END IF;
+
+        INSERT INTO aredl.submission_history (id, submission_id, status, user_notes, reviewer_id, reviewer_notes, private_reviewer_notes, locked, mobile, custom_copy_id, video_url, raw_url, mod_menu, priority, timestamp)
+        VALUES (uuid_generate_v4(), NEW.id, NEW.status, NEW.user_notes, NEW.reviewer_id, NEW.reviewer_notes, NEW.private_reviewer_notes, NEW.locked, NEW.mobile, NEW.custom_copy_id, NEW.video_url, NEW.raw_url, NEW.mod_menu, NEW.priority, CLOCK_TIMESTAMP());
+
+        RETURN NEW;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION arepl.submission_log_history()
+RETURNS TRIGGER AS
+$$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        INSERT INTO arepl.submission_history (id, submission_id, status, user_notes, reviewer_id, reviewer_notes, private_reviewer_notes, locked, mobile, custom_copy_id, video_url, raw_url, mod_menu, priority, completion_time, timestamp)
+        VALUES (uuid_generate_v4(), NEW.id, NEW.status, NEW.user_notes, NEW.reviewer_id, NEW.reviewer_notes, NEW.private_reviewer_notes, NEW.locked, NEW.mobile, NEW.custom_copy_id, NEW.video_url, NEW.raw_url, NEW.mod_menu, NEW.priority, NEW.completion_time, CLOCK_TIMESTAMP());
+        RETURN NEW;
+    END IF;
+
+    IF TG_OP = 'UPDATE' THEN
+        IF NEW IS NOT DISTINCT FROM OLD THEN
+            RETURN NEW;
+        END IF;
+
+        IF arepl.submission_is_only_claim_toggle(OLD, NEW) THEN
+            RETURN NEW;
+        END IF;
+
+        INSERT INTO arepl.submission_history (id, submission_id, status, user_notes, reviewer_id, reviewer_notes, private_reviewer_notes, locked, mobile, custom_copy_id, video_url, raw_url, mod_menu, priority, completion_time, timestamp)
+        VALUES (uuid_generate_v4(), NEW.id, NEW.status, NEW.user_notes, NEW.reviewer_id, NEW.reviewer_notes, NEW.private_reviewer_notes, NEW.locked, NEW.mobile, NEW.custom_copy_id, NEW.video_url, NEW.raw_url, NEW.mod_menu, NEW.priority, NEW.completion_time, CLOCK_TIMESTAMP());
+
+        RETURN NEW;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+ALTER TABLE aredl.submissions DROP COLUMN IF EXISTS reason_codes;
+ALTER TABLE aredl.submission_history DROP COLUMN IF EXISTS reason_codes;
+ALTER TABLE arepl.submissions DROP COLUMN IF EXISTS reason_codes;
+ALTER TABLE arepl.submission_history DROP COLUMN IF EXISTS reason_codes;
+
+DROP TABLE IF EXISTS submission_reasons;
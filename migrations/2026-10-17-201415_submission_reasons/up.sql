@@ -0,0 +1,111 @@
+CREATE TABLE submission_reasons (
+    code VARCHAR PRIMARY KEY,
+    label VARCHAR NOT NULL,
+    translations JSONB NOT NULL DEFAULT '{}'::jsonb,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+
+INSERT INTO submission_reasons (code, label) VALUES
+    ('missing_clicks', 'Missing clicks'),
+    ('no_endscreen', 'No endscreen'),
+    ('wrong_copy', 'Wrong copy of the level'),
+    ('incomplete_run', 'Incomplete run'),
+    ('missing_raw_footage', 'Missing raw footage'),
+    ('insufficient_raw_footage', 'Raw footage does not meet the requirements'),
+    ('disallowed_hack', 'Disallowed hack or mod menu'),
+    ('video_unavailable', 'Video unavailable');
+
+ALTER TABLE aredl.submissions ADD COLUMN reason_codes TEXT[] NOT NULL DEFAULT '{}';
+ALTER TABLE aredl.submission_history ADD COLUMN reason_codes TEXT[] NOT NULL DEFAULT '{}';
+ALTER TABLE arepl.submissions ADD COLUMN reason_codes TEXT[] NOT NULL DEFAULT '{}';
+ALTER TABLE arepl.submission_history ADD COLUMN reason_codes TEXT[] NOT NULL DEFAULT '{}';
+
+CREATE OR REPLACE FUNCTION aredl.submission_log_history()
+RETURNS TRIGGER AS
+$$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        INSERT INTO aredl.submission_history (id, submission_id, status, user_notes, reviewer_id, reviewer_notes, private_reviewer_notes, locked, mobile, custom_copy_id, video_url, raw_url, mod_menu, priority, reason_codes, timestamp)
+        VALUES (uuid_generate_v4(), NEW.id, NEW.status, NEW.user_notes, NEW.reviewer_id, NEW.reviewer_notes, NEW.private_reviewer_notes, NEW.locked, NEW.mobile, NEW.custom_copy_id, NEW.video_url, NEW.raw_url, NEW.mod_menu, NEW.priority, NEW.reason_codes, CLOCK_TIMESTAMP());
+        RETURN NEW;
+    END IF;
+
+    IF TG_OP = 'UPDATE' THEN
+        IF NEW IS NOT DISTINCT FROM OLD THEN
+            RETURN NEW;
+        END IF;
+
+        IF aredl.submission_is_only_claim_toggle(OLD, NEW) THEN
+            RETURN NEW;
+        END IF;
+
+        INSERT INTO aredl.submission_history (id, submission_id, status, user_notes, reviewer_id, reviewer_notes, private_reviewer_notes, locked, mobile, custom_copy_id, video_url, raw_url, mod_menu, priority, reason_codes, timestamp)
+        VALUES (uuid_generate_v4(), NEW.id, NEW.status, NEW.user_notes, NEW.reviewer_id, NEW.reviewer_notes, NEW.private_reviewer_notes, NEW.locked, NEW.mobile, NEW.custom_copy_id, NEW.video_url, NEW.raw_url, NEW.mod_menu, NEW.priority, NEW.reason_codes, CLOCK_TIMESTAMP());
+
+        RETURN NEW;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION arepl.submission_log_history()
+RETURNS TRIGGER AS
+$$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        INSERT INTO arepl.submission_history (id, submission_id, status, user_notes, reviewer_id, reviewer_notes, private_reviewer_notes, locked, mobile, custom_copy_id, video_url, raw_url, mod_menu, priority, completion_time, reason_codes, timestamp)
+        VALUES (uuid_generate_v4(), NEW.id, NEW.status, NEW.user_notes, NEW.reviewer_id, NEW.reviewer_notes, NEW.private_reviewer_notes, NEW.locked, NEW.mobile, NEW.custom_copy_id, NEW.video_url, NEW.raw_url, NEW.mod_menu, NEW.priority, NEW.completion_time, NEW.reason_codes, CLOCK_TIMESTAMP());
+        RETURN NEW;
+    END IF;
+
+    IF TG_OP = 'UPDATE' THEN
+        IF NEW IS NOT DISTINCT FROM OLD THEN
+            RETURN NEW;
+        END IF;
+
+        IF arepl.submission_is_only_claim_toggle(OLD, NEW) THEN
+            RETURN NEW;
+        END IF;
+
+        INSERT INTO arepl.submission_history (id, submission_id, status, user_notes, reviewer_id, reviewer_notes, private_reviewer_notes, locked, mobile, custom_copy_id, video_url, raw_url, mod_menu, priority, completion_time, reason_codes, timestamp)
+        VALUES (uuid_generate_v4(), NEW.id, NEW.status, NEW.user_notes, NEW.reviewer_id, NEW.reviewer_notes, NEW.private_reviewer_notes, NEW.locked, NEW.mobile, NEW.custom_copy_id, NEW.video_url, NEW.raw_url, NEW.mod_menu, NEW.priority, NEW.completion_time, NEW.reason_codes, CLOCK_TIMESTAMP());
+
+        RETURN NEW;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE MATERIALIZED VIEW aredl.submission_reason_stats AS
+SELECT
+    DATE(h.timestamp) AS day,
+    h.reviewer_id,
+    reason.code AS reason_code,
+    COUNT(DISTINCT h.submission_id) FILTER (WHERE h.status = 'Denied') AS denied,
+    COUNT(DISTINCT h.submission_id) FILTER (WHERE h.status = 'UnderConsideration') AS under_consideration
+FROM aredl.submission_history h
+CROSS JOIN LATERAL UNNEST(h.reason_codes) AS reason(code)
+WHERE h.status IN ('Denied', 'UnderConsideration')
+GROUP BY DATE(h.timestamp), h.reviewer_id, reason.code;
+
+CREATE UNIQUE INDEX aredl_submission_reason_stats_idx
+    ON aredl.submission_reason_stats (day, reviewer_id, reason_code);
+
+CREATE MATERIALIZED VIEW arepl.submission_reason_stats AS
+SELECT
+    DATE(h.timestamp) AS day,
+    h.reviewer_id,
+    reason.code AS reason_code,
+    COUNT(DISTINCT h.submission_id) FILTER (WHERE h.status = 'Denied') AS denied,
+    COUNT(DISTINCT h.submission_id) FILTER (WHERE h.status = 'UnderConsideration') AS under_consideration
+FROM arepl.submission_history h
+CROSS JOIN LATERAL UNNEST(h.reason_codes) AS reason(code)
+WHERE h.status IN ('Denied', 'UnderConsideration')
+GROUP BY DATE(h.timestamp), h.reviewer_id, reason.code;
+
+CREATE UNIQUE INDEX arepl_submission_reason_stats_idx
+    ON arepl.submission_reason_stats (day, reviewer_id, reason_code);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS reviewer_conflicts;
+DROP TABLE IF EXISTS reviewer_conflict_settings;
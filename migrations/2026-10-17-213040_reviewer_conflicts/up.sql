@@ -0,0 +1,21 @@
+CREATE TABLE reviewer_conflict_settings (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    exclude_clanmates BOOLEAN NOT NULL DEFAULT TRUE,
+    exclude_flagged_users BOOLEAN NOT NULL DEFAULT TRUE,
+    exclude_level_creators BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO reviewer_conflict_settings DEFAULT VALUES;
+
+CREATE TABLE reviewer_conflicts (
+    reviewer_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (reviewer_id, user_id),
+    CHECK (reviewer_id <> user_id)
+);
+
+CREATE INDEX reviewer_conflicts_user_id_idx ON reviewer_conflicts (user_id);
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS submission_requirement_rules;
+DROP TYPE IF EXISTS submission_rule_level_status;
+DROP TYPE IF EXISTS submission_rule_list;
@@ -0,0 +1,31 @@
+CREATE TYPE submission_rule_list AS ENUM ('aredl', 'arepl');
+CREATE TYPE submission_rule_level_status AS ENUM ('Pending', 'MainList', 'Legacy', 'Removed');
+
+CREATE TABLE submission_requirement_rules (
+    id UUID NOT NULL DEFAULT gen_random_uuid() PRIMARY KEY,
+    list submission_rule_list NOT NULL,
+    description VARCHAR NOT NULL,
+    priority INTEGER NOT NULL DEFAULT 0,
+    level_status submission_rule_level_status,
+    min_position INTEGER,
+    max_position INTEGER,
+    tag VARCHAR,
+    mobile BOOLEAN,
+    requires_raw_footage BOOLEAN,
+    requires_mod_menu BOOLEAN,
+    requires_custom_copy BOOLEAN,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (min_position IS NULL OR max_position IS NULL OR min_position <= max_position)
+);
+
+CREATE INDEX submission_requirement_rules_list_idx ON submission_requirement_rules (list, priority DESC);
+
+-- reproduce the previously hardcoded requirements
+INSERT INTO submission_requirement_rules (list, description, priority, level_status, max_position, requires_raw_footage) VALUES
+    ('aredl', 'This level requires raw footage', 10, 'MainList', 400, TRUE),
+    ('aredl', 'Main list levels outside of the top 400 do not require raw footage', 0, 'MainList', NULL, FALSE);
+
+INSERT INTO submission_requirement_rules (list, description, requires_raw_footage) VALUES
+    ('arepl', 'Platformer submissions require raw footage', TRUE);
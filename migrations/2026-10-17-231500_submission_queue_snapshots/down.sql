@@ -0,0 +1,2 @@
+DROP TABLE aredl.submission_queue_snapshots;
+DROP TABLE arepl.submission_queue_snapshots;
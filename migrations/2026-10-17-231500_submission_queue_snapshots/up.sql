@@ -0,0 +1,17 @@
+CREATE TABLE aredl.submission_queue_snapshots (
+    taken_at TIMESTAMPTZ PRIMARY KEY DEFAULT now(),
+    regular_submissions_in_queue BIGINT NOT NULL,
+    regular_submissions_without_raw_in_queue BIGINT NOT NULL,
+    priority_submissions_in_queue BIGINT NOT NULL,
+    priority_submissions_without_raw_in_queue BIGINT NOT NULL,
+    uc_submissions BIGINT NOT NULL
+);
+
+CREATE TABLE arepl.submission_queue_snapshots (
+    taken_at TIMESTAMPTZ PRIMARY KEY DEFAULT now(),
+    regular_submissions_in_queue BIGINT NOT NULL,
+    regular_submissions_without_raw_in_queue BIGINT NOT NULL,
+    priority_submissions_in_queue BIGINT NOT NULL,
+    priority_submissions_without_raw_in_queue BIGINT NOT NULL,
+    uc_submissions BIGINT NOT NULL
+);
@@ -0,0 +1,4 @@
+DROP TABLE aredl.submission_closure_levels;
+DROP TABLE aredl.submission_closures;
+DROP TABLE arepl.submission_closure_levels;
+DROP TABLE arepl.submission_closures;
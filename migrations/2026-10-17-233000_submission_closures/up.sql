@@ -0,0 +1,43 @@
+CREATE TABLE aredl.submission_closures (
+    id UUID NOT NULL DEFAULT gen_random_uuid(),
+    reason TEXT NOT NULL,
+    starts_at TIMESTAMPTZ NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    ends_at TIMESTAMPTZ,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL ON UPDATE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    lifted_by UUID REFERENCES users(id) ON DELETE SET NULL ON UPDATE CASCADE,
+    lifted_at TIMESTAMPTZ,
+    applies_to_all BOOLEAN NOT NULL DEFAULT false,
+    PRIMARY KEY(id),
+    CHECK (ends_at IS NULL OR ends_at > starts_at)
+);
+
+CREATE TABLE aredl.submission_closure_levels (
+    closure_id UUID NOT NULL REFERENCES aredl.submission_closures(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    level_id UUID NOT NULL REFERENCES aredl.levels(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    PRIMARY KEY(closure_id, level_id)
+);
+
+CREATE INDEX aredl_submission_closure_levels_level_idx ON aredl.submission_closure_levels (level_id);
+
+CREATE TABLE arepl.submission_closures (
+    id UUID NOT NULL DEFAULT gen_random_uuid(),
+    reason TEXT NOT NULL,
+    starts_at TIMESTAMPTZ NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    ends_at TIMESTAMPTZ,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL ON UPDATE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    lifted_by UUID REFERENCES users(id) ON DELETE SET NULL ON UPDATE CASCADE,
+    lifted_at TIMESTAMPTZ,
+    applies_to_all BOOLEAN NOT NULL DEFAULT false,
+    PRIMARY KEY(id),
+    CHECK (ends_at IS NULL OR ends_at > starts_at)
+);
+
+CREATE TABLE arepl.submission_closure_levels (
+    closure_id UUID NOT NULL REFERENCES arepl.submission_closures(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    level_id UUID NOT NULL REFERENCES arepl.levels(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    PRIMARY KEY(closure_id, level_id)
+);
+
+CREATE INDEX arepl_submission_closure_levels_level_idx ON arepl.submission_closure_levels (level_id);
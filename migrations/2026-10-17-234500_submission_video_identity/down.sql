@@ -0,0 +1,84 @@
+CREATE OR REPLACE FUNCTION aredl.submission_sync_record()
+RETURNS TRIGGER AS
+$$
+BEGIN
+    IF NEW.status = 'Accepted' THEN
+        INSERT INTO aredl.records AS r (
+            level_id,
+            submitted_by,
+            mobile,
+            video_url,
+            submission_id
+        )
+        VALUES (
+            NEW.level_id,
+            NEW.submitted_by,
+            NEW.mobile,
+            NEW.video_url,
+            NEW.id
+        )
+        ON CONFLICT (level_id, submitted_by)
+        DO UPDATE SET
+            mobile = EXCLUDED.mobile,
+            video_url = EXCLUDED.video_url;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP INDEX aredl.aredl_records_video_identity_idx;
+DROP INDEX aredl.aredl_submissions_video_identity_idx;
+
+ALTER TABLE aredl.records
+    DROP COLUMN video_provider,
+    DROP COLUMN video_content_id;
+
+ALTER TABLE aredl.submissions
+    DROP COLUMN video_provider,
+    DROP COLUMN video_content_id,
+    DROP COLUMN video_reused;
+
+CREATE OR REPLACE FUNCTION arepl.submission_sync_record()
+RETURNS TRIGGER AS
+$$
+BEGIN
+    IF NEW.status = 'Accepted' THEN
+        INSERT INTO arepl.records AS r (
+            level_id,
+            submitted_by,
+            mobile,
+            video_url,
+            completion_time,
+            submission_id
+        )
+        VALUES (
+            NEW.level_id,
+            NEW.submitted_by,
+            NEW.mobile,
+            NEW.video_url,
+            NEW.completion_time,
+            NEW.id
+        )
+        ON CONFLICT (level_id, submitted_by)
+        DO UPDATE SET
+            mobile = EXCLUDED.mobile,
+            video_url = EXCLUDED.video_url,
+            completion_time = EXCLUDED.completion_time;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP INDEX arepl.arepl_records_video_identity_idx;
+DROP INDEX arepl.arepl_submissions_video_identity_idx;
+
+ALTER TABLE arepl.records
+    DROP COLUMN video_provider,
+    DROP COLUMN video_content_id;
+
+ALTER TABLE arepl.submissions
+    DROP COLUMN video_provider,
+    DROP COLUMN video_content_id,
+    DROP COLUMN video_reused;
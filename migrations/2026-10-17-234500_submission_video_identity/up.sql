@@ -0,0 +1,180 @@
+-- mirrors the URL parsing of the streaming providers, file hosts are identified the next time the video is edited
+CREATE FUNCTION pg_temp.identify_video(url TEXT, OUT provider TEXT, OUT content_id TEXT) AS
+$$
+BEGIN
+    content_id := substring(url FROM '(?:youtube\.com/(?:watch\?(?:.*&)?v=|shorts/|live/)|youtu\.be/)([A-Za-z0-9_-]{11})');
+    IF content_id IS NOT NULL THEN
+        provider := 'YouTube';
+        RETURN;
+    END IF;
+
+    content_id := coalesce(
+        substring(url FROM '^https?://(?:www\.)?vimeo\.com/([0-9]{1,20})(?:[/?#]|$)'),
+        substring(url FROM '^https?://player\.vimeo\.com/video/([0-9]{1,20})(?:[/?#]|$)')
+    );
+    IF content_id IS NOT NULL THEN
+        provider := 'Vimeo';
+        RETURN;
+    END IF;
+
+    content_id := substring(url FROM '^https?://(?:www\.)?twitch\.tv/(?:videos|[^/?#]+/(?:video|v))/([0-9]{1,30})(?:[/?#]|$)');
+    IF content_id IS NOT NULL THEN
+        provider := 'Twitch';
+        RETURN;
+    END IF;
+
+    content_id := substring(url FROM '^https?://(?:www\.|m\.)?bilibili\.com/video/([A-Za-z0-9]+)(?:[/?#]|$)');
+    IF content_id IS NOT NULL THEN
+        provider := 'BiliBili';
+        RETURN;
+    END IF;
+
+    content_id := substring(url FROM '^https?://(?:www\.)?medal\.tv/(?:[a-z]{2}/)?(?:games/[^/?#]+/)?clips/([A-Za-z0-9_-]{1,128})(?:[/?#]|$)');
+    IF content_id IS NOT NULL THEN
+        provider := 'Medal';
+        RETURN;
+    END IF;
+
+    content_id := substring(url FROM '^https?://(?:www\.)?streamable\.com/(?:[eo]/)?([A-Za-z0-9_-]{1,32})/?(?:[?#]|$)');
+    IF content_id IS NOT NULL THEN
+        provider := 'Streamable';
+    END IF;
+END;
+$$ LANGUAGE plpgsql;
+
+ALTER TABLE aredl.submissions
+    ADD COLUMN video_provider TEXT,
+    ADD COLUMN video_content_id TEXT,
+    ADD COLUMN video_reused BOOLEAN NOT NULL DEFAULT false;
+
+ALTER TABLE aredl.records
+    ADD COLUMN video_provider TEXT,
+    ADD COLUMN video_content_id TEXT;
+
+CREATE INDEX aredl_submissions_video_identity_idx ON aredl.submissions (video_provider, video_content_id);
+CREATE INDEX aredl_records_video_identity_idx ON aredl.records (video_provider, video_content_id);
+
+ALTER TABLE aredl.submissions DISABLE TRIGGER USER;
+UPDATE aredl.submissions
+SET (video_provider, video_content_id) = (SELECT provider, content_id FROM pg_temp.identify_video(video_url));
+UPDATE aredl.submissions s
+SET video_reused = true
+WHERE EXISTS (
+    SELECT 1 FROM aredl.submissions o
+    WHERE o.video_provider = s.video_provider
+        AND o.video_content_id = s.video_content_id
+        AND (o.submitted_by <> s.submitted_by OR o.level_id <> s.level_id)
+        AND o.status <> 'Denied'
+        AND o.created_at < s.created_at
+);
+ALTER TABLE aredl.submissions ENABLE TRIGGER USER;
+
+ALTER TABLE aredl.records DISABLE TRIGGER USER;
+UPDATE aredl.records
+SET (video_provider, video_content_id) = (SELECT provider, content_id FROM pg_temp.identify_video(video_url));
+ALTER TABLE aredl.records ENABLE TRIGGER USER;
+
+CREATE OR REPLACE FUNCTION aredl.submission_sync_record()
+RETURNS TRIGGER AS
+$$
+BEGIN
+    IF NEW.status = 'Accepted' THEN
+        INSERT INTO aredl.records AS r (
+            level_id,
+            submitted_by,
+            mobile,
+            video_url,
+            video_provider,
+            video_content_id,
+            submission_id
+        )
+        VALUES (
+            NEW.level_id,
+            NEW.submitted_by,
+            NEW.mobile,
+            NEW.video_url,
+            NEW.video_provider,
+            NEW.video_content_id,
+            NEW.id
+        )
+        ON CONFLICT (level_id, submitted_by)
+        DO UPDATE SET
+            mobile = EXCLUDED.mobile,
+            video_url = EXCLUDED.video_url,
+            video_provider = EXCLUDED.video_provider,
+            video_content_id = EXCLUDED.video_content_id;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+ALTER TABLE arepl.submissions
+    ADD COLUMN video_provider TEXT,
+    ADD COLUMN video_content_id TEXT,
+    ADD COLUMN video_reused BOOLEAN NOT NULL DEFAULT false;
+
+ALTER TABLE arepl.records
+    ADD COLUMN video_provider TEXT,
+    ADD COLUMN video_content_id TEXT;
+
+CREATE INDEX arepl_submissions_video_identity_idx ON arepl.submissions (video_provider, video_content_id);
+CREATE INDEX arepl_records_video_identity_idx ON arepl.records (video_provider, video_content_id);
+
+ALTER TABLE arepl.submissions DISABLE TRIGGER USER;
+UPDATE arepl.submissions
+SET (video_provider, video_content_id) = (SELECT provider, content_id FROM pg_temp.identify_video(video_url));
+UPDATE arepl.submissions s
+SET video_reused = true
+WHERE EXISTS (
+    SELECT 1 FROM arepl.submissions o
+    WHERE o.video_provider = s.video_provider
+        AND o.video_content_id = s.video_content_id
+        AND (o.submitted_by <> s.submitted_by OR o.level_id <> s.level_id)
+        AND o.status <> 'Denied'
+        AND o.created_at < s.created_at
+);
+ALTER TABLE arepl.submissions ENABLE TRIGGER USER;
+
+ALTER TABLE arepl.records DISABLE TRIGGER USER;
+UPDATE arepl.records
+SET (video_provider, video_content_id) = (SELECT provider, content_id FROM pg_temp.identify_video(video_url));
+ALTER TABLE arepl.records ENABLE TRIGGER USER;
+
+CREATE OR REPLACE FUNCTION arepl.submission_sync_record()
+RETURNS TRIGGER AS
+$$
+BEGIN
+    IF NEW.status = 'Accepted' THEN
+        INSERT INTO arepl.records AS r (
+            level_id,
+            submitted_by,
+            mobile,
+            video_url,
+            completion_time,
+            video_provider,
+            video_content_id,
+            submission_id
+        )
+        VALUES (
+            NEW.level_id,
+            NEW.submitted_by,
+            NEW.mobile,
+            NEW.video_url,
+            NEW.completion_time,
+            NEW.video_provider,
+            NEW.video_content_id,
+            NEW.id
+        )
+        ON CONFLICT (level_id, submitted_by)
+        DO UPDATE SET
+            mobile = EXCLUDED.mobile,
+            video_url = EXCLUDED.video_url,
+            completion_time = EXCLUDED.completion_time,
+            video_provider = EXCLUDED.video_provider,
+            video_content_id = EXCLUDED.video_content_id;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
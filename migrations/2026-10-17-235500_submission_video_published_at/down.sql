@@ -0,0 +1,15 @@
+DROP TRIGGER submission_log_history_upd ON arepl.submissions;
+CREATE TRIGGER submission_log_history_upd AFTER UPDATE ON arepl.submissions FOR EACH ROW EXECUTE FUNCTION arepl.submission_log_history();
+
+DROP TRIGGER submission_updated_at ON arepl.submissions;
+CREATE TRIGGER submission_updated_at BEFORE UPDATE ON arepl.submissions FOR EACH ROW EXECUTE FUNCTION arepl.submission_updated_at();
+
+DROP TRIGGER submission_log_history_upd ON aredl.submissions;
+CREATE TRIGGER submission_log_history_upd AFTER UPDATE ON aredl.submissions FOR EACH ROW EXECUTE FUNCTION aredl.submission_log_history();
+
+DROP TRIGGER submission_updated_at ON aredl.submissions;
+CREATE TRIGGER submission_updated_at BEFORE UPDATE ON aredl.submissions FOR EACH ROW EXECUTE FUNCTION aredl.submission_updated_at();
+
+ALTER TABLE aredl.submissions DROP COLUMN video_published_at;
+
+ALTER TABLE arepl.submissions DROP COLUMN video_published_at;
@@ -0,0 +1,24 @@
+ALTER TABLE aredl.submissions ADD COLUMN video_published_at TIMESTAMPTZ;
+
+ALTER TABLE arepl.submissions ADD COLUMN video_published_at TIMESTAMPTZ;
+
+-- The publish date is stored after the submission was saved, this is not an edit of the submission
+DROP TRIGGER submission_updated_at ON aredl.submissions;
+CREATE TRIGGER submission_updated_at BEFORE UPDATE ON aredl.submissions FOR EACH ROW
+WHEN ((to_jsonb(NEW) - 'video_published_at') IS DISTINCT FROM (to_jsonb(OLD) - 'video_published_at'))
+EXECUTE FUNCTION aredl.submission_updated_at();
+
+DROP TRIGGER submission_log_history_upd ON aredl.submissions;
+CREATE TRIGGER submission_log_history_upd AFTER UPDATE ON aredl.submissions FOR EACH ROW
+WHEN ((to_jsonb(NEW) - 'video_published_at') IS DISTINCT FROM (to_jsonb(OLD) - 'video_published_at'))
+EXECUTE FUNCTION aredl.submission_log_history();
+
+DROP TRIGGER submission_updated_at ON arepl.submissions;
+CREATE TRIGGER submission_updated_at BEFORE UPDATE ON arepl.submissions FOR EACH ROW
+WHEN ((to_jsonb(NEW) - 'video_published_at') IS DISTINCT FROM (to_jsonb(OLD) - 'video_published_at'))
+EXECUTE FUNCTION arepl.submission_updated_at();
+
+DROP TRIGGER submission_log_history_upd ON arepl.submissions;
+CREATE TRIGGER submission_log_history_upd AFTER UPDATE ON arepl.submissions FOR EACH ROW
+WHEN ((to_jsonb(NEW) - 'video_published_at') IS DISTINCT FROM (to_jsonb(OLD) - 'video_published_at'))
+EXECUTE FUNCTION arepl.submission_log_history();
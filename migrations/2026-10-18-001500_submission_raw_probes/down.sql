@@ -0,0 +1,2 @@
+DROP TABLE aredl.submission_raw_probes;
+DROP TABLE arepl.submission_raw_probes;
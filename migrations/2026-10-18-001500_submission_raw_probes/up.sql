@@ -0,0 +1,33 @@
+CREATE TABLE aredl.submission_raw_probes (
+    submission_id UUID PRIMARY KEY REFERENCES aredl.submissions(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    raw_url TEXT NOT NULL,
+    error TEXT,
+    duration_seconds DOUBLE PRECISION,
+    width INTEGER,
+    height INTEGER,
+    fps DOUBLE PRECISION,
+    container TEXT,
+    file_created_at TIMESTAMPTZ,
+    reencoded BOOLEAN,
+    flags TEXT[] NOT NULL DEFAULT '{}',
+    probed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    attempts INTEGER NOT NULL DEFAULT 1,
+    retry_at TIMESTAMPTZ
+);
+
+CREATE TABLE arepl.submission_raw_probes (
+    submission_id UUID PRIMARY KEY REFERENCES arepl.submissions(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    raw_url TEXT NOT NULL,
+    error TEXT,
+    duration_seconds DOUBLE PRECISION,
+    width INTEGER,
+    height INTEGER,
+    fps DOUBLE PRECISION,
+    container TEXT,
+    file_created_at TIMESTAMPTZ,
+    reencoded BOOLEAN,
+    flags TEXT[] NOT NULL DEFAULT '{}',
+    probed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    attempts INTEGER NOT NULL DEFAULT 1,
+    retry_at TIMESTAMPTZ
+);
@@ -0,0 +1,28 @@
+DELETE FROM notifications WHERE category = 'RecordVideo';
+DELETE FROM notification_preferences WHERE category = 'RecordVideo';
+
+ALTER TYPE notification_category RENAME TO notification_category_old;
+
+CREATE TYPE notification_category AS ENUM (
+    'General',
+    'SubmissionStatus',
+    'ClanInvite',
+    'MergeRequest',
+    'Bounty',
+    'Badge'
+);
+
+ALTER TABLE notifications
+    ALTER COLUMN category DROP DEFAULT,
+    ALTER COLUMN category TYPE notification_category USING category::text::notification_category,
+    ALTER COLUMN category SET DEFAULT 'General';
+
+ALTER TABLE notification_preferences
+    ALTER COLUMN category TYPE notification_category USING category::text::notification_category;
+
+DROP TYPE notification_category_old;
+
+DROP TABLE arepl.record_video_checks;
+DROP TABLE aredl.record_video_checks;
+
+DROP TYPE video_availability;
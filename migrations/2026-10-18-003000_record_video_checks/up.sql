@@ -0,0 +1,27 @@
+CREATE TYPE video_availability AS ENUM ('Available', 'Unavailable', 'Unknown');
+
+CREATE TABLE aredl.record_video_checks (
+    record_id UUID PRIMARY KEY REFERENCES aredl.records(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    video_url TEXT NOT NULL,
+    availability video_availability NOT NULL,
+    error TEXT,
+    unavailable_since TIMESTAMPTZ,
+    checked_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX aredl_record_video_checks_availability_idx ON aredl.record_video_checks (availability);
+CREATE INDEX aredl_record_video_checks_checked_at_idx ON aredl.record_video_checks (checked_at);
+
+CREATE TABLE arepl.record_video_checks (
+    record_id UUID PRIMARY KEY REFERENCES arepl.records(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    video_url TEXT NOT NULL,
+    availability video_availability NOT NULL,
+    error TEXT,
+    unavailable_since TIMESTAMPTZ,
+    checked_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX arepl_record_video_checks_availability_idx ON arepl.record_video_checks (availability);
+CREATE INDEX arepl_record_video_checks_checked_at_idx ON arepl.record_video_checks (checked_at);
+
+ALTER TYPE notification_category ADD VALUE IF NOT EXISTS 'RecordVideo';
@@ -0,0 +1,47 @@
+CREATE OR REPLACE FUNCTION arepl.submission_sync_record()
+RETURNS TRIGGER AS
+$$
+BEGIN
+    IF NEW.status = 'Accepted' THEN
+        INSERT INTO arepl.records AS r (
+            level_id,
+            submitted_by,
+            mobile,
+            video_url,
+            completion_time,
+            video_provider,
+            video_content_id,
+            submission_id
+        )
+        VALUES (
+            NEW.level_id,
+            NEW.submitted_by,
+            NEW.mobile,
+            NEW.video_url,
+            NEW.completion_time,
+            NEW.video_provider,
+            NEW.video_content_id,
+            NEW.id
+        )
+        ON CONFLICT (level_id, submitted_by)
+        DO UPDATE SET
+            mobile = EXCLUDED.mobile,
+            video_url = EXCLUDED.video_url,
+            completion_time = EXCLUDED.completion_time,
+            video_provider = EXCLUDED.video_provider,
+            video_content_id = EXCLUDED.video_content_id;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER submission_supersede_personal_best ON arepl.submissions;
+DROP FUNCTION arepl.submission_supersede_personal_best();
+
+DROP TRIGGER record_log_personal_best_upd ON arepl.records;
+DROP TRIGGER record_log_personal_best_ins ON arepl.records;
+
+DROP FUNCTION arepl.record_log_personal_best();
+
+DROP TABLE arepl.personal_bests;
@@ -0,0 +1,133 @@
+CREATE TABLE arepl.personal_bests (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    record_id UUID NOT NULL REFERENCES arepl.records(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    completion_time BIGINT NOT NULL,
+    video_url VARCHAR NOT NULL,
+    mobile BOOLEAN NOT NULL,
+    achieved_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    superseded_at TIMESTAMPTZ
+);
+
+CREATE INDEX arepl_personal_bests_record_idx ON arepl.personal_bests (record_id, achieved_at);
+CREATE UNIQUE INDEX arepl_personal_bests_current_idx ON arepl.personal_bests (record_id) WHERE superseded_at IS NULL;
+
+INSERT INTO arepl.personal_bests (record_id, completion_time, video_url, mobile, achieved_at)
+SELECT id, completion_time, video_url, mobile, achieved_at
+FROM arepl.records;
+
+-- Any change to a record corrects its current personal best, a new one is only started once
+-- an improvement is accepted and has superseded the previous one
+CREATE OR REPLACE FUNCTION arepl.record_log_personal_best()
+RETURNS TRIGGER AS
+$$
+BEGIN
+    IF TG_OP = 'UPDATE' THEN
+        UPDATE arepl.personal_bests
+        SET completion_time = NEW.completion_time,
+            video_url = NEW.video_url,
+            mobile = NEW.mobile,
+            achieved_at = CASE
+                WHEN NEW.achieved_at IS DISTINCT FROM OLD.achieved_at THEN NEW.achieved_at
+                ELSE achieved_at
+            END
+        WHERE record_id = NEW.id AND superseded_at IS NULL;
+
+        IF FOUND THEN
+            RETURN NEW;
+        END IF;
+    END IF;
+
+    INSERT INTO arepl.personal_bests (record_id, completion_time, video_url, mobile, achieved_at)
+    VALUES (
+        NEW.id,
+        NEW.completion_time,
+        NEW.video_url,
+        NEW.mobile,
+        CASE WHEN TG_OP = 'INSERT' THEN NEW.achieved_at ELSE CLOCK_TIMESTAMP() END
+    );
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER record_log_personal_best_ins
+AFTER INSERT ON arepl.records
+FOR EACH ROW EXECUTE FUNCTION arepl.record_log_personal_best();
+
+CREATE TRIGGER record_log_personal_best_upd
+AFTER UPDATE OF completion_time, video_url, mobile, achieved_at ON arepl.records
+FOR EACH ROW EXECUTE FUNCTION arepl.record_log_personal_best();
+
+-- Accepting a faster run supersedes the current personal best before the record is updated,
+-- edits of already accepted submissions only correct it
+CREATE OR REPLACE FUNCTION arepl.submission_supersede_personal_best()
+RETURNS TRIGGER AS
+$$
+BEGIN
+    UPDATE arepl.personal_bests pb
+    SET superseded_at = CLOCK_TIMESTAMP()
+    FROM arepl.records r
+    WHERE r.level_id = NEW.level_id
+        AND r.submitted_by = NEW.submitted_by
+        AND pb.record_id = r.id
+        AND pb.superseded_at IS NULL
+        AND NEW.completion_time < r.completion_time;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER submission_supersede_personal_best
+BEFORE UPDATE OF status ON arepl.submissions
+FOR EACH ROW
+WHEN (NEW.status = 'Accepted' AND OLD.status IS DISTINCT FROM NEW.status)
+EXECUTE FUNCTION arepl.submission_supersede_personal_best();
+
+-- An accepted improvement is dated by the faster run, not the first completion
+CREATE OR REPLACE FUNCTION arepl.submission_sync_record()
+RETURNS TRIGGER AS
+$$
+DECLARE
+    accepting BOOLEAN := TG_OP = 'INSERT';
+BEGIN
+    IF TG_OP = 'UPDATE' THEN
+        accepting := OLD.status IS DISTINCT FROM NEW.status;
+    END IF;
+
+    IF NEW.status = 'Accepted' THEN
+        INSERT INTO arepl.records AS r (
+            level_id,
+            submitted_by,
+            mobile,
+            video_url,
+            completion_time,
+            video_provider,
+            video_content_id,
+            submission_id
+        )
+        VALUES (
+            NEW.level_id,
+            NEW.submitted_by,
+            NEW.mobile,
+            NEW.video_url,
+            NEW.completion_time,
+            NEW.video_provider,
+            NEW.video_content_id,
+            NEW.id
+        )
+        ON CONFLICT (level_id, submitted_by)
+        DO UPDATE SET
+            mobile = EXCLUDED.mobile,
+            video_url = EXCLUDED.video_url,
+            completion_time = EXCLUDED.completion_time,
+            video_provider = EXCLUDED.video_provider,
+            video_content_id = EXCLUDED.video_content_id,
+            achieved_at = CASE
+                WHEN accepting AND EXCLUDED.completion_time < r.completion_time THEN CLOCK_TIMESTAMP()
+                ELSE r.achieved_at
+            END;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
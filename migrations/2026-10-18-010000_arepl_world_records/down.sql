@@ -0,0 +1,14 @@
+DROP TRIGGER level_refresh_world_record ON arepl.levels;
+DROP FUNCTION arepl.level_refresh_world_record();
+
+DROP TRIGGER user_refresh_arepl_world_records ON users;
+DROP FUNCTION arepl.user_refresh_world_records();
+
+DROP TRIGGER record_refresh_world_record ON arepl.records;
+DROP FUNCTION arepl.record_refresh_world_record();
+
+DROP FUNCTION arepl.refresh_world_record_change(UUID);
+
+DROP TABLE arepl.world_record_changes;
+DROP TYPE arepl.world_record_change_kind;
+DROP VIEW arepl.world_records;
@@ -0,0 +1,160 @@
+-- The fastest non-verification record of every ranked level
+CREATE VIEW arepl.world_records AS
+SELECT DISTINCT ON (r.level_id)
+    r.level_id,
+    r.id AS record_id,
+    r.submitted_by,
+    r.completion_time,
+    r.achieved_at
+FROM arepl.records r
+JOIN arepl.levels l ON l.id = r.level_id
+JOIN users u ON u.id = r.submitted_by
+WHERE l.status IN ('MainList', 'Pending')
+    AND NOT r.is_verification
+    AND u.ban_level <= 2
+ORDER BY r.level_id, r.completion_time, r.achieved_at, r.id;
+
+CREATE TYPE arepl.world_record_change_kind AS ENUM ('Set', 'Beaten', 'Removed');
+
+CREATE TABLE arepl.world_record_changes (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    level_id UUID NOT NULL REFERENCES arepl.levels(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    kind arepl.world_record_change_kind NOT NULL,
+    record_id UUID REFERENCES arepl.records(id) ON DELETE SET NULL ON UPDATE CASCADE,
+    completion_time BIGINT,
+    previous_record_id UUID REFERENCES arepl.records(id) ON DELETE SET NULL ON UPDATE CASCADE,
+    previous_completion_time BIGINT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+
+CREATE INDEX arepl_world_record_changes_level_idx ON arepl.world_record_changes (level_id, created_at);
+CREATE INDEX arepl_world_record_changes_created_at_idx ON arepl.world_record_changes (created_at);
+
+INSERT INTO arepl.world_record_changes (level_id, kind, record_id, completion_time, created_at)
+SELECT level_id, 'Set', record_id, completion_time, achieved_at
+FROM arepl.world_records;
+
+-- Logs a change if the world record of a level is not the one that was last logged.
+-- A world record that stops counting (deleted, banned holder, verification, unranked level)
+-- is logged as removed instead of being beaten by the slower record that takes over.
+CREATE OR REPLACE FUNCTION arepl.refresh_world_record_change(p_level_id UUID)
+RETURNS VOID AS
+$$
+DECLARE
+    wr_record_id UUID;
+    wr_completion_time BIGINT;
+    last_id UUID;
+    last_record_id UUID;
+    last_completion_time BIGINT;
+BEGIN
+    -- the level itself is being deleted
+    IF NOT EXISTS (SELECT 1 FROM arepl.levels WHERE id = p_level_id) THEN
+        RETURN;
+    END IF;
+
+    SELECT record_id, completion_time
+    INTO wr_record_id, wr_completion_time
+    FROM arepl.world_records
+    WHERE level_id = p_level_id;
+
+    SELECT id, record_id, completion_time
+    INTO last_id, last_record_id, last_completion_time
+    FROM arepl.world_record_changes
+    WHERE level_id = p_level_id
+    ORDER BY created_at DESC, id DESC
+    LIMIT 1;
+
+    IF wr_record_id IS NULL THEN
+        IF last_completion_time IS NOT NULL THEN
+            INSERT INTO arepl.world_record_changes (
+                level_id, kind, previous_record_id, previous_completion_time
+            )
+            VALUES (p_level_id, 'Removed', last_record_id, last_completion_time);
+        END IF;
+        RETURN;
+    END IF;
+
+    -- a slower time on the standing world record is a correction, not a new world record
+    IF wr_record_id = last_record_id AND wr_completion_time >= last_completion_time THEN
+        UPDATE arepl.world_record_changes
+        SET completion_time = wr_completion_time
+        WHERE id = last_id;
+        RETURN;
+    END IF;
+
+    INSERT INTO arepl.world_record_changes (
+        level_id, kind, record_id, completion_time, previous_record_id, previous_completion_time
+    )
+    VALUES (
+        p_level_id,
+        CASE
+            WHEN last_completion_time IS NULL THEN 'Set'
+            WHEN wr_completion_time < last_completion_time THEN 'Beaten'
+            ELSE 'Removed'
+        END::arepl.world_record_change_kind,
+        wr_record_id,
+        wr_completion_time,
+        last_record_id,
+        last_completion_time
+    );
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION arepl.record_refresh_world_record()
+RETURNS TRIGGER AS
+$$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        PERFORM arepl.refresh_world_record_change(OLD.level_id);
+    END IF;
+
+    IF TG_OP = 'INSERT' OR (TG_OP = 'UPDATE' AND NEW.level_id <> OLD.level_id) THEN
+        PERFORM arepl.refresh_world_record_change(NEW.level_id);
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER record_refresh_world_record
+AFTER INSERT OR DELETE OR UPDATE OF level_id, submitted_by, completion_time, achieved_at, is_verification
+ON arepl.records
+FOR EACH ROW EXECUTE FUNCTION arepl.record_refresh_world_record();
+
+CREATE OR REPLACE FUNCTION arepl.user_refresh_world_records()
+RETURNS TRIGGER AS
+$$
+DECLARE
+    record_level_id UUID;
+BEGIN
+    FOR record_level_id IN
+        SELECT DISTINCT level_id FROM arepl.records WHERE submitted_by = NEW.id
+    LOOP
+        PERFORM arepl.refresh_world_record_change(record_level_id);
+    END LOOP;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER user_refresh_arepl_world_records
+AFTER UPDATE OF ban_level ON users
+FOR EACH ROW
+WHEN (OLD.ban_level IS DISTINCT FROM NEW.ban_level)
+EXECUTE FUNCTION arepl.user_refresh_world_records();
+
+CREATE OR REPLACE FUNCTION arepl.level_refresh_world_record()
+RETURNS TRIGGER AS
+$$
+BEGIN
+    PERFORM arepl.refresh_world_record_change(NEW.id);
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER level_refresh_world_record
+AFTER UPDATE OF status ON arepl.levels
+FOR EACH ROW
+WHEN (OLD.status IS DISTINCT FROM NEW.status)
+EXECUTE FUNCTION arepl.level_refresh_world_record();
@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS external_sync_logs;
+DROP TABLE IF EXISTS external_sources;
+DROP TYPE IF EXISTS external_sync_list;
+DROP TYPE IF EXISTS external_importer;
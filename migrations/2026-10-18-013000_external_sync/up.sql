@@ -0,0 +1,52 @@
+CREATE TYPE external_importer AS ENUM ('pemonlist', 'json');
+CREATE TYPE external_sync_list AS ENUM ('aredl', 'arepl');
+
+CREATE TABLE external_sources (
+    id UUID NOT NULL DEFAULT gen_random_uuid() PRIMARY KEY,
+    name VARCHAR NOT NULL UNIQUE,
+    importer external_importer NOT NULL,
+    base_url VARCHAR NOT NULL,
+    field_mapping JSONB NOT NULL,
+    auto_accept BOOLEAN NOT NULL DEFAULT FALSE,
+    target_list external_sync_list NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE external_sync_logs (
+    id UUID NOT NULL DEFAULT gen_random_uuid() PRIMARY KEY,
+    source_id UUID NOT NULL REFERENCES external_sources(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    triggered_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    dry_run BOOLEAN NOT NULL,
+    succeeded BOOLEAN NOT NULL,
+    created_count INTEGER NOT NULL DEFAULT 0,
+    updated_count INTEGER NOT NULL DEFAULT 0,
+    unchanged_count INTEGER NOT NULL DEFAULT 0,
+    skipped_count INTEGER NOT NULL DEFAULT 0,
+    changes JSONB NOT NULL DEFAULT '[]',
+    error VARCHAR,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+
+CREATE INDEX external_sync_logs_source_idx ON external_sync_logs (source_id, created_at DESC);
+CREATE INDEX external_sync_logs_user_idx ON external_sync_logs (user_id, created_at DESC);
+
+-- reproduce the previously hardcoded pemonlist importer
+INSERT INTO external_sources (name, importer, base_url, field_mapping, auto_accept, target_list) VALUES (
+    'pemonlist',
+    'pemonlist',
+    'https://pemonlist.com/api/player',
+    '{
+        "records": "/records",
+        "level_id": "/level/level_id",
+        "completion_time": "/formatted_time",
+        "completion_time_format": "formatted",
+        "video_url": "/video_id",
+        "video_url_template": "https://www.youtube.com/watch?v={}",
+        "mobile": "/mobile"
+    }',
+    TRUE,
+    'arepl'
+);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS external_sync_subscriptions;
+ALTER TABLE external_sync_logs DROP COLUMN IF EXISTS removed_count;
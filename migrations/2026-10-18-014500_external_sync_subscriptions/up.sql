@@ -0,0 +1,12 @@
+ALTER TABLE external_sync_logs ADD COLUMN removed_count INTEGER NOT NULL DEFAULT 0;
+
+CREATE TABLE external_sync_subscriptions (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    source_id UUID NOT NULL REFERENCES external_sources(id) ON DELETE CASCADE,
+    last_synced_at TIMESTAMPTZ,
+    last_log_id UUID REFERENCES external_sync_logs(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, source_id)
+);
+
+CREATE INDEX external_sync_subscriptions_due_idx ON external_sync_subscriptions (last_synced_at NULLS FIRST);
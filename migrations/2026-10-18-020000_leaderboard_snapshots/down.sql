@@ -0,0 +1,6 @@
+DROP TABLE aredl.clans_leaderboard_snapshots;
+DROP TABLE aredl.country_leaderboard_snapshots;
+DROP TABLE aredl.user_leaderboard_snapshots;
+DROP TABLE arepl.clans_leaderboard_snapshots;
+DROP TABLE arepl.country_leaderboard_snapshots;
+DROP TABLE arepl.user_leaderboard_snapshots;
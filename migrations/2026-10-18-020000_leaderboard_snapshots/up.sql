@@ -0,0 +1,75 @@
+CREATE TABLE aredl.user_leaderboard_snapshots (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    snapshot_date DATE NOT NULL,
+    rank INTEGER NOT NULL,
+    raw_rank INTEGER NOT NULL,
+    extremes_rank INTEGER NOT NULL,
+    hardest_rank INTEGER NOT NULL,
+    country_rank INTEGER NOT NULL,
+    total_points INTEGER NOT NULL,
+    pack_points INTEGER NOT NULL,
+    extremes INTEGER NOT NULL,
+    PRIMARY KEY (user_id, snapshot_date)
+);
+
+CREATE INDEX aredl_user_leaderboard_snapshots_date_idx ON aredl.user_leaderboard_snapshots (snapshot_date);
+
+CREATE TABLE aredl.country_leaderboard_snapshots (
+    country INTEGER NOT NULL,
+    snapshot_date DATE NOT NULL,
+    rank INTEGER NOT NULL,
+    extremes_rank INTEGER NOT NULL,
+    hardest_rank INTEGER NOT NULL,
+    level_points INTEGER NOT NULL,
+    extremes INTEGER NOT NULL,
+    PRIMARY KEY (country, snapshot_date)
+);
+
+CREATE TABLE aredl.clans_leaderboard_snapshots (
+    clan_id UUID NOT NULL REFERENCES clans(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    snapshot_date DATE NOT NULL,
+    rank INTEGER NOT NULL,
+    extremes_rank INTEGER NOT NULL,
+    hardest_rank INTEGER NOT NULL,
+    level_points INTEGER NOT NULL,
+    extremes INTEGER NOT NULL,
+    PRIMARY KEY (clan_id, snapshot_date)
+);
+
+CREATE TABLE arepl.user_leaderboard_snapshots (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    snapshot_date DATE NOT NULL,
+    rank INTEGER NOT NULL,
+    raw_rank INTEGER NOT NULL,
+    extremes_rank INTEGER NOT NULL,
+    hardest_rank INTEGER NOT NULL,
+    country_rank INTEGER NOT NULL,
+    total_points INTEGER NOT NULL,
+    pack_points INTEGER NOT NULL,
+    extremes INTEGER NOT NULL,
+    PRIMARY KEY (user_id, snapshot_date)
+);
+
+CREATE INDEX arepl_user_leaderboard_snapshots_date_idx ON arepl.user_leaderboard_snapshots (snapshot_date);
+
+CREATE TABLE arepl.country_leaderboard_snapshots (
+    country INTEGER NOT NULL,
+    snapshot_date DATE NOT NULL,
+    rank INTEGER NOT NULL,
+    extremes_rank INTEGER NOT NULL,
+    hardest_rank INTEGER NOT NULL,
+    level_points INTEGER NOT NULL,
+    extremes INTEGER NOT NULL,
+    PRIMARY KEY (country, snapshot_date)
+);
+
+CREATE TABLE arepl.clans_leaderboard_snapshots (
+    clan_id UUID NOT NULL REFERENCES clans(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    snapshot_date DATE NOT NULL,
+    rank INTEGER NOT NULL,
+    extremes_rank INTEGER NOT NULL,
+    hardest_rank INTEGER NOT NULL,
+    level_points INTEGER NOT NULL,
+    extremes INTEGER NOT NULL,
+    PRIMARY KEY (clan_id, snapshot_date)
+);
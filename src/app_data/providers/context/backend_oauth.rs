@@ -324,7 +324,7 @@ fn token_cipher() -> Result<&'static XChaCha20Poly1305, ApiError> {
     })
 }
 
-fn encrypt_db_token_value(value: &str, aad: &[u8]) -> Result<String, ApiError> {
+pub(crate) fn encrypt_db_token_value(value: &str, aad: &[u8]) -> Result<String, ApiError> {
     let cipher = token_cipher()?;
     let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
 
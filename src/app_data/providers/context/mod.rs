@@ -8,8 +8,7 @@ use std::sync::Arc;
 
 use crate::{app_data::db::DbAppState, providers::context::backend_oauth::OAuthProviderContext};
 
-#[cfg(test)]
-pub(crate) use backend_oauth::decrypt_db_token_value;
+pub(crate) use backend_oauth::{decrypt_db_token_value, encrypt_db_token_value};
 
 #[derive(Clone)]
 pub struct ProviderContext {
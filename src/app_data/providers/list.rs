@@ -1,8 +1,12 @@
 pub mod bilibili;
+pub mod direct_file;
+pub mod dropbox;
 pub mod gdrive;
 pub mod medal;
 pub mod mega;
+pub mod onedrive;
 pub mod outplayed;
+pub mod streamable;
 pub mod twitch;
 pub mod vimeo;
 pub mod youtube;
@@ -0,0 +1,131 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::header::{self, HeaderMap};
+use url::{Host, Url};
+
+use crate::{
+    error_handler::ApiError,
+    providers::{
+        model::ProviderMatch,
+        public_host::{is_internal_domain, public_client},
+    },
+};
+
+use super::super::{
+    context::ProviderContext,
+    model::{
+        ContentDataLocation, ContentMetadata, NormalizedProviderMatch, Provider, ProviderId,
+        ProviderUsage,
+    },
+};
+
+const VIDEO_EXTENSIONS: &[&str] = &["mp4", "mkv", "mov", "webm", "avi", "flv", "m4v", "ts"];
+
+/// Links straight to a video file on any other host.
+pub struct DirectFileProvider;
+
+#[async_trait]
+impl Provider for DirectFileProvider {
+    fn id(&self) -> ProviderId {
+        ProviderId::DirectFile
+    }
+
+    fn usage(&self) -> ProviderUsage {
+        ProviderUsage::RawFootage
+    }
+
+    // matches any host, the registry only falls back to it when no other provider matched
+    fn hosts(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn match_url(&self, url: &Url) -> Option<ProviderMatch> {
+        // the file is fetched by the server, so internal addresses must not be reachable through it
+        match url.host() {
+            Some(Host::Domain(domain))
+                if url.scheme() == "https" && !is_internal_domain(domain) => {}
+            _ => return None,
+        }
+
+        let (_, extension) = url.path().rsplit_once('.')?;
+        if !VIDEO_EXTENSIONS.contains(&extension.to_lowercase().as_str()) {
+            return None;
+        }
+
+        let mut content_url = url.clone();
+        content_url.set_fragment(None);
+
+        Some(ProviderMatch {
+            provider: ProviderId::DirectFile,
+            content_id: content_url.into(),
+            timestamp: None,
+            other_id: None,
+        })
+    }
+
+    fn normalize_url(&self, _raw_url: &Url, matched: &ProviderMatch) -> String {
+        matched.content_id.clone()
+    }
+
+    async fn fetch_metadata(
+        &self,
+        matched: &NormalizedProviderMatch,
+        _context: &ProviderContext,
+    ) -> Result<Option<ContentMetadata>, ApiError> {
+        // the name is resolved again here, it may point somewhere else than when the URL was matched
+        let url = Url::parse(&matched.content_id)?;
+        let response = public_client(&url)
+            .await?
+            .head(url)
+            .send()
+            .await
+            .map_err(|e| ApiError::BadGateway(format!("Failed to request file: {e}")))?;
+
+        if matches!(
+            response.status(),
+            reqwest::StatusCode::NOT_FOUND | reqwest::StatusCode::GONE
+        ) {
+            return Err(ApiError::NotFound("This file doesn't exist anymore"));
+        }
+
+        if response.status().is_redirection() {
+            return Err(ApiError::BadGateway(
+                "File host redirected, link the file directly instead",
+            ));
+        }
+
+        if !response.status().is_success() {
+            return Err(ApiError::BadGateway("File host returned non-success"));
+        }
+
+        let published_at = response
+            .headers()
+            .get(header::LAST_MODIFIED)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+            .map(|value| value.with_timezone(&Utc));
+
+        if published_at.is_none() {
+            return Ok(None);
+        }
+
+        Ok(Some(ContentMetadata {
+            provider: ProviderId::DirectFile,
+            video_id: matched.content_id.clone(),
+            published_at,
+        }))
+    }
+
+    async fn get_content_location(
+        &self,
+        matched: &NormalizedProviderMatch,
+        _context: &ProviderContext,
+    ) -> Result<Option<ContentDataLocation>, ApiError> {
+        let url = Url::parse(&matched.content_id)?;
+        Ok(Some(ContentDataLocation {
+            client: Some(public_client(&url).await?),
+            url: url.into(),
+            headers: HeaderMap::new(),
+        }))
+    }
+}
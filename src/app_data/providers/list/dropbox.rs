@@ -0,0 +1,104 @@
+use async_trait::async_trait;
+use reqwest::header::HeaderMap;
+use url::Url;
+
+use crate::{error_handler::ApiError, providers::model::ProviderMatch};
+
+use super::super::parse::is_ascii_id;
+use super::super::{
+    context::ProviderContext,
+    model::{ContentDataLocation, NormalizedProviderMatch, Provider, ProviderId, ProviderUsage},
+};
+
+pub struct DropboxProvider;
+
+#[async_trait]
+impl Provider for DropboxProvider {
+    fn id(&self) -> ProviderId {
+        ProviderId::Dropbox
+    }
+
+    fn usage(&self) -> ProviderUsage {
+        ProviderUsage::RawFootage
+    }
+
+    fn hosts(&self) -> &'static [&'static str] {
+        &[
+            "dropbox.com",
+            "www.dropbox.com",
+            "dl.dropboxusercontent.com",
+        ]
+    }
+
+    fn match_url(&self, url: &Url) -> Option<ProviderMatch> {
+        // /s/<id>/<name>
+        // /scl/fi/<id>/<name>?rlkey=<key>
+        let path = url.path().trim_matches('/');
+        let mut parts = path.split('/');
+
+        let (content_id, rlkey) = match parts.next()? {
+            "s" => (parts.next()?, None),
+            "scl" => {
+                if parts.next()? != "fi" {
+                    return None;
+                }
+                let rlkey = url
+                    .query_pairs()
+                    .find(|(key, _)| key == "rlkey")
+                    .map(|(_, value)| value.into_owned())?;
+                (parts.next()?, Some(rlkey))
+            }
+            _ => return None,
+        };
+        let name = parts.next()?;
+
+        if parts.next().is_some() || !is_ascii_id(content_id, 1, 64) || name.is_empty() {
+            return None;
+        }
+
+        if let Some(rlkey) = &rlkey {
+            if !is_ascii_id(rlkey, 1, 64) {
+                return None;
+            }
+        }
+
+        Some(ProviderMatch {
+            provider: ProviderId::Dropbox,
+            content_id: content_id.to_owned(),
+            timestamp: None,
+            other_id: rlkey,
+        })
+    }
+
+    fn normalize_url(&self, raw_url: &Url, matched: &ProviderMatch) -> String {
+        // the file name is part of the link, so it's taken from the original url
+        let name = raw_url
+            .path()
+            .trim_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or_default();
+
+        match matched.other_id.as_deref() {
+            Some(rlkey) => format!(
+                "https://www.dropbox.com/scl/fi/{}/{name}?rlkey={rlkey}",
+                matched.content_id
+            ),
+            None => format!("https://www.dropbox.com/s/{}/{name}", matched.content_id),
+        }
+    }
+
+    async fn get_content_location(
+        &self,
+        matched: &NormalizedProviderMatch,
+        _context: &ProviderContext,
+    ) -> Result<Option<ContentDataLocation>, ApiError> {
+        let separator = if matched.other_id.is_some() { '&' } else { '?' };
+
+        Ok(Some(ContentDataLocation {
+            url: format!("{}{separator}dl=1", matched.normalized_url),
+            headers: HeaderMap::new(),
+            client: None,
+        }))
+    }
+}
@@ -120,6 +120,10 @@ impl Provider for GoogleDriveProvider {
             })?,
         );
 
-        Ok(Some(ContentDataLocation { url, headers }))
+        Ok(Some(ContentDataLocation {
+            url,
+            headers,
+            client: None,
+        }))
     }
 }
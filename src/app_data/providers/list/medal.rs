@@ -90,6 +90,13 @@ impl Provider for MedalProvider {
             .await
             .map_err(|e| ApiError::BadGateway(format!("Medal API error: {e}")))?;
 
+        if matches!(
+            response.status(),
+            reqwest::StatusCode::NOT_FOUND | reqwest::StatusCode::GONE
+        ) {
+            return Err(ApiError::NotFound("This Medal clip doesn't exist anymore"));
+        }
+
         if !response.status().is_success() {
             return Err(ApiError::BadGateway("Medal API returned non-success"));
         }
@@ -0,0 +1,172 @@
+use async_trait::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
+use serde_json::Value;
+use url::Url;
+
+use crate::{error_handler::ApiError, providers::model::ProviderMatch};
+
+use super::super::{
+    context::ProviderContext,
+    model::{
+        ContentDataLocation, ContentMetadata, NormalizedProviderMatch, Provider, ProviderId,
+        ProviderUsage,
+    },
+};
+
+pub struct OneDriveProvider;
+
+fn is_onedrive_id(value: &str) -> bool {
+    (1..=256).contains(&value.len())
+        && value
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'!' | b'_' | b'-' | b'/'))
+}
+
+// the shares API accepts any sharing link, encoded as "u!" followed by the unpadded base64url of the link
+fn shares_url(matched: &NormalizedProviderMatch, item: &str) -> String {
+    let onedrive_base = std::env::var("ONEDRIVE_API_BASE_URL")
+        .unwrap_or_else(|_| "https://api.onedrive.com/v1.0".to_owned());
+
+    format!(
+        "{}/shares/u!{}/{item}",
+        onedrive_base.trim_end_matches('/'),
+        URL_SAFE_NO_PAD.encode(&matched.normalized_url)
+    )
+}
+
+#[async_trait]
+impl Provider for OneDriveProvider {
+    fn id(&self) -> ProviderId {
+        ProviderId::OneDrive
+    }
+
+    fn usage(&self) -> ProviderUsage {
+        ProviderUsage::RawFootage
+    }
+
+    fn hosts(&self) -> &'static [&'static str] {
+        &["1drv.ms", "onedrive.live.com"]
+    }
+
+    fn match_url(&self, url: &Url) -> Option<ProviderMatch> {
+        // 1drv.ms/<type>/<token>
+        // onedrive.live.com/?resid=<id>&authkey=<key>
+        // onedrive.live.com/redir?resid=<id>&authkey=<key>
+        // onedrive.live.com/embed?resid=<id>&authkey=<key>
+        let (content_id, other_id) = if url.host_str() == Some("1drv.ms") {
+            let (link_type, token) = url.path().trim_matches('/').split_once('/')?;
+            if link_type.len() != 1 || !link_type.bytes().all(|b| b.is_ascii_lowercase()) {
+                return None;
+            }
+            (token.to_owned(), Some(link_type.to_owned()))
+        } else {
+            if !matches!(url.path(), "/" | "/redir" | "/embed" | "/download") {
+                return None;
+            }
+            let query_value = |name: &str| {
+                url.query_pairs()
+                    .find(|(key, _)| key == name)
+                    .map(|(_, value)| value.into_owned())
+            };
+            (query_value("resid")?, query_value("authkey"))
+        };
+
+        if !is_onedrive_id(&content_id) {
+            return None;
+        }
+
+        if let Some(other_id) = &other_id {
+            if !is_onedrive_id(other_id) {
+                return None;
+            }
+        }
+
+        Some(ProviderMatch {
+            provider: ProviderId::OneDrive,
+            content_id,
+            timestamp: None,
+            other_id,
+        })
+    }
+
+    fn normalize_url(&self, raw_url: &Url, matched: &ProviderMatch) -> String {
+        if raw_url.host_str() == Some("1drv.ms") {
+            return format!(
+                "https://1drv.ms/{}/{}",
+                matched.other_id.as_deref().unwrap_or_default(),
+                matched.content_id
+            );
+        }
+
+        match matched.other_id.as_deref() {
+            Some(authkey) => format!(
+                "https://onedrive.live.com/redir?resid={}&authkey={authkey}",
+                matched.content_id
+            ),
+            None => format!(
+                "https://onedrive.live.com/redir?resid={}",
+                matched.content_id
+            ),
+        }
+    }
+
+    async fn fetch_metadata(
+        &self,
+        matched: &NormalizedProviderMatch,
+        context: &ProviderContext,
+    ) -> Result<Option<ContentMetadata>, ApiError> {
+        let response = context
+            .http
+            .get(shares_url(matched, "driveItem"))
+            .send()
+            .await
+            .map_err(|e| ApiError::BadGateway(format!("OneDrive API error: {e}")))?;
+
+        if matches!(
+            response.status(),
+            reqwest::StatusCode::NOT_FOUND | reqwest::StatusCode::GONE
+        ) {
+            return Err(ApiError::NotFound(
+                "This OneDrive file doesn't exist anymore",
+            ));
+        }
+
+        if !response.status().is_success() {
+            return Err(ApiError::BadGateway("OneDrive API returned non-success"));
+        }
+
+        let json: Value = response
+            .json()
+            .await
+            .map_err(|e| ApiError::BadGateway(format!("Failed to parse OneDrive response: {e}")))?;
+
+        let published_at = json
+            .get("createdDateTime")
+            .and_then(Value::as_str)
+            .and_then(|created_at| created_at.parse::<DateTime<Utc>>().ok());
+
+        if published_at.is_none() {
+            return Ok(None);
+        }
+
+        Ok(Some(ContentMetadata {
+            provider: ProviderId::OneDrive,
+            video_id: matched.content_id.clone(),
+            published_at,
+        }))
+    }
+
+    async fn get_content_location(
+        &self,
+        matched: &NormalizedProviderMatch,
+        _context: &ProviderContext,
+    ) -> Result<Option<ContentDataLocation>, ApiError> {
+        Ok(Some(ContentDataLocation {
+            url: shares_url(matched, "root/content"),
+            headers: HeaderMap::new(),
+            client: None,
+        }))
+    }
+}
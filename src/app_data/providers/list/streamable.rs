@@ -0,0 +1,115 @@
+use async_trait::async_trait;
+use reqwest::header::HeaderMap;
+use serde_json::Value;
+use url::Url;
+
+use crate::{error_handler::ApiError, providers::model::ProviderMatch};
+
+use super::super::parse::is_ascii_id;
+use super::super::{
+    context::ProviderContext,
+    model::{ContentDataLocation, NormalizedProviderMatch, Provider, ProviderId, ProviderUsage},
+};
+
+pub struct StreamableProvider;
+
+#[async_trait]
+impl Provider for StreamableProvider {
+    fn id(&self) -> ProviderId {
+        ProviderId::Streamable
+    }
+
+    fn usage(&self) -> ProviderUsage {
+        ProviderUsage::RawFootage
+    }
+
+    fn hosts(&self) -> &'static [&'static str] {
+        &["streamable.com", "www.streamable.com"]
+    }
+
+    fn match_url(&self, url: &Url) -> Option<ProviderMatch> {
+        // /<shortcode>
+        // /e/<shortcode>
+        // /o/<shortcode>
+        let path = url.path().trim_matches('/');
+        let mut parts = path.split('/');
+
+        let content_id = match parts.next()? {
+            "e" | "o" => parts.next()?,
+            shortcode => shortcode,
+        };
+
+        if parts.next().is_some() || !is_ascii_id(content_id, 1, 32) {
+            return None;
+        }
+
+        Some(ProviderMatch {
+            provider: ProviderId::Streamable,
+            content_id: content_id.to_owned(),
+            timestamp: None,
+            other_id: None,
+        })
+    }
+
+    fn normalize_url(&self, _raw_url: &Url, matched: &ProviderMatch) -> String {
+        format!("https://streamable.com/{}", matched.content_id)
+    }
+
+    async fn get_content_location(
+        &self,
+        matched: &NormalizedProviderMatch,
+        context: &ProviderContext,
+    ) -> Result<Option<ContentDataLocation>, ApiError> {
+        let streamable_base = std::env::var("STREAMABLE_API_BASE_URL")
+            .unwrap_or_else(|_| "https://api.streamable.com".to_owned());
+
+        let url = format!(
+            "{}/videos/{}",
+            streamable_base.trim_end_matches('/'),
+            matched.content_id
+        );
+
+        let response = context
+            .http
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| ApiError::BadGateway(format!("Streamable API error: {e}")))?;
+
+        if matches!(
+            response.status(),
+            reqwest::StatusCode::NOT_FOUND | reqwest::StatusCode::GONE
+        ) {
+            return Err(ApiError::NotFound(
+                "This Streamable video doesn't exist anymore",
+            ));
+        }
+
+        if !response.status().is_success() {
+            return Err(ApiError::BadGateway("Streamable API returned non-success"));
+        }
+
+        let json: Value = response.json().await.map_err(|e| {
+            ApiError::BadGateway(format!("Failed to parse Streamable response: {e}"))
+        })?;
+
+        // the original upload is only kept for some videos, the mp4 is streamable's own encode
+        let file_url = ["original", "mp4"].iter().find_map(|file| {
+            json.get("files")?
+                .get(file)?
+                .get("url")?
+                .as_str()
+                .filter(|url| !url.is_empty())
+        });
+
+        Ok(file_url.map(|file_url| ContentDataLocation {
+            url: if file_url.starts_with("//") {
+                format!("https:{file_url}")
+            } else {
+                file_url.to_owned()
+            },
+            headers: HeaderMap::new(),
+            client: None,
+        }))
+    }
+}
@@ -136,6 +136,10 @@ impl Provider for TwitchProvider {
             .await
             .map_err(|e| ApiError::BadGateway(format!("Twitch API error: {e}")))?;
 
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ApiError::NotFound("This Twitch video doesn't exist"));
+        }
+
         if !response.status().is_success() {
             tracing::warn!("full Twitch API response: {:?}", response);
             return Err(ApiError::BadGateway("Twitch API returned non-success"));
@@ -157,7 +161,7 @@ impl Provider for TwitchProvider {
             .and_then(|s| s.parse::<DateTime<Utc>>().ok());
 
         let Some(_) = first else {
-            return Ok(None);
+            return Err(ApiError::NotFound("This Twitch video doesn't exist"));
         };
 
         Ok(Some(ContentMetadata {
@@ -155,8 +155,11 @@ impl Provider for YouTubeProvider {
             .and_then(|v| v.as_array())
             .map_or(&[], Vec::as_slice);
 
+        // the API silently leaves out videos that were deleted or made private
         let Some(first) = items.first() else {
-            return Ok(None);
+            return Err(ApiError::NotFound(
+                "This YouTube video doesn't exist or is private",
+            ));
         };
 
         let snippet = first.get("snippet").and_then(|v| v.as_object()).cloned();
@@ -0,0 +1,41 @@
+use prometheus::{IntCounterVec, Opts, Registry};
+use std::sync::LazyLock;
+use strum_macros::Display;
+
+use super::model::ProviderId;
+
+/// Outcome of every URL match, metadata lookup and content location lookup, by provider.
+pub static PROVIDER_OPERATIONS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    IntCounterVec::new(
+        Opts::new(
+            "provider_operations_total",
+            "Provider URL matches, metadata and content location lookups by outcome",
+        ),
+        &["provider", "operation", "outcome"],
+    )
+    .expect("Provider metric options should be valid")
+});
+
+#[derive(Debug, Clone, Copy, Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum ProviderOperation {
+    Match,
+    Metadata,
+    ContentLocation,
+}
+
+pub fn register_metrics(registry: &Registry) -> Result<(), prometheus::Error> {
+    registry.register(Box::new(PROVIDER_OPERATIONS.clone()))
+}
+
+// URLs that no provider recognizes the host of are counted under "Unknown"
+pub fn record_operation(provider: Option<ProviderId>, operation: ProviderOperation, success: bool) {
+    let provider = provider.map_or_else(|| "Unknown".to_owned(), |provider| provider.to_string());
+    PROVIDER_OPERATIONS
+        .with_label_values(&[
+            provider.as_str(),
+            &operation.to_string(),
+            if success { "success" } else { "failure" },
+        ])
+        .inc();
+}
@@ -1,7 +1,9 @@
 pub mod context;
 pub mod list;
+pub mod metrics;
 pub mod model;
 mod parse;
+pub mod public_host;
 mod state;
 pub mod test_utils;
 mod tests;
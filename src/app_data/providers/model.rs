@@ -4,12 +4,15 @@ use reqwest::header::HeaderMap;
 use reqwest::{header, Client, StatusCode};
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::Arc};
+use strum_macros::Display;
 use url::Url;
+use utoipa::ToSchema;
 
 use super::context::ProviderContext;
+use super::metrics::{record_operation, ProviderOperation};
 use crate::error_handler::ApiError;
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, Display, ToSchema)]
 pub enum ProviderId {
     YouTube,
     Vimeo,
@@ -20,9 +23,13 @@ pub enum ProviderId {
     GoogleDrive,
     Mega,
     Mediafire,
+    Streamable,
+    Dropbox,
+    OneDrive,
+    DirectFile,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, ToSchema)]
 pub enum ProviderUsage {
     CompletionVideo,
     RawFootage,
@@ -54,6 +61,13 @@ pub struct NormalizedProviderMatch {
     pub normalized_url: String,
 }
 
+/// What a video URL points to, regardless of timestamps, playlists or how the URL is written.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VideoIdentity {
+    pub provider: ProviderId,
+    pub content_id: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContentMetadata {
     pub provider: ProviderId,
@@ -65,6 +79,17 @@ pub struct ContentMetadata {
 pub struct ContentDataLocation {
     pub url: String,
     pub headers: HeaderMap,
+    // restricted client for hosts that aren't trusted, a default one is used when unset
+    pub client: Option<Client>,
+}
+
+impl NormalizedProviderMatch {
+    pub fn identity(&self) -> VideoIdentity {
+        VideoIdentity {
+            provider: self.provider,
+            content_id: self.content_id.clone(),
+        }
+    }
 }
 
 pub struct ProviderRegistry {
@@ -89,11 +114,27 @@ impl ProviderRegistry {
     }
 
     pub fn match_url(&self, url: &Url) -> Result<NormalizedProviderMatch, ApiError> {
-        for provider in self.providers.values() {
+        // providers accepting any host are only tried once no dedicated provider matched
+        let (dedicated, catch_all): (Vec<_>, Vec<_>) = self
+            .providers
+            .values()
+            .partition(|provider| !provider.hosts().is_empty());
+        for provider in dedicated.into_iter().chain(catch_all) {
             if let Some(matched) = provider.parse_url(url)? {
+                record_operation(Some(matched.provider), ProviderOperation::Match, true);
                 return Ok(matched);
             }
         }
+
+        // a known host with an unrecognized URL usually means the provider changed its URL scheme
+        let host_owner = url.host_str().and_then(|host| {
+            self.providers
+                .values()
+                .find(|provider| provider.hosts().contains(&host))
+                .map(|provider| provider.id())
+        });
+        record_operation(host_owner, ProviderOperation::Match, false);
+
         Err(ApiError::UnprocessableEntity(
             "URL does not match any known supported providers. Please refer to our guidelines for a list of supported websites.",
         ))
@@ -156,7 +197,7 @@ pub const FETCH_RANGE_CHUNK_SIZE: u64 = 16 * 1024 * 1024;
 
 impl ContentDataLocation {
     async fn fetch_range(&self, start: u64, len: u64) -> Result<Vec<u8>, ApiError> {
-        let client = Client::new();
+        let client = self.client.clone().unwrap_or_default();
         let end = start
             .checked_add(len)
             .and_then(|v| v.checked_sub(1))
@@ -0,0 +1,115 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use reqwest::{redirect::Policy, Client};
+use url::{Host, Url};
+
+use crate::error_handler::ApiError;
+
+// names that only ever resolve inside a private network
+const INTERNAL_SUFFIXES: &[&str] = &[".localhost", ".local", ".internal", ".home.arpa"];
+
+/// Whether a host name is obviously internal, before it's even resolved.
+pub fn is_internal_domain(domain: &str) -> bool {
+    let domain = domain.trim_end_matches('.').to_lowercase();
+    domain == "localhost"
+        || !domain.contains('.')
+        || INTERNAL_SUFFIXES
+            .iter()
+            .any(|suffix| domain.ends_with(suffix))
+}
+
+/// Whether an address is reachable on the public internet, and therefore safe for the server to connect to.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(mapped) => is_public_ipv4(mapped),
+            None => is_public_ipv6(ip),
+        },
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    // carrier-grade NAT (100.64.0.0/10) and the IETF protocol assignments (192.0.0.0/24)
+    let shared = a == 100 && (b & 0b1100_0000) == 64;
+    let protocol = a == 192 && b == 0 && ip.octets()[2] == 0;
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        || shared
+        || protocol
+        || a == 0
+        || a >= 240)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    // 64:ff9b::/96 embeds an ipv4 address behind a NAT64 gateway
+    let nat64 = ip.segments()[..6] == [0x64, 0xff9b, 0, 0, 0, 0];
+    if nat64 {
+        let [.., a, b, c, d] = ip.octets();
+        return is_public_ipv4(Ipv4Addr::new(a, b, c, d));
+    }
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        || ip.is_unique_local()
+        || ip.is_unicast_link_local()
+        || ip.to_ipv4().is_some())
+}
+
+/// Resolves the host of a URL, failing if it is missing, internal or resolves to any non-public address.
+pub async fn resolve_public_host(url: &Url) -> Result<Vec<SocketAddr>, ApiError> {
+    let port = url
+        .port_or_known_default()
+        .ok_or_else(|| ApiError::BadRequest("URL has no known port"))?;
+
+    let addresses: Vec<SocketAddr> = match url.host() {
+        Some(Host::Domain(domain)) => {
+            if is_internal_domain(domain) {
+                return Err(ApiError::BadRequest("URL points to an internal host"));
+            }
+            tokio::net::lookup_host((domain, port))
+                .await
+                .map_err(|e| ApiError::BadGateway(format!("Failed to resolve host: {e}")))?
+                .collect()
+        }
+        Some(Host::Ipv4(ip)) => vec![SocketAddr::new(IpAddr::V4(ip), port)],
+        Some(Host::Ipv6(ip)) => vec![SocketAddr::new(IpAddr::V6(ip), port)],
+        None => return Err(ApiError::BadRequest("URL has no host")),
+    };
+
+    if addresses.is_empty() {
+        return Err(ApiError::BadGateway("Host did not resolve to any address"));
+    }
+    // a single internal address is enough, the connection could be made to any of them
+    if addresses.iter().any(|address| !is_public_ip(address.ip())) {
+        return Err(ApiError::BadRequest("URL points to an internal host"));
+    }
+
+    Ok(addresses)
+}
+
+/// Builds a client that can only reach the already validated public addresses of the URL's host.
+pub async fn public_client(url: &Url) -> Result<Client, ApiError> {
+    let addresses = resolve_public_host(url).await?;
+    pinned_client(url, &addresses)
+}
+
+/// Builds a client that connects to the given addresses for the URL's host.
+/// Redirects are not followed, since they could lead anywhere, including back into the private network.
+pub fn pinned_client(url: &Url, addresses: &[SocketAddr]) -> Result<Client, ApiError> {
+    let mut builder = Client::builder().redirect(Policy::none());
+    // pinning the addresses keeps a second DNS lookup from returning something else
+    if let Some(Host::Domain(domain)) = url.host() {
+        builder = builder.resolve_to_addrs(domain, addresses);
+    }
+
+    builder
+        .build()
+        .map_err(|e| ApiError::InternalServerError(format!("Failed to build client: {e}")))
+}
@@ -3,12 +3,15 @@ use url::Url;
 use super::{
     context::ProviderContext,
     list::{
-        bilibili::BiliBiliProvider, gdrive::GoogleDriveProvider, medal::MedalProvider,
-        mega::MegaProvider, outplayed::OutplayedProvider, twitch::TwitchProvider,
-        vimeo::VimeoProvider, youtube::YouTubeProvider,
+        bilibili::BiliBiliProvider, direct_file::DirectFileProvider, dropbox::DropboxProvider,
+        gdrive::GoogleDriveProvider, medal::MedalProvider, mega::MegaProvider,
+        onedrive::OneDriveProvider, outplayed::OutplayedProvider, streamable::StreamableProvider,
+        twitch::TwitchProvider, vimeo::VimeoProvider, youtube::YouTubeProvider,
     },
+    metrics::{record_operation, ProviderOperation},
     model::{
         ContentDataLocation, ContentMetadata, NormalizedProviderMatch, Provider, ProviderRegistry,
+        VideoIdentity,
     },
 };
 use crate::{
@@ -74,6 +77,11 @@ impl ProvidersAppState {
         }
     }
 
+    // used to recognize the same video behind differently written URLs, unknown providers have no identity
+    pub fn identify_video(&self, url: &str) -> Option<VideoIdentity> {
+        self.parse_url(url).ok().map(|matched| matched.identity())
+    }
+
     pub async fn get_content_location(
         &self,
         matched: &NormalizedProviderMatch,
@@ -82,7 +90,13 @@ impl ProvidersAppState {
             .registry
             .get(matched.provider)
             .ok_or_else(|| ApiError::InternalServerError("Provider not registered"))?;
-        provider.get_content_location(matched, &self.context).await
+        let result = provider.get_content_location(matched, &self.context).await;
+        record_operation(
+            Some(matched.provider),
+            ProviderOperation::ContentLocation,
+            result.is_ok(),
+        );
+        result
     }
 
     pub async fn fetch_metadata(
@@ -93,7 +107,13 @@ impl ProvidersAppState {
             .registry
             .get(matched.provider)
             .ok_or_else(|| ApiError::InternalServerError("Provider not registered"))?;
-        provider.fetch_metadata(matched, &self.context).await
+        let result = provider.fetch_metadata(matched, &self.context).await;
+        record_operation(
+            Some(matched.provider),
+            ProviderOperation::Metadata,
+            result.is_ok(),
+        );
+        result
     }
 }
 
@@ -122,6 +142,10 @@ pub async fn init_app_state(db: Arc<DbAppState>) -> Arc<ProvidersAppState> {
         Arc::new(OutplayedProvider) as Arc<dyn Provider>,
         Arc::new(GoogleDriveProvider) as Arc<dyn Provider>,
         Arc::new(MegaProvider) as Arc<dyn Provider>,
+        Arc::new(StreamableProvider) as Arc<dyn Provider>,
+        Arc::new(DropboxProvider) as Arc<dyn Provider>,
+        Arc::new(OneDriveProvider) as Arc<dyn Provider>,
+        Arc::new(DirectFileProvider) as Arc<dyn Provider>,
     ]);
 
     Arc::new(ProvidersAppState::new(registry, context))
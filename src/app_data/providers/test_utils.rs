@@ -12,7 +12,7 @@ use {
 };
 
 #[cfg(test)]
-fn set_token_encryption_env() {
+pub fn set_token_encryption_env() {
     std::env::set_var(
         "OAUTH_TOKEN_ENCRYPTION_KEY",
         "l/ai+o6bpEWvvdzYuiIHbbN5TeQo8pMaqbZ3u1bvEa4=",
@@ -289,6 +289,28 @@ pub async fn mock_youtube_videos_endpoint<'a>(
         .await
 }
 
+#[cfg(test)]
+pub async fn mock_youtube_missing_video_endpoint<'a>(
+    server: &'a MockServer,
+    video_id: &str,
+) -> Mock<'a> {
+    let video_id = video_id.to_owned();
+
+    server
+        .mock_async(move |when, then| {
+            when.method(GET)
+                .path("/youtube/v3/videos")
+                .query_param("part", "snippet")
+                .query_param("id", &video_id)
+                .header_exists("Authorization");
+
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(r#"{"items":[]}"#);
+        })
+        .await
+}
+
 #[cfg(test)]
 pub async fn mock_medal_content_endpoint<'a>(
     server: &'a MockServer,
@@ -308,6 +330,71 @@ pub async fn mock_medal_content_endpoint<'a>(
         .await
 }
 
+#[cfg(test)]
+pub async fn mock_google_drive_file_endpoint<'a>(
+    server: &'a MockServer,
+    file_id: &str,
+    body: &[u8],
+) -> Mock<'a> {
+    let file_id = file_id.to_owned();
+    let body = body.to_vec();
+
+    server
+        .mock_async(move |when, then| {
+            when.method(GET)
+                .path(format!("/drive/v3/files/{file_id}"))
+                .query_param("alt", "media")
+                .header_exists("Authorization");
+
+            then.status(206)
+                .header("content-type", "video/mp4")
+                .body(body);
+        })
+        .await
+}
+
+#[cfg(test)]
+pub async fn mock_streamable_video_endpoint<'a>(
+    server: &'a MockServer,
+    shortcode: &str,
+    file_url: &str,
+) -> Mock<'a> {
+    let shortcode = shortcode.to_owned();
+    let file_url = file_url.to_owned();
+
+    server
+        .mock_async(move |when, then| {
+            when.method(GET).path(format!("/videos/{shortcode}"));
+
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(format!(
+                    r#"{{"status":2,"files":{{"mp4":{{"url":"{file_url}","width":1280,"height":720}}}}}}"#
+                ));
+        })
+        .await
+}
+
+#[cfg(test)]
+pub async fn mock_onedrive_drive_item_endpoint<'a>(
+    server: &'a MockServer,
+    created_at: &str,
+) -> Mock<'a> {
+    let created_at = created_at.to_owned();
+
+    server
+        .mock_async(move |when, then| {
+            when.method(GET)
+                .path_prefix("/shares/u!")
+                .path_suffix("/driveItem");
+
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(format!(r#"{{"createdDateTime":"{created_at}"}}"#));
+        })
+        .await
+}
+
 #[cfg(test)]
 pub async fn mock_twitch_videos_endpoint<'a>(
     server: &'a MockServer,
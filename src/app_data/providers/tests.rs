@@ -9,10 +9,17 @@ use {
                 twitch::new_twitch_context, ProviderContext,
             },
             init_app_state,
-            list::{medal::MedalProvider, twitch::TwitchProvider, youtube::YouTubeProvider},
+            list::{
+                direct_file::DirectFileProvider, dropbox::DropboxProvider, medal::MedalProvider,
+                onedrive::OneDriveProvider, streamable::StreamableProvider, twitch::TwitchProvider,
+                youtube::YouTubeProvider,
+            },
+            model::NormalizedProviderMatch,
+            public_host::{is_public_ip, pinned_client, resolve_public_host},
             test_utils::{
                 clear_oauth_env, mock_google_token_endpoint, mock_google_token_refresh_endpoint,
-                mock_medal_content_endpoint, mock_patreon_token_endpoint,
+                mock_medal_content_endpoint, mock_onedrive_drive_item_endpoint,
+                mock_patreon_token_endpoint, mock_streamable_video_endpoint,
                 mock_twitch_token_endpoint, mock_twitch_videos_endpoint,
                 mock_youtube_videos_endpoint, raw_stored_oauth_refresh_token, seed_oauth_token,
                 set_oauth_env, stored_oauth_refresh_token,
@@ -23,7 +30,7 @@ use {
     chrono::{DateTime, Utc},
     httpmock::prelude::*,
     serial_test::serial,
-    std::sync::Arc,
+    std::{net::IpAddr, sync::Arc},
     url::Url,
 };
 
@@ -247,6 +254,46 @@ async fn match_and_normalize_all_supported_providers() {
                 "AbCDeF",
                 "https://mega.nz/file/AbCDeF#GhIjKlMn",
             ),
+            // Streamable
+            (
+                "https://streamable.com/e/abc12x?autoplay=1",
+                ProviderId::Streamable,
+                "abc12x",
+                "https://streamable.com/abc12x",
+            ),
+            // Dropbox
+            (
+                "https://www.dropbox.com/s/a1b2c3d4e5/run.mp4?dl=0",
+                ProviderId::Dropbox,
+                "a1b2c3d4e5",
+                "https://www.dropbox.com/s/a1b2c3d4e5/run.mp4",
+            ),
+            (
+                "https://www.dropbox.com/scl/fi/a1b2c3d4e5/run.mp4?rlkey=k3y&st=xyz&dl=0",
+                ProviderId::Dropbox,
+                "a1b2c3d4e5",
+                "https://www.dropbox.com/scl/fi/a1b2c3d4e5/run.mp4?rlkey=k3y",
+            ),
+            // OneDrive
+            (
+                "https://1drv.ms/v/s!AbCdEf123?e=xyz",
+                ProviderId::OneDrive,
+                "s!AbCdEf123",
+                "https://1drv.ms/v/s!AbCdEf123",
+            ),
+            (
+                "https://onedrive.live.com/?cid=ABC123&resid=ABC123!456&authkey=!XyZ&ithint=video",
+                ProviderId::OneDrive,
+                "ABC123!456",
+                "https://onedrive.live.com/redir?resid=ABC123!456&authkey=!XyZ",
+            ),
+            // Direct file
+            (
+                "https://files.example.com/raws/run.MP4?token=abc#t=5",
+                ProviderId::DirectFile,
+                "https://files.example.com/raws/run.MP4?token=abc",
+                "https://files.example.com/raws/run.MP4?token=abc",
+            ),
         ];
 
     for (input, provider, id, expected_norm) in cases {
@@ -288,6 +335,16 @@ async fn reject_unsupported_or_malformed_urls() {
         "https://drive.google.com/folders/",
         "https://drive.google.com/drive/u/0/my-drive",
         "https://drive.google.com/drive/u/0/shared-with-me",
+        "https://streamable.com/abc12x/extra",
+        "https://www.dropbox.com/home/raws",
+        "https://onedrive.live.com/about",
+        "http://files.example.com/run.mp4",
+        "https://127.0.0.1/run.mp4",
+        "https://localhost/run.mp4",
+        "https://nas.local/run.mp4",
+        "https://metadata.google.internal/run.mp4",
+        "https://intranet/run.mp4",
+        "https://files.example.com/run.txt",
     ];
 
     for url in invalid_urls {
@@ -766,3 +823,222 @@ async fn twitch_fetch_metadata_returns_published_at() {
 
     clear_oauth_env(OAuthProvider::Twitch);
 }
+
+#[cfg(test)]
+fn plain_context() -> ProviderContext {
+    ProviderContext {
+        http: reqwest::Client::new(),
+        db: None,
+        discord_auth: None,
+        google_auth: None,
+        patreon_auth: None,
+        twitch_auth: None,
+    }
+}
+
+#[actix_web::test]
+#[serial]
+async fn streamable_content_location_uses_api_file_url() {
+    let server = MockServer::start_async().await;
+    let streamable_mock = mock_streamable_video_endpoint(
+        &server,
+        "abc12x",
+        "//cdn-cf-east.streamable.com/video/mp4/abc12x.mp4?token=xyz",
+    )
+    .await;
+    std::env::set_var("STREAMABLE_API_BASE_URL", server.base_url());
+
+    let provider = StreamableProvider;
+    let matched = provider
+        .parse_url(&Url::parse("https://streamable.com/abc12x").unwrap())
+        .unwrap()
+        .expect("streamable url should match");
+
+    let location = provider
+        .get_content_location(&matched, &plain_context())
+        .await
+        .expect("get_content_location must succeed")
+        .expect("location must exist");
+
+    assert_eq!(streamable_mock.calls_async().await, 1);
+    assert_eq!(
+        location.url,
+        "https://cdn-cf-east.streamable.com/video/mp4/abc12x.mp4?token=xyz"
+    );
+
+    std::env::remove_var("STREAMABLE_API_BASE_URL");
+}
+
+#[tokio::test]
+async fn dropbox_content_location_forces_download() {
+    let provider = DropboxProvider;
+
+    for (input, expected) in [
+        (
+            "https://www.dropbox.com/s/a1b2c3d4e5/run.mp4?dl=0",
+            "https://www.dropbox.com/s/a1b2c3d4e5/run.mp4?dl=1",
+        ),
+        (
+            "https://www.dropbox.com/scl/fi/a1b2c3d4e5/run.mp4?rlkey=k3y&dl=0",
+            "https://www.dropbox.com/scl/fi/a1b2c3d4e5/run.mp4?rlkey=k3y&dl=1",
+        ),
+    ] {
+        let matched = provider
+            .parse_url(&Url::parse(input).unwrap())
+            .unwrap()
+            .expect("dropbox url should match");
+        let location = provider
+            .get_content_location(&matched, &plain_context())
+            .await
+            .expect("get_content_location must succeed")
+            .expect("location must exist");
+        assert_eq!(location.url, expected);
+    }
+}
+
+#[actix_web::test]
+#[serial]
+async fn onedrive_fetch_metadata_and_content_location_use_shares_api() {
+    let server = MockServer::start_async().await;
+    let onedrive_mock = mock_onedrive_drive_item_endpoint(&server, "2009-10-25T06:57:33Z").await;
+    std::env::set_var("ONEDRIVE_API_BASE_URL", server.base_url());
+
+    let provider = OneDriveProvider;
+    let matched = provider
+        .parse_url(&Url::parse("https://1drv.ms/v/s!AbCdEf123").unwrap())
+        .unwrap()
+        .expect("onedrive url should match");
+
+    let meta = provider
+        .fetch_metadata(&matched, &plain_context())
+        .await
+        .expect("fetch_metadata must succeed")
+        .expect("metadata must exist");
+    assert_eq!(onedrive_mock.calls_async().await, 1);
+    let expected: DateTime<Utc> = "2009-10-25T06:57:33Z".parse().unwrap();
+    assert_eq!(meta.published_at, Some(expected));
+
+    let location = provider
+        .get_content_location(&matched, &plain_context())
+        .await
+        .expect("get_content_location must succeed")
+        .expect("location must exist");
+    // base64url of "https://1drv.ms/v/s!AbCdEf123"
+    assert_eq!(
+        location.url,
+        format!(
+            "{}/shares/u!aHR0cHM6Ly8xZHJ2Lm1zL3YvcyFBYkNkRWYxMjM/root/content",
+            server.base_url()
+        )
+    );
+
+    std::env::remove_var("ONEDRIVE_API_BASE_URL");
+}
+
+#[test]
+fn only_public_addresses_are_reachable() {
+    let internal = [
+        "127.0.0.1",
+        "10.1.2.3",
+        "172.16.0.1",
+        "192.168.1.1",
+        "169.254.169.254",
+        "100.64.0.1",
+        "0.0.0.0",
+        "::1",
+        "::",
+        "fe80::1",
+        "fd00::1",
+        "::ffff:127.0.0.1",
+        "64:ff9b::a9fe:a9fe",
+    ];
+    for ip in internal {
+        assert!(
+            !is_public_ip(ip.parse::<IpAddr>().unwrap()),
+            "{ip} should not be public"
+        );
+    }
+
+    for ip in ["93.184.216.34", "2606:4700::1111", "::ffff:8.8.8.8"] {
+        assert!(
+            is_public_ip(ip.parse::<IpAddr>().unwrap()),
+            "{ip} should be public"
+        );
+    }
+}
+
+#[tokio::test]
+async fn internal_hosts_are_not_resolved() {
+    for url in [
+        "https://127.0.0.1/run.mp4",
+        "https://[::1]/run.mp4",
+        "https://[fd12:3456::1]/run.mp4",
+        "https://169.254.169.254/latest/meta-data/run.mp4",
+        "https://localhost/run.mp4",
+        "https://files.localhost/run.mp4",
+    ] {
+        let error = resolve_public_host(&Url::parse(url).unwrap())
+            .await
+            .expect_err(&format!("{url} should be rejected"));
+        assert_eq!(error.error_status_code, 400, "unexpected status for {url}");
+    }
+}
+
+#[tokio::test]
+async fn direct_file_refuses_to_fetch_internal_addresses() {
+    let provider = DirectFileProvider;
+    // what a public name resolving to an internal address ends up fetching
+    let matched = NormalizedProviderMatch {
+        provider: ProviderId::DirectFile,
+        content_id: "https://10.0.0.5/run.mp4".into(),
+        other_id: None,
+        normalized_url: "https://10.0.0.5/run.mp4".into(),
+    };
+
+    let error = provider
+        .fetch_metadata(&matched, &plain_context())
+        .await
+        .expect_err("internal address must not be fetched");
+    assert_eq!(error.error_status_code, 400);
+
+    let error = provider
+        .get_content_location(&matched, &plain_context())
+        .await
+        .expect_err("internal address must not be probed");
+    assert_eq!(error.error_status_code, 400);
+}
+
+#[tokio::test]
+async fn pinned_client_does_not_follow_redirects() {
+    let server = MockServer::start_async().await;
+    let redirect = server
+        .mock_async(|when, then| {
+            when.method("HEAD").path("/run.mp4");
+            then.status(302)
+                .header("Location", server.url("/internal.mp4"));
+        })
+        .await;
+    let target = server
+        .mock_async(|when, then| {
+            when.path("/internal.mp4");
+            then.status(200);
+        })
+        .await;
+
+    // the public name is pinned to the mock server, standing in for the addresses it resolved to
+    let url = Url::parse(&format!(
+        "http://files.example.com:{}/run.mp4",
+        server.port()
+    ))
+    .unwrap();
+    let response = pinned_client(&url, &[*server.address()])
+        .unwrap()
+        .head(url)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 302);
+    assert_eq!(redirect.calls_async().await, 1);
+    assert_eq!(target.calls_async().await, 0);
+}
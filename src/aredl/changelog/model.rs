@@ -1,13 +1,16 @@
 use crate::app_data::db::DbConnection;
 use crate::aredl::levels::{BaseLevel, LevelStatus};
 use crate::error_handler::ApiError;
+use crate::notifications::{NotificationList, WebsocketNotification, WebsocketNotificationType};
 use crate::page_helper::{PageQuery, Paginated};
 use crate::schema::aredl::{levels, position_history};
 use chrono::{DateTime, Utc};
 use diesel::pg::Pg;
 use diesel::Selectable;
 use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
 use utoipa::ToSchema;
+use uuid::Uuid;
 
 use diesel::prelude::*;
 #[derive(Serialize, Deserialize, Queryable, Selectable, Debug)]
@@ -186,7 +189,127 @@ impl ChangelogPage {
     }
 }
 
+impl ChangelogEntry {
+    /// Index of the latest changelog entry of a level, if it has any.
+    pub fn latest_index(conn: &mut DbConnection, level_id: Uuid) -> Result<Option<i32>, ApiError> {
+        let index = position_history::table
+            .filter(position_history::affected_level.eq(level_id))
+            .select(diesel::dsl::max(position_history::i))
+            .first::<Option<i32>>(conn)?;
+        Ok(index)
+    }
+
+    /// Finds the changelog entries of a level recorded after `since` and up to `until`, oldest first.
+    /// Entries are not bounded on a side that is `None`.
+    pub fn find_for_level_between(
+        conn: &mut DbConnection,
+        level_id: Uuid,
+        since: Option<i32>,
+        until: Option<i32>,
+    ) -> Result<Vec<Self>, ApiError> {
+        let (level_affected, level_above, level_below) = diesel::alias!(
+            levels as level_affected,
+            levels as level_above,
+            levels as level_below,
+        );
+
+        let records = position_history::table
+            .filter(position_history::affected_level.eq(level_id))
+            .filter(position_history::i.gt(since.unwrap_or(0)))
+            .filter(position_history::i.le(until.unwrap_or(i32::MAX)))
+            .order(position_history::i.asc())
+            .inner_join(
+                level_affected
+                    .on(position_history::affected_level.eq(level_affected.field(levels::id))),
+            )
+            .left_join(
+                level_above
+                    .on(position_history::level_above.eq(level_above.field(levels::id).nullable())),
+            )
+            .left_join(
+                level_below
+                    .on(position_history::level_below.eq(level_below.field(levels::id).nullable())),
+            )
+            .select((
+                ChangelogEntryData::as_select(),
+                level_affected.fields(<BaseLevel as Selectable<Pg>>::construct_selection()),
+                level_above
+                    .fields(<BaseLevel as Selectable<Pg>>::construct_selection())
+                    .nullable(),
+                level_below
+                    .fields(<BaseLevel as Selectable<Pg>>::construct_selection())
+                    .nullable(),
+            ))
+            .load::<(
+                ChangelogEntryData,
+                BaseLevel,
+                Option<BaseLevel>,
+                Option<BaseLevel>,
+            )>(conn)?;
+
+        Ok(records
+            .into_iter()
+            .map(|(entry, affected, above, below)| ChangelogEntry {
+                action: ChangelogAction::from_data(
+                    &entry,
+                    &affected,
+                    above.as_ref(),
+                    below.as_ref(),
+                ),
+                created_at: entry.created_at,
+                affected_level: affected,
+                level_above: above,
+                level_below: below,
+            })
+            .collect())
+    }
+
+    /// Emits a list change notification for every changelog entry of a level recorded after `since` and up to `until`.
+    pub fn notify_between(
+        conn: &mut DbConnection,
+        notify_tx: &broadcast::Sender<WebsocketNotification>,
+        level_id: Uuid,
+        since: Option<i32>,
+        until: Option<i32>,
+    ) {
+        let entries = match Self::find_for_level_between(conn, level_id, since, until) {
+            Ok(entries) => entries,
+            Err(error) => {
+                tracing::error!(
+                    "Failed to load changelog entries of level {level_id} for notifications: {error}"
+                );
+                return;
+            }
+        };
+
+        for entry in entries {
+            if let Some(notification_type) = entry.action.websocket_type() {
+                WebsocketNotification::send(
+                    conn,
+                    notify_tx,
+                    notification_type,
+                    Some(NotificationList::Aredl),
+                    &entry,
+                );
+            }
+        }
+    }
+}
+
 impl ChangelogAction {
+    pub fn websocket_type(&self) -> Option<WebsocketNotificationType> {
+        match self {
+            Self::Placed { .. } => Some(WebsocketNotificationType::LevelPlaced),
+            Self::Raised { .. } => Some(WebsocketNotificationType::LevelRaised),
+            Self::Lowered { .. } => Some(WebsocketNotificationType::LevelLowered),
+            Self::Swapped { .. } => Some(WebsocketNotificationType::LevelSwapped),
+            Self::MovedToLegacy { .. } => Some(WebsocketNotificationType::LevelMovedToLegacy),
+            Self::MovedFromLegacy { .. } => Some(WebsocketNotificationType::LevelMovedFromLegacy),
+            Self::Removed { .. } => Some(WebsocketNotificationType::LevelRemoved),
+            Self::Pending | Self::Unknown { .. } => None,
+        }
+    }
+
     pub fn from_data(
         entry: &ChangelogEntryData,
         level: &BaseLevel,
@@ -1,5 +1,5 @@
 use crate::app_data::db::DbConnection;
-use crate::aredl::leaderboard::LeaderboardOrder;
+use crate::aredl::leaderboard::{LeaderboardOrder, PastRanks};
 use crate::aredl::levels::BaseLevel;
 use crate::clans::Clan;
 use crate::error_handler::ApiError;
@@ -33,6 +33,12 @@ pub struct ClansLeaderboardEntry {
 pub struct ClansLeaderboardEntryResolved {
     /// Rank of the clan, sorted by total points (including packs).
     pub rank: i32,
+    /// How many places the clan moved up, sorted by total points (including packs), since yesterday.
+    /// Negative if the clan moved down, not set if the clan was not ranked yesterday.
+    pub rank_change_day: Option<i32>,
+    /// How many places the clan moved up, sorted by total points (including packs), since last week.
+    /// Negative if the clan moved down, not set if the clan was not ranked last week.
+    pub rank_change_week: Option<i32>,
     /// Rank of the clan, sorted by count of extremes completed.
     pub extremes_rank: i32,
     /// Rank of the clan, sorted by hardest completed level position.
@@ -112,10 +118,18 @@ impl ClansLeaderboardPage {
             ))
             .load(conn)?;
 
+        let clan_ids = raw_entries
+            .iter()
+            .map(|(entry, ..)| entry.clan_id)
+            .collect::<Vec<_>>();
+        let past_ranks = PastRanks::for_clans(conn, &clan_ids)?;
+
         let entries_resolved = raw_entries
             .into_iter()
             .map(|(entry, clan, hardest)| ClansLeaderboardEntryResolved {
                 rank: entry.rank,
+                rank_change_day: past_ranks.change_since_yesterday(&entry.clan_id, entry.rank),
+                rank_change_week: past_ranks.change_since_last_week(&entry.clan_id, entry.rank),
                 extremes_rank: entry.extremes_rank,
                 hardest_rank: entry.hardest_rank,
                 clan,
@@ -0,0 +1,294 @@
+use crate::app_data::db::DbConnection;
+use crate::error_handler::ApiError;
+use crate::schema::aredl::{
+    clans_leaderboard, clans_leaderboard_snapshots, country_leaderboard,
+    country_leaderboard_snapshots, user_leaderboard, user_leaderboard_snapshots,
+};
+use chrono::{Days, NaiveDate, Utc};
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use diesel::sql_types::Date;
+use diesel::upsert::excluded;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::hash::Hash;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// How far back the rank history goes when no start date is given.
+const DEFAULT_HISTORY_DAYS: u64 = 90;
+
+/// The leaderboard standing of a user at the end of a day.
+#[derive(Serialize, Deserialize, Queryable, Selectable, Debug, ToSchema)]
+#[diesel(table_name = user_leaderboard_snapshots, check_for_backend(Pg))]
+pub struct RankHistoryEntry {
+    /// Day of the snapshot.
+    pub snapshot_date: NaiveDate,
+    /// Rank of the user in the global leaderboard, sorted by total points (including packs).
+    pub rank: i32,
+    /// Rank of the user in the global leaderboard, sorted by total points (excluding packs).
+    pub raw_rank: i32,
+    /// Rank of the user in the global leaderboard, sorted by count of extremes completed.
+    pub extremes_rank: i32,
+    /// Rank of the user in the global leaderboard, sorted by hardest completed level position.
+    pub hardest_rank: i32,
+    /// Rank of the user in the country leaderboard, sorted by total points (including packs).
+    pub country_rank: i32,
+    /// Total points of the user, including pack points.
+    pub total_points: i32,
+    /// Pack points of the user.
+    pub pack_points: i32,
+    /// Count of extremes the user has completed.
+    pub extremes: i32,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct RankHistoryQuery {
+    /// Only include snapshots since this day. Defaults to 90 days ago.
+    pub since: Option<NaiveDate>,
+    /// Only include snapshots until this day. Defaults to today.
+    pub until: Option<NaiveDate>,
+}
+
+/// Ranks of the leaderboard entries from yesterday's and last week's snapshots.
+pub struct PastRanks<K> {
+    yesterday: HashMap<K, i32>,
+    last_week: HashMap<K, i32>,
+}
+
+/// Stores today's standings of the user, country and clan leaderboards.
+/// Snapshots taken later on the same day replace the earlier ones, so each day keeps its last standings.
+pub fn take_leaderboard_snapshots(conn: &mut DbConnection) -> Result<(), ApiError> {
+    let today = Utc::now().date_naive();
+    conn.transaction(|conn| -> Result<(), ApiError> {
+        diesel::insert_into(user_leaderboard_snapshots::table)
+            .values(user_leaderboard::table.select((
+                user_leaderboard::user_id,
+                today.into_sql::<Date>(),
+                user_leaderboard::rank,
+                user_leaderboard::raw_rank,
+                user_leaderboard::extremes_rank,
+                user_leaderboard::hardest_rank,
+                user_leaderboard::country_rank,
+                user_leaderboard::total_points,
+                user_leaderboard::pack_points,
+                user_leaderboard::extremes,
+            )))
+            .into_columns((
+                user_leaderboard_snapshots::user_id,
+                user_leaderboard_snapshots::snapshot_date,
+                user_leaderboard_snapshots::rank,
+                user_leaderboard_snapshots::raw_rank,
+                user_leaderboard_snapshots::extremes_rank,
+                user_leaderboard_snapshots::hardest_rank,
+                user_leaderboard_snapshots::country_rank,
+                user_leaderboard_snapshots::total_points,
+                user_leaderboard_snapshots::pack_points,
+                user_leaderboard_snapshots::extremes,
+            ))
+            .on_conflict((
+                user_leaderboard_snapshots::user_id,
+                user_leaderboard_snapshots::snapshot_date,
+            ))
+            .do_update()
+            .set((
+                user_leaderboard_snapshots::rank.eq(excluded(user_leaderboard_snapshots::rank)),
+                user_leaderboard_snapshots::raw_rank
+                    .eq(excluded(user_leaderboard_snapshots::raw_rank)),
+                user_leaderboard_snapshots::extremes_rank
+                    .eq(excluded(user_leaderboard_snapshots::extremes_rank)),
+                user_leaderboard_snapshots::hardest_rank
+                    .eq(excluded(user_leaderboard_snapshots::hardest_rank)),
+                user_leaderboard_snapshots::country_rank
+                    .eq(excluded(user_leaderboard_snapshots::country_rank)),
+                user_leaderboard_snapshots::total_points
+                    .eq(excluded(user_leaderboard_snapshots::total_points)),
+                user_leaderboard_snapshots::pack_points
+                    .eq(excluded(user_leaderboard_snapshots::pack_points)),
+                user_leaderboard_snapshots::extremes
+                    .eq(excluded(user_leaderboard_snapshots::extremes)),
+            ))
+            .execute(conn)?;
+
+        diesel::insert_into(country_leaderboard_snapshots::table)
+            .values(country_leaderboard::table.select((
+                country_leaderboard::country,
+                today.into_sql::<Date>(),
+                country_leaderboard::rank,
+                country_leaderboard::extremes_rank,
+                country_leaderboard::hardest_rank,
+                country_leaderboard::level_points,
+                country_leaderboard::extremes,
+            )))
+            .into_columns((
+                country_leaderboard_snapshots::country,
+                country_leaderboard_snapshots::snapshot_date,
+                country_leaderboard_snapshots::rank,
+                country_leaderboard_snapshots::extremes_rank,
+                country_leaderboard_snapshots::hardest_rank,
+                country_leaderboard_snapshots::level_points,
+                country_leaderboard_snapshots::extremes,
+            ))
+            .on_conflict((
+                country_leaderboard_snapshots::country,
+                country_leaderboard_snapshots::snapshot_date,
+            ))
+            .do_update()
+            .set((
+                country_leaderboard_snapshots::rank
+                    .eq(excluded(country_leaderboard_snapshots::rank)),
+                country_leaderboard_snapshots::extremes_rank
+                    .eq(excluded(country_leaderboard_snapshots::extremes_rank)),
+                country_leaderboard_snapshots::hardest_rank
+                    .eq(excluded(country_leaderboard_snapshots::hardest_rank)),
+                country_leaderboard_snapshots::level_points
+                    .eq(excluded(country_leaderboard_snapshots::level_points)),
+                country_leaderboard_snapshots::extremes
+                    .eq(excluded(country_leaderboard_snapshots::extremes)),
+            ))
+            .execute(conn)?;
+
+        diesel::insert_into(clans_leaderboard_snapshots::table)
+            .values(clans_leaderboard::table.select((
+                clans_leaderboard::clan_id,
+                today.into_sql::<Date>(),
+                clans_leaderboard::rank,
+                clans_leaderboard::extremes_rank,
+                clans_leaderboard::hardest_rank,
+                clans_leaderboard::level_points,
+                clans_leaderboard::extremes,
+            )))
+            .into_columns((
+                clans_leaderboard_snapshots::clan_id,
+                clans_leaderboard_snapshots::snapshot_date,
+                clans_leaderboard_snapshots::rank,
+                clans_leaderboard_snapshots::extremes_rank,
+                clans_leaderboard_snapshots::hardest_rank,
+                clans_leaderboard_snapshots::level_points,
+                clans_leaderboard_snapshots::extremes,
+            ))
+            .on_conflict((
+                clans_leaderboard_snapshots::clan_id,
+                clans_leaderboard_snapshots::snapshot_date,
+            ))
+            .do_update()
+            .set((
+                clans_leaderboard_snapshots::rank.eq(excluded(clans_leaderboard_snapshots::rank)),
+                clans_leaderboard_snapshots::extremes_rank
+                    .eq(excluded(clans_leaderboard_snapshots::extremes_rank)),
+                clans_leaderboard_snapshots::hardest_rank
+                    .eq(excluded(clans_leaderboard_snapshots::hardest_rank)),
+                clans_leaderboard_snapshots::level_points
+                    .eq(excluded(clans_leaderboard_snapshots::level_points)),
+                clans_leaderboard_snapshots::extremes
+                    .eq(excluded(clans_leaderboard_snapshots::extremes)),
+            ))
+            .execute(conn)?;
+
+        Ok(())
+    })
+}
+
+impl RankHistoryEntry {
+    /// Lists the daily standings of a user, oldest first.
+    pub fn find_all(
+        conn: &mut DbConnection,
+        user_id: Uuid,
+        query: &RankHistoryQuery,
+    ) -> Result<Vec<Self>, ApiError> {
+        let until = query.until.unwrap_or_else(|| Utc::now().date_naive());
+        let since = query
+            .since
+            .unwrap_or_else(|| until - Days::new(DEFAULT_HISTORY_DAYS));
+        if since > until {
+            return Err(ApiError::BadRequest("`since` must be before `until`."));
+        }
+
+        Ok(user_leaderboard_snapshots::table
+            .filter(user_leaderboard_snapshots::user_id.eq(user_id))
+            .filter(user_leaderboard_snapshots::snapshot_date.between(since, until))
+            .order(user_leaderboard_snapshots::snapshot_date.asc())
+            .select(RankHistoryEntry::as_select())
+            .load::<RankHistoryEntry>(conn)?)
+    }
+}
+
+fn comparison_dates() -> (NaiveDate, NaiveDate) {
+    let today = Utc::now().date_naive();
+    (today - Days::new(1), today - Days::new(7))
+}
+
+impl<K: Eq + Hash> PastRanks<K> {
+    fn from_rows(rows: Vec<(K, NaiveDate, i32)>, yesterday: NaiveDate) -> Self {
+        let mut past = Self {
+            yesterday: HashMap::new(),
+            last_week: HashMap::new(),
+        };
+        for (key, date, rank) in rows {
+            if date == yesterday {
+                past.yesterday.insert(key, rank);
+            } else {
+                past.last_week.insert(key, rank);
+            }
+        }
+        past
+    }
+
+    /// How many places the entry moved up since yesterday, negative if it moved down.
+    pub fn change_since_yesterday(&self, key: &K, rank: i32) -> Option<i32> {
+        self.yesterday.get(key).map(|past| past - rank)
+    }
+
+    /// How many places the entry moved up since last week, negative if it moved down.
+    pub fn change_since_last_week(&self, key: &K, rank: i32) -> Option<i32> {
+        self.last_week.get(key).map(|past| past - rank)
+    }
+}
+
+impl PastRanks<Uuid> {
+    /// Finds the past global ranks, sorted by total points, of the given users.
+    pub fn for_users(conn: &mut DbConnection, user_ids: &[Uuid]) -> Result<Self, ApiError> {
+        let (yesterday, last_week) = comparison_dates();
+        let rows = user_leaderboard_snapshots::table
+            .filter(user_leaderboard_snapshots::user_id.eq_any(user_ids))
+            .filter(user_leaderboard_snapshots::snapshot_date.eq_any([yesterday, last_week]))
+            .select((
+                user_leaderboard_snapshots::user_id,
+                user_leaderboard_snapshots::snapshot_date,
+                user_leaderboard_snapshots::rank,
+            ))
+            .load::<(Uuid, NaiveDate, i32)>(conn)?;
+        Ok(Self::from_rows(rows, yesterday))
+    }
+
+    /// Finds the past ranks, sorted by total points, of the given clans.
+    pub fn for_clans(conn: &mut DbConnection, clan_ids: &[Uuid]) -> Result<Self, ApiError> {
+        let (yesterday, last_week) = comparison_dates();
+        let rows = clans_leaderboard_snapshots::table
+            .filter(clans_leaderboard_snapshots::clan_id.eq_any(clan_ids))
+            .filter(clans_leaderboard_snapshots::snapshot_date.eq_any([yesterday, last_week]))
+            .select((
+                clans_leaderboard_snapshots::clan_id,
+                clans_leaderboard_snapshots::snapshot_date,
+                clans_leaderboard_snapshots::rank,
+            ))
+            .load::<(Uuid, NaiveDate, i32)>(conn)?;
+        Ok(Self::from_rows(rows, yesterday))
+    }
+}
+
+impl PastRanks<i32> {
+    /// Finds the past ranks, sorted by total points, of every country.
+    pub fn for_countries(conn: &mut DbConnection) -> Result<Self, ApiError> {
+        let (yesterday, last_week) = comparison_dates();
+        let rows = country_leaderboard_snapshots::table
+            .filter(country_leaderboard_snapshots::snapshot_date.eq_any([yesterday, last_week]))
+            .select((
+                country_leaderboard_snapshots::country,
+                country_leaderboard_snapshots::snapshot_date,
+                country_leaderboard_snapshots::rank,
+            ))
+            .load::<(i32, NaiveDate, i32)>(conn)?;
+        Ok(Self::from_rows(rows, yesterday))
+    }
+}
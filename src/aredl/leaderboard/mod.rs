@@ -1,5 +1,6 @@
 mod clans;
 mod countries;
+mod history;
 mod model;
 mod routes;
 
@@ -8,5 +9,6 @@ pub mod test_utils;
 #[cfg(test)]
 mod tests;
 
+pub use history::*;
 pub use model::*;
 pub use routes::{init_routes, ApiDoc};
@@ -20,3 +20,21 @@ pub async fn refresh_test_leaderboards(db: &Arc<DbAppState>) {
         .execute(conn)
         .expect("Failed to update position history");
 }
+
+/// Moves every leaderboard snapshot the given amount of days into the past.
+#[cfg(test)]
+pub async fn backdate_test_leaderboard_snapshots(db: &Arc<DbAppState>, days: i32) {
+    let conn = &mut db.connection().unwrap();
+    for table in [
+        "user_leaderboard_snapshots",
+        "country_leaderboard_snapshots",
+        "clans_leaderboard_snapshots",
+    ] {
+        diesel::sql_query(format!(
+            "UPDATE aredl.{table} SET snapshot_date = snapshot_date - $1"
+        ))
+        .bind::<diesel::sql_types::Integer, _>(days)
+        .execute(conn)
+        .expect("Failed to backdate leaderboard snapshots");
+    }
+}
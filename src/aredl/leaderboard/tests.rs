@@ -2,7 +2,10 @@
 use {
     crate::aredl::records::test_utils::create_test_record,
     crate::{
-        aredl::leaderboard::test_utils::refresh_test_leaderboards,
+        aredl::leaderboard::{
+            take_leaderboard_snapshots,
+            test_utils::{backdate_test_leaderboard_snapshots, refresh_test_leaderboards},
+        },
         aredl::levels::test_utils::{
             create_test_level, create_test_level_with_record, get_test_level,
         },
@@ -317,3 +320,68 @@ async fn get_clans_leaderboard_with_filters() {
         mkl.id
     );
 }
+
+#[actix_web::test]
+async fn leaderboards_include_rank_changes() {
+    let (app, db, _, _) = init_test_app().await;
+
+    let (climber, _) = create_test_user(&db, None).await;
+    let (leader, _) = create_test_user(&db, None).await;
+    create_test_level_with_record(&db, climber).await;
+    create_test_level_with_record(&db, leader).await;
+    create_test_level_with_record(&db, leader).await;
+
+    set_test_user_country(&db, climber, Some(840)).await;
+    set_test_user_country(&db, leader, Some(124)).await;
+    let climber_clan = create_test_clan(&db).await;
+    create_test_clan_member(&db, climber_clan, climber, 0).await;
+    let leader_clan = create_test_clan(&db).await;
+    create_test_clan_member(&db, leader_clan, leader, 0).await;
+
+    refresh_test_leaderboards(&db).await;
+    take_leaderboard_snapshots(&mut db.connection().unwrap()).unwrap();
+    backdate_test_leaderboard_snapshots(&db, 1).await;
+
+    // newer levels are placed higher, so these are worth more than the leader's
+    create_test_level_with_record(&db, climber).await;
+    create_test_level_with_record(&db, climber).await;
+    refresh_test_leaderboards(&db).await;
+
+    let req = test::TestRequest::get()
+        .uri("/aredl/leaderboard/")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: serde_json::Value = read_body_json(resp).await;
+    assert_eq!(body["data"][0]["user"]["id"], climber.to_string());
+    assert_eq!(body["data"][0]["rank_change_day"], 1);
+    assert!(body["data"][0]["rank_change_week"].is_null());
+    assert_eq!(body["data"][1]["user"]["id"], leader.to_string());
+    assert_eq!(body["data"][1]["rank_change_day"], -1);
+
+    let req = test::TestRequest::get()
+        .uri("/aredl/leaderboard/countries")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    let body: serde_json::Value = read_body_json(resp).await;
+    assert_eq!(body["data"][0]["country"], 840);
+    assert_eq!(body["data"][0]["rank_change_day"], 1);
+
+    let req = test::TestRequest::get()
+        .uri("/aredl/leaderboard/clans")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    let body: serde_json::Value = read_body_json(resp).await;
+    assert_eq!(body["data"][0]["clan"]["id"], climber_clan.to_string());
+    assert_eq!(body["data"][0]["rank_change_day"], 1);
+
+    // a week later, only the week-old snapshot is compared against
+    backdate_test_leaderboard_snapshots(&db, 6).await;
+    let req = test::TestRequest::get()
+        .uri("/aredl/leaderboard/")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    let body: serde_json::Value = read_body_json(resp).await;
+    assert!(body["data"][0]["rank_change_day"].is_null());
+    assert_eq!(body["data"][0]["rank_change_week"], 1);
+}
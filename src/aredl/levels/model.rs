@@ -1,7 +1,9 @@
 use crate::app_data::db::DbConnection;
+use crate::aredl::changelog::ChangelogEntry;
 use crate::aredl::levels::records::LevelResolvedRecord;
 use crate::aredl::records::Record;
 use crate::error_handler::ApiError;
+use crate::notifications::WebsocketNotification;
 use crate::schema::aredl::{levels, position_history, position_history_full_view, records};
 use crate::schema::users;
 use crate::users::{BaseUser, BaseUserWithBanLevel};
@@ -10,6 +12,7 @@ use diesel_derive_enum::DbEnum;
 use serde::{Deserialize, Serialize};
 use serde_with::rust::double_option;
 use std::collections::HashSet;
+use tokio::sync::broadcast;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
@@ -307,20 +310,45 @@ impl Level {
         Ok(time_machine_levels)
     }
 
-    pub fn create(conn: &mut DbConnection, level: LevelPlace) -> Result<Self, ApiError> {
+    pub fn create(
+        conn: &mut DbConnection,
+        notify_tx: &broadcast::Sender<WebsocketNotification>,
+        level: LevelPlace,
+    ) -> Result<Self, ApiError> {
         let level = diesel::insert_into(levels::table)
             .values(level)
             .returning(Self::as_select())
-            .get_result(conn)?;
+            .get_result::<Self>(conn)?;
+        ChangelogEntry::notify_between(conn, notify_tx, level.id, None, None);
         Ok(level)
     }
 
-    pub fn update(conn: &mut DbConnection, id: Uuid, level: LevelUpdate) -> Result<Self, ApiError> {
-        let level = diesel::update(levels::table)
-            .set(level)
-            .filter(levels::id.eq(id))
-            .returning(Self::as_select())
-            .get_result(conn)?;
+    pub fn update(
+        conn: &mut DbConnection,
+        notify_tx: &broadcast::Sender<WebsocketNotification>,
+        id: Uuid,
+        level: LevelUpdate,
+    ) -> Result<Self, ApiError> {
+        // the position_history triggers record position and status changes, the entries between these indexes were caused by this update
+        let (since, until, level) = conn.transaction(
+            |conn| -> Result<(Option<i32>, Option<i32>, Self), ApiError> {
+                // concurrent updates of the same level wait here instead of reading the same index
+                levels::table
+                    .filter(levels::id.eq(id))
+                    .select(levels::id)
+                    .for_update()
+                    .first::<Uuid>(conn)?;
+                let since = ChangelogEntry::latest_index(conn, id)?;
+                let level = diesel::update(levels::table)
+                    .set(level)
+                    .filter(levels::id.eq(id))
+                    .returning(Self::as_select())
+                    .get_result::<Self>(conn)?;
+                let until = ChangelogEntry::latest_index(conn, id)?;
+                Ok((since, until, level))
+            },
+        )?;
+        ChangelogEntry::notify_between(conn, notify_tx, level.id, since, until);
         Ok(level)
     }
 }
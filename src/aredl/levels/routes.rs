@@ -7,8 +7,10 @@ use crate::aredl::levels::{
 use crate::auth::{Authenticated, Permission, UserAuth};
 use crate::cache_control::CacheController;
 use crate::error_handler::ApiError;
+use crate::notifications::WebsocketNotification;
 use actix_web::{get, patch, post, web, HttpResponse};
 use std::sync::Arc;
+use tokio::sync::broadcast;
 use tracing_actix_web::RootSpan;
 use utoipa::OpenApi;
 
@@ -63,11 +65,18 @@ async fn list(
 async fn create(
     db: web::Data<Arc<DbAppState>>,
     level: web::Json<LevelPlace>,
+    notify_tx: web::Data<broadcast::Sender<WebsocketNotification>>,
     root_span: RootSpan,
 ) -> Result<HttpResponse, ApiError> {
     root_span.record("body", tracing::field::debug(&level));
-    let level =
-        web::block(move || Level::create(&mut db.connection()?, level.into_inner())).await??;
+    let level = web::block(move || {
+        Level::create(
+            &mut db.connection()?,
+            notify_tx.get_ref(),
+            level.into_inner(),
+        )
+    })
+    .await??;
     Ok(HttpResponse::Ok().json(level))
 }
 
@@ -88,13 +97,14 @@ async fn update(
     db: web::Data<Arc<DbAppState>>,
     level_id: web::Path<String>,
     level: web::Json<LevelUpdate>,
+    notify_tx: web::Data<broadcast::Sender<WebsocketNotification>>,
     root_span: RootSpan,
 ) -> Result<HttpResponse, ApiError> {
     root_span.record("body", tracing::field::debug(&level));
     let level = web::block(move || {
         let conn = &mut db.connection()?;
         let level_id = resolve_level_id(conn, level_id.into_inner().as_str())?;
-        Level::update(conn, level_id, level.into_inner())
+        Level::update(conn, notify_tx.get_ref(), level_id, level.into_inner())
     })
     .await??;
     Ok(HttpResponse::Ok().json(level))
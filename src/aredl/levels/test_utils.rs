@@ -85,6 +85,19 @@ pub async fn set_test_level_position(db: &Arc<DbAppState>, level_id: Uuid, posit
         .expect("Failed to update test aredl level position");
 }
 
+#[cfg(test)]
+pub async fn set_test_level_tags(db: &Arc<DbAppState>, level_id: Uuid, tags: &[&str]) {
+    diesel::update(levels::table.filter(levels::id.eq(level_id)))
+        .set(
+            levels::tags.eq(tags
+                .iter()
+                .map(|tag| Some((*tag).to_owned()))
+                .collect::<Vec<_>>()),
+        )
+        .execute(&mut db.connection().unwrap())
+        .expect("Failed to update test aredl level tags");
+}
+
 #[cfg(test)]
 pub async fn create_test_level_with_record(db: &Arc<DbAppState>, user_id: Uuid) -> (Uuid, Uuid) {
     let level_id = create_test_level(db).await;
@@ -109,6 +122,14 @@ pub async fn set_test_level_gd_id(db: &Arc<DbAppState>, level_id: Uuid, gd_id: i
         .expect("Failed to set test aredl level GD ID");
 }
 
+#[cfg(test)]
+pub async fn set_test_level_two_player(db: &Arc<DbAppState>, level_id: Uuid, two_player: bool) {
+    diesel::update(levels::table.filter(levels::id.eq(level_id)))
+        .set(levels::two_player.eq(two_player))
+        .execute(&mut db.connection().unwrap())
+        .expect("Failed to set test aredl level two player flag");
+}
+
 #[cfg(test)]
 pub async fn refresh_test_position_history(db: &Arc<DbAppState>) {
     sql_query("REFRESH MATERIALIZED VIEW aredl.position_history_full_view")
@@ -15,7 +15,7 @@ use {
     },
     actix_web::test::{self, read_body_json},
     serde_json::json,
-    tokio::time::{sleep, Duration},
+    tokio::time::{sleep, timeout, Duration},
 };
 
 #[actix_web::test]
@@ -195,6 +195,135 @@ async fn update_level() {
     assert_eq!(body["name"].to_string(), update_data["name"].to_string());
 }
 
+#[actix_web::test]
+async fn create_level_notifies_placement() {
+    let (app, db, auth, notify_tx) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, Some(Permission::LevelModify)).await;
+    let token =
+        create_test_token(user_id, &auth.jwt_encoding_key).expect("Failed to generate token");
+    let mut rx = notify_tx.subscribe();
+
+    let req = test::TestRequest::post()
+        .uri("/aredl/levels")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({
+            "name": "Placed Level",
+            "position": 1,
+            "level_id": rand::random_range(1..=100_000_000),
+            "publisher_id": user_id.to_string(),
+            "status": "MainList",
+            "two_player": false
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: serde_json::Value = read_body_json(resp).await;
+
+    let notification = timeout(Duration::from_secs(1), rx.recv())
+        .await
+        .expect("Timed out waiting for websocket notification")
+        .expect("Failed to receive websocket notification");
+
+    assert_eq!(notification.notification_type, "LEVEL_PLACED");
+    assert_eq!(notification.list.as_deref(), Some("aredl"));
+    assert_eq!(notification.data["affected_level"]["id"], body["id"]);
+    assert_eq!(notification.data["action"]["Placed"]["new_position"], 1);
+    assert_eq!(notification.data["action"]["Placed"]["status"], "MainList");
+}
+
+#[actix_web::test]
+async fn move_level_notifies_raise() {
+    let (app, db, auth, notify_tx) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, Some(Permission::LevelModify)).await;
+    let token =
+        create_test_token(user_id, &auth.jwt_encoding_key).expect("Failed to generate token");
+    let level_id = create_test_level(&db).await;
+    // every new test level is placed at the top, pushing this one down
+    for _ in 0..3 {
+        create_test_level(&db).await;
+    }
+    let mut rx = notify_tx.subscribe();
+
+    let req = test::TestRequest::patch()
+        .uri(&format!("/aredl/levels/{level_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"position": 1}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    let notification = timeout(Duration::from_secs(1), rx.recv())
+        .await
+        .expect("Timed out waiting for websocket notification")
+        .expect("Failed to receive websocket notification");
+
+    assert_eq!(notification.notification_type, "LEVEL_RAISED");
+    assert_eq!(notification.list.as_deref(), Some("aredl"));
+    assert_eq!(
+        notification.data["affected_level"]["id"],
+        level_id.to_string()
+    );
+    assert_eq!(notification.data["action"]["Raised"]["new_position"], 1);
+    assert!(
+        notification.data["action"]["Raised"]["old_position"]
+            .as_i64()
+            .unwrap()
+            >= 4
+    );
+}
+
+#[actix_web::test]
+async fn remove_level_notifies_removal() {
+    let (app, db, auth, notify_tx) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, Some(Permission::LevelModify)).await;
+    let token =
+        create_test_token(user_id, &auth.jwt_encoding_key).expect("Failed to generate token");
+    let level_id = create_test_level(&db).await;
+    let mut rx = notify_tx.subscribe();
+
+    let req = test::TestRequest::patch()
+        .uri(&format!("/aredl/levels/{level_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"status": "Removed"}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    let notification = timeout(Duration::from_secs(1), rx.recv())
+        .await
+        .expect("Timed out waiting for websocket notification")
+        .expect("Failed to receive websocket notification");
+
+    assert_eq!(notification.notification_type, "LEVEL_REMOVED");
+    assert_eq!(
+        notification.data["affected_level"]["id"],
+        level_id.to_string()
+    );
+}
+
+#[actix_web::test]
+async fn rename_level_does_not_notify() {
+    let (app, db, auth, notify_tx) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, Some(Permission::LevelModify)).await;
+    let token =
+        create_test_token(user_id, &auth.jwt_encoding_key).expect("Failed to generate token");
+    let level_id = create_test_level(&db).await;
+    let mut rx = notify_tx.subscribe();
+
+    let req = test::TestRequest::patch()
+        .uri(&format!("/aredl/levels/{level_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"name": "Renamed Level"}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    assert!(
+        rx.try_recv().is_err(),
+        "Renaming a level should not emit a list change"
+    );
+}
+
 #[actix_web::test]
 async fn find_level() {
     let (app, db, _, _) = init_test_app().await;
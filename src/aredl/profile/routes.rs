@@ -1,6 +1,8 @@
+use crate::aredl::leaderboard::{RankHistoryEntry, RankHistoryQuery};
 use crate::aredl::profile::ProfileResolved;
 use crate::cache_control::CacheController;
 use crate::error_handler::ApiError;
+use crate::users::User;
 use crate::{
     app_data::db::DbAppState,
     auth::{Authenticated, UserAuth},
@@ -38,10 +40,45 @@ async fn find(
     Ok(HttpResponse::Ok().json(profile))
 }
 
+#[utoipa::path(
+    get,
+    summary = "Rank history",
+    description = "Get the daily AREDL leaderboard ranks, points and extremes of a user, oldest first",
+    tag = "AREDL",
+    params(
+        ("id" = String, description = "The user UUID or discord ID to lookup the rank history for"),
+        ("since" = Option<NaiveDate>, Query, description = "Only include days since this date. Defaults to 90 days ago"),
+        ("until" = Option<NaiveDate>, Query, description = "Only include days until this date. Defaults to today"),
+    ),
+    responses(
+        (status = 200, body = [RankHistoryEntry])
+    ),
+)]
+#[get(
+    "/{id}/rank-history",
+    wrap = "CacheController::public_with_max_age(300)"
+)]
+async fn rank_history(
+    db: web::Data<Arc<DbAppState>>,
+    id: web::Path<String>,
+    query: web::Query<RankHistoryQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let history = web::block(move || {
+        let conn = &mut db.connection()?;
+        let user = User::from_str(conn, id.as_str())?;
+        RankHistoryEntry::find_all(conn, user.id, &query)
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(history))
+}
+
 #[derive(OpenApi)]
-#[openapi(components(schemas(ProfileResolved)), paths(find))]
+#[openapi(
+    components(schemas(ProfileResolved, RankHistoryEntry)),
+    paths(find, rank_history)
+)]
 pub struct ApiDoc;
 
 pub fn init_routes(config: &mut web::ServiceConfig) {
-    config.service(web::scope("profile").service(find));
+    config.service(web::scope("profile").service(rank_history).service(find));
 }
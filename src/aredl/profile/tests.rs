@@ -1,13 +1,20 @@
 #[cfg(test)]
 use {
     crate::{
-        aredl::levels::test_utils::create_test_level_with_record,
+        aredl::{
+            leaderboard::{
+                take_leaderboard_snapshots,
+                test_utils::{backdate_test_leaderboard_snapshots, refresh_test_leaderboards},
+            },
+            levels::test_utils::create_test_level_with_record,
+        },
         auth::{create_test_token, Permission},
         roles::test_utils::{add_user_to_role, create_test_hidden_role, create_test_role},
         test_utils::*,
         users::test_utils::{create_test_user, set_test_user_discord_id},
     },
     actix_web::test::{self, read_body_json},
+    chrono::{Days, Utc},
 };
 
 #[actix_web::test]
@@ -157,3 +164,50 @@ async fn get_profile_includes_badges_and_featured_badge() {
         .iter()
         .any(|badge| badge["badge_code"] == badge_code));
 }
+
+#[actix_web::test]
+async fn get_profile_rank_history() {
+    let (app, db, _, _) = init_test_app().await;
+    let (user, _) = create_test_user(&db, None).await;
+    create_test_level_with_record(&db, user).await;
+
+    refresh_test_leaderboards(&db).await;
+    take_leaderboard_snapshots(&mut db.connection().unwrap()).unwrap();
+    backdate_test_leaderboard_snapshots(&db, 1).await;
+    create_test_level_with_record(&db, user).await;
+    refresh_test_leaderboards(&db).await;
+    take_leaderboard_snapshots(&mut db.connection().unwrap()).unwrap();
+
+    let req = test::TestRequest::get()
+        .uri(format!("/aredl/profile/{user}/rank-history").as_str())
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: serde_json::Value = read_body_json(resp).await;
+    let history = body.as_array().unwrap();
+    assert_eq!(history.len(), 2);
+    let yesterday = Utc::now().date_naive() - Days::new(1);
+    assert_eq!(history[0]["snapshot_date"], yesterday.to_string());
+    assert_eq!(history[0]["extremes"], 1);
+    assert_eq!(history[1]["extremes"], 2);
+    assert_eq!(history[1]["rank"], 1);
+
+    let req = test::TestRequest::get()
+        .uri(
+            format!("/aredl/profile/{user}/rank-history?since={yesterday}&until={yesterday}")
+                .as_str(),
+        )
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    let body: serde_json::Value = read_body_json(resp).await;
+    assert_eq!(body.as_array().unwrap().len(), 1);
+
+    let req = test::TestRequest::get()
+        .uri(
+            format!("/aredl/profile/{user}/rank-history?since=2026-02-01&until=2026-01-01")
+                .as_str(),
+        )
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400);
+}
@@ -1,5 +1,6 @@
 mod model;
 mod routes;
+pub mod video_checks;
 
 #[cfg(test)]
 pub mod test_utils;
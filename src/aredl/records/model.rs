@@ -4,6 +4,7 @@ use crate::app_data::db::{DbAppState, DbConnection};
 use crate::aredl::levels::ExtendedBaseLevel;
 use crate::aredl::submissions::patch::SubmissionPatchMod;
 use crate::aredl::submissions::post::SubmissionPostMod;
+use crate::aredl::submissions::videos::SubmissionVideo;
 use crate::aredl::submissions::{Submission, SubmissionStatus};
 use crate::auth::Authenticated;
 use crate::error_handler::ApiError;
@@ -11,6 +12,7 @@ use crate::page_helper::{PageQuery, Paginated};
 use crate::providers::ProvidersAppState;
 use crate::schema::{aredl::levels, aredl::records, aredl::submissions, users};
 use crate::users::badges::UserBadge;
+use crate::users::me::notifications::Notification;
 use crate::users::{user_filter, ExtendedBaseUser};
 use actix_web::web;
 use chrono::{DateTime, Utc};
@@ -18,6 +20,7 @@ use diesel::dsl::count;
 use diesel::pg::Pg;
 use diesel::{Insertable, Selectable};
 use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
@@ -232,6 +235,7 @@ impl Submission {
     pub fn upsert_from_record_insert(
         conn: &mut DbConnection,
         record: RecordInsert,
+        video: SubmissionVideo,
         authenticated: &Authenticated,
     ) -> Result<Self, ApiError> {
         let existing_submission_id = submissions::table
@@ -244,6 +248,7 @@ impl Submission {
         if let Some(submission_id) = existing_submission_id {
             let submission_update = (
                 SubmissionPatchMod::from_record_insert(record),
+                video,
                 submissions::reviewer_id.eq(Some(authenticated.user_id)),
             );
             Ok(
@@ -255,6 +260,7 @@ impl Submission {
         } else {
             let submission_insert = (
                 SubmissionPostMod::from_record_insert(record),
+                video,
                 submissions::reviewer_id.eq(Some(authenticated.user_id)),
             );
             Ok(diesel::insert_into(submissions::table)
@@ -270,8 +276,10 @@ impl Record {
         conn: &mut DbConnection,
         record: &RecordInsert,
         authenticated: &Authenticated,
-    ) -> Result<Self, ApiError> {
-        conn.transaction(|conn| -> Result<Self, ApiError> {
+        providers: &ProvidersAppState,
+    ) -> Result<(Self, Option<Notification>), ApiError> {
+        let video = SubmissionVideo::from(providers.identify_video(&record.video_url));
+        conn.transaction(|conn| -> Result<(Self, Option<Notification>), ApiError> {
             if authenticated.user_id == record.submitted_by {
                 return Err(ApiError::Forbidden(
                     "You cannot create records for yourself",
@@ -279,7 +287,7 @@ impl Record {
             }
             // Create the corresponding submission first and let triggers initialize the record
             let submission =
-                Submission::upsert_from_record_insert(conn, record.clone(), authenticated)?;
+                Submission::upsert_from_record_insert(conn, record.clone(), video, authenticated)?;
 
             // Then update the record-specific fields
             let record_patch = RecordUpdate::from_record_insert(record);
@@ -290,9 +298,9 @@ impl Record {
                 .returning(Record::as_select())
                 .get_result::<Self>(conn)?;
 
-            UserBadge::update_user_badges(conn, result.submitted_by)?;
+            let notification = UserBadge::update_user_badges(conn, result.submitted_by)?;
 
-            Ok(result)
+            Ok((result, notification))
         })
     }
 
@@ -301,11 +309,17 @@ impl Record {
         record_id: Uuid,
         record: &RecordPatch,
         authenticated: &Authenticated,
-    ) -> Result<Self, ApiError> {
-        conn.transaction(|conn| -> Result<Self, ApiError> {
+        providers: &ProvidersAppState,
+    ) -> Result<(Self, Option<Notification>), ApiError> {
+        let video = record
+            .video_url
+            .as_deref()
+            .map(|video_url| SubmissionVideo::from(providers.identify_video(video_url)));
+        conn.transaction(|conn| -> Result<(Self, Option<Notification>), ApiError> {
             // Update the corresponding submission first and let triggers update the record
             let submission_patch = (
                 SubmissionPatchMod::from_record_update(record.clone()),
+                video,
                 submissions::reviewer_id.eq(Some(authenticated.user_id)),
             );
 
@@ -340,9 +354,9 @@ impl Record {
                     .get_result::<Self>(conn)?
             };
 
-            UserBadge::update_user_badges(conn, submitted_by)?;
+            let notification = UserBadge::update_user_badges(conn, submitted_by)?;
 
-            Ok(result)
+            Ok((result, notification))
         })
     }
 
@@ -460,10 +474,14 @@ impl Record {
         }
     }
 
+    /// Sets the record's achievement date to the publish date of its video, fetching it unless it is already known.
+    /// Records whose date was already changed are left alone unless `overwrite` is set.
     pub async fn update_timestamp(
         db: web::Data<Arc<DbAppState>>,
         record_id: Uuid,
         providers: &ProvidersAppState,
+        video_published_at: Option<DateTime<Utc>>,
+        overwrite: bool,
     ) -> Result<Self, ApiError> {
         let db_clone = db.clone();
         let record: Record = web::block(move || {
@@ -477,11 +495,14 @@ impl Record {
         })
         .await??;
 
-        if record.achieved_at < record.created_at - chrono::Duration::seconds(1) {
+        if !overwrite && record.achieved_at < record.created_at - chrono::Duration::seconds(1) {
             return Ok(record);
         }
 
-        let achieved_at = Record::fetch_completion_timestamp(record.clone(), providers).await;
+        let achieved_at = match video_published_at {
+            Some(published_at) => published_at,
+            None => Record::fetch_completion_timestamp(record.clone(), providers).await,
+        };
 
         let result = web::block(move || -> Result<Record, ApiError> {
             let conn = &mut db_clone.connection()?;
@@ -500,9 +521,12 @@ impl Record {
         db: web::Data<Arc<DbAppState>>,
         submission: &Submission,
         providers: web::Data<Arc<ProvidersAppState>>,
+        inbox_tx: web::Data<broadcast::Sender<Notification>>,
+        use_video_date: Option<bool>,
     ) {
         let submission_id = submission.id;
         let submitted_by = submission.submitted_by;
+        let video_published_at = submission.video_published_at;
 
         tokio::spawn(async move {
             let record = match db
@@ -520,43 +544,59 @@ impl Record {
                 }
             };
 
-            let record = match Record::update_timestamp(db.clone(), record.id, providers.get_ref())
+            let record = if use_video_date == Some(false) {
+                record
+            } else {
+                match Record::update_timestamp(
+                    db.clone(),
+                    record.id,
+                    providers.get_ref(),
+                    video_published_at,
+                    use_video_date == Some(true),
+                )
                 .await
+                {
+                    Ok(updated_record) => updated_record,
+                    Err(error) => {
+                        tracing::warn!(
+                            error = %error.error_message,
+                            ?record.id,
+                            ?submission_id,
+                            "Failed to process post submission accept actions: failed to update record's achieved_at timestamp"
+                        );
+                        record
+                    }
+                }
+            };
+
+            match db
+                .connection()
+                .and_then(|mut conn| record.complete_bounty_if_exists(&mut conn))
             {
-                Ok(updated_record) => updated_record,
+                Ok(notifications) => Notification::send_live(&inbox_tx, notifications),
                 Err(error) => {
                     tracing::warn!(
                         error = %error.error_message,
                         ?record.id,
-                        ?submission_id,
-                        "Failed to process post submission accept actions: failed to update record's achieved_at timestamp"
+                        level_id = ?record.level_id,
+                        "Failed to process post submission accept actions: failed to complete bounty"
                     );
-                    record
                 }
-            };
-
-            if let Err(error) = db
-                .connection()
-                .and_then(|mut conn| record.complete_bounty_if_exists(&mut conn))
-            {
-                tracing::warn!(
-                    error = %error.error_message,
-                    ?record.id,
-                    level_id = ?record.level_id,
-                    "Failed to process post submission accept actions: failed to complete bounty"
-                );
             }
 
-            if let Err(error) = db
+            match db
                 .connection()
                 .and_then(|mut conn| UserBadge::update_user_badges(&mut conn, submitted_by))
             {
-                tracing::warn!(
-                    error = %error.error_message,
-                    user_id = ?submitted_by,
-                    level_id = ?record.level_id,
-                    "Failed to process post submission accept actions: failed to update user badges"
-                );
+                Ok(notification) => Notification::send_live(&inbox_tx, notification),
+                Err(error) => {
+                    tracing::warn!(
+                        error = %error.error_message,
+                        user_id = ?submitted_by,
+                        level_id = ?record.level_id,
+                        "Failed to process post submission accept actions: failed to update user badges"
+                    );
+                }
             }
         });
     }
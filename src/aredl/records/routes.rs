@@ -1,6 +1,7 @@
 use crate::app_data::db::DbAppState;
 use crate::aredl::levels::id_resolver::resolve_level_id;
 use crate::aredl::records::model::{RecordInsert, RecordPatch};
+use crate::aredl::records::video_checks;
 use crate::aredl::records::{
     MutualVictors, MutualVictorsQuery, Record, RecordsQueryOptions, ResolvedRecord,
     ResolvedRecordPage,
@@ -10,8 +11,10 @@ use crate::cache_control::CacheController;
 use crate::error_handler::ApiError;
 use crate::page_helper::{PageQuery, Paginated};
 use crate::providers::ProvidersAppState;
+use crate::users::me::notifications::Notification;
 use actix_web::{delete, get, patch, post, web, HttpResponse};
 use std::sync::Arc;
+use tokio::sync::broadcast;
 use tracing_actix_web::RootSpan;
 use utoipa::OpenApi;
 use uuid::Uuid;
@@ -58,13 +61,21 @@ async fn create(
     db: web::Data<Arc<DbAppState>>,
     record: web::Json<RecordInsert>,
     authenticated: Authenticated,
+    providers: web::Data<Arc<ProvidersAppState>>,
     root_span: RootSpan,
+    inbox_tx: web::Data<broadcast::Sender<Notification>>,
 ) -> Result<HttpResponse, ApiError> {
     root_span.record("body", tracing::field::debug(&record));
-    let record = web::block(move || {
-        Record::create(&mut db.connection()?, &record.into_inner(), &authenticated)
+    let (record, notification) = web::block(move || {
+        Record::create(
+            &mut db.connection()?,
+            &record.into_inner(),
+            &authenticated,
+            providers.get_ref(),
+        )
     })
     .await??;
+    Notification::send_live(&inbox_tx, notification);
     Ok(HttpResponse::Ok().json(record))
 }
 
@@ -91,18 +102,22 @@ async fn update(
     id: web::Path<Uuid>,
     record: web::Json<RecordPatch>,
     authenticated: Authenticated,
+    providers: web::Data<Arc<ProvidersAppState>>,
     root_span: RootSpan,
+    inbox_tx: web::Data<broadcast::Sender<Notification>>,
 ) -> Result<HttpResponse, ApiError> {
     root_span.record("body", tracing::field::debug(&record));
-    let record = web::block(move || {
+    let (record, notification) = web::block(move || {
         Record::update(
             &mut db.connection()?,
             id.into_inner(),
             &record.into_inner(),
             &authenticated,
+            providers.get_ref(),
         )
     })
     .await??;
+    Notification::send_live(&inbox_tx, notification);
     Ok(HttpResponse::Ok().json(record))
 }
 
@@ -131,7 +146,8 @@ async fn update_timestamp(
     id: web::Path<Uuid>,
     providers: web::Data<Arc<ProvidersAppState>>,
 ) -> Result<HttpResponse, ApiError> {
-    let record = Record::update_timestamp(db, id.into_inner(), providers.get_ref()).await?;
+    let record =
+        Record::update_timestamp(db, id.into_inner(), providers.get_ref(), None, false).await?;
     Ok(HttpResponse::Ok().json(record))
 }
 
@@ -278,6 +294,9 @@ async fn find_me(
     tags(
         (name = "AREDL - Records", description = "Endpoints for fetching and managing records")
     ),
+    nest(
+        (path = "/", api=video_checks::ApiDoc),
+    ),
     components(
         schemas(
             Record,
@@ -310,6 +329,7 @@ pub fn init_routes(config: &mut web::ServiceConfig) {
             .service(find_all)
             .service(find_me)
             .service(mutual_victors)
+            .configure(video_checks::init_routes)
             .service(find),
     );
 }
@@ -0,0 +1,7 @@
+mod model;
+mod routes;
+#[cfg(test)]
+mod tests;
+
+pub use model::*;
+pub use routes::{init_routes, ApiDoc};
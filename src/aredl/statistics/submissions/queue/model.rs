@@ -0,0 +1,150 @@
+use crate::{
+    app_data::db::DbConnection,
+    aredl::submissions::{queue::SubmissionQueueSnapshot, SubmissionStatus},
+    error_handler::ApiError,
+    schema::aredl::{submission_history, submission_queue_snapshots, submissions},
+};
+use chrono::{DateTime, DurationRound as _, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use diesel::prelude::*;
+/// Maximum amount of buckets a single request can cover.
+const MAX_BUCKETS: usize = 1000;
+
+#[derive(Serialize, Deserialize, Debug, ToSchema, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QueueHistoryInterval {
+    /// One bucket per day.
+    #[default]
+    Daily,
+    /// One bucket per hour.
+    Hourly,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct QueueHistoryQuery {
+    /// Size of the buckets, defaults to daily.
+    pub interval: Option<QueueHistoryInterval>,
+    /// Only include data since this timestamp. Defaults to 30 days ago for daily buckets and 48 hours ago for hourly ones.
+    pub since: Option<DateTime<Utc>>,
+    /// Only include data until this timestamp. Defaults to now.
+    pub until: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct QueueHistoryBucket {
+    /// Start of the bucket.
+    pub start: DateTime<Utc>,
+    /// Last snapshot of the queue taken during the bucket, if any.
+    pub queue: Option<SubmissionQueueSnapshot>,
+    /// Amount of submissions accepted or denied by a reviewer during the bucket.
+    pub resolved: i64,
+    /// Median time, in seconds, from submitting to being accepted or denied, for the submissions resolved during the bucket.
+    pub median_wait_seconds: Option<i64>,
+}
+
+impl QueueHistoryInterval {
+    fn length(self) -> TimeDelta {
+        match self {
+            Self::Daily => TimeDelta::days(1),
+            Self::Hourly => TimeDelta::hours(1),
+        }
+    }
+
+    fn default_range(self) -> TimeDelta {
+        match self {
+            Self::Daily => TimeDelta::days(30),
+            Self::Hourly => TimeDelta::hours(48),
+        }
+    }
+}
+
+impl QueueHistoryBucket {
+    /// Lists the queue depth and wait times bucketed by day or hour, oldest first.
+    pub fn find_all(
+        conn: &mut DbConnection,
+        query: &QueueHistoryQuery,
+    ) -> Result<Vec<Self>, ApiError> {
+        let interval = query.interval.unwrap_or_default();
+        let length = interval.length();
+        let until = query.until.unwrap_or_else(Utc::now);
+        let since = query
+            .since
+            .unwrap_or_else(|| until - interval.default_range());
+        if since >= until {
+            return Err(ApiError::BadRequest("`since` must be before `until`."));
+        }
+
+        let first_start = since.duration_trunc(length).map_err(|e| {
+            ApiError::InternalServerError(format!("Failed to truncate timestamp: {e}"))
+        })?;
+        let mut buckets = std::iter::successors(Some(first_start), |start| Some(*start + length))
+            .take_while(|start| *start < until)
+            .take(MAX_BUCKETS + 1)
+            .map(|start| Self {
+                start,
+                queue: None,
+                resolved: 0,
+                median_wait_seconds: None,
+            })
+            .collect::<Vec<_>>();
+        if buckets.len() > MAX_BUCKETS {
+            return Err(ApiError::BadRequest(format!(
+                "A single request cannot cover more than {MAX_BUCKETS} buckets."
+            )));
+        }
+
+        let bucket_index = |timestamp: DateTime<Utc>| {
+            usize::try_from((timestamp - first_start).num_seconds() / length.num_seconds()).ok()
+        };
+
+        let snapshots = submission_queue_snapshots::table
+            .filter(submission_queue_snapshots::taken_at.ge(first_start))
+            .filter(submission_queue_snapshots::taken_at.lt(until))
+            .order(submission_queue_snapshots::taken_at.asc())
+            .select(SubmissionQueueSnapshot::as_select())
+            .load::<SubmissionQueueSnapshot>(conn)?;
+        for snapshot in snapshots {
+            // snapshots are ordered, so each bucket ends up with its latest one
+            if let Some(bucket) = bucket_index(snapshot.taken_at).and_then(|i| buckets.get_mut(i)) {
+                bucket.queue = Some(snapshot);
+            }
+        }
+
+        // reviews without a reviewer, such as synced records, are not waited on
+        let resolutions = submission_history::table
+            .inner_join(submissions::table)
+            .filter(
+                submission_history::status
+                    .eq_any([SubmissionStatus::Accepted, SubmissionStatus::Denied]),
+            )
+            .filter(submission_history::reviewer_id.is_not_null())
+            .filter(submission_history::timestamp.ge(first_start))
+            .filter(submission_history::timestamp.lt(until))
+            .select((submission_history::timestamp, submissions::created_at))
+            .load::<(DateTime<Utc>, DateTime<Utc>)>(conn)?;
+
+        let mut waits = vec![Vec::new(); buckets.len()];
+        for (resolved_at, created_at) in resolutions {
+            if let Some(bucket_waits) = bucket_index(resolved_at).and_then(|i| waits.get_mut(i)) {
+                bucket_waits.push((resolved_at - created_at).num_seconds().max(0));
+            }
+        }
+
+        for (bucket, mut bucket_waits) in buckets.iter_mut().zip(waits) {
+            bucket.resolved = i64::try_from(bucket_waits.len()).unwrap_or(i64::MAX);
+            bucket_waits.sort_unstable();
+            let middle = bucket_waits.len() / 2;
+            bucket.median_wait_seconds = if bucket_waits.len() % 2 == 0 {
+                bucket_waits
+                    .get(middle.wrapping_sub(1))
+                    .zip(bucket_waits.get(middle))
+                    .map(|(lower, upper)| i64::midpoint(*lower, *upper))
+            } else {
+                bucket_waits.get(middle).copied()
+            };
+        }
+
+        Ok(buckets)
+    }
+}
@@ -0,0 +1,113 @@
+#[cfg(test)]
+use {
+    crate::{
+        aredl::{
+            levels::test_utils::create_test_level,
+            submissions::{
+                queue::SubmissionQueueSnapshot,
+                test_utils::{
+                    create_test_submission, insert_history_entry, set_test_submission_created_at,
+                },
+                SubmissionStatus,
+            },
+        },
+        auth::{create_test_token, Permission},
+        test_utils::{assert_error_response, init_test_app},
+        users::test_utils::{create_test_full_reviewer, create_test_user},
+    },
+    actix_http::StatusCode,
+    actix_web::{
+        http::header,
+        test::{self, read_body_json},
+    },
+    chrono::{Duration, Utc},
+    serde_json::Value,
+};
+
+#[actix_web::test]
+async fn queue_history_includes_snapshots_and_wait_times() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (reviewer, _) = create_test_full_reviewer(&db).await;
+    let token = create_test_token(reviewer, &auth.jwt_encoding_key).unwrap();
+    let (user, _) = create_test_user(&db, None).await;
+
+    let accepted = create_test_submission(create_test_level(&db).await, user, &db).await;
+    set_test_submission_created_at(&db, accepted, Utc::now() - Duration::hours(3));
+    insert_history_entry(accepted, Some(reviewer), SubmissionStatus::Accepted, &db).await;
+    let denied = create_test_submission(create_test_level(&db).await, user, &db).await;
+    set_test_submission_created_at(&db, denied, Utc::now() - Duration::hours(1));
+    insert_history_entry(denied, Some(reviewer), SubmissionStatus::Denied, &db).await;
+    // resolutions without a reviewer are not counted
+    let synced = create_test_submission(create_test_level(&db).await, user, &db).await;
+    insert_history_entry(synced, None, SubmissionStatus::Accepted, &db).await;
+
+    SubmissionQueueSnapshot::take(&mut db.connection().unwrap()).unwrap();
+
+    let req = test::TestRequest::get()
+        .uri("/aredl/statistics/submissions/queue?interval=Hourly")
+        .insert_header((header::AUTHORIZATION, format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    let body: Value = read_body_json(resp).await;
+    let buckets = body.as_array().expect("Response should be an array");
+    assert!(
+        (48..=49).contains(&buckets.len()),
+        "Hourly buckets should cover the last 48 hours"
+    );
+    assert!(buckets[0]["queue"].is_null());
+
+    let current = buckets.last().unwrap();
+    assert_eq!(current["queue"]["regular_submissions_in_queue"], 3);
+    assert_eq!(current["queue"]["uc_submissions"], 0);
+    assert_eq!(current["resolved"], 2);
+    let median = current["median_wait_seconds"].as_i64().unwrap();
+    assert!(
+        (7190..=7210).contains(&median),
+        "Median wait should be around two hours, got {median}"
+    );
+}
+
+#[actix_web::test]
+async fn queue_history_validation() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (reviewer, _) = create_test_full_reviewer(&db).await;
+    let token = create_test_token(reviewer, &auth.jwt_encoding_key).unwrap();
+    let (user, _) = create_test_user(&db, Some(Permission::SubmissionReview)).await;
+    let user_token = create_test_token(user, &auth.jwt_encoding_key).unwrap();
+
+    let req = test::TestRequest::get()
+        .uri("/aredl/statistics/submissions/queue")
+        .insert_header((header::AUTHORIZATION, format!("Bearer {user_token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let req = test::TestRequest::get()
+        .uri("/aredl/statistics/submissions/queue?since=2026-02-01T00:00:00Z&until=2026-01-01T00:00:00Z")
+        .insert_header((header::AUTHORIZATION, format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_error_response!(
+        resp,
+        StatusCode::BAD_REQUEST,
+        Some("`since` must be before `until`.")
+    );
+
+    let req = test::TestRequest::get()
+        .uri("/aredl/statistics/submissions/queue?interval=Hourly&since=2025-01-01T00:00:00Z&until=2026-01-01T00:00:00Z")
+        .insert_header((header::AUTHORIZATION, format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let req = test::TestRequest::get()
+        .uri("/aredl/statistics/submissions/queue?since=2025-01-01T00:00:00Z&until=2026-01-01T00:00:00Z")
+        .insert_header((header::AUTHORIZATION, format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: Value = read_body_json(resp).await;
+    assert_eq!(body.as_array().unwrap().len(), 365);
+}
@@ -0,0 +1,9 @@
+#[cfg(test)]
+use {crate::app_data::db::DbAppState, diesel::prelude::*, diesel::sql_query, std::sync::Arc};
+
+#[cfg(test)]
+pub async fn refresh_test_submission_reason_stats(db: &Arc<DbAppState>) {
+    sql_query("REFRESH MATERIALIZED VIEW aredl.submission_reason_stats")
+        .execute(&mut db.connection().unwrap())
+        .expect("Failed to refresh submission reason stats");
+}
@@ -0,0 +1,64 @@
+#[cfg(test)]
+use {
+    crate::{
+        aredl::{
+            levels::test_utils::create_test_level,
+            statistics::submissions::reasons::test_utils::refresh_test_submission_reason_stats,
+            submissions::test_utils::create_test_submission,
+        },
+        auth::create_test_token,
+        submission_reasons::test_utils::create_test_reason,
+        test_utils::init_test_app,
+        users::test_utils::{create_test_full_reviewer, create_test_user},
+    },
+    actix_web::{
+        http::header,
+        test::{self, read_body_json},
+    },
+    serde_json::{json, Value},
+};
+
+#[actix_web::test]
+async fn submission_reason_stats_count_reviews() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, None).await;
+    let (reviewer_id, _) = create_test_full_reviewer(&db).await;
+    let token = create_test_token(reviewer_id, &auth.jwt_encoding_key).unwrap();
+    let code = create_test_reason(&db).await;
+
+    for status in ["Denied", "Denied", "UnderConsideration"] {
+        let level_id = create_test_level(&db).await;
+        let submission_id = create_test_submission(level_id, user_id, &db).await;
+        let req = test::TestRequest::patch()
+            .uri(&format!("/aredl/submissions/{submission_id}"))
+            .insert_header((header::AUTHORIZATION, format!("Bearer {token}")))
+            .set_json(json!({"status": status, "reason_codes": [code]}))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success(), "status is {}", resp.status());
+    }
+    refresh_test_submission_reason_stats(&db).await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "/aredl/statistics/submissions/reasons?reviewer_id={reviewer_id}"
+        ))
+        .insert_header((header::AUTHORIZATION, format!("Bearer {token}")))
+        .insert_header((header::ACCEPT_LANGUAGE, "fr"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    let body: Value = read_body_json(resp).await;
+    let rows = body.as_array().expect("Response should be an array");
+    assert_eq!(
+        rows.len(),
+        1,
+        "Only the reviewer's reasons should be counted"
+    );
+    assert_eq!(rows[0]["code"], code.as_str());
+    assert_eq!(rows[0]["text"], "Raison de test");
+    assert_eq!(rows[0]["denied"], 2);
+    assert_eq!(rows[0]["under_consideration"], 1);
+    assert_eq!(rows[0]["total"], 3);
+}
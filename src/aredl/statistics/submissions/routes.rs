@@ -5,7 +5,9 @@ use utoipa::OpenApi;
 
 use crate::{
     app_data::db::DbAppState,
-    aredl::statistics::submissions::{daily, total_submissions, ResolvedQueueLevelSubmissionsRow},
+    aredl::statistics::submissions::{
+        daily, queue, reasons, total_submissions, ResolvedQueueLevelSubmissionsRow,
+    },
     cache_control::CacheController,
     error_handler::ApiError,
 };
@@ -28,6 +30,8 @@ pub async fn total(db: web::Data<Arc<DbAppState>>) -> Result<HttpResponse, ApiEr
 	paths(total),
 	nest(
         (path = "/daily", api=daily::ApiDoc),
+        (path = "/queue", api=queue::ApiDoc),
+        (path = "/reasons", api=reasons::ApiDoc),
     ),
 	components(schemas(ResolvedQueueLevelSubmissionsRow)),
 )]
@@ -37,6 +41,8 @@ pub fn init_routes(config: &mut web::ServiceConfig) {
     config.service(
         web::scope("/submissions")
             .configure(daily::init_routes)
+            .configure(queue::init_routes)
+            .configure(reasons::init_routes)
             .service(total),
     );
 }
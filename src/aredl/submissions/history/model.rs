@@ -5,6 +5,7 @@ use crate::{
     error_handler::ApiError,
     roles::ReviewerVisibility,
     schema::{aredl::submission_history, users},
+    submission_reasons::{LocalizedSubmissionReason, RequestLocales, SubmissionReasonCatalogue},
     users::BaseUser,
 };
 use chrono::{DateTime, Utc};
@@ -28,6 +29,7 @@ pub struct SubmissionHistory {
     pub mod_menu: Option<String>,
     pub user_notes: Option<String>,
     pub reviewer_notes: Option<String>,
+    pub reason_codes: Vec<Option<String>>,
     pub private_reviewer_notes: Option<String>,
     pub reviewer_id: Option<Uuid>,
     pub locked: Option<bool>,
@@ -46,6 +48,7 @@ pub struct SubmissionHistoryResolved {
     pub mod_menu: Option<String>,
     pub user_notes: Option<String>,
     pub reviewer_notes: Option<String>,
+    pub reasons: Vec<LocalizedSubmissionReason>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub private_reviewer_notes: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -59,13 +62,18 @@ pub struct SubmissionHistoryOptions {
 }
 
 impl SubmissionHistoryResolved {
-    pub fn from_data(history_row: (SubmissionHistory, Option<BaseUser>)) -> Self {
+    pub fn from_data(
+        history_row: (SubmissionHistory, Option<BaseUser>),
+        catalogue: &SubmissionReasonCatalogue,
+        locales: &RequestLocales,
+    ) -> Self {
         let (history, user) = history_row;
         Self {
             id: history.id,
             submission_id: history.submission_id,
             status: history.status,
             reviewer_notes: history.reviewer_notes,
+            reasons: catalogue.localize(&history.reason_codes, locales),
             private_reviewer_notes: history.private_reviewer_notes,
             reviewer: user,
             user_notes: history.user_notes,
@@ -83,6 +91,7 @@ impl SubmissionHistoryResolved {
         conn: &mut DbConnection,
         id: Uuid,
         authenticated: &Authenticated,
+        locales: &RequestLocales,
     ) -> Result<Vec<SubmissionHistoryResolved>, ApiError> {
         let history_row = submission_history::table
             .left_join(users::table.on(submission_history::reviewer_id.eq(users::id.nullable())))
@@ -95,9 +104,10 @@ impl SubmissionHistoryResolved {
             .filter(submission_history::submission_id.eq(id))
             .load::<(SubmissionHistory, Option<BaseUser>)>(conn)?;
 
+        let catalogue = SubmissionReasonCatalogue::load(conn)?;
         let mut resolved_history = history_row
             .into_iter()
-            .map(SubmissionHistoryResolved::from_data)
+            .map(|row| SubmissionHistoryResolved::from_data(row, &catalogue, locales))
             .collect::<Vec<_>>();
 
         let visibility = ReviewerVisibility::new(conn, authenticated)?;
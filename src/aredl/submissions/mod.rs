@@ -1,11 +1,15 @@
+pub mod bulk;
+pub mod external_sync;
 mod history;
 mod model;
 pub mod patch;
 pub mod post;
-mod queue;
+pub mod queue;
+pub mod raw_probes;
 pub mod resolved;
 mod routes;
 mod status;
+pub mod videos;
 
 #[cfg(test)]
 pub mod test_utils;
@@ -1,17 +1,22 @@
 use crate::{
     app_data::db::DbConnection,
     aredl::levels::LevelStatus,
-    aredl::submissions::{status::SubmissionsEnabled, Submission, SubmissionStatus},
+    aredl::submissions::{
+        status::{closures::SubmissionClosure, SubmissionsEnabled},
+        videos::SubmissionVideo,
+        Submission, SubmissionStatus,
+    },
     auth::{Authenticated, Permission},
     error_handler::ApiError,
-    notifications::WebsocketNotification,
+    notifications::{NotificationList, WebsocketNotification, WebsocketNotificationType},
     providers::ProvidersAppState,
     schema::{
         aredl::{levels, submissions},
         shifts, users,
     },
     shifts::{Shift, ShiftStatus},
-    users::me::notifications::{Notification, NotificationType},
+    submission_reasons::{RequestLocales, SubmissionReason, SubmissionReasonCatalogue},
+    users::me::notifications::{Notification, NotificationPayload, NotificationType},
 };
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
@@ -83,10 +88,34 @@ pub struct SubmissionPatchMod {
     /// [MOD ONLY] Private notes given by the reviewer when reviewing the record.
     #[serde(default, with = "double_option")]
     pub private_reviewer_notes: Option<Option<String>>,
+    /// [MOD ONLY] Codes of the reasons for denying the submission or putting it under consideration.
+    /// They are cleared whenever the status changes without new reasons being given.
+    #[schema(value_type = Option<Vec<String>>)]
+    pub reason_codes: Option<Vec<Option<String>>>,
     /// [MOD ONLY] Whether or not this submission should be locked
     pub locked: Option<bool>,
 }
 
+/// A moderator's edit, along with how accepting the submission should treat the resulting record.
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct SubmissionPatchModBody {
+    #[serde(flatten)]
+    pub patch: SubmissionPatchMod,
+    /// [MOD ONLY] When accepting, whether to set the record's achievement date to the publish date of the video.
+    /// By default only new records get it, `true` also overwrites the date of an existing record and `false` leaves it unchanged.
+    pub use_video_date: Option<bool>,
+}
+
+/// Everything a review changed that has to be sent out once it has been committed.
+pub struct ReviewSideEffects {
+    /// The websocket event announcing the new status, if it changed.
+    pub websocket_type: Option<WebsocketNotificationType>,
+    /// The reviewer's shift, if this review completed it.
+    pub completed_shift: Option<Shift>,
+    /// Inbox notifications sent to the submitter.
+    pub notifications: Vec<Notification>,
+}
+
 impl Submission {
     pub fn update_user_shift(
         conn: &mut DbConnection,
@@ -139,6 +168,93 @@ impl Submission {
         }
         Ok(None)
     }
+
+    /// Notifies the submitter and updates the shift of the given reviewer, if any, after a reviewer changed the status of a submission.
+    pub fn apply_review_side_effects(
+        connection: &mut DbConnection,
+        updated: &Submission,
+        old_status: &SubmissionStatus,
+        new_status: &SubmissionStatus,
+        shift_reviewer_id: Option<Uuid>,
+    ) -> Result<ReviewSideEffects, ApiError> {
+        let mut notifications = Vec::new();
+        // Side effects when status changes to reviewed state
+
+        if (*new_status == SubmissionStatus::Accepted
+            || *new_status == SubmissionStatus::Denied
+            || *new_status == SubmissionStatus::UnderConsideration)
+            && old_status != new_status
+        {
+            // Send user notification
+            let level_name = levels::table
+                .filter(levels::id.eq(updated.level_id))
+                .select(levels::name)
+                .first::<String>(connection)?;
+
+            let reasons = SubmissionReasonCatalogue::load(connection)?
+                .localize(&updated.reason_codes, &RequestLocales::default())
+                .into_iter()
+                .map(|reason| reason.text)
+                .collect::<Vec<_>>();
+            let reasons_suffix = if reasons.is_empty() {
+                String::new()
+            } else {
+                format!(" Reasons: {}.", reasons.join(", "))
+            };
+
+            let (notif_type, message) = match new_status {
+                SubmissionStatus::Accepted => (
+                    NotificationType::Success,
+                    format!("Your submission for {level_name:?} has been accepted!"),
+                ),
+                SubmissionStatus::Denied => (
+                    NotificationType::Failure,
+                    format!("Your submission for {level_name:?} has been denied.{reasons_suffix}"),
+                ),
+                SubmissionStatus::UnderConsideration => (
+                    NotificationType::Info,
+                    format!("Your submission for {level_name:?} has been put under consideration.{reasons_suffix}"),
+                ),
+                SubmissionStatus::Pending
+                | SubmissionStatus::Claimed
+                | SubmissionStatus::UnderReview => {
+                    return Err(ApiError::InternalServerError(
+                        "Unexpected submission status while creating notification",
+                    ));
+                }
+            };
+
+            notifications.extend(Notification::create(
+                connection,
+                updated.submitted_by,
+                message,
+                notif_type,
+                Some(NotificationPayload::Submission {
+                    submission_id: updated.id,
+                    level_id: updated.level_id,
+                    list: NotificationList::Aredl,
+                    reason_codes: updated.reason_codes.iter().flatten().cloned().collect(),
+                }),
+            )?);
+        }
+
+        let websocket_type = (old_status != new_status)
+            .then_some(new_status)
+            .and_then(SubmissionStatus::websocket_type);
+
+        let completed_shift = match shift_reviewer_id {
+            Some(reviewer_id) => {
+                Submission::update_user_shift(connection, reviewer_id, old_status, new_status)?
+            }
+            None => None,
+        };
+
+        Ok(ReviewSideEffects {
+            websocket_type,
+            completed_shift,
+            notifications,
+        })
+    }
 }
 
 impl SubmissionPatchUser {
@@ -163,6 +279,10 @@ impl SubmissionPatchUser {
                 },
             )?);
         }
+        let video = patch
+            .video_url
+            .as_deref()
+            .map(|video_url| SubmissionVideo::from(providers.identify_video(video_url)));
 
         if let Some(Some(raw_url)) = patch.raw_url.as_ref() {
             patch.raw_url = Some(Some(providers.validate_raw_footage_url(raw_url).map_err(
@@ -201,6 +321,11 @@ impl SubmissionPatchUser {
             ));
         }
 
+        let video_reused = video
+            .as_ref()
+            .map(|video| video.is_reused(conn, Some(id), user, old_submission.level_id))
+            .transpose()?;
+
         match old_submission.status {
             SubmissionStatus::Claimed
             | SubmissionStatus::UnderConsideration
@@ -220,6 +345,17 @@ impl SubmissionPatchUser {
             ));
         }
 
+        if old_submission.status != SubmissionStatus::Pending {
+            if let Some(closure) =
+                SubmissionClosure::find_active_for_level(conn, old_submission.level_id)?
+            {
+                return Err(ApiError::BadRequest(format!(
+                    "Only pending submissions can be edited while submissions for this level are closed: {}",
+                    closure.reason
+                )));
+            }
+        }
+
         let level_status = levels::table
             .filter(levels::id.eq(old_submission.level_id))
             .select(levels::status)
@@ -242,9 +378,12 @@ impl SubmissionPatchUser {
             .filter(submissions::submitted_by.eq(user))
             .set((
                 patch,
+                video,
+                video_reused.map(|reused| submissions::video_reused.eq(reused)),
                 submissions::status.eq(SubmissionStatus::Pending),
                 submissions::reviewer_id.eq::<Option<Uuid>>(None),
                 submissions::reviewer_notes.eq::<Option<String>>(None),
+                submissions::reason_codes.eq(Vec::<Option<String>>::new()),
             ))
             .returning(Submission::as_select())
             .get_result::<Submission>(conn)?;
@@ -260,6 +399,7 @@ impl SubmissionPatchMod {
         conn: &mut DbConnection,
         authenticated: &Authenticated,
         notify_tx: &broadcast::Sender<WebsocketNotification>,
+        inbox_tx: &broadcast::Sender<Notification>,
         providers: &ProvidersAppState,
     ) -> Result<Submission, ApiError> {
         if patch == Self::default() {
@@ -275,6 +415,10 @@ impl SubmissionPatchMod {
                 },
             )?);
         }
+        let video = patch
+            .video_url
+            .as_deref()
+            .map(|video_url| SubmissionVideo::from(providers.identify_video(video_url)));
 
         if let Some(Some(raw_url)) = patch.raw_url.as_ref() {
             patch.raw_url = Some(Some(providers.validate_raw_footage_url(raw_url).map_err(
@@ -316,12 +460,57 @@ impl SubmissionPatchMod {
             ));
         }
 
-        let (result, websocket_type, completed_shift) = conn.transaction(
-            |connection| -> Result<(Submission, Option<&'static str>, Option<Shift>), ApiError> {
+        if Submission::conflicts_with_reviewer(
+            conn,
+            id,
+            authenticated.user_id,
+            can_edit_non_self_claimed,
+        )? {
+            return Err(ApiError::Forbidden(
+                "You have a conflict of interest with this submission.",
+            ));
+        }
+
+        let video_reused = video
+            .as_ref()
+            .map(|video| {
+                video.is_reused(
+                    conn,
+                    Some(id),
+                    old_submission.submitted_by,
+                    old_submission.level_id,
+                )
+            })
+            .transpose()?;
+
+        let resulting_status = patch
+            .status
+            .clone()
+            .unwrap_or(old_submission.status.clone());
+
+        if let Some(reason_codes) = patch.reason_codes.as_ref() {
+            if !reason_codes.is_empty()
+                && resulting_status != SubmissionStatus::Denied
+                && resulting_status != SubmissionStatus::UnderConsideration
+            {
+                return Err(ApiError::BadRequest(
+                    "Reasons can only be given when denying a submission or putting it under consideration.",
+                ));
+            }
+            patch.reason_codes = Some(SubmissionReason::validate_codes(conn, reason_codes)?);
+        } else if resulting_status != old_submission.status {
+            // reasons only apply to the review they were given in
+            patch.reason_codes = Some(Vec::new());
+        }
+
+        let (result, side_effects) = conn.transaction(
+            |connection| -> Result<(Submission, ReviewSideEffects), ApiError> {
                 let updated = diesel::update(submissions::table)
                     .filter(submissions::id.eq(id))
                     .set((
                         patch.clone(),
+                        video.clone(),
+                        video_reused.map(|reused| submissions::video_reused.eq(reused)),
                         submissions::reviewer_id.eq(Some(authenticated.user_id)),
                     ))
                     .returning(Submission::as_select())
@@ -330,64 +519,36 @@ impl SubmissionPatchMod {
                 let old_status = old_submission.status;
                 let new_status = patch.status.unwrap_or(old_status.clone());
 
-                // Side effects when status changes to reviewed state
-
-                if (new_status == SubmissionStatus::Accepted
-                    || new_status == SubmissionStatus::Denied
-                    || new_status == SubmissionStatus::UnderConsideration)
-                    && old_status != new_status
-                {
-                    // Send user notification
-                    let level_name = levels::table
-                        .filter(levels::id.eq(updated.level_id))
-                        .select(levels::name)
-                        .first::<String>(connection)?;
-
-                    let (notif_type, message) = match new_status {
-                        SubmissionStatus::Accepted => (
-                            NotificationType::Success,
-                            format!("Your submission for {level_name:?} has been accepted!"),
-                        ),
-                        SubmissionStatus::Denied => (
-                            NotificationType::Failure,
-                            format!("Your submission for {level_name:?} has been denied."),
-                        ),
-                        SubmissionStatus::UnderConsideration => (
-                            NotificationType::Info,
-                            format!("Your submission for {level_name:?} has been put under consideration."),
-                        ),
-                        SubmissionStatus::Pending
-                        | SubmissionStatus::Claimed
-                        | SubmissionStatus::UnderReview => {
-                            return Err(ApiError::InternalServerError(
-                                "Unexpected submission status while creating notification",
-                            ));
-                        }
-                    };
-
-                    Notification::create(connection, updated.submitted_by, message, notif_type)?;
-                }
-
-                let websocket_type = (old_status != new_status)
-                    .then_some(&new_status)
-                    .and_then(SubmissionStatus::websocket_type);
-
-                let completed_shift = Submission::update_user_shift(
+                let side_effects = Submission::apply_review_side_effects(
                     connection,
-                    authenticated.user_id,
+                    &updated,
                     &old_status,
                     &new_status,
+                    Some(authenticated.user_id),
                 )?;
 
-                Ok((updated, websocket_type, completed_shift))
+                Ok((updated, side_effects))
             },
         )?;
 
-        if let Some(notification_type) = websocket_type {
-            WebsocketNotification::send(notify_tx, notification_type, &result);
+        Notification::send_live(inbox_tx, side_effects.notifications);
+        if let Some(notification_type) = side_effects.websocket_type {
+            WebsocketNotification::send(
+                conn,
+                notify_tx,
+                notification_type,
+                Some(NotificationList::Aredl),
+                &result,
+            );
         }
-        if let Some(completed_shift) = completed_shift {
-            WebsocketNotification::send(notify_tx, "SHIFT_COMPLETED", &completed_shift);
+        if let Some(completed_shift) = side_effects.completed_shift {
+            WebsocketNotification::send(
+                conn,
+                notify_tx,
+                WebsocketNotificationType::ShiftCompleted,
+                None,
+                &completed_shift,
+            );
         }
 
         Ok(result)
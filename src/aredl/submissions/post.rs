@@ -1,13 +1,18 @@
 use crate::aredl::bounty::Bounty;
-use crate::notifications::WebsocketNotification;
+use crate::notifications::{NotificationList, WebsocketNotification, WebsocketNotificationType};
 use crate::{
     app_data::db::DbConnection,
     aredl::levels::LevelStatus,
-    aredl::submissions::{status::SubmissionsEnabled, Submission, SubmissionStatus},
+    aredl::submissions::{
+        status::{closures::SubmissionClosure, SubmissionsEnabled},
+        videos::SubmissionVideo,
+        Submission, SubmissionStatus,
+    },
     auth::{Authenticated, Permission},
     error_handler::ApiError,
     providers::ProvidersAppState,
     schema::aredl::{levels, submissions},
+    submission_requirements::{RequirementLevel, SubmissionRequirements, SubmissionRuleList},
 };
 use serde::{Deserialize, Serialize};
 use tokio::sync::broadcast;
@@ -193,6 +198,8 @@ impl Submission {
                 e
             })?;
 
+        let video = SubmissionVideo::from(providers.identify_video(&submission_body.video_url));
+
         if let Some(raw_url) = submission_body.raw_url.as_ref() {
             submission_body.raw_url = Some(providers.validate_raw_footage_url(raw_url).map_err(
                 |mut e| {
@@ -212,6 +219,15 @@ impl Submission {
                 return Err(ApiError::Forbidden("Submissions are currently disabled"));
             }
 
+            if authenticated.user_id == inserted_submission.submitted_by {
+                if let Some(closure) = SubmissionClosure::find_active_for_level(
+                    connection,
+                    inserted_submission.level_id,
+                )? {
+                    return Err(ApiError::Forbidden(closure.error_message()));
+                }
+            }
+
             // check if any submissions exist already
             let exists_submission = submissions::table
                 .filter(submissions::submitted_by.eq(inserted_submission.submitted_by))
@@ -227,20 +243,21 @@ impl Submission {
             }
 
             // check that this level exists, accepts submissions, and
-            // raw footage is provided when required
+            // the submission includes everything the requirement rules ask for
             let level_info = levels::table
                 .filter(levels::id.eq(inserted_submission.level_id))
                 .select((
                     levels::status,
                     levels::position,
+                    levels::tags,
                     levels::requires_raw_footage,
                 ))
-                .first::<(LevelStatus, Option<i32>, bool)>(connection)
+                .first::<(LevelStatus, Option<i32>, Vec<Option<String>>, bool)>(connection)
                 .optional()?;
 
             match level_info {
                 None => return Err(ApiError::NotFound("Could not find this level")),
-                Some((status, position, requires_raw_footage)) => {
+                Some((status, position, tags, requires_raw_footage)) => {
                     if status == LevelStatus::Legacy {
                         return Err(ApiError::UnprocessableEntity(
                             "This level is on the legacy list and is not accepting records.",
@@ -250,29 +267,51 @@ impl Submission {
                         return Err(ApiError::Gone("This level has been removed from the list."));
                     }
 
-                    let raw_is_required = match status {
-                        LevelStatus::Pending => requires_raw_footage,
-                        LevelStatus::MainList => position.is_some_and(|position| position <= 400),
-                        LevelStatus::Legacy | LevelStatus::Removed => false,
-                    };
-
-                    if raw_is_required && inserted_submission.raw_url.is_none() {
-                        return Err(ApiError::UnprocessableEntity(
-                            "This level requires raw footage",
-                        ));
-                    }
+                    SubmissionRequirements::resolve(
+                        connection,
+                        SubmissionRuleList::Aredl,
+                        &RequirementLevel {
+                            status: (&status).into(),
+                            position,
+                            tags: &tags,
+                            requires_raw_footage,
+                        },
+                        inserted_submission.mobile,
+                    )?
+                    .check(
+                        inserted_submission.raw_url.as_deref(),
+                        inserted_submission.mod_menu.as_deref(),
+                        inserted_submission.custom_copy_id,
+                    )?;
                 }
             }
 
+            let video_reused = video.is_reused(
+                connection,
+                None,
+                inserted_submission.submitted_by,
+                inserted_submission.level_id,
+            )?;
+
             let submission = diesel::insert_into(submissions::table)
-                .values(&inserted_submission)
+                .values((
+                    &inserted_submission,
+                    &video,
+                    submissions::video_reused.eq(video_reused),
+                ))
                 .returning(Self::as_select())
                 .get_result(connection)?;
 
             Ok(submission)
         })?;
 
-        WebsocketNotification::send(notify_tx, "SUBMISSION_CREATED", &submission);
+        WebsocketNotification::send(
+            conn,
+            notify_tx,
+            WebsocketNotificationType::SubmissionCreated,
+            Some(NotificationList::Aredl),
+            &submission,
+        );
 
         Ok(submission)
     }
@@ -1,11 +1,17 @@
 use crate::{
     app_data::db::DbConnection,
     aredl::submissions::{Submission, SubmissionFilter, SubmissionStatus},
+    auth::{permission::get_users_with_permission, Permission},
     error_handler::ApiError,
-    schema::aredl::submissions,
+    schema::aredl::{submission_queue_snapshots, submission_stats, submissions},
+    shifts::{QueueEta, ShiftCapacity, THROUGHPUT_WINDOW_DAYS},
 };
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use diesel::pg::Pg;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
@@ -24,6 +30,32 @@ pub struct SubmissionQueue {
     pub uc_submissions: i64,
     /// The timestamp of the oldest pending submission in the queue, if any.
     pub oldest_submission: Option<DateTime<Utc>>,
+    /// Estimated review time of a new regular submission with raw footage, if it can be estimated.
+    pub regular_eta: Option<QueueEta>,
+    /// Estimated review time of a new regular submission without raw footage, if it can be estimated.
+    pub regular_without_raw_eta: Option<QueueEta>,
+    /// Estimated review time of a new priority submission with raw footage, if it can be estimated.
+    pub priority_eta: Option<QueueEta>,
+    /// Estimated review time of a new priority submission without raw footage, if it can be estimated.
+    pub priority_without_raw_eta: Option<QueueEta>,
+}
+
+/// Queue depth at a point in time, recorded on a schedule to track how the queue evolves.
+#[derive(Serialize, Deserialize, Queryable, Selectable, Insertable, Debug, Clone, ToSchema)]
+#[diesel(table_name = submission_queue_snapshots, check_for_backend(Pg))]
+pub struct SubmissionQueueSnapshot {
+    /// Timestamp of when the snapshot was taken.
+    pub taken_at: DateTime<Utc>,
+    /// The amount of pending submissions that were not marked as priority.
+    pub regular_submissions_in_queue: i64,
+    /// The amount of pending submissions that were not marked as priority and did not have raw footage.
+    pub regular_submissions_without_raw_in_queue: i64,
+    /// The amount of pending submissions that were marked as priority.
+    pub priority_submissions_in_queue: i64,
+    /// The amount of pending submissions that were marked as priority and did not have raw footage.
+    pub priority_submissions_without_raw_in_queue: i64,
+    /// The amount of submissions that were under consideration.
+    pub uc_submissions: i64,
 }
 
 #[derive(serde::Serialize, utoipa::ToSchema)]
@@ -32,6 +64,101 @@ pub struct QueuePositionResponse {
     pub position: i64,
     /// Whether the submission is in the priority queue or not.
     pub priority: bool,
+    /// Estimated review time of the submission, if it can be estimated.
+    pub eta: Option<QueueEta>,
+}
+
+/// How long the loaded throughput is reused before it is loaded again.
+const THROUGHPUT_CACHE_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// Recent review throughput of all reviewers and of the reviewers that can claim submissions with raw footage.
+pub struct QueueThroughput {
+    daily_reviews: Vec<i64>,
+    capacity: ShiftCapacity,
+    raw_daily_reviews: Vec<i64>,
+    raw_capacity: ShiftCapacity,
+}
+
+impl QueueThroughput {
+    pub fn load(conn: &mut DbConnection) -> Result<Self, ApiError> {
+        let raw_reviewers =
+            get_users_with_permission(conn, Permission::SubmissionEditWithRawFootage)?
+                .into_iter()
+                .collect::<Vec<_>>();
+
+        Ok(Self {
+            daily_reviews: Self::daily_reviews(conn, None)?,
+            capacity: ShiftCapacity::load(conn, None)?,
+            raw_daily_reviews: Self::daily_reviews(conn, Some(&raw_reviewers))?,
+            raw_capacity: ShiftCapacity::load(conn, Some(&raw_reviewers))?,
+        })
+    }
+
+    // amount of reviews done on each of the last full days, optionally only by the given reviewers
+    fn daily_reviews(
+        conn: &mut DbConnection,
+        reviewers: Option<&[Uuid]>,
+    ) -> Result<Vec<i64>, ApiError> {
+        let today = Utc::now().date_naive();
+        let window_start = today - Days::new(THROUGHPUT_WINDOW_DAYS);
+
+        let mut query = submission_stats::table
+            .filter(submission_stats::day.ge(window_start))
+            .filter(submission_stats::day.lt(today))
+            .select((
+                submission_stats::day,
+                submission_stats::accepted
+                    + submission_stats::denied
+                    + submission_stats::under_consideration,
+            ))
+            .into_boxed();
+        query = match reviewers {
+            Some(reviewers) => query.filter(submission_stats::reviewer_id.eq_any(reviewers)),
+            None => query.filter(submission_stats::reviewer_id.is_null()),
+        };
+
+        let mut per_day: HashMap<NaiveDate, i64> = HashMap::new();
+        for (day, reviewed) in query.load::<(NaiveDate, i64)>(conn)? {
+            *per_day.entry(day).or_default() += reviewed;
+        }
+
+        // days without any history are missing from the stats, nothing was reviewed on them
+        Ok(window_start
+            .iter_days()
+            .take_while(|day| *day < today)
+            .map(|day| per_day.get(&day).copied().unwrap_or_default())
+            .collect())
+    }
+
+    /// Submissions with raw footage can only be claimed by some reviewers, so only their throughput is used.
+    pub fn eta(&self, ahead: i64, has_raw_footage: bool) -> Option<QueueEta> {
+        if has_raw_footage {
+            QueueEta::estimate(ahead, &self.raw_daily_reviews, &self.raw_capacity)
+        } else {
+            QueueEta::estimate(ahead, &self.daily_reviews, &self.capacity)
+        }
+    }
+}
+
+/// Keeps the last loaded throughput around, so queue requests don't have to aggregate the review history every time.
+#[derive(Default)]
+pub struct QueueThroughputCache {
+    cached: Mutex<Option<(Instant, Arc<QueueThroughput>)>>,
+}
+
+impl QueueThroughputCache {
+    pub fn get(&self, conn: &mut DbConnection) -> Result<Arc<QueueThroughput>, ApiError> {
+        let mut cached = self.cached.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some((loaded_at, throughput)) = cached.as_ref() {
+            if loaded_at.elapsed() < THROUGHPUT_CACHE_TTL {
+                return Ok(throughput.clone());
+            }
+        }
+
+        let throughput = Arc::new(QueueThroughput::load(conn)?);
+        *cached = Some((Instant::now(), throughput.clone()));
+        Ok(throughput)
+    }
 }
 
 impl Submission {
@@ -66,8 +193,9 @@ impl Submission {
 
     pub fn get_queue_position(
         conn: &mut DbConnection,
+        throughput: &QueueThroughputCache,
         submission_id: Uuid,
-    ) -> Result<(i64, bool), ApiError> {
+    ) -> Result<QueuePositionResponse, ApiError> {
         let (target_priority, target_created_at, target_priority_at, target_has_raw_footage): (
             bool,
             DateTime<Utc>,
@@ -96,7 +224,61 @@ impl Submission {
             .get_result::<i64>(conn)?
             + 1;
 
-        Ok((position, target_priority))
+        // priority submissions are always claimed first
+        let priority_ahead = if target_priority {
+            0
+        } else {
+            submissions::table
+                .filter(SubmissionQueue::pending_filter(
+                    true,
+                    !target_has_raw_footage,
+                ))
+                .count()
+                .get_result::<i64>(conn)?
+        };
+
+        let eta = throughput
+            .get(conn)?
+            .eta(position - 1 + priority_ahead, target_has_raw_footage);
+
+        Ok(QueuePositionResponse {
+            position,
+            priority: target_priority,
+            eta,
+        })
+    }
+}
+
+impl SubmissionQueueSnapshot {
+    /// Counts the submissions currently waiting in the queue.
+    pub fn current(conn: &mut DbConnection) -> Result<Self, ApiError> {
+        let pending_count = |conn: &mut DbConnection, priority: bool, exclude_raw: bool| {
+            submissions::table
+                .filter(SubmissionQueue::pending_filter(priority, exclude_raw))
+                .count()
+                .get_result::<i64>(conn)
+        };
+
+        Ok(Self {
+            taken_at: Utc::now(),
+            regular_submissions_in_queue: pending_count(conn, false, false)?,
+            regular_submissions_without_raw_in_queue: pending_count(conn, false, true)?,
+            priority_submissions_in_queue: pending_count(conn, true, false)?,
+            priority_submissions_without_raw_in_queue: pending_count(conn, true, true)?,
+            uc_submissions: submissions::table
+                .filter(submissions::status.eq(SubmissionStatus::UnderConsideration))
+                .count()
+                .get_result::<i64>(conn)?,
+        })
+    }
+
+    /// Records the current queue depth.
+    pub fn take(conn: &mut DbConnection) -> Result<Self, ApiError> {
+        let snapshot = diesel::insert_into(submission_queue_snapshots::table)
+            .values(Self::current(conn)?)
+            .returning(Self::as_select())
+            .get_result::<Self>(conn)?;
+        Ok(snapshot)
     }
 }
 
@@ -115,31 +297,18 @@ impl SubmissionQueue {
         }
     }
 
-    pub fn get_queue(conn: &mut DbConnection) -> Result<Self, ApiError> {
-        let regular_submissions_in_queue = submissions::table
-            .filter(Self::pending_filter(false, false))
-            .count()
-            .get_result::<i64>(conn)?;
-
-        let regular_submissions_without_raw_in_queue = submissions::table
-            .filter(Self::pending_filter(false, true))
-            .count()
-            .get_result::<i64>(conn)?;
-
-        let priority_submissions_in_queue = submissions::table
-            .filter(Self::pending_filter(true, false))
-            .count()
-            .get_result::<i64>(conn)?;
-
-        let priority_submissions_without_raw_in_queue = submissions::table
-            .filter(Self::pending_filter(true, true))
-            .count()
-            .get_result::<i64>(conn)?;
-
-        let uc_submissions = submissions::table
-            .filter(submissions::status.eq(SubmissionStatus::UnderConsideration))
-            .count()
-            .get_result::<i64>(conn)?;
+    pub fn get_queue(
+        conn: &mut DbConnection,
+        throughput: &QueueThroughputCache,
+    ) -> Result<Self, ApiError> {
+        let SubmissionQueueSnapshot {
+            regular_submissions_in_queue,
+            regular_submissions_without_raw_in_queue,
+            priority_submissions_in_queue,
+            priority_submissions_without_raw_in_queue,
+            uc_submissions,
+            ..
+        } = SubmissionQueueSnapshot::current(conn)?;
 
         let oldest_submission = submissions::table
             .filter(submissions::status.eq(SubmissionStatus::Pending))
@@ -148,6 +317,8 @@ impl SubmissionQueue {
             .first::<DateTime<Utc>>(conn)
             .optional()?;
 
+        let throughput = throughput.get(conn)?;
+
         Ok(Self {
             regular_submissions_in_queue,
             regular_submissions_without_raw_in_queue,
@@ -155,6 +326,18 @@ impl SubmissionQueue {
             priority_submissions_without_raw_in_queue,
             uc_submissions,
             oldest_submission,
+            regular_eta: throughput.eta(
+                priority_submissions_in_queue + regular_submissions_in_queue,
+                true,
+            ),
+            regular_without_raw_eta: throughput.eta(
+                priority_submissions_without_raw_in_queue
+                    + regular_submissions_without_raw_in_queue,
+                false,
+            ),
+            priority_eta: throughput.eta(priority_submissions_in_queue, true),
+            priority_without_raw_eta: throughput
+                .eta(priority_submissions_without_raw_in_queue, false),
         })
     }
 }
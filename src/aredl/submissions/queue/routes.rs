@@ -1,12 +1,13 @@
 use crate::{
     app_data::db::DbAppState,
     aredl::submissions::{
-        queue::{QueuePositionResponse, SubmissionQueue},
+        queue::{QueuePositionResponse, QueueThroughputCache, SubmissionQueue},
         Submission,
     },
     auth::{Authenticated, UserAuth},
     cache_control::CacheController,
     error_handler::ApiError,
+    shifts::QueueEta,
 };
 use actix_web::{get, web, HttpResponse};
 use std::sync::Arc;
@@ -16,7 +17,7 @@ use uuid::Uuid;
 #[utoipa::path(
     get,
     summary = "[Auth]Get queue position for a submission",
-    description = "Returns the position of a specific submission in the pending queue.",
+    description = "Returns the position of a specific submission in the pending queue, along with an estimate of when it gets reviewed based on the recent review throughput and the scheduled shifts.",
     tag = "AREDL - Submissions",
     responses(
         (status = 200, description = "Queue position found", body = QueuePositionResponse),
@@ -33,35 +34,41 @@ use uuid::Uuid;
 #[get("{id}/queue", wrap = "UserAuth::load()")]
 async fn get_queue_position(
     db: web::Data<Arc<DbAppState>>,
+    throughput: web::Data<Arc<QueueThroughputCache>>,
     id: web::Path<Uuid>,
     _auth: Authenticated,
 ) -> Result<HttpResponse, ApiError> {
-    let (position, priority) =
-        web::block(move || Submission::get_queue_position(&mut db.connection()?, id.into_inner()))
-            .await??;
+    let position = web::block(move || {
+        Submission::get_queue_position(&mut db.connection()?, &throughput, id.into_inner())
+    })
+    .await??;
 
-    Ok(HttpResponse::Ok().json(QueuePositionResponse { position, priority }))
+    Ok(HttpResponse::Ok().json(position))
 }
 
 #[utoipa::path(
     get,
     summary = "Get submissions queue",
-    description = "Get the amount of pending submissions.",
+    description = "Get the amount of pending submissions, along with estimates of how long new submissions take to be reviewed.",
     tag = "AREDL - Submissions",
     responses(
         (status = 200, body = SubmissionQueue)
     )
 )]
 #[get("queue", wrap = "CacheController::public_with_max_age(60)")]
-async fn get_queue(db: web::Data<Arc<DbAppState>>) -> Result<HttpResponse, ApiError> {
+async fn get_queue(
+    db: web::Data<Arc<DbAppState>>,
+    throughput: web::Data<Arc<QueueThroughputCache>>,
+) -> Result<HttpResponse, ApiError> {
     let submission =
-        web::block(move || SubmissionQueue::get_queue(&mut db.connection()?)).await??;
+        web::block(move || SubmissionQueue::get_queue(&mut db.connection()?, &throughput))
+            .await??;
     Ok(HttpResponse::Ok().json(submission))
 }
 
 #[derive(OpenApi)]
 #[openapi(
-    components(schemas(SubmissionQueue, QueuePositionResponse,)),
+    components(schemas(SubmissionQueue, QueuePositionResponse, QueueEta,)),
     paths(get_queue, get_queue_position,)
 )]
 pub struct ApiDoc;
@@ -0,0 +1,223 @@
+use crate::{
+    app_data::db::{DbAppState, DbConnection},
+    aredl::submissions::SubmissionStatus,
+    error_handler::ApiError,
+    providers::ProvidersAppState,
+    schema::aredl::{submission_raw_probes, submissions},
+    utils::probe::{RawFootageAnalysis, RawFootageFlag},
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Arc};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use diesel::{pg::Pg, prelude::*};
+
+// failures are usually transient, like a rate limited or briefly unreachable host, so they are retried a few times
+const MAX_PROBE_ATTEMPTS: i32 = 5;
+const PROBE_RETRY_BACKOFF_MINUTES: i64 = 30;
+
+#[derive(Queryable, Selectable, Insertable, AsChangeset, Debug)]
+#[diesel(table_name = submission_raw_probes, treat_none_as_null = true, check_for_backend(Pg))]
+struct SubmissionRawProbeRow {
+    submission_id: Uuid,
+    raw_url: String,
+    error: Option<String>,
+    duration_seconds: Option<f64>,
+    width: Option<i32>,
+    height: Option<i32>,
+    fps: Option<f64>,
+    container: Option<String>,
+    file_created_at: Option<DateTime<Utc>>,
+    reencoded: Option<bool>,
+    flags: Vec<Option<String>>,
+    probed_at: DateTime<Utc>,
+    attempts: i32,
+    retry_at: Option<DateTime<Utc>>,
+}
+
+/// Result of the automatic check of a submission's raw footage.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct RawFootageProbe {
+    /// Raw footage URL that was probed.
+    pub raw_url: String,
+    /// Why the raw footage could not be probed, if it failed.
+    pub error: Option<String>,
+    /// Duration of the video, in seconds.
+    pub duration_seconds: Option<f64>,
+    /// Width of the video, in pixels.
+    pub width: Option<i32>,
+    /// Height of the video, in pixels.
+    pub height: Option<i32>,
+    /// Frames per second of the video.
+    pub fps: Option<f64>,
+    /// File format of the raw footage.
+    pub container: Option<String>,
+    /// Creation time recorded in the file metadata.
+    pub file_created_at: Option<DateTime<Utc>>,
+    /// Whether the file metadata mentions video editing or transcoding software.
+    pub reencoded: Option<bool>,
+    /// Red flags for the reviewer to look at.
+    pub flags: Vec<RawFootageFlag>,
+    /// Timestamp of when the raw footage was probed.
+    pub probed_at: DateTime<Utc>,
+}
+
+impl SubmissionRawProbeRow {
+    fn new(
+        submission_id: Uuid,
+        raw_url: String,
+        submitted_at: DateTime<Utc>,
+        attempts: i32,
+        analysis: Result<RawFootageAnalysis, ApiError>,
+    ) -> Self {
+        match analysis {
+            Ok(analysis) => Self {
+                submission_id,
+                raw_url,
+                error: None,
+                flags: analysis
+                    .flags(submitted_at)
+                    .iter()
+                    .map(|flag| Some(flag.to_string()))
+                    .collect(),
+                duration_seconds: analysis.duration_seconds,
+                width: analysis.width,
+                height: analysis.height,
+                fps: analysis.fps,
+                container: analysis.container,
+                file_created_at: analysis.file_created_at,
+                reencoded: Some(analysis.reencoded),
+                probed_at: Utc::now(),
+                attempts,
+                retry_at: None,
+            },
+            Err(error) => Self {
+                submission_id,
+                raw_url,
+                error: Some(error.error_message),
+                duration_seconds: None,
+                width: None,
+                height: None,
+                fps: None,
+                container: None,
+                file_created_at: None,
+                reencoded: None,
+                flags: Vec::new(),
+                probed_at: Utc::now(),
+                attempts,
+                // the wait doubles after every failed attempt
+                retry_at: (attempts < MAX_PROBE_ATTEMPTS).then(|| {
+                    Utc::now() + Duration::minutes(PROBE_RETRY_BACKOFF_MINUTES << (attempts - 1))
+                }),
+            },
+        }
+    }
+}
+
+impl From<SubmissionRawProbeRow> for RawFootageProbe {
+    fn from(row: SubmissionRawProbeRow) -> Self {
+        Self {
+            raw_url: row.raw_url,
+            error: row.error,
+            duration_seconds: row.duration_seconds,
+            width: row.width,
+            height: row.height,
+            fps: row.fps,
+            container: row.container,
+            file_created_at: row.file_created_at,
+            reencoded: row.reencoded,
+            flags: row
+                .flags
+                .into_iter()
+                .flatten()
+                .filter_map(|flag| flag.parse().ok())
+                .collect(),
+            probed_at: row.probed_at,
+        }
+    }
+}
+
+impl RawFootageProbe {
+    /// Probes of the given submissions, leaving out those of raw footage that has been changed since.
+    pub fn find_for(
+        conn: &mut DbConnection,
+        submission_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Self>, ApiError> {
+        let rows = submission_raw_probes::table
+            .inner_join(submissions::table)
+            .filter(submission_raw_probes::submission_id.eq_any(submission_ids))
+            .filter(submissions::raw_url.eq(submission_raw_probes::raw_url.nullable()))
+            .select(SubmissionRawProbeRow::as_select())
+            .load::<SubmissionRawProbeRow>(conn)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.submission_id, row.into()))
+            .collect())
+    }
+
+    /// Probes the raw footage of submissions waiting for a review that was not probed yet, oldest first.
+    /// Failed probes are tried again once their backoff has passed.
+    pub async fn probe_unchecked(
+        db: &Arc<DbAppState>,
+        providers: &ProvidersAppState,
+        limit: i64,
+    ) -> Result<usize, ApiError> {
+        let unchecked = submissions::table
+            .left_join(submission_raw_probes::table)
+            .filter(submissions::status.eq_any([
+                SubmissionStatus::Pending,
+                SubmissionStatus::Claimed,
+                SubmissionStatus::UnderReview,
+                SubmissionStatus::UnderConsideration,
+            ]))
+            .filter(submissions::raw_url.is_not_null())
+            .filter(
+                submission_raw_probes::raw_url.nullable().is_null().or(
+                    submission_raw_probes::raw_url
+                        .nullable()
+                        .ne(submissions::raw_url)
+                        .or(submission_raw_probes::retry_at.le(Utc::now())),
+                ),
+            )
+            .order(submissions::created_at.asc())
+            .limit(limit)
+            .select((
+                submissions::id,
+                submissions::raw_url.assume_not_null(),
+                submissions::created_at,
+                submission_raw_probes::raw_url.nullable(),
+                submission_raw_probes::attempts.nullable(),
+            ))
+            .load::<(Uuid, String, DateTime<Utc>, Option<String>, Option<i32>)>(
+                &mut db.connection()?,
+            )?;
+
+        let count = unchecked.len();
+        for (submission_id, raw_url, submitted_at, probed_url, attempts) in unchecked {
+            // changed raw footage starts over
+            let attempts = match (probed_url, attempts) {
+                (Some(probed_url), Some(attempts)) if probed_url == raw_url => attempts + 1,
+                _ => 1,
+            };
+            let analysis = RawFootageAnalysis::probe_url(providers, &raw_url).await;
+            let row = SubmissionRawProbeRow::new(
+                submission_id,
+                raw_url,
+                submitted_at,
+                attempts,
+                analysis,
+            );
+
+            diesel::insert_into(submission_raw_probes::table)
+                .values(&row)
+                .on_conflict(submission_raw_probes::submission_id)
+                .do_update()
+                .set(&row)
+                .execute(&mut db.connection()?)?;
+        }
+        Ok(count)
+    }
+}
@@ -0,0 +1,248 @@
+#[cfg(test)]
+use {
+    crate::{
+        app_data::db::DbAppState,
+        aredl::{levels::test_utils::create_test_level, submissions::raw_probes::RawFootageProbe},
+        auth::{create_test_token, oauth::OAuthProvider},
+        providers::{
+            context::{google::new_google_context, ProviderContext},
+            list::{gdrive::GoogleDriveProvider, youtube::YouTubeProvider},
+            model::{Provider, ProviderRegistry},
+            test_utils::{
+                clear_oauth_env, mock_google_drive_file_endpoint, mock_google_token_endpoint,
+                seed_oauth_token, set_oauth_env,
+            },
+            ProvidersAppState,
+        },
+        schema::aredl::submission_raw_probes,
+        test_utils::init_test_app_with_providers,
+        users::test_utils::{create_test_full_reviewer, create_test_user},
+        utils::probe::test_utils::{clear_probe_tools_env, set_probe_tools_env},
+    },
+    actix_web::test::{self, read_body_json},
+    chrono::{DateTime, Duration, Utc},
+    diesel::prelude::*,
+    httpmock::MockServer,
+    serde_json::{json, Value},
+    serial_test::serial,
+    std::sync::Arc,
+    uuid::Uuid,
+};
+
+#[cfg(test)]
+async fn drive_providers(db: Option<Arc<DbAppState>>) -> ProvidersAppState {
+    let google_auth = new_google_context()
+        .await
+        .expect("Failed to create Google OAuth context");
+    ProvidersAppState::new(
+        ProviderRegistry::new(vec![
+            Arc::new(YouTubeProvider) as Arc<dyn Provider>,
+            Arc::new(GoogleDriveProvider) as Arc<dyn Provider>,
+        ]),
+        ProviderContext {
+            http: reqwest::Client::new(),
+            db,
+            discord_auth: None,
+            google_auth: Some(Arc::new(google_auth)),
+            patreon_auth: None,
+            twitch_auth: None,
+        },
+    )
+}
+
+#[actix_web::test]
+#[serial]
+async fn probe_new_raw_footage() {
+    clear_oauth_env(OAuthProvider::Google);
+    let server = MockServer::start_async().await;
+    set_oauth_env(OAuthProvider::Google, &server.base_url());
+    mock_google_token_endpoint(&server, 3600, "test_access").await;
+    let file_mock =
+        mock_google_drive_file_endpoint(&server, "rawfile001", b"not a real video").await;
+    let _tools = set_probe_tools_env(
+        &json!({
+            "streams": [{
+                "codec_type": "video",
+                "width": 1280,
+                "height": 720,
+                "avg_frame_rate": "24/1"
+            }],
+            "format": {
+                "format_name": "mov,mp4,m4a,3gp,3g2,mj2",
+                "duration": "93.500000",
+                "tags": {
+                    "encoder": "HandBrake 1.6.1 2023012300",
+                    "creation_time": "2000-01-01T00:00:00.000000Z"
+                }
+            }
+        }),
+        &json!([{ "FileType": "MP4", "MIMEType": "video/mp4" }]),
+    );
+
+    let (app, db, auth, _) =
+        init_test_app_with_providers(Arc::new(drive_providers(None).await)).await;
+    seed_oauth_token(&db, OAuthProvider::Google, Some("refresh_a"));
+    let providers = drive_providers(Some(db.clone())).await;
+    let (moderator, _) = create_test_full_reviewer(&db).await;
+    let moderator_token = create_test_token(moderator, &auth.jwt_encoding_key).unwrap();
+    let (user, _) = create_test_user(&db, None).await;
+    let token = create_test_token(user, &auth.jwt_encoding_key).unwrap();
+    let level = create_test_level(&db).await;
+
+    let req = test::TestRequest::post()
+        .uri("/aredl/submissions/")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({
+            "level_id": level,
+            "video_url": "https://youtu.be/rawprobe001",
+            "raw_url": "https://drive.google.com/file/d/rawfile001/view",
+            "mobile": false
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let submission: Value = read_body_json(resp).await;
+    let submission_uri = format!("/aredl/submissions/{}", submission["id"].as_str().unwrap());
+
+    let probed = RawFootageProbe::probe_unchecked(&db, &providers, 1000)
+        .await
+        .expect("Failed to probe raw footage");
+    assert!(probed >= 1);
+    file_mock.assert_async().await;
+
+    let req = test::TestRequest::get()
+        .uri(&submission_uri)
+        .insert_header(("Authorization", format!("Bearer {moderator_token}")))
+        .to_request();
+    let body: Value = read_body_json(test::call_service(&app, req).await).await;
+    let probe = &body["raw_footage_probe"];
+    assert!(probe["error"].is_null(), "probe is {probe}");
+    assert_eq!(probe["width"], 1280);
+    assert_eq!(probe["height"], 720);
+    assert_eq!(probe["fps"], 24.0);
+    assert_eq!(probe["duration_seconds"], 93.5);
+    assert_eq!(probe["container"], "MP4");
+    assert_eq!(probe["reencoded"], true);
+    assert_eq!(
+        probe["flags"],
+        json!(["Reencoded", "LowFrameRate", "MissingAudio"])
+    );
+
+    // flags also show up in the reviewer's queue
+    let req = test::TestRequest::get()
+        .uri(&format!("/aredl/submissions/?level_filter={level}"))
+        .insert_header(("Authorization", format!("Bearer {moderator_token}")))
+        .to_request();
+    let body: Value = read_body_json(test::call_service(&app, req).await).await;
+    let listed = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|listed| listed["id"] == submission["id"])
+        .expect("Submission is missing from the queue");
+    assert_eq!(listed["raw_footage_probe"]["flags"], probe["flags"]);
+
+    // the submitter doesn't see any of it
+    let req = test::TestRequest::get()
+        .uri(&submission_uri)
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let body: Value = read_body_json(test::call_service(&app, req).await).await;
+    assert!(body.get("raw_footage_probe").is_none());
+
+    // already probed footage is left alone
+    RawFootageProbe::probe_unchecked(&db, &providers, 1000)
+        .await
+        .expect("Failed to probe raw footage");
+    file_mock.assert_async().await;
+
+    clear_probe_tools_env();
+    clear_oauth_env(OAuthProvider::Google);
+}
+
+#[actix_web::test]
+#[serial]
+async fn retry_failed_probe_after_backoff() {
+    clear_oauth_env(OAuthProvider::Google);
+    let server = MockServer::start_async().await;
+    set_oauth_env(OAuthProvider::Google, &server.base_url());
+    mock_google_token_endpoint(&server, 3600, "test_access").await;
+    let _tools = set_probe_tools_env(
+        &json!({
+            "streams": [{ "codec_type": "video", "width": 1920, "height": 1080, "avg_frame_rate": "60/1" }],
+            "format": { "format_name": "mov,mp4,m4a,3gp,3g2,mj2", "duration": "60.000000" }
+        }),
+        &json!([{ "FileType": "MP4", "MIMEType": "video/mp4" }]),
+    );
+
+    let (app, db, auth, _) =
+        init_test_app_with_providers(Arc::new(drive_providers(None).await)).await;
+    seed_oauth_token(&db, OAuthProvider::Google, Some("refresh_a"));
+    let providers = drive_providers(Some(db.clone())).await;
+    let (user, _) = create_test_user(&db, None).await;
+    let token = create_test_token(user, &auth.jwt_encoding_key).unwrap();
+    let level = create_test_level(&db).await;
+
+    let req = test::TestRequest::post()
+        .uri("/aredl/submissions/")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({
+            "level_id": level,
+            "video_url": "https://youtu.be/rawprobe002",
+            "raw_url": "https://drive.google.com/file/d/rawfile002/view",
+            "mobile": false
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let submission: Value = read_body_json(resp).await;
+    let submission_id = Uuid::parse_str(submission["id"].as_str().unwrap()).unwrap();
+
+    let load_probe = || {
+        submission_raw_probes::table
+            .filter(submission_raw_probes::submission_id.eq(submission_id))
+            .select((
+                submission_raw_probes::error,
+                submission_raw_probes::attempts,
+                submission_raw_probes::retry_at,
+            ))
+            .first::<(Option<String>, i32, Option<DateTime<Utc>>)>(&mut db.connection().unwrap())
+            .expect("Failed to load raw footage probe")
+    };
+
+    // the file can't be downloaded yet
+    RawFootageProbe::probe_unchecked(&db, &providers, 1000)
+        .await
+        .expect("Failed to probe raw footage");
+    let (error, attempts, retry_at) = load_probe();
+    assert!(error.is_some());
+    assert_eq!(attempts, 1);
+    assert!(retry_at.expect("Failed probe has no retry time") > Utc::now());
+
+    let file_mock =
+        mock_google_drive_file_endpoint(&server, "rawfile002", b"not a real video").await;
+
+    // nothing happens before the backoff has passed
+    RawFootageProbe::probe_unchecked(&db, &providers, 1000)
+        .await
+        .expect("Failed to probe raw footage");
+    file_mock.assert_calls_async(0).await;
+
+    diesel::update(submission_raw_probes::table)
+        .filter(submission_raw_probes::submission_id.eq(submission_id))
+        .set(submission_raw_probes::retry_at.eq(Utc::now() - Duration::minutes(1)))
+        .execute(&mut db.connection().unwrap())
+        .expect("Failed to move the retry time");
+
+    RawFootageProbe::probe_unchecked(&db, &providers, 1000)
+        .await
+        .expect("Failed to probe raw footage");
+    file_mock.assert_async().await;
+    let (error, attempts, retry_at) = load_probe();
+    assert!(error.is_none(), "error is {error:?}");
+    assert_eq!(attempts, 2);
+    assert!(retry_at.is_none());
+
+    clear_probe_tools_env();
+    clear_oauth_env(OAuthProvider::Google);
+}
@@ -2,7 +2,11 @@ use crate::{
     app_data::db::DbConnection,
     aredl::{
         levels::ExtendedBaseLevel,
-        submissions::{Submission, SubmissionResolved, SubmissionStatus},
+        submissions::{
+            raw_probes::RawFootageProbe,
+            videos::{VideoDateWarning, VideoUse},
+            Submission, SubmissionResolved, SubmissionStatus,
+        },
     },
     auth::Authenticated,
     error_handler::ApiError,
@@ -12,6 +16,7 @@ use crate::{
         aredl::{levels, submission_history, submissions},
         users,
     },
+    submission_reasons::{RequestLocales, SubmissionReasonCatalogue},
     users::{user_filter, ExtendedBaseUser},
 };
 use diesel::dsl::{auto_type, AliasedFields, AsSelect, Nullable};
@@ -92,7 +97,11 @@ fn resolve_query<'a>(q: submissions::BoxedQuery<'a, Pg>) -> _ {
 }
 
 impl SubmissionResolved {
-    pub fn from_data(resolved: ResolvedSubmissionRow) -> SubmissionResolved {
+    pub fn from_data(
+        resolved: ResolvedSubmissionRow,
+        catalogue: &SubmissionReasonCatalogue,
+        locales: &RequestLocales,
+    ) -> SubmissionResolved {
         let (submission, level, submitter, reviewer) = resolved;
 
         SubmissionResolved {
@@ -102,6 +111,7 @@ impl SubmissionResolved {
             mobile: submission.mobile,
             custom_copy_id: submission.custom_copy_id,
             video_url: submission.video_url,
+            video_published_at: submission.video_published_at,
             raw_url: submission.raw_url,
             mod_menu: submission.mod_menu,
             status: submission.status,
@@ -109,6 +119,11 @@ impl SubmissionResolved {
             priority: submission.priority,
             priority_at: submission.priority_at,
             reviewer_notes: submission.reviewer_notes,
+            reasons: catalogue.localize(&submission.reason_codes, locales),
+            video_reused: None,
+            video_reuses: None,
+            video_date_warnings: None,
+            raw_footage_probe: None,
             private_reviewer_notes: submission.private_reviewer_notes,
             user_notes: submission.user_notes,
             locked: submission.locked,
@@ -121,6 +136,7 @@ impl SubmissionResolved {
         conn: &mut DbConnection,
         id: Uuid,
         authenticated: &Authenticated,
+        locales: &RequestLocales,
     ) -> Result<SubmissionResolved, ApiError> {
         let mut query = submissions::table
             .filter(submissions::id.eq(id))
@@ -132,8 +148,12 @@ impl SubmissionResolved {
             query = query.filter(submissions::submitted_by.eq(authenticated.user_id));
         }
 
-        let mut resolved =
-            Self::from_data(resolve_query(query).first::<ResolvedSubmissionRow>(conn)?);
+        let catalogue = SubmissionReasonCatalogue::load(conn)?;
+        let mut resolved = Self::from_data(
+            resolve_query(query).first::<ResolvedSubmissionRow>(conn)?,
+            &catalogue,
+            locales,
+        );
 
         visibility
             .should_hide_reviewer(
@@ -145,6 +165,18 @@ impl SubmissionResolved {
             )
             .apply_extended(&mut resolved.reviewer, &mut resolved.private_reviewer_notes);
 
+        if visibility.is_reviewer {
+            resolved.video_reused = Some(
+                submissions::table
+                    .filter(submissions::id.eq(id))
+                    .select(submissions::video_reused)
+                    .first::<bool>(conn)?,
+            );
+            resolved.video_reuses = Some(VideoUse::find_reuses(conn, id)?);
+            resolved.video_date_warnings = Some(VideoDateWarning::find(conn, id)?);
+            resolved.raw_footage_probe = RawFootageProbe::find_for(conn, &[id])?.remove(&id);
+        }
+
         Ok(resolved)
     }
 }
@@ -155,6 +187,7 @@ impl ResolvedSubmissionPage {
         page_query: PageQuery<D>,
         options: SubmissionQueryOptions,
         authenticated: &Authenticated,
+        locales: &RequestLocales,
     ) -> Result<Paginated<Self>, ApiError> {
         let visibility = ReviewerVisibility::new(conn, authenticated)?;
 
@@ -247,9 +280,10 @@ impl ResolvedSubmissionPage {
         )
         .load::<ResolvedSubmissionRow>(conn)?;
 
+        let catalogue = SubmissionReasonCatalogue::load(conn)?;
         let mut submissions = submissions
             .into_iter()
-            .map(SubmissionResolved::from_data)
+            .map(|row| SubmissionResolved::from_data(row, &catalogue, locales))
             .collect::<Vec<_>>();
 
         let total_count: i64 = build_filtered().count().get_result(conn)?;
@@ -271,6 +305,14 @@ impl ResolvedSubmissionPage {
                 );
         }
 
+        if visibility.is_reviewer {
+            let ids = submissions.iter().map(|s| s.id).collect::<Vec<_>>();
+            let mut probes = RawFootageProbe::find_for(conn, &ids)?;
+            for submission in &mut submissions {
+                submission.raw_footage_probe = probes.remove(&submission.id);
+            }
+        }
+
         Ok(Paginated::<Self>::from_data(
             page_query,
             total_count,
@@ -283,10 +325,11 @@ impl ResolvedSubmissionPage {
         page_query: PageQuery<D>,
         mut options: SubmissionQueryOptions,
         authenticated: &Authenticated,
+        locales: &RequestLocales,
     ) -> Result<Paginated<Self>, ApiError> {
         options.submitter_filter = Some(authenticated.user_id.to_string());
         options.reviewer_filter = None;
 
-        Self::find_all(conn, page_query, options, authenticated)
+        Self::find_all(conn, page_query, options, authenticated, locales)
     }
 }
@@ -42,6 +42,7 @@ pub async fn insert_history_entry(
         id: Uuid::new_v4(),
         submission_id,
         reviewer_notes: None,
+        reason_codes: Vec::new(),
         video_url: Some("https://video.com".to_owned()),
         raw_url: Some("https://raw.com".to_owned()),
         mobile: Some(false),
@@ -82,6 +83,18 @@ pub fn set_test_submission_reviewer(
         .expect("Failed to set test aredl submission reviewer");
 }
 
+#[cfg(test)]
+pub fn set_test_submission_reviewer_notes(
+    db: &Arc<DbAppState>,
+    submission_id: Uuid,
+    reviewer_notes: Option<&str>,
+) {
+    diesel::update(submissions::table.filter(submissions::id.eq(submission_id)))
+        .set(submissions::reviewer_notes.eq(reviewer_notes.map(str::to_owned)))
+        .execute(&mut db.connection().unwrap())
+        .expect("Failed to set test aredl submission reviewer notes");
+}
+
 #[cfg(test)]
 pub fn set_test_submission_reviewer_with_private_notes(
     db: &Arc<DbAppState>,
@@ -174,6 +187,18 @@ pub fn set_test_submissions_raw_url(
         .expect("Failed to set test aredl submissions raw URL");
 }
 
+#[cfg(test)]
+pub fn set_test_submission_created_at(
+    db: &Arc<DbAppState>,
+    submission_id: Uuid,
+    created_at: DateTime<Utc>,
+) {
+    diesel::update(submissions::table.filter(submissions::id.eq(submission_id)))
+        .set(submissions::created_at.eq(created_at))
+        .execute(&mut db.connection().unwrap())
+        .expect("Failed to set test aredl submission created_at");
+}
+
 #[cfg(test)]
 pub fn get_test_submission(db: &Arc<DbAppState>, submission_id: Uuid) -> Submission {
     get_test_submission_optional(db, submission_id).expect("Failed to fetch test aredl submission")
@@ -322,3 +347,35 @@ pub async fn create_two_test_submissions_with_different_timestamps(
 
     (older_id, newer_id)
 }
+
+/// Records `count` accepted reviews by the reviewer at the given time and refreshes the submission stats.
+#[cfg(test)]
+pub async fn create_test_reviews(
+    db: &Arc<DbAppState>,
+    reviewer_id: Uuid,
+    count: usize,
+    timestamp: DateTime<Utc>,
+) {
+    let level_id = create_test_level(db).await;
+    let submission_id = create_test_submission(level_id, Uuid::new_v4(), db).await;
+    set_test_submission_status(db, submission_id, SubmissionStatus::Accepted);
+    // only the entries inserted below should count as reviews
+    diesel::delete(
+        submission_history::table.filter(submission_history::submission_id.eq(submission_id)),
+    )
+    .execute(&mut db.connection().unwrap())
+    .expect("Failed to clear test aredl submission history");
+    for _ in 0..count {
+        insert_history_entry(
+            submission_id,
+            Some(reviewer_id),
+            SubmissionStatus::Accepted,
+            db,
+        )
+        .await;
+    }
+    set_history_timestamp(db, submission_id, timestamp);
+    diesel::sql_query("REFRESH MATERIALIZED VIEW aredl.submission_stats")
+        .execute(&mut db.connection().unwrap())
+        .expect("Failed to refresh submission stats");
+}
@@ -5,7 +5,9 @@ use {
         aredl::{
             bounty::test_utils::create_test_bounty,
             levels::{
-                test_utils::{create_test_level, set_test_level_status},
+                test_utils::{
+                    create_test_level, get_test_level, set_test_level_status, set_test_level_tags,
+                },
                 LevelStatus,
             },
             records::test_utils::{get_test_record, get_test_record_for_level_and_user},
@@ -13,12 +15,14 @@ use {
                 status::SubmissionsEnabled,
                 test_utils::{
                     create_priority_queue_order_test_submissions,
-                    create_regular_queue_order_test_submissions, create_test_submission,
-                    create_two_test_submissions_with_different_timestamps, get_test_submission,
-                    get_test_submission_optional, latest_test_submission_history,
-                    set_test_submission_raw_url, set_test_submission_raw_url_status_and_reviewer,
-                    set_test_submission_reviewer, set_test_submission_reviewer_with_private_notes,
-                    set_test_submission_status, set_test_submissions_raw_url,
+                    create_regular_queue_order_test_submissions, create_test_reviews,
+                    create_test_submission, create_two_test_submissions_with_different_timestamps,
+                    get_test_submission, get_test_submission_optional,
+                    latest_test_submission_history, set_test_submission_raw_url,
+                    set_test_submission_raw_url_status_and_reviewer, set_test_submission_reviewer,
+                    set_test_submission_reviewer_notes,
+                    set_test_submission_reviewer_with_private_notes, set_test_submission_status,
+                    set_test_submissions_raw_url,
                 },
                 SubmissionStatus,
             },
@@ -35,9 +39,13 @@ use {
             ProvidersAppState,
         },
         shifts::{
-            test_utils::{create_test_shift, get_test_shift, set_test_shift_target_count},
+            test_utils::{
+                create_test_shift, get_test_shift, set_test_shift_start_at,
+                set_test_shift_target_count,
+            },
             ShiftStatus,
         },
+        submission_reasons::test_utils::create_test_reason,
         test_utils::*,
         users::test_utils::{
             create_test_auditor, create_test_full_reviewer, create_test_hidden_reviewer,
@@ -907,6 +915,135 @@ async fn regular_submission_queue_position_still_uses_created_at() {
     assert!(!body["priority"].as_bool().unwrap());
 }
 
+#[actix_web::test]
+async fn submission_queue_eta_uses_recent_throughput() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user, _) = create_test_user(&db, None).await;
+    let (other_user, _) = create_test_user(&db, None).await;
+    let (reviewer, _) = create_test_full_reviewer(&db).await;
+    let token = create_test_token(user, &auth.jwt_encoding_key).unwrap();
+    let level = create_test_level(&db).await;
+    create_test_submission(level, other_user, &db).await;
+    let submission = create_test_submission(level, user, &db).await;
+
+    // 14 reviews over the last 14 days average out to one review a day
+    create_test_reviews(&db, reviewer, 14, Utc::now() - ChronoDuration::days(1)).await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/aredl/submissions/{submission}/queue"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    let body: serde_json::Value = read_body_json(resp).await;
+    assert_eq!(body["position"].as_i64().unwrap(), 2);
+    assert_eq!(body["eta"]["daily_reviews"], 1.0);
+    assert_eq!(body["eta"]["estimated_hours"], 48.0);
+    assert_eq!(body["eta"]["min_hours"], 48.0);
+    assert_eq!(
+        body["eta"]["max_hours"], 192.0,
+        "A mostly idle window should be capped at a quarter of the average throughput"
+    );
+}
+
+#[actix_web::test]
+async fn submission_queue_eta_reuses_loaded_throughput() {
+    let (app, db, _, _) = init_test_app().await;
+    let (user, _) = create_test_user(&db, None).await;
+    let (reviewer, _) = create_test_full_reviewer(&db).await;
+    let level = create_test_level(&db).await;
+    create_test_submission(level, user, &db).await;
+    create_test_reviews(&db, reviewer, 14, Utc::now() - ChronoDuration::days(1)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/aredl/submissions/queue")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    let body: serde_json::Value = read_body_json(resp).await;
+    assert_eq!(body["regular_eta"]["daily_reviews"], 1.0);
+
+    create_test_reviews(&db, reviewer, 14, Utc::now() - ChronoDuration::days(1)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/aredl/submissions/queue")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    let body: serde_json::Value = read_body_json(resp).await;
+    assert_eq!(
+        body["regular_eta"]["daily_reviews"], 1.0,
+        "The throughput should not be reloaded on every request"
+    );
+}
+
+#[actix_web::test]
+async fn submission_queue_eta_without_raw_reviewers() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user, _) = create_test_user(&db, None).await;
+    let (reviewer, _) =
+        create_test_user_with_permissions(&db, &[Permission::SubmissionReview]).await;
+    let token = create_test_token(user, &auth.jwt_encoding_key).unwrap();
+    let level = create_test_level(&db).await;
+    let submission = create_test_submission(level, user, &db).await;
+    create_test_reviews(&db, reviewer, 14, Utc::now() - ChronoDuration::days(1)).await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/aredl/submissions/{submission}/queue"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    let body: serde_json::Value = read_body_json(resp).await;
+    assert!(
+        body["eta"].is_null(),
+        "Submissions with raw footage should only count reviewers that can claim them"
+    );
+
+    set_test_submission_raw_url(&db, submission, None);
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/aredl/submissions/{submission}/queue"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    let body: serde_json::Value = read_body_json(resp).await;
+    assert_eq!(body["eta"]["estimated_hours"], 24.0);
+}
+
+#[actix_web::test]
+async fn submission_queue_eta_scales_with_upcoming_shifts() {
+    let (app, db, _, _) = init_test_app().await;
+    let (user, _) = create_test_user(&db, None).await;
+    let (reviewer, _) = create_test_full_reviewer(&db).await;
+    let level = create_test_level(&db).await;
+    create_test_submission(level, user, &db).await;
+    create_test_reviews(&db, reviewer, 14, Utc::now() - ChronoDuration::days(1)).await;
+
+    // as many shifts in the upcoming week as in the last two weeks doubles the expected throughput
+    let past_shift = create_test_shift(&db, reviewer, false).await;
+    set_test_shift_start_at(&db, past_shift, Utc::now() - ChronoDuration::days(2));
+    create_test_shift(&db, reviewer, false).await;
+
+    let req = test::TestRequest::get()
+        .uri("/aredl/submissions/queue")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    let body: serde_json::Value = read_body_json(resp).await;
+    assert_eq!(body["regular_eta"]["daily_reviews"], 2.0);
+    assert_eq!(
+        body["regular_eta"]["estimated_hours"], 24.0,
+        "A new submission should wait for the pending one and itself"
+    );
+    assert_eq!(body["priority_eta"]["estimated_hours"], 12.0);
+    assert!(
+        body["regular_without_raw_eta"]["estimated_hours"].is_number(),
+        "Reviews from raw footage reviewers should also count towards other submissions"
+    );
+}
+
 #[actix_web::test]
 async fn claim_priority_submission_uses_priority_at() {
     let (app, db, auth, _) = init_test_app().await;
@@ -2125,3 +2262,445 @@ async fn patch_submission_requires_claim_or_non_self_claimed_edit_permission() {
 
     assert_eq!(body["status"], "Accepted");
 }
+
+#[actix_web::test]
+async fn deny_submission_with_reasons() {
+    let (app, db, auth, _) = init_test_app().await;
+
+    let (user_id, _) = create_test_user(&db, None).await;
+    let (moderator_id, _) = create_test_full_reviewer(&db).await;
+    let token =
+        create_test_token(moderator_id, &auth.jwt_encoding_key).expect("Failed to generate token");
+    let level_id = create_test_level(&db).await;
+    let submission = create_test_submission(level_id, user_id, &db).await;
+    let code = create_test_reason(&db).await;
+
+    let req = test::TestRequest::patch()
+        .uri(format!("/aredl/submissions/{submission}").as_str())
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"status": "Denied", "reason_codes": [code, "missing_clicks"]}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    let expected = vec![Some(code.clone()), Some("missing_clicks".to_owned())];
+    let denied_submission = get_test_submission(&db, submission);
+    assert_eq!(denied_submission.reason_codes, expected);
+
+    let history_entry = latest_test_submission_history(&db, submission);
+    assert_eq!(history_entry.status, SubmissionStatus::Denied);
+    assert_eq!(
+        history_entry.reason_codes, expected,
+        "Reasons should be stored in the submission history"
+    );
+
+    // reasons are cleared when the status changes without new reasons
+    let req = test::TestRequest::patch()
+        .uri(format!("/aredl/submissions/{submission}").as_str())
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"status": "UnderConsideration"}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    assert!(get_test_submission(&db, submission).reason_codes.is_empty());
+}
+
+#[actix_web::test]
+async fn submission_reasons_are_validated() {
+    let (app, db, auth, _) = init_test_app().await;
+
+    let (user_id, _) = create_test_user(&db, None).await;
+    let (moderator_id, _) = create_test_full_reviewer(&db).await;
+    let token =
+        create_test_token(moderator_id, &auth.jwt_encoding_key).expect("Failed to generate token");
+    let level_id = create_test_level(&db).await;
+    let submission = create_test_submission(level_id, user_id, &db).await;
+
+    let req = test::TestRequest::patch()
+        .uri(format!("/aredl/submissions/{submission}").as_str())
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"status": "Denied", "reason_codes": ["not_a_real_reason"]}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let req = test::TestRequest::patch()
+        .uri(format!("/aredl/submissions/{submission}").as_str())
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"status": "Accepted", "reason_codes": ["missing_clicks"]}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let stored = get_test_submission(&db, submission);
+    assert_eq!(stored.status, SubmissionStatus::Pending);
+    assert!(stored.reason_codes.is_empty());
+}
+
+#[actix_web::test]
+async fn bulk_accept_submissions_by_ids() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (reviewer_id, _) = create_test_user_with_permissions(
+        &db,
+        &[
+            Permission::SubmissionReview,
+            Permission::SubmissionEditWithRawFootage,
+            Permission::SubmissionBulkEdit,
+        ],
+    )
+    .await;
+    let token = create_test_token(reviewer_id, &auth.jwt_encoding_key).unwrap();
+    let shift_id = create_test_shift(&db, reviewer_id, true).await;
+
+    let (first_user, _) = create_test_user(&db, None).await;
+    let (second_user, _) = create_test_user(&db, None).await;
+    let first_level = create_test_level(&db).await;
+    let second_level = create_test_level(&db).await;
+    let first = create_test_submission(first_level, first_user, &db).await;
+    let second = create_test_submission(second_level, second_user, &db).await;
+    let own = create_test_submission(first_level, reviewer_id, &db).await;
+    let missing = Uuid::new_v4();
+    for claimed in [first, second] {
+        set_test_submission_status(&db, claimed, SubmissionStatus::Claimed);
+        set_test_submission_reviewer(&db, claimed, Some(reviewer_id));
+    }
+
+    let req = test::TestRequest::post()
+        .uri("/aredl/submissions/bulk")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({
+            "action": "Accept",
+            "ids": [first, second, own, missing],
+            "reviewer_notes": "GG"
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: serde_json::Value = read_body_json(resp).await;
+    assert_eq!(body["updated"].as_array().unwrap().len(), 2);
+    let skipped: Vec<&str> = body["skipped"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|skipped| skipped["id"].as_str().unwrap())
+        .collect();
+    assert!(skipped.contains(&own.to_string().as_str()));
+    assert!(skipped.contains(&missing.to_string().as_str()));
+
+    for (submission_id, level_id, user_id) in [
+        (first, first_level, first_user),
+        (second, second_level, second_user),
+    ] {
+        let history = latest_test_submission_history(&db, submission_id);
+        assert_eq!(history.status, SubmissionStatus::Accepted);
+        assert_eq!(history.reviewer_id, Some(reviewer_id));
+        assert_eq!(history.reviewer_notes.as_deref(), Some("GG"));
+        get_test_record_for_level_and_user(&db, level_id, user_id);
+    }
+    assert_eq!(
+        get_test_submission(&db, own).status,
+        SubmissionStatus::Pending,
+        "Own submissions should be left untouched"
+    );
+    assert_eq!(get_test_shift(&db, shift_id).completed_count, 2);
+}
+
+#[actix_web::test]
+async fn bulk_accept_skips_unclaimed_submissions() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (reviewer_id, _) = create_test_user_with_permissions(
+        &db,
+        &[
+            Permission::SubmissionReview,
+            Permission::SubmissionEditWithRawFootage,
+            Permission::SubmissionBulkEdit,
+        ],
+    )
+    .await;
+    let token = create_test_token(reviewer_id, &auth.jwt_encoding_key).unwrap();
+    let (user_id, _) = create_test_user(&db, None).await;
+    let submission = create_test_submission(create_test_level(&db).await, user_id, &db).await;
+
+    // like a single edit, reviewers can only review submissions they claimed themselves
+    let req = test::TestRequest::post()
+        .uri("/aredl/submissions/bulk")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"action": "Accept", "ids": [submission]}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: serde_json::Value = read_body_json(resp).await;
+    assert!(body["updated"].as_array().unwrap().is_empty());
+    assert_eq!(body["skipped"][0]["id"], submission.to_string());
+
+    let stored = get_test_submission(&db, submission);
+    assert_eq!(stored.status, SubmissionStatus::Pending);
+    assert_eq!(stored.reviewer_id, None);
+}
+
+#[actix_web::test]
+async fn bulk_deny_submissions_by_level_filter() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (reviewer_id, _) = create_test_user_with_permissions(
+        &db,
+        &[
+            Permission::SubmissionReview,
+            Permission::SubmissionEditWithRawFootage,
+            Permission::SubmissionEditNonSelfClaimed,
+            Permission::SubmissionBulkEdit,
+        ],
+    )
+    .await;
+    let token = create_test_token(reviewer_id, &auth.jwt_encoding_key).unwrap();
+    let shift_id = create_test_shift(&db, reviewer_id, true).await;
+    let level_id = create_test_level(&db).await;
+    let other_level_id = create_test_level(&db).await;
+    let (first_user, _) = create_test_user(&db, None).await;
+    let (second_user, _) = create_test_user(&db, None).await;
+    let first = create_test_submission(level_id, first_user, &db).await;
+    let second = create_test_submission(level_id, second_user, &db).await;
+    let other = create_test_submission(other_level_id, first_user, &db).await;
+
+    let req = test::TestRequest::post()
+        .uri("/aredl/submissions/bulk")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({
+            "action": "Deny",
+            "filter": {"level_id": level_id, "status": "Pending"},
+            "reason_codes": ["video_unavailable"]
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    for submission_id in [first, second] {
+        let denied = get_test_submission(&db, submission_id);
+        assert_eq!(denied.status, SubmissionStatus::Denied);
+        assert_eq!(
+            denied.reason_codes,
+            vec![Some("video_unavailable".to_owned())]
+        );
+        assert_eq!(
+            latest_test_submission_history(&db, submission_id).status,
+            SubmissionStatus::Denied
+        );
+    }
+    assert_eq!(
+        get_test_submission(&db, other).status,
+        SubmissionStatus::Pending
+    );
+    // none of them were claimed by the reviewer, so they don't count toward their shift
+    assert_eq!(get_test_shift(&db, shift_id).completed_count, 0);
+}
+
+#[actix_web::test]
+async fn bulk_unclaim_and_prioritize_submissions() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (reviewer_id, _) = create_test_user_with_permissions(
+        &db,
+        &[
+            Permission::SubmissionReview,
+            Permission::SubmissionEditWithRawFootage,
+            Permission::SubmissionEditNonSelfClaimed,
+            Permission::SubmissionBulkEdit,
+        ],
+    )
+    .await;
+    let token = create_test_token(reviewer_id, &auth.jwt_encoding_key).unwrap();
+    let (other_reviewer, _) = create_test_full_reviewer(&db).await;
+    let (user_id, _) = create_test_user(&db, None).await;
+    let claimed = create_test_submission(create_test_level(&db).await, user_id, &db).await;
+    let pending = create_test_submission(create_test_level(&db).await, user_id, &db).await;
+    set_test_submission_status(&db, claimed, SubmissionStatus::Claimed);
+    set_test_submission_reviewer(&db, claimed, Some(other_reviewer));
+    set_test_submission_reviewer_notes(&db, claimed, Some("Check the clicks"));
+
+    let req = test::TestRequest::post()
+        .uri("/aredl/submissions/bulk")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"action": "Unclaim", "ids": [claimed, pending]}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: serde_json::Value = read_body_json(resp).await;
+    assert_eq!(body["skipped"][0]["id"], pending.to_string());
+
+    let unclaimed = get_test_submission(&db, claimed);
+    assert_eq!(unclaimed.status, SubmissionStatus::Pending);
+    assert_eq!(unclaimed.reviewer_id, None);
+
+    let req = test::TestRequest::post()
+        .uri("/aredl/submissions/bulk")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"action": "Prioritize", "filter": {"submitter": user_id}}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    assert!(get_test_submission(&db, claimed).priority);
+    assert!(get_test_submission(&db, pending).priority);
+    // actions without notes leave the existing ones alone
+    assert_eq!(
+        get_test_submission(&db, claimed).reviewer_notes.as_deref(),
+        Some("Check the clicks")
+    );
+}
+
+#[actix_web::test]
+async fn bulk_edit_requires_permission_and_valid_target() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (reviewer_id, _) = create_test_full_reviewer(&db).await;
+    let reviewer_token = create_test_token(reviewer_id, &auth.jwt_encoding_key).unwrap();
+    let (bulk_reviewer_id, _) = create_test_user(&db, Some(Permission::SubmissionBulkEdit)).await;
+    let bulk_token = create_test_token(bulk_reviewer_id, &auth.jwt_encoding_key).unwrap();
+    let (user_id, _) = create_test_user(&db, None).await;
+    let submission = create_test_submission(create_test_level(&db).await, user_id, &db).await;
+
+    let req = test::TestRequest::post()
+        .uri("/aredl/submissions/bulk")
+        .insert_header(("Authorization", format!("Bearer {reviewer_token}")))
+        .set_json(json!({"action": "Accept", "ids": [submission]}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    for body in [
+        json!({"action": "Lock", "ids": [submission], "filter": {"submitter": user_id}}),
+        json!({"action": "Lock", "filter": {}}),
+        json!({"action": "Accept", "ids": [submission], "reason_codes": ["missing_clicks"]}),
+    ] {
+        let req = test::TestRequest::post()
+            .uri("/aredl/submissions/bulk")
+            .insert_header(("Authorization", format!("Bearer {bulk_token}")))
+            .set_json(body)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    // submissions with raw footage need the matching permission
+    let req = test::TestRequest::post()
+        .uri("/aredl/submissions/bulk")
+        .insert_header(("Authorization", format!("Bearer {bulk_token}")))
+        .set_json(json!({"action": "Lock", "ids": [submission]}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: serde_json::Value = read_body_json(resp).await;
+    assert_eq!(body["skipped"][0]["id"], submission.to_string());
+    assert!(!get_test_submission(&db, submission).locked);
+
+    // submissions claimed by another reviewer need the matching permission
+    set_test_submission_raw_url(&db, submission, None);
+    set_test_submission_status(&db, submission, SubmissionStatus::Claimed);
+    set_test_submission_reviewer(&db, submission, Some(reviewer_id));
+    let req = test::TestRequest::post()
+        .uri("/aredl/submissions/bulk")
+        .insert_header(("Authorization", format!("Bearer {bulk_token}")))
+        .set_json(json!({"action": "Unclaim", "ids": [submission]}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: serde_json::Value = read_body_json(resp).await;
+    assert_eq!(body["skipped"][0]["id"], submission.to_string());
+    assert_eq!(
+        get_test_submission(&db, submission).reviewer_id,
+        Some(reviewer_id)
+    );
+}
+
+#[actix_web::test]
+async fn claim_with_level_and_mobile_filters() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, None).await;
+    let (reviewer_id, _) = create_test_full_reviewer(&db).await;
+    let token = create_test_token(reviewer_id, &auth.jwt_encoding_key).unwrap();
+    let skipped_level = create_test_level(&db).await;
+    let wanted_level = create_test_level(&db).await;
+    create_test_submission(skipped_level, user_id, &db).await;
+    let wanted = create_test_submission(wanted_level, user_id, &db).await;
+
+    // test submissions are desktop submissions
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "/aredl/submissions/claim?level_filter={wanted_level}&mobile_filter=true"
+        ))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "/aredl/submissions/claim?level_filter={wanted_level},{}&mobile_filter=false",
+            Uuid::new_v4()
+        ))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: serde_json::Value = read_body_json(resp).await;
+    assert_eq!(body["id"], wanted.to_string());
+    assert_eq!(
+        get_test_submission(&db, wanted).status,
+        SubmissionStatus::Claimed
+    );
+
+    // levels can also be given by their GD ID
+    let (other_user, _) = create_test_user(&db, None).await;
+    let by_gd_id = create_test_submission(wanted_level, other_user, &db).await;
+    let gd_id = get_test_level(&db, wanted_level).await.level_id;
+    let req = test::TestRequest::get()
+        .uri(&format!("/aredl/submissions/claim?level_filter={gd_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: serde_json::Value = read_body_json(resp).await;
+    assert_eq!(body["id"], by_gd_id.to_string());
+
+    let req = test::TestRequest::get()
+        .uri("/aredl/submissions/claim?level_filter=not-a-uuid")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn claim_with_tag_and_position_filters() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, None).await;
+    let (reviewer_id, _) = create_test_full_reviewer(&db).await;
+    let token = create_test_token(reviewer_id, &auth.jwt_encoding_key).unwrap();
+    let tag = format!("tag_{}", Uuid::new_v4().simple());
+    let level_id = create_test_level(&db).await;
+    set_test_level_tags(&db, level_id, &[&tag, "Wave"]).await;
+    let submission = create_test_submission(level_id, user_id, &db).await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "/aredl/submissions/claim?tag_filter=Other,{tag}&min_position=100000"
+        ))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let req = test::TestRequest::get()
+        .uri("/aredl/submissions/claim?min_position=10&max_position=5")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "/aredl/submissions/claim?tag_filter=Other,{tag}&max_position=100000"
+        ))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: serde_json::Value = read_body_json(resp).await;
+    assert_eq!(body["id"], submission.to_string());
+}
@@ -0,0 +1,8 @@
+mod model;
+mod routes;
+
+#[cfg(test)]
+mod tests;
+
+pub use model::*;
+pub use routes::{init_routes, ApiDoc};
@@ -3,10 +3,12 @@ use crate::arepl::levels::ExtendedBaseLevel;
 use crate::arepl::records::Record;
 use crate::auth::{Authenticated, Permission};
 use crate::error_handler::ApiError;
+use crate::notifications::NotificationList;
 use crate::schema::{
     arepl::{bounties, bounty_completed, levels},
     users,
 };
+use crate::users::me::notifications::{Notification, NotificationPayload, NotificationType};
 use chrono::{DateTime, Utc};
 use diesel::dsl::count;
 use diesel::pg::Pg;
@@ -301,7 +303,11 @@ impl Bounty {
 }
 
 impl Record {
-    pub fn complete_bounty_if_exists(&self, conn: &mut DbConnection) -> Result<(), ApiError> {
+    /// Completes the running bounties of the record's level, returning the notifications sent to the record holder.
+    pub fn complete_bounty_if_exists(
+        &self,
+        conn: &mut DbConnection,
+    ) -> Result<Vec<Notification>, ApiError> {
         let bounties = bounties::table
             .filter(bounties::level_id.eq(self.level_id))
             .filter(bounties::start_date.le(self.achieved_at))
@@ -313,43 +319,66 @@ impl Record {
             .select(bounties::id)
             .load::<Uuid>(conn)?;
 
+        let mut notifications = Vec::new();
         for bounty_id in bounties {
-            conn.transaction(|conn| -> Result<(), ApiError> {
-                let bounty = bounties::table
-                    .filter(bounties::id.eq(bounty_id))
-                    .for_update()
-                    .first::<Bounty>(conn)?;
-
-                let current_completions = bounty.count_completions(conn)?;
+            let notification =
+                conn.transaction(|conn| -> Result<Option<Notification>, ApiError> {
+                    let bounty = bounties::table
+                        .filter(bounties::id.eq(bounty_id))
+                        .for_update()
+                        .first::<Bounty>(conn)?;
+
+                    let current_completions = bounty.count_completions(conn)?;
+
+                    if let Some(target) = bounty.target_submissions {
+                        if current_completions >= i64::from(target) {
+                            return Ok(None);
+                        }
+                    }
 
-                if let Some(target) = bounty.target_submissions {
-                    if current_completions >= i64::from(target) {
-                        return Ok(());
+                    let inserted = diesel::insert_into(bounty_completed::table)
+                        .values((
+                            bounty_completed::bounty_id.eq(bounty.id),
+                            bounty_completed::user_id.eq(self.submitted_by),
+                            bounty_completed::completed_at.eq(Utc::now()),
+                        ))
+                        .on_conflict((bounty_completed::bounty_id, bounty_completed::user_id))
+                        .do_nothing()
+                        .execute(conn)?;
+
+                    let mut notification = None;
+                    if inserted > 0 {
+                        let level_name = levels::table
+                            .filter(levels::id.eq(bounty.level_id))
+                            .select(levels::name)
+                            .first::<String>(conn)?;
+                        notification = Notification::create(
+                            conn,
+                            self.submitted_by,
+                            format!("Your record on {level_name:?} completed a bounty!"),
+                            NotificationType::Success,
+                            Some(NotificationPayload::Bounty {
+                                bounty_id: bounty.id,
+                                record_id: self.id,
+                                level_id: bounty.level_id,
+                                list: NotificationList::Arepl,
+                            }),
+                        )?;
                     }
-                }
-
-                diesel::insert_into(bounty_completed::table)
-                    .values((
-                        bounty_completed::bounty_id.eq(bounty.id),
-                        bounty_completed::user_id.eq(self.submitted_by),
-                        bounty_completed::completed_at.eq(Utc::now()),
-                    ))
-                    .on_conflict((bounty_completed::bounty_id, bounty_completed::user_id))
-                    .do_nothing()
-                    .execute(conn)?;
-
-                if let Some(target) = bounty.target_submissions {
-                    if bounty.count_completions(conn)? >= i64::from(target) {
-                        diesel::update(bounties::table.filter(bounties::id.eq(bounty.id)))
-                            .set(bounties::end_date.eq(Utc::now()))
-                            .execute(conn)?;
+
+                    if let Some(target) = bounty.target_submissions {
+                        if bounty.count_completions(conn)? >= i64::from(target) {
+                            diesel::update(bounties::table.filter(bounties::id.eq(bounty.id)))
+                                .set(bounties::end_date.eq(Utc::now()))
+                                .execute(conn)?;
+                        }
                     }
-                }
 
-                Ok(())
-            })?;
+                    Ok(notification)
+                })?;
+            notifications.extend(notification);
         }
 
-        Ok(())
+        Ok(notifications)
     }
 }
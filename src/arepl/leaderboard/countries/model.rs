@@ -1,5 +1,5 @@
 use crate::app_data::db::DbConnection;
-use crate::arepl::leaderboard::LeaderboardOrder;
+use crate::arepl::leaderboard::{LeaderboardOrder, PastRanks};
 use crate::arepl::levels::BaseLevel;
 use crate::error_handler::ApiError;
 use crate::scheduled::refresh_matviews::MatviewRefreshLog;
@@ -31,6 +31,12 @@ pub struct CountryLeaderboardEntry {
 pub struct CountryLeaderboardEntryResolved {
     /// Rank of the country, sorted by total points (including packs).
     pub rank: i32,
+    /// How many places the country moved up, sorted by total points (including packs), since yesterday.
+    /// Negative if the country moved down, not set if the country was not ranked yesterday.
+    pub rank_change_day: Option<i32>,
+    /// How many places the country moved up, sorted by total points (including packs), since last week.
+    /// Negative if the country moved down, not set if the country was not ranked last week.
+    pub rank_change_week: Option<i32>,
     /// Rank of the country, sorted by count of extremes completed.
     pub extremes_rank: i32,
     /// Rank of the country, sorted by hardest completed level position.
@@ -90,10 +96,14 @@ impl CountryLeaderboardPage {
             ))
             .load(conn)?;
 
+        let past_ranks = PastRanks::for_countries(conn)?;
+
         let entries_resolved = raw_entries
             .into_iter()
             .map(|(entry, hardest)| CountryLeaderboardEntryResolved {
                 rank: entry.rank,
+                rank_change_day: past_ranks.change_since_yesterday(&entry.country, entry.rank),
+                rank_change_week: past_ranks.change_since_last_week(&entry.country, entry.rank),
                 extremes_rank: entry.extremes_rank,
                 hardest_rank: entry.hardest_rank,
                 country: entry.country,
@@ -1,10 +1,12 @@
 mod clans;
 mod countries;
+mod history;
 mod model;
 mod routes;
 pub mod test_utils;
 #[cfg(test)]
 mod tests;
 
+pub use history::*;
 pub use model::*;
 pub use routes::{init_routes, ApiDoc};
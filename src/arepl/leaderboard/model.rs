@@ -1,4 +1,5 @@
 use crate::app_data::db::DbConnection;
+use crate::arepl::leaderboard::PastRanks;
 use crate::arepl::levels::BaseLevel;
 use crate::clans::Clan;
 use crate::error_handler::ApiError;
@@ -40,6 +41,12 @@ pub struct LeaderboardEntry {
 pub struct LeaderboardEntryResolved {
     /// Rank of the user in the global leaderboard, sorted by total points (including packs).
     pub rank: i32,
+    /// How many places the user moved up in the global leaderboard, sorted by total points (including packs), since yesterday.
+    /// Negative if the user moved down, not set if the user was not ranked yesterday.
+    pub rank_change_day: Option<i32>,
+    /// How many places the user moved up in the global leaderboard, sorted by total points (including packs), since last week.
+    /// Negative if the user moved down, not set if the user was not ranked last week.
+    pub rank_change_week: Option<i32>,
     /// Rank of the user in the global leaderboard, sorted by count of extremes completed.
     pub extremes_rank: i32,
     /// Rank of the user in the global leaderboard, sorted by total points (excluding packs).
@@ -177,10 +184,15 @@ impl LeaderboardPage {
             ))
             .load(conn)?;
 
+        let user_ids = raw.iter().map(|(e, ..)| e.user_id).collect::<Vec<_>>();
+        let past_ranks = PastRanks::for_users(conn, &user_ids)?;
+
         let entries_resolved = raw
             .into_iter()
             .map(|(e, user, clan, lvl)| LeaderboardEntryResolved {
                 rank: e.rank,
+                rank_change_day: past_ranks.change_since_yesterday(&e.user_id, e.rank),
+                rank_change_week: past_ranks.change_since_last_week(&e.user_id, e.rank),
                 extremes_rank: e.extremes_rank,
                 raw_rank: e.raw_rank,
                 hardest_rank: e.hardest_rank,
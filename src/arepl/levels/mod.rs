@@ -5,6 +5,7 @@ pub(crate) mod id_resolver;
 mod model;
 mod notes;
 mod packs;
+pub mod personal_bests;
 pub mod records;
 mod routes;
 mod updates;
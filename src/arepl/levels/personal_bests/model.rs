@@ -0,0 +1,117 @@
+use crate::app_data::db::DbConnection;
+use crate::arepl::levels::LevelStatus;
+use crate::error_handler::ApiError;
+use crate::schema::{
+    arepl::{levels, personal_bests, records},
+    users,
+};
+use crate::users::ExtendedBaseUser;
+use chrono::{DateTime, Utc};
+use diesel::pg::Pg;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use diesel::prelude::*;
+#[derive(Serialize, Deserialize, Queryable, Selectable, Debug, ToSchema)]
+#[diesel(table_name = personal_bests, check_for_backend(Pg))]
+/// A time a player held as their personal best on a level.
+pub struct PersonalBest {
+    /// Internal UUID of the personal best.
+    pub id: Uuid,
+    /// Internal UUID of the record this time belongs to.
+    pub record_id: Uuid,
+    /// Completion time in milliseconds.
+    pub completion_time: i64,
+    /// Video link of the run.
+    pub video_url: String,
+    /// Whether the run was completed on mobile or not.
+    pub mobile: bool,
+    /// Timestamp of when this time was achieved.
+    pub achieved_at: DateTime<Utc>,
+    /// Timestamp of when a faster run of the same player replaced this time. Null for the current personal best.
+    pub superseded_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+/// A player who held the world record of a level.
+pub struct WorldRecordHolder {
+    /// User who set the world record.
+    pub user: ExtendedBaseUser,
+    /// Internal UUID of the record the world record belongs to.
+    pub record_id: Uuid,
+    /// Completion time in milliseconds.
+    pub completion_time: i64,
+    /// Video link of the run.
+    pub video_url: String,
+    /// Whether the run was completed on mobile or not.
+    pub mobile: bool,
+    /// Timestamp of when the world record was set.
+    pub achieved_at: DateTime<Utc>,
+    /// Timestamp of when the world record was beaten. Null if it still stands.
+    pub beaten_at: Option<DateTime<Utc>>,
+}
+
+impl PersonalBest {
+    /// Lists every personal best of a user on a level, oldest first.
+    pub fn find_for_user(
+        conn: &mut DbConnection,
+        level_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Vec<Self>, ApiError> {
+        let personal_bests = personal_bests::table
+            .inner_join(records::table)
+            .filter(records::level_id.eq(level_id))
+            .filter(records::submitted_by.eq(user_id))
+            .order((personal_bests::achieved_at.asc(), personal_bests::id.asc()))
+            .select(Self::as_select())
+            .load::<Self>(conn)?;
+        Ok(personal_bests)
+    }
+}
+
+impl WorldRecordHolder {
+    /// Lists every time that was the fastest on a level when it was achieved, oldest first.
+    /// Counts the same records as the `arepl.world_records` view.
+    pub fn find_all_by_level(
+        conn: &mut DbConnection,
+        level_id: Uuid,
+    ) -> Result<Vec<Self>, ApiError> {
+        let personal_bests = personal_bests::table
+            .inner_join(
+                records::table
+                    .inner_join(users::table)
+                    .inner_join(levels::table.on(records::level_id.eq(levels::id))),
+            )
+            .filter(records::level_id.eq(level_id))
+            .filter(records::is_verification.eq(false))
+            .filter(levels::status.eq_any([LevelStatus::MainList, LevelStatus::Pending]))
+            .filter(users::ban_level.le(2))
+            .order((personal_bests::achieved_at.asc(), personal_bests::id.asc()))
+            .select((PersonalBest::as_select(), ExtendedBaseUser::as_select()))
+            .load::<(PersonalBest, ExtendedBaseUser)>(conn)?;
+
+        let mut holders: Vec<Self> = Vec::new();
+        for (personal_best, user) in personal_bests {
+            if holders
+                .last()
+                .is_some_and(|holder| holder.completion_time <= personal_best.completion_time)
+            {
+                continue;
+            }
+            if let Some(previous) = holders.last_mut() {
+                previous.beaten_at = Some(personal_best.achieved_at);
+            }
+            holders.push(Self {
+                user,
+                record_id: personal_best.record_id,
+                completion_time: personal_best.completion_time,
+                video_url: personal_best.video_url,
+                mobile: personal_best.mobile,
+                achieved_at: personal_best.achieved_at,
+                beaten_at: None,
+            });
+        }
+        Ok(holders)
+    }
+}
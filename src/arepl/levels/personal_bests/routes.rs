@@ -0,0 +1,78 @@
+use crate::app_data::db::DbAppState;
+use crate::arepl::levels::id_resolver::resolve_level_id;
+use crate::arepl::levels::personal_bests::{PersonalBest, WorldRecordHolder};
+use crate::cache_control::CacheController;
+use crate::error_handler::ApiError;
+use actix_web::{get, web, HttpResponse};
+use std::sync::Arc;
+use utoipa::OpenApi;
+use uuid::Uuid;
+
+#[utoipa::path(
+    get,
+    summary = "List world record history",
+    description = "List every player who held the world record of this level, along with when they set it and when it was beaten",
+    tag = "AREDL (P) - Levels",
+    params(
+        ("level_id" = String, description = "Level ID (Can be internal UUID, or GD ID. For the latter, add a _2p suffix to target the 2p version)"),
+    ),
+    responses(
+        (status = 200, body = [WorldRecordHolder])
+    ),
+)]
+#[get("/world-records", wrap = "CacheController::public_with_max_age(900)")]
+async fn find_world_records(
+    db: web::Data<Arc<DbAppState>>,
+    level_id: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let holders = web::block(move || {
+        let conn = &mut db.connection()?;
+        let level_id = resolve_level_id(conn, level_id.into_inner().as_str())?;
+        WorldRecordHolder::find_all_by_level(conn, level_id)
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(holders))
+}
+
+#[utoipa::path(
+    get,
+    summary = "List a player's personal bests",
+    description = "List every time a player held as their personal best on this level, oldest first",
+    tag = "AREDL (P) - Levels",
+    params(
+        ("level_id" = String, description = "Level ID (Can be internal UUID, or GD ID. For the latter, add a _2p suffix to target the 2p version)"),
+        ("user_id" = Uuid, description = "Internal UUID of the player"),
+    ),
+    responses(
+        (status = 200, body = [PersonalBest])
+    ),
+)]
+#[get("/{user_id}", wrap = "CacheController::public_with_max_age(900)")]
+async fn find_for_user(
+    db: web::Data<Arc<DbAppState>>,
+    path: web::Path<(String, Uuid)>,
+) -> Result<HttpResponse, ApiError> {
+    let (level_id, user_id) = path.into_inner();
+    let personal_bests = web::block(move || {
+        let conn = &mut db.connection()?;
+        let level_id = resolve_level_id(conn, level_id.as_str())?;
+        PersonalBest::find_for_user(conn, level_id, user_id)
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(personal_bests))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    components(schemas(PersonalBest, WorldRecordHolder)),
+    paths(find_world_records, find_for_user)
+)]
+pub struct ApiDoc;
+
+pub fn init_routes(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/{level_id}/personal-bests")
+            .service(find_world_records)
+            .service(find_for_user),
+    );
+}
@@ -1,14 +1,16 @@
 use crate::app_data::db::DbAppState;
 use crate::arepl::levels::{
-    creators, custom_copies, history, id_resolver::resolve_level_id, packs, records, Level,
-    LevelPlace, LevelUpdate, LevelWithUserCompletionStatus, ResolvedLevel,
+    creators, custom_copies, history, id_resolver::resolve_level_id, packs, personal_bests,
+    records, Level, LevelPlace, LevelUpdate, LevelWithUserCompletionStatus, ResolvedLevel,
 };
 use crate::arepl::levels::{notes, updates, LevelQueryOptions};
 use crate::auth::{Authenticated, Permission, UserAuth};
 use crate::cache_control::CacheController;
 use crate::error_handler::ApiError;
+use crate::notifications::WebsocketNotification;
 use actix_web::{get, patch, post, web, HttpResponse};
 use std::sync::Arc;
+use tokio::sync::broadcast;
 use tracing_actix_web::RootSpan;
 use utoipa::OpenApi;
 
@@ -70,11 +72,18 @@ async fn list(
 async fn create(
     db: web::Data<Arc<DbAppState>>,
     level: web::Json<LevelPlace>,
+    notify_tx: web::Data<broadcast::Sender<WebsocketNotification>>,
     root_span: RootSpan,
 ) -> Result<HttpResponse, ApiError> {
     root_span.record("body", tracing::field::debug(&level));
-    let level =
-        web::block(move || Level::create(&mut db.connection()?, level.into_inner())).await??;
+    let level = web::block(move || {
+        Level::create(
+            &mut db.connection()?,
+            notify_tx.get_ref(),
+            level.into_inner(),
+        )
+    })
+    .await??;
     Ok(HttpResponse::Ok().json(level))
 }
 
@@ -99,13 +108,14 @@ async fn update(
     db: web::Data<Arc<DbAppState>>,
     level_id: web::Path<String>,
     level: web::Json<LevelUpdate>,
+    notify_tx: web::Data<broadcast::Sender<WebsocketNotification>>,
     root_span: RootSpan,
 ) -> Result<HttpResponse, ApiError> {
     root_span.record("body", tracing::field::debug(&level));
     let level = web::block(move || {
         let conn = &mut db.connection()?;
         let level_id = resolve_level_id(conn, level_id.into_inner().as_str())?;
-        Level::update(conn, level_id, level.into_inner())
+        Level::update(conn, notify_tx.get_ref(), level_id, level.into_inner())
     })
     .await??;
     Ok(HttpResponse::Ok().json(level))
@@ -144,6 +154,7 @@ async fn find(
         (path = "/{level_id}/history", api = history::ApiDoc),
         (path = "/{level_id}/records", api = records::ApiDoc),
         (path = "/{level_id}/packs", api = packs::ApiDoc),
+        (path = "/{level_id}/personal-bests", api = personal_bests::ApiDoc),
         (path = "/custom-copies", api = custom_copies::ApiDoc),
         (path = "/notes", api = notes::ApiDoc),
         (path = "/updates", api = updates::ApiDoc),
@@ -170,6 +181,7 @@ pub fn init_routes(config: &mut web::ServiceConfig) {
         web::scope("/levels")
             .configure(history::init_routes)
             .configure(packs::init_routes)
+            .configure(personal_bests::init_routes)
             .configure(records::init_routes)
             .configure(creators::init_routes)
             .configure(custom_copies::init_routes)
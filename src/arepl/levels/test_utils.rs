@@ -85,6 +85,19 @@ pub async fn set_test_level_position(db: &Arc<DbAppState>, level_id: Uuid, posit
         .expect("Failed to update test arepl level position");
 }
 
+#[cfg(test)]
+pub async fn set_test_level_tags(db: &Arc<DbAppState>, level_id: Uuid, tags: &[&str]) {
+    diesel::update(levels::table.filter(levels::id.eq(level_id)))
+        .set(
+            levels::tags.eq(tags
+                .iter()
+                .map(|tag| Some((*tag).to_owned()))
+                .collect::<Vec<_>>()),
+        )
+        .execute(&mut db.connection().unwrap())
+        .expect("Failed to update test arepl level tags");
+}
+
 #[cfg(test)]
 pub async fn create_test_level_with_record(db: &Arc<DbAppState>, user_id: Uuid) -> (Uuid, Uuid) {
     let level_id = create_test_level(db).await;
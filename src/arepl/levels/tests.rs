@@ -8,6 +8,10 @@ use {
                 refresh_test_position_history, set_test_level_gd_id,
             },
             packs::test_utils::create_test_pack,
+            records::test_utils::{
+                create_test_record, improve_test_record, set_test_record_achieved_at,
+                set_test_record_completion_time, set_test_record_verification,
+            },
         },
         auth::{create_test_token, Permission},
         test_utils::*,
@@ -16,7 +20,7 @@ use {
     actix_web::test::{self, read_body_json},
     serde_json::json,
     std::time::Duration,
-    tokio::time::sleep,
+    tokio::time::{sleep, timeout},
 };
 
 #[actix_web::test]
@@ -188,6 +192,135 @@ async fn update_level() {
     assert_eq!(body["name"].to_string(), update_data["name"].to_string());
 }
 
+#[actix_web::test]
+async fn create_level_notifies_placement() {
+    let (app, db, auth, notify_tx) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, Some(Permission::LevelModify)).await;
+    let token =
+        create_test_token(user_id, &auth.jwt_encoding_key).expect("Failed to generate token");
+    let mut rx = notify_tx.subscribe();
+
+    let req = test::TestRequest::post()
+        .uri("/arepl/levels")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({
+            "name": "Placed Level",
+            "position": 1,
+            "level_id": rand::random_range(1..=100_000_000),
+            "publisher_id": user_id.to_string(),
+            "status": "MainList",
+            "two_player": false
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: serde_json::Value = read_body_json(resp).await;
+
+    let notification = timeout(Duration::from_secs(1), rx.recv())
+        .await
+        .expect("Timed out waiting for websocket notification")
+        .expect("Failed to receive websocket notification");
+
+    assert_eq!(notification.notification_type, "LEVEL_PLACED");
+    assert_eq!(notification.list.as_deref(), Some("arepl"));
+    assert_eq!(notification.data["affected_level"]["id"], body["id"]);
+    assert_eq!(notification.data["action"]["Placed"]["new_position"], 1);
+    assert_eq!(notification.data["action"]["Placed"]["status"], "MainList");
+}
+
+#[actix_web::test]
+async fn move_level_notifies_raise() {
+    let (app, db, auth, notify_tx) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, Some(Permission::LevelModify)).await;
+    let token =
+        create_test_token(user_id, &auth.jwt_encoding_key).expect("Failed to generate token");
+    let level_id = create_test_level(&db).await;
+    // every new test level is placed at the top, pushing this one down
+    for _ in 0..3 {
+        create_test_level(&db).await;
+    }
+    let mut rx = notify_tx.subscribe();
+
+    let req = test::TestRequest::patch()
+        .uri(&format!("/arepl/levels/{level_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"position": 1}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    let notification = timeout(Duration::from_secs(1), rx.recv())
+        .await
+        .expect("Timed out waiting for websocket notification")
+        .expect("Failed to receive websocket notification");
+
+    assert_eq!(notification.notification_type, "LEVEL_RAISED");
+    assert_eq!(notification.list.as_deref(), Some("arepl"));
+    assert_eq!(
+        notification.data["affected_level"]["id"],
+        level_id.to_string()
+    );
+    assert_eq!(notification.data["action"]["Raised"]["new_position"], 1);
+    assert!(
+        notification.data["action"]["Raised"]["old_position"]
+            .as_i64()
+            .unwrap()
+            >= 4
+    );
+}
+
+#[actix_web::test]
+async fn remove_level_notifies_removal() {
+    let (app, db, auth, notify_tx) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, Some(Permission::LevelModify)).await;
+    let token =
+        create_test_token(user_id, &auth.jwt_encoding_key).expect("Failed to generate token");
+    let level_id = create_test_level(&db).await;
+    let mut rx = notify_tx.subscribe();
+
+    let req = test::TestRequest::patch()
+        .uri(&format!("/arepl/levels/{level_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"status": "Removed"}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    let notification = timeout(Duration::from_secs(1), rx.recv())
+        .await
+        .expect("Timed out waiting for websocket notification")
+        .expect("Failed to receive websocket notification");
+
+    assert_eq!(notification.notification_type, "LEVEL_REMOVED");
+    assert_eq!(
+        notification.data["affected_level"]["id"],
+        level_id.to_string()
+    );
+}
+
+#[actix_web::test]
+async fn rename_level_does_not_notify() {
+    let (app, db, auth, notify_tx) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, Some(Permission::LevelModify)).await;
+    let token =
+        create_test_token(user_id, &auth.jwt_encoding_key).expect("Failed to generate token");
+    let level_id = create_test_level(&db).await;
+    let mut rx = notify_tx.subscribe();
+
+    let req = test::TestRequest::patch()
+        .uri(&format!("/arepl/levels/{level_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"name": "Renamed Level"}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    assert!(
+        rx.try_recv().is_err(),
+        "Renaming a level should not emit a list change"
+    );
+}
+
 #[actix_web::test]
 async fn find_level() {
     let (app, db, _, _) = init_test_app().await;
@@ -388,3 +521,62 @@ async fn get_level_records() {
         "Record IDs do not match!"
     );
 }
+
+#[actix_web::test]
+async fn get_level_world_record_history() {
+    let (app, db, _, _) = init_test_app().await;
+    let level_id = create_test_level(&db).await;
+    let (first, _) = create_test_user(&db, None).await;
+    let (slower, _) = create_test_user(&db, None).await;
+    let (second, _) = create_test_user(&db, None).await;
+    let (verifier, _) = create_test_user(&db, None).await;
+
+    let mut records = Vec::new();
+    for (user, completion_time, achieved_at) in [
+        (first, 60_000, "2026-01-01T00:00:00Z"),
+        (slower, 70_000, "2026-02-01T00:00:00Z"),
+        (second, 50_000, "2026-03-01T00:00:00Z"),
+    ] {
+        let record_id = create_test_record(&db, user, level_id).await;
+        set_test_record_completion_time(&db, record_id, completion_time).await;
+        set_test_record_achieved_at(&db, record_id, achieved_at.parse().unwrap()).await;
+        records.push(record_id);
+    }
+    // verifications are not ranked, so a faster one never holds the world record
+    let verification = create_test_record(&db, verifier, level_id).await;
+    set_test_record_verification(&db, verification, true).await;
+    set_test_record_completion_time(&db, verification, 30_000).await;
+    // the first holder takes the world record back with a faster run
+    improve_test_record(&db, records[0], 40_000).await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "/arepl/levels/{level_id}/personal-bests/world-records"
+        ))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert!(res.status().is_success(), "status is {}", res.status());
+    let body: serde_json::Value = read_body_json(res).await;
+    let holders = body.as_array().unwrap();
+
+    let summary: Vec<(String, i64)> = holders
+        .iter()
+        .map(|holder| {
+            (
+                holder["user"]["id"].as_str().unwrap().to_owned(),
+                holder["completion_time"].as_i64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (first.to_string(), 60_000),
+            (second.to_string(), 50_000),
+            (first.to_string(), 40_000),
+        ]
+    );
+    assert_eq!(holders[0]["beaten_at"], holders[1]["achieved_at"]);
+    assert_eq!(holders[1]["beaten_at"], holders[2]["achieved_at"]);
+    assert!(holders[2]["beaten_at"].is_null());
+}
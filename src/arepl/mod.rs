@@ -7,9 +7,10 @@ pub mod levels;
 mod packs;
 mod packtiers;
 mod profile;
-mod records;
+pub mod records;
 mod routes;
 mod statistics;
 
 pub mod submissions;
+pub mod world_records;
 pub use routes::{init_routes, ApiDoc};
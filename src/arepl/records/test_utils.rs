@@ -36,6 +36,34 @@ pub async fn create_test_record(db: &Arc<DbAppState>, user_id: Uuid, level_id: U
         .expect("Failed to retrieve test aredl record ID")
 }
 
+#[cfg(test)]
+pub async fn create_test_record_with_completion_time(
+    db: &Arc<DbAppState>,
+    user_id: Uuid,
+    level_id: Uuid,
+    completion_time: i64,
+) -> Uuid {
+    let conn = &mut db.connection().unwrap();
+    let submission_id = diesel::insert_into(submissions::table)
+        .values((
+            submissions::submitted_by.eq(user_id),
+            submissions::video_url.eq("https://youtube.com/watch?v=xvFZjo5PgG0"),
+            submissions::level_id.eq(level_id),
+            submissions::status.eq(SubmissionStatus::Accepted),
+            submissions::mobile.eq(false),
+            submissions::completion_time.eq(completion_time),
+        ))
+        .returning(submissions::id)
+        .get_result::<Uuid>(conn)
+        .expect("Failed to create test arepl record");
+
+    records::table
+        .filter(records::submission_id.eq(submission_id))
+        .select(records::id)
+        .first::<Uuid>(conn)
+        .expect("Failed to retrieve test arepl record ID")
+}
+
 #[cfg(test)]
 pub fn get_test_record(db: &Arc<DbAppState>, record_id: Uuid) -> Record {
     records::table
@@ -137,3 +165,38 @@ pub async fn set_test_record_achieved_at(
         .execute(&mut db.connection().unwrap())
         .expect("Failed to update test arepl record achieved_at");
 }
+
+#[cfg(test)]
+pub async fn set_test_record_completion_time(
+    db: &Arc<DbAppState>,
+    record_id: Uuid,
+    completion_time: i64,
+) {
+    diesel::update(records::table.filter(records::id.eq(record_id)))
+        .set(records::completion_time.eq(completion_time))
+        .execute(&mut db.connection().unwrap())
+        .expect("Failed to update test arepl record completion time");
+}
+
+#[cfg(test)]
+pub async fn improve_test_record(db: &Arc<DbAppState>, record_id: Uuid, completion_time: i64) {
+    let conn = &mut db.connection().unwrap();
+    let submission_id = records::table
+        .filter(records::id.eq(record_id))
+        .select(records::submission_id)
+        .first::<Uuid>(conn)
+        .expect("Failed to retrieve test arepl record submission");
+
+    diesel::update(submissions::table.filter(submissions::id.eq(submission_id)))
+        .set((
+            submissions::status.eq(SubmissionStatus::Pending),
+            submissions::completion_time.eq(completion_time),
+        ))
+        .execute(conn)
+        .expect("Failed to resubmit test arepl record");
+
+    diesel::update(submissions::table.filter(submissions::id.eq(submission_id)))
+        .set(submissions::status.eq(SubmissionStatus::Accepted))
+        .execute(conn)
+        .expect("Failed to accept test arepl record improvement");
+}
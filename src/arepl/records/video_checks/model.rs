@@ -0,0 +1,329 @@
+use crate::{
+    app_data::db::{DbAppState, DbConnection},
+    arepl::levels::ExtendedBaseLevel,
+    error_handler::ApiError,
+    notifications::{NotificationList, WebsocketNotification, WebsocketNotificationType},
+    page_helper::{PageQuery, Paginated},
+    providers::ProvidersAppState,
+    schema::{
+        arepl::{levels, record_video_checks, records},
+        users,
+    },
+    users::{
+        me::notifications::{Notification, NotificationPayload, NotificationType},
+        user_filter, ExtendedBaseUser,
+    },
+};
+use actix_http::StatusCode;
+use chrono::{DateTime, Duration, Utc};
+use diesel::pg::Pg;
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use diesel::prelude::*;
+/// How long a record video is considered fresh after being checked.
+const RECHECK_INTERVAL: Duration = Duration::days(7);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, DbEnum)]
+#[ExistingTypePath = "crate::schema::arepl::sql_types::VideoAvailability"]
+#[DbValueStyle = "PascalCase"]
+pub enum VideoAvailability {
+    /// The provider still serves the video.
+    Available,
+    /// The provider reports that the video doesn't exist anymore, or is private.
+    Unavailable,
+    /// The video could not be checked, either because the lookup failed or the provider doesn't support it.
+    Unknown,
+}
+
+#[derive(
+    Serialize, Deserialize, Queryable, Selectable, Insertable, AsChangeset, Debug, ToSchema,
+)]
+#[diesel(table_name = record_video_checks, treat_none_as_null = true, check_for_backend(Pg))]
+pub struct RecordVideoCheck {
+    /// Internal UUID of the record.
+    pub record_id: Uuid,
+    /// Video link that was checked.
+    pub video_url: String,
+    /// Whether the video was still available.
+    pub availability: VideoAvailability,
+    /// Why the video could not be checked, or what the provider answered if it is unavailable.
+    pub error: Option<String>,
+    /// Timestamp of when the video was first found to be unavailable.
+    pub unavailable_since: Option<DateTime<Utc>>,
+    /// Timestamp of when the video was last checked.
+    pub checked_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct ResolvedRecordVideoCheck {
+    /// Internal UUID of the record.
+    pub record_id: Uuid,
+    /// Level the record is for.
+    pub level: ExtendedBaseLevel,
+    /// User who holds the record.
+    pub submitted_by: ExtendedBaseUser,
+    /// Video link that was checked.
+    pub video_url: String,
+    /// Whether the video was still available.
+    pub availability: VideoAvailability,
+    /// Why the video could not be checked, or what the provider answered if it is unavailable.
+    pub error: Option<String>,
+    /// Timestamp of when the video was first found to be unavailable.
+    pub unavailable_since: Option<DateTime<Utc>>,
+    /// Timestamp of when the video was last checked.
+    pub checked_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct RecordVideoCheckPage {
+    data: Vec<ResolvedRecordVideoCheck>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct RecordVideoCheckQueryOptions {
+    /// Only list checks with this outcome. Defaults to unavailable videos.
+    pub availability_filter: Option<VideoAvailability>,
+    pub level_filter: Option<Uuid>,
+    pub submitter_filter: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+struct RecordVideoUnavailableEvent<'a> {
+    record_id: Uuid,
+    level_id: Uuid,
+    submitted_by: Uuid,
+    video_url: &'a str,
+}
+
+struct DueRecord {
+    id: Uuid,
+    video_url: String,
+    level_id: Uuid,
+    level_name: String,
+    submitted_by: Uuid,
+    previous: Option<RecordVideoCheck>,
+}
+
+impl RecordVideoCheck {
+    /// Checks the videos of records that were never checked, changed their video or were last checked a while ago, least recently checked first.
+    pub async fn check_due(
+        db: &Arc<DbAppState>,
+        providers: &ProvidersAppState,
+        notify_tx: &broadcast::Sender<WebsocketNotification>,
+        inbox_tx: &broadcast::Sender<Notification>,
+        limit: i64,
+    ) -> Result<usize, ApiError> {
+        let due = records::table
+            .inner_join(levels::table.on(levels::id.eq(records::level_id)))
+            .left_join(record_video_checks::table)
+            .filter(
+                record_video_checks::record_id
+                    .nullable()
+                    .is_null()
+                    .or(record_video_checks::video_url
+                        .nullable()
+                        .ne(records::video_url.nullable()))
+                    .or(record_video_checks::checked_at
+                        .nullable()
+                        .lt(Utc::now() - RECHECK_INTERVAL)),
+            )
+            .order(
+                record_video_checks::checked_at
+                    .nullable()
+                    .asc()
+                    .nulls_first(),
+            )
+            .limit(limit)
+            .select((
+                records::id,
+                records::video_url,
+                records::level_id,
+                levels::name,
+                records::submitted_by,
+                Option::<RecordVideoCheck>::as_select(),
+            ))
+            .load::<(Uuid, String, Uuid, String, Uuid, Option<RecordVideoCheck>)>(
+                &mut db.connection()?,
+            )?;
+
+        let count = due.len();
+        for (id, video_url, level_id, level_name, submitted_by, previous) in due {
+            let record = DueRecord {
+                id,
+                video_url,
+                level_id,
+                level_name,
+                submitted_by,
+                previous,
+            };
+            let outcome = async {
+                let matched = providers.parse_url(&record.video_url)?;
+                providers.fetch_metadata(&matched).await
+            }
+            .await;
+            record.save_check(&mut db.connection()?, notify_tx, inbox_tx, outcome)?;
+        }
+        Ok(count)
+    }
+}
+
+impl DueRecord {
+    fn save_check<T>(
+        self,
+        conn: &mut DbConnection,
+        notify_tx: &broadcast::Sender<WebsocketNotification>,
+        inbox_tx: &broadcast::Sender<Notification>,
+        outcome: Result<Option<T>, ApiError>,
+    ) -> Result<(), ApiError> {
+        let (availability, error) = match outcome {
+            Ok(Some(_)) => (VideoAvailability::Available, None),
+            // the provider has no way to look the video up
+            Ok(None) => (VideoAvailability::Unknown, None),
+            Err(error) if error.error_status_code == StatusCode::NOT_FOUND.as_u16() => {
+                (VideoAvailability::Unavailable, Some(error.error_message))
+            }
+            Err(error) => (VideoAvailability::Unknown, Some(error.error_message)),
+        };
+
+        let previous = self
+            .previous
+            .filter(|previous| previous.video_url == self.video_url);
+
+        let now = Utc::now();
+        let (availability, unavailable_since) = match (availability, previous) {
+            // a failed lookup doesn't tell anything new, so the last known state is kept
+            (VideoAvailability::Unknown, Some(previous)) => {
+                (previous.availability, previous.unavailable_since)
+            }
+            (VideoAvailability::Unavailable, Some(previous)) => (
+                VideoAvailability::Unavailable,
+                previous.unavailable_since.or(Some(now)),
+            ),
+            (VideoAvailability::Unavailable, None) => (VideoAvailability::Unavailable, Some(now)),
+            (availability @ (VideoAvailability::Available | VideoAvailability::Unknown), _) => {
+                (availability, None)
+            }
+        };
+        let newly_unavailable = unavailable_since == Some(now);
+
+        let check = RecordVideoCheck {
+            record_id: self.id,
+            video_url: self.video_url,
+            availability,
+            error,
+            unavailable_since,
+            checked_at: now,
+        };
+
+        diesel::insert_into(record_video_checks::table)
+            .values(&check)
+            .on_conflict(record_video_checks::record_id)
+            .do_update()
+            .set(&check)
+            .execute(conn)?;
+
+        if newly_unavailable {
+            let notification = Notification::create(
+                conn,
+                self.submitted_by,
+                format!(
+                    "The video of your {} record is no longer available. Please contact staff with a new link to keep your record.",
+                    self.level_name
+                ),
+                NotificationType::Failure,
+                Some(NotificationPayload::RecordVideo {
+                    record_id: self.id,
+                    level_id: self.level_id,
+                    list: NotificationList::Arepl,
+                }),
+            )?;
+            Notification::send_live(inbox_tx, notification);
+
+            WebsocketNotification::send(
+                conn,
+                notify_tx,
+                WebsocketNotificationType::RecordVideoUnavailable,
+                Some(NotificationList::Arepl),
+                &RecordVideoUnavailableEvent {
+                    record_id: self.id,
+                    level_id: self.level_id,
+                    submitted_by: self.submitted_by,
+                    video_url: &check.video_url,
+                },
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl ResolvedRecordVideoCheck {
+    pub fn find_all<const D: i64>(
+        conn: &mut DbConnection,
+        page_query: PageQuery<D>,
+        options: &RecordVideoCheckQueryOptions,
+    ) -> Result<Paginated<RecordVideoCheckPage>, ApiError> {
+        let availability = options
+            .availability_filter
+            .unwrap_or(VideoAvailability::Unavailable);
+
+        let build_filtered = || {
+            let mut q = record_video_checks::table
+                .inner_join(records::table)
+                .filter(record_video_checks::video_url.eq(records::video_url))
+                .filter(record_video_checks::availability.eq(availability))
+                .into_boxed::<Pg>();
+            if let Some(level) = options.level_filter {
+                q = q.filter(records::level_id.eq(level));
+            }
+            if let Some(submitter) = &options.submitter_filter {
+                q = q
+                    .filter(records::submitted_by.eq_any(user_filter(submitter).select(users::id)));
+            }
+            q
+        };
+
+        let total_count: i64 = build_filtered().count().get_result(conn)?;
+
+        let checks = build_filtered()
+            .inner_join(users::table.on(records::submitted_by.eq(users::id)))
+            .inner_join(levels::table.on(records::level_id.eq(levels::id)))
+            .order((
+                record_video_checks::unavailable_since.desc().nulls_last(),
+                record_video_checks::checked_at.desc(),
+            ))
+            .limit(page_query.per_page())
+            .offset(page_query.offset())
+            .select((
+                RecordVideoCheck::as_select(),
+                ExtendedBaseUser::as_select(),
+                ExtendedBaseLevel::as_select(),
+            ))
+            .load::<(RecordVideoCheck, ExtendedBaseUser, ExtendedBaseLevel)>(conn)?;
+
+        let data = checks
+            .into_iter()
+            .map(|(check, submitted_by, level)| Self {
+                record_id: check.record_id,
+                level,
+                submitted_by,
+                video_url: check.video_url,
+                availability: check.availability,
+                error: check.error,
+                unavailable_since: check.unavailable_since,
+                checked_at: check.checked_at,
+            })
+            .collect();
+
+        Ok(Paginated::from_data(
+            page_query,
+            total_count,
+            RecordVideoCheckPage { data },
+        ))
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{
+    app_data::db::DbAppState,
+    arepl::records::video_checks::{
+        RecordVideoCheck, RecordVideoCheckPage, RecordVideoCheckQueryOptions,
+        ResolvedRecordVideoCheck, VideoAvailability,
+    },
+    auth::{Permission, UserAuth},
+    error_handler::ApiError,
+    page_helper::{PageQuery, Paginated},
+};
+use actix_web::{get, web, HttpResponse};
+use std::sync::Arc;
+use utoipa::OpenApi;
+
+#[utoipa::path(
+    get,
+    summary = "[Staff]List record video checks",
+    description = "List records by the outcome of the last check of their video. By default, only records whose video is no longer available are listed, most recently broken first.",
+    tag = "AREDL (P) - Records",
+    params(
+        ("page" = Option<i64>, Query, description = "The page of the list to fetch"),
+        ("per_page" = Option<i64>, Query, description = "The number of entries to fetch per page"),
+        ("availability_filter" = Option<VideoAvailability>, Query, description = "The check outcome to filter by, defaults to Unavailable"),
+        ("level_filter" = Option<Uuid>, Query, description = "The level internal UUID to filter by"),
+        ("submitter_filter" = Option<String>, Query, description = "The record holder (UUID, discord ID, or username) to filter by"),
+    ),
+    responses(
+        (status = 200, body = Paginated<RecordVideoCheckPage>)
+    ),
+    security(
+        ("access_token" = ["RecordModify"]),
+        ("api_key" = ["RecordModify"]),
+    )
+)]
+#[get("video-checks", wrap = "UserAuth::require(Permission::RecordModify)")]
+async fn find_all(
+    db: web::Data<Arc<DbAppState>>,
+    page_query: web::Query<PageQuery<100>>,
+    options: web::Query<RecordVideoCheckQueryOptions>,
+) -> Result<HttpResponse, ApiError> {
+    let checks = web::block(move || {
+        ResolvedRecordVideoCheck::find_all(
+            &mut db.connection()?,
+            page_query.into_inner(),
+            &options.into_inner(),
+        )
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(checks))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    components(schemas(
+        RecordVideoCheck,
+        RecordVideoCheckPage,
+        RecordVideoCheckQueryOptions,
+        ResolvedRecordVideoCheck,
+        VideoAvailability
+    )),
+    paths(find_all)
+)]
+pub struct ApiDoc;
+
+pub fn init_routes(config: &mut web::ServiceConfig) {
+    config.service(find_all);
+}
@@ -0,0 +1,165 @@
+#[cfg(test)]
+use {
+    crate::{
+        app_data::db::DbAppState,
+        arepl::{
+            levels::test_utils::create_test_level,
+            records::{test_utils::create_test_record, video_checks::RecordVideoCheck},
+        },
+        auth::{create_test_token, oauth::OAuthProvider, Permission},
+        notifications::WebsocketNotification,
+        providers::{
+            context::{google::new_google_context, ProviderContext},
+            list::youtube::YouTubeProvider,
+            model::{Provider, ProviderRegistry},
+            test_utils::{
+                clear_oauth_env, mock_google_token_endpoint, mock_youtube_missing_video_endpoint,
+                mock_youtube_videos_endpoint, seed_oauth_token, set_oauth_env,
+            },
+            ProvidersAppState,
+        },
+        schema::{arepl::records, notifications},
+        test_utils::{init_test_app, init_test_app_with_providers},
+        users::{me::notifications::Notification, test_utils::create_test_user},
+    },
+    actix_web::test::{self, read_body_json},
+    diesel::prelude::*,
+    httpmock::MockServer,
+    serde_json::Value,
+    serial_test::serial,
+    std::sync::Arc,
+    tokio::sync::broadcast,
+    uuid::Uuid,
+};
+
+#[cfg(test)]
+async fn youtube_providers(db: Option<Arc<DbAppState>>) -> ProvidersAppState {
+    let google_auth = new_google_context()
+        .await
+        .expect("Failed to create Google OAuth context");
+    ProvidersAppState::new(
+        ProviderRegistry::new(vec![Arc::new(YouTubeProvider) as Arc<dyn Provider>]),
+        ProviderContext {
+            http: reqwest::Client::new(),
+            db,
+            discord_auth: None,
+            google_auth: Some(Arc::new(google_auth)),
+            patreon_auth: None,
+            twitch_auth: None,
+        },
+    )
+}
+
+#[actix_web::test]
+#[serial]
+async fn report_unavailable_record_videos() {
+    clear_oauth_env(OAuthProvider::Google);
+    let server = MockServer::start_async().await;
+    set_oauth_env(OAuthProvider::Google, &server.base_url());
+    mock_google_token_endpoint(&server, 3600, "test_access").await;
+    mock_youtube_videos_endpoint(&server, "xvFZjo5PgG0", "2009-10-25T06:57:33Z").await;
+    let missing_mock = mock_youtube_missing_video_endpoint(&server, "deletedvid1").await;
+
+    let (app, db, auth, _) =
+        init_test_app_with_providers(Arc::new(youtube_providers(None).await)).await;
+    seed_oauth_token(&db, OAuthProvider::Google, Some("refresh_a"));
+    let providers = youtube_providers(Some(db.clone())).await;
+    let (notify_tx, mut notify_rx) = broadcast::channel::<WebsocketNotification>(16);
+    let (inbox_tx, mut inbox_rx) = broadcast::channel::<Notification>(16);
+
+    let (moderator, _) = create_test_user(&db, Some(Permission::RecordModify)).await;
+    let token = create_test_token(moderator, &auth.jwt_encoding_key).unwrap();
+    let (holder, _) = create_test_user(&db, None).await;
+    let level = create_test_level(&db).await;
+    let other_level = create_test_level(&db).await;
+    let broken = create_test_record(&db, holder, level).await;
+    let available = create_test_record(&db, holder, other_level).await;
+    diesel::update(records::table.filter(records::id.eq(broken)))
+        .set(records::video_url.eq("https://youtube.com/watch?v=deletedvid1"))
+        .execute(&mut db.connection().unwrap())
+        .unwrap();
+
+    let checked = RecordVideoCheck::check_due(&db, &providers, &notify_tx, &inbox_tx, 1000)
+        .await
+        .expect("Failed to check record videos");
+    assert!(checked >= 2);
+    missing_mock.assert_async().await;
+
+    let event = notify_rx.try_recv().expect("Staff should be notified");
+    assert_eq!(event.notification_type, "RECORD_VIDEO_UNAVAILABLE");
+    assert_eq!(event.data["record_id"], broken.to_string());
+    assert!(
+        std::iter::from_fn(|| inbox_rx.try_recv().ok())
+            .any(|notification| notification.user_id == holder),
+        "The holder should be notified live"
+    );
+
+    let holder_notifications = notifications::table
+        .filter(notifications::user_id.eq(holder))
+        .select(notifications::payload)
+        .load::<Option<Value>>(&mut db.connection().unwrap())
+        .unwrap();
+    assert_eq!(holder_notifications.len(), 1);
+    let payload = holder_notifications[0].as_ref().unwrap();
+    assert_eq!(payload["record_id"], broken.to_string());
+
+    let req = test::TestRequest::get()
+        .uri("/arepl/records/video-checks")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: Value = read_body_json(resp).await;
+    let listed: Vec<Uuid> = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|check| check["record_id"].as_str().unwrap().parse().unwrap())
+        .collect();
+    assert!(listed.contains(&broken));
+    assert!(!listed.contains(&available));
+    let entry = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|check| check["record_id"] == broken.to_string())
+        .unwrap();
+    assert_eq!(entry["availability"], "Unavailable");
+    assert_eq!(entry["submitted_by"]["id"], holder.to_string());
+    assert!(entry["unavailable_since"].is_string());
+
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "/arepl/records/video-checks?availability_filter=Available&level_filter={other_level}"
+        ))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let body: Value = read_body_json(test::call_service(&app, req).await).await;
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+    assert_eq!(body["data"][0]["record_id"], available.to_string());
+
+    // checking again right away skips both records and does not notify the holder twice
+    RecordVideoCheck::check_due(&db, &providers, &notify_tx, &inbox_tx, 1000)
+        .await
+        .expect("Failed to check record videos");
+    let holder_notification_count: i64 = notifications::table
+        .filter(notifications::user_id.eq(holder))
+        .count()
+        .get_result(&mut db.connection().unwrap())
+        .unwrap();
+    assert_eq!(holder_notification_count, 1);
+}
+
+#[actix_web::test]
+async fn video_checks_require_permission() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user, _) = create_test_user(&db, None).await;
+    let token = create_test_token(user, &auth.jwt_encoding_key).unwrap();
+
+    let req = test::TestRequest::get()
+        .uri("/arepl/records/video-checks")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 403);
+}
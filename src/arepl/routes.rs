@@ -1,6 +1,6 @@
 use crate::arepl::{
     bounty, changelog, clan, country, leaderboard, levels, packs, packtiers, profile, records,
-    statistics, submissions,
+    statistics, submissions, world_records,
 };
 use actix_web::web;
 use utoipa::OpenApi;
@@ -23,6 +23,7 @@ use utoipa::OpenApi;
         (path = "/records", api=records::ApiDoc),
         (path = "/statistics", api=statistics::ApiDoc),
         (path = "/bounty-board", api=bounty::ApiDoc),
+        (path = "/world-records", api=world_records::ApiDoc),
     ),
 )]
 pub struct ApiDoc;
@@ -41,6 +42,7 @@ pub fn init_routes(config: &mut web::ServiceConfig) {
             .configure(clan::init_routes)
             .configure(records::init_routes)
             .configure(statistics::init_routes)
-            .configure(bounty::init_routes),
+            .configure(bounty::init_routes)
+            .configure(world_records::init_routes),
     );
 }
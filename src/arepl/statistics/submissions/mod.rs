@@ -1,5 +1,7 @@
 mod daily;
 mod model;
+mod queue;
+mod reasons;
 mod routes;
 #[cfg(test)]
 mod test_utils;
@@ -0,0 +1,53 @@
+use crate::{
+    app_data::db::DbAppState,
+    arepl::{
+        statistics::submissions::queue::{
+            QueueHistoryBucket, QueueHistoryInterval, QueueHistoryQuery,
+        },
+        submissions::queue::SubmissionQueueSnapshot,
+    },
+    auth::{Permission, UserAuth},
+    error_handler::ApiError,
+};
+use actix_web::{get, web, HttpResponse};
+use std::sync::Arc;
+use utoipa::OpenApi;
+
+#[utoipa::path(
+    get,
+    summary = "[Staff]Queue history",
+    description = "Get how the submission queue evolved over time, bucketed by day or hour. Each bucket contains the last snapshot of the queue taken during it, as well as the median wait time of the submissions resolved during it.",
+    tag = "AREDL (P) - Statistics",
+    params(
+        ("interval" = Option<QueueHistoryInterval>, Query, description = "Size of the buckets, defaults to `Daily`"),
+        ("since" = Option<DateTime<Utc>>, Query, description = "Only include data since this timestamp"),
+        ("until" = Option<DateTime<Utc>>, Query, description = "Only include data until this timestamp"),
+    ),
+    responses((status = 200, body = [QueueHistoryBucket])),
+    security(("access_token" = ["SubmissionSeeStatistics"]), ("api_key" = ["SubmissionSeeStatistics"]))
+)]
+#[get("", wrap = "UserAuth::require(Permission::SubmissionSeeStatistics)")]
+pub async fn queue_history(
+    db: web::Data<Arc<DbAppState>>,
+    query: web::Query<QueueHistoryQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let data =
+        web::block(move || QueueHistoryBucket::find_all(&mut db.connection()?, &query)).await??;
+    Ok(HttpResponse::Ok().json(data))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    components(schemas(
+        QueueHistoryBucket,
+        QueueHistoryInterval,
+        QueueHistoryQuery,
+        SubmissionQueueSnapshot
+    )),
+    paths(queue_history)
+)]
+pub struct ApiDoc;
+
+pub fn init_routes(config: &mut web::ServiceConfig) {
+    config.service(web::scope("/queue").service(queue_history));
+}
@@ -0,0 +1,9 @@
+mod model;
+mod routes;
+#[cfg(test)]
+pub mod test_utils;
+#[cfg(test)]
+mod tests;
+
+pub use model::*;
+pub use routes::{init_routes, ApiDoc};
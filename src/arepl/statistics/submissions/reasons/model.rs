@@ -0,0 +1,109 @@
+use crate::{
+    app_data::db::DbConnection,
+    auth::Authenticated,
+    error_handler::ApiError,
+    roles::ReviewerVisibility,
+    schema::arepl::submission_reason_stats,
+    submission_reasons::{RequestLocales, SubmissionReasonCatalogue},
+};
+use chrono::NaiveDate;
+use diesel::pg::Pg;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use diesel::prelude::*;
+#[derive(Deserialize, ToSchema)]
+pub struct ReasonStatsQuery {
+    pub since: Option<NaiveDate>,
+    pub until: Option<NaiveDate>,
+    pub reviewer_id: Option<Uuid>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ReasonStatsRow {
+    /// Code of the reason.
+    pub code: String,
+    /// Text of the reason, in the language requested through the `Accept-Language` header if available.
+    pub text: String,
+    /// Amount of submissions denied with this reason.
+    pub denied: i64,
+    /// Amount of submissions put under consideration with this reason.
+    pub under_consideration: i64,
+    /// Total amount of reviews that used this reason.
+    pub total: i64,
+}
+
+impl ReasonStatsRow {
+    /// Counts how often each reason was given, most used first.
+    pub fn find_all(
+        conn: &mut DbConnection,
+        options: &ReasonStatsQuery,
+        authenticated: &Authenticated,
+        locales: &RequestLocales,
+    ) -> Result<Vec<Self>, ApiError> {
+        let visibility = ReviewerVisibility::new(conn, authenticated)?;
+
+        if let Some(reviewer_id) = options.reviewer_id {
+            if !visibility.can_see_stats(reviewer_id, false) {
+                return Ok(Vec::new());
+            }
+        }
+
+        let mut query = submission_reason_stats::table
+            .select((
+                submission_reason_stats::reason_code,
+                submission_reason_stats::denied,
+                submission_reason_stats::under_consideration,
+            ))
+            .into_boxed::<Pg>();
+
+        if let Some(date) = options.since {
+            query = query.filter(submission_reason_stats::day.ge(date));
+        }
+
+        if let Some(date) = options.until {
+            query = query.filter(submission_reason_stats::day.le(date));
+        }
+
+        if let Some(reviewer_id) = options.reviewer_id {
+            query = query.filter(submission_reason_stats::reviewer_id.eq(reviewer_id));
+        } else if !visibility.can_see_other_stats {
+            query = query.filter(submission_reason_stats::reviewer_id.eq(authenticated.user_id));
+        }
+
+        let counts = query.load::<(String, i64, i64)>(conn)?.into_iter().fold(
+            HashMap::<String, (i64, i64)>::new(),
+            |mut map, (code, denied, under_consideration)| {
+                let entry = map.entry(code).or_default();
+                entry.0 += denied;
+                entry.1 += under_consideration;
+                map
+            },
+        );
+
+        let catalogue = SubmissionReasonCatalogue::load(conn)?;
+
+        let mut rows = counts
+            .into_iter()
+            .map(|(code, (denied, under_consideration))| {
+                let text = catalogue
+                    .localize(&[Some(code.clone())], locales)
+                    .pop()
+                    .map_or_else(|| code.clone(), |reason| reason.text);
+                Self {
+                    code,
+                    text,
+                    denied,
+                    under_consideration,
+                    total: denied + under_consideration,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        rows.sort_unstable_by(|a, b| b.total.cmp(&a.total).then_with(|| a.code.cmp(&b.code)));
+
+        Ok(rows)
+    }
+}
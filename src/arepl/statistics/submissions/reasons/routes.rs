@@ -0,0 +1,53 @@
+use crate::{
+    app_data::db::DbAppState,
+    arepl::statistics::submissions::reasons::{ReasonStatsQuery, ReasonStatsRow},
+    auth::{Authenticated, Permission, UserAuth},
+    error_handler::ApiError,
+    submission_reasons::RequestLocales,
+};
+use actix_web::{get, web, HttpResponse};
+use std::sync::Arc;
+use utoipa::OpenApi;
+
+#[utoipa::path(
+    get,
+    summary = "[Staff]Submission reasons statistics",
+    description = "Count how often each reason was given when denying submissions or putting them under consideration, most used first. Without `SubmissionSeeOtherReviewerStatistics`, only your own reviews are counted.",
+    tag = "AREDL (P) - Statistics",
+    params(
+        ("since" = Option<NaiveDate>, Query, description = "Only include data since this date"),
+        ("until" = Option<NaiveDate>, Query, description = "Only include data until this date"),
+        ("reviewer_id" = Option<Uuid>, Query, description = "Filter for a specific moderator"),
+    ),
+    responses((status = 200, body = [ReasonStatsRow])),
+    security(("access_token" = ["SubmissionSeeStatistics"]), ("api_key" = ["SubmissionSeeStatistics"]))
+)]
+#[get("", wrap = "UserAuth::require(Permission::SubmissionSeeStatistics)")]
+pub async fn reason_stats(
+    db: web::Data<Arc<DbAppState>>,
+    query: web::Query<ReasonStatsQuery>,
+    authenticated: Authenticated,
+    locales: RequestLocales,
+) -> Result<HttpResponse, ApiError> {
+    let data = web::block(move || {
+        ReasonStatsRow::find_all(
+            &mut db.connection()?,
+            &query.into_inner(),
+            &authenticated,
+            &locales,
+        )
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(data))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    components(schemas(ReasonStatsRow, ReasonStatsQuery)),
+    paths(reason_stats)
+)]
+pub struct ApiDoc;
+
+pub fn init_routes(config: &mut web::ServiceConfig) {
+    config.service(web::scope("/reasons").service(reason_stats));
+}
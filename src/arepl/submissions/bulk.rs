@@ -0,0 +1,377 @@
+use crate::{
+    app_data::db::DbConnection,
+    arepl::submissions::{Submission, SubmissionStatus},
+    auth::{Authenticated, Permission},
+    error_handler::ApiError,
+    notifications::{NotificationList, WebsocketNotification, WebsocketNotificationType},
+    schema::{arepl::submissions, users},
+    shifts::Shift,
+    submission_reasons::SubmissionReason,
+    users::{me::notifications::Notification, user_filter},
+};
+use diesel::pg::Pg;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use diesel::prelude::*;
+/// Maximum amount of submissions a single bulk action can target.
+pub const MAX_BULK_SUBMISSIONS: usize = 500;
+
+#[derive(Serialize, Deserialize, Debug, ToSchema, Clone, Copy, PartialEq, Eq)]
+pub enum SubmissionBulkAction {
+    /// Accept the submissions, which creates their records.
+    Accept,
+    /// Deny the submissions.
+    Deny,
+    /// Put claimed submissions back in the queue.
+    Unclaim,
+    /// Lock the submissions so that their submitters cannot edit them anymore.
+    Lock,
+    /// Unlock the submissions.
+    Unlock,
+    /// Move the submissions to the priority queue.
+    Prioritize,
+    /// Move the submissions back to the regular queue.
+    Deprioritize,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema, Default)]
+pub struct SubmissionBulkFilter {
+    /// Only target submissions for this level.
+    pub level_id: Option<Uuid>,
+    /// Only target submissions from this submitter (UUID, discord ID, or username).
+    pub submitter: Option<String>,
+    /// Only target submissions with this status.
+    pub status: Option<SubmissionStatus>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct SubmissionBulkEdit {
+    /// The action to apply to every targeted submission.
+    pub action: SubmissionBulkAction,
+    /// Internal UUIDs of the submissions to target. Exactly one of `ids` and `filter` must be given.
+    pub ids: Option<Vec<Uuid>>,
+    /// Filter selecting the submissions to target. Exactly one of `ids` and `filter` must be given.
+    pub filter: Option<SubmissionBulkFilter>,
+    /// Notes given to every accepted or denied submission.
+    pub reviewer_notes: Option<String>,
+    /// Codes of the reasons given to every denied submission.
+    #[schema(value_type = Option<Vec<String>>)]
+    pub reason_codes: Option<Vec<Option<String>>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct SkippedSubmission {
+    /// Internal UUID of the submission.
+    pub id: Uuid,
+    /// Why the action was not applied to this submission.
+    pub reason: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct SubmissionBulkResult {
+    /// Submissions the action was applied to.
+    pub updated: Vec<Submission>,
+    /// Targeted submissions the action was not applied to.
+    pub skipped: Vec<SkippedSubmission>,
+}
+
+struct BulkSideEffects {
+    websocket_events: Vec<(WebsocketNotificationType, Submission)>,
+    completed_shifts: Vec<Shift>,
+    notifications: Vec<Notification>,
+}
+
+/// The reviewer applying a bulk action, along with the permissions the per-submission checks need.
+struct BulkReviewer {
+    id: Uuid,
+    can_edit_non_self_claimed: bool,
+    can_edit_with_raw_footage: bool,
+}
+
+impl SubmissionBulkAction {
+    fn skip_reason(
+        self,
+        conn: &mut DbConnection,
+        submission: &Submission,
+        reviewer: &BulkReviewer,
+    ) -> Result<Option<&'static str>, ApiError> {
+        if submission.submitted_by == reviewer.id {
+            return Ok(Some("You cannot review your own submissions."));
+        }
+        if !reviewer.can_edit_with_raw_footage && submission.raw_url.is_some() {
+            return Ok(Some(
+                "You do not have permission to edit submissions with raw footage.",
+            ));
+        }
+        if !reviewer.can_edit_non_self_claimed
+            && (submission.status != SubmissionStatus::Claimed
+                || submission.reviewer_id != Some(reviewer.id))
+        {
+            return Ok(Some(
+                "You do not have permission to edit submissions you have not claimed.",
+            ));
+        }
+        if Submission::conflicts_with_reviewer(
+            conn,
+            submission.id,
+            reviewer.id,
+            reviewer.can_edit_non_self_claimed,
+        )? {
+            return Ok(Some(
+                "You have a conflict of interest with this submission.",
+            ));
+        }
+        let (already_applied, reason) = match self {
+            Self::Accept => (
+                submission.status == SubmissionStatus::Accepted,
+                "This submission is already accepted.",
+            ),
+            Self::Deny => (
+                submission.status == SubmissionStatus::Denied,
+                "This submission is already denied.",
+            ),
+            Self::Unclaim => (
+                submission.status != SubmissionStatus::Claimed,
+                "This submission is not claimed.",
+            ),
+            Self::Lock => (submission.locked, "This submission is already locked."),
+            Self::Unlock => (!submission.locked, "This submission is not locked."),
+            Self::Prioritize => (
+                submission.priority,
+                "This submission is already in the priority queue.",
+            ),
+            Self::Deprioritize => (
+                !submission.priority,
+                "This submission is not in the priority queue.",
+            ),
+        };
+        Ok(already_applied.then_some(reason))
+    }
+}
+
+impl SubmissionBulkEdit {
+    fn target_ids(&self, conn: &mut DbConnection) -> Result<Vec<Uuid>, ApiError> {
+        match (&self.ids, &self.filter) {
+            (Some(ids), None) => {
+                if ids.is_empty() {
+                    return Err(ApiError::BadRequest("No submissions were given."));
+                }
+                Ok(ids.clone())
+            }
+            (None, Some(filter)) => {
+                if filter.level_id.is_none()
+                    && filter.submitter.is_none()
+                    && filter.status.is_none()
+                {
+                    return Err(ApiError::BadRequest(
+                        "The filter must include at least one condition.",
+                    ));
+                }
+
+                let mut query = submissions::table.into_boxed::<Pg>();
+                if let Some(level_id) = filter.level_id {
+                    query = query.filter(submissions::level_id.eq(level_id));
+                }
+                if let Some(submitter) = &filter.submitter {
+                    query = query.filter(
+                        submissions::submitted_by.eq_any(user_filter(submitter).select(users::id)),
+                    );
+                }
+                if let Some(status) = filter.status.clone() {
+                    query = query.filter(submissions::status.eq(status));
+                }
+                Ok(query.select(submissions::id).load::<Uuid>(conn)?)
+            }
+            _ => Err(ApiError::BadRequest(
+                "Exactly one of `ids` and `filter` must be given.",
+            )),
+        }
+    }
+
+    /// Applies the action to every targeted submission in a single transaction.
+    pub fn apply(
+        mut self,
+        conn: &mut DbConnection,
+        authenticated: &Authenticated,
+        notify_tx: &broadcast::Sender<WebsocketNotification>,
+        inbox_tx: &broadcast::Sender<Notification>,
+    ) -> Result<SubmissionBulkResult, ApiError> {
+        if let Some(reason_codes) = self.reason_codes.as_ref() {
+            if self.action != SubmissionBulkAction::Deny {
+                return Err(ApiError::BadRequest(
+                    "Reasons can only be given when denying submissions.",
+                ));
+            }
+            self.reason_codes = Some(SubmissionReason::validate_codes(conn, reason_codes)?);
+        }
+
+        if self.reviewer_notes.is_some()
+            && !matches!(
+                self.action,
+                SubmissionBulkAction::Accept | SubmissionBulkAction::Deny
+            )
+        {
+            return Err(ApiError::BadRequest(
+                "Reviewer notes can only be given when accepting or denying submissions.",
+            ));
+        }
+
+        let reviewer = BulkReviewer {
+            id: authenticated.user_id,
+            can_edit_non_self_claimed: authenticated
+                .has_permission(conn, Permission::SubmissionEditNonSelfClaimed)?,
+            can_edit_with_raw_footage: authenticated
+                .has_permission(conn, Permission::SubmissionEditWithRawFootage)?,
+        };
+
+        let target_ids = self.target_ids(conn)?;
+        if target_ids.len() > MAX_BULK_SUBMISSIONS {
+            return Err(ApiError::BadRequest(format!(
+                "A bulk action cannot target more than {MAX_BULK_SUBMISSIONS} submissions."
+            )));
+        }
+
+        let (result, side_effects) = conn.transaction(
+            |connection| -> Result<(SubmissionBulkResult, BulkSideEffects), ApiError> {
+                let targets = submissions::table
+                    .filter(submissions::id.eq_any(&target_ids))
+                    .order(submissions::created_at.asc())
+                    .for_update()
+                    .select(Submission::as_select())
+                    .load::<Submission>(connection)?;
+
+                let mut result = SubmissionBulkResult {
+                    updated: Vec::with_capacity(targets.len()),
+                    skipped: target_ids
+                        .iter()
+                        .filter(|id| !targets.iter().any(|submission| submission.id == **id))
+                        .map(|id| SkippedSubmission {
+                            id: *id,
+                            reason: "This submission does not exist.".to_owned(),
+                        })
+                        .collect(),
+                };
+                let mut side_effects = BulkSideEffects {
+                    websocket_events: Vec::new(),
+                    completed_shifts: Vec::new(),
+                    notifications: Vec::new(),
+                };
+
+                for submission in targets {
+                    if let Some(reason) =
+                        self.action
+                            .skip_reason(connection, &submission, &reviewer)?
+                    {
+                        result.skipped.push(SkippedSubmission {
+                            id: submission.id,
+                            reason: reason.to_owned(),
+                        });
+                        continue;
+                    }
+
+                    let updated = self.apply_one(connection, submission.id, reviewer.id)?;
+
+                    // only submissions the reviewer claimed themselves count toward their shift
+                    let shift_reviewer_id =
+                        (submission.reviewer_id == Some(reviewer.id)).then_some(reviewer.id);
+                    let review_side_effects = Submission::apply_review_side_effects(
+                        connection,
+                        &updated,
+                        &submission.status,
+                        &updated.status,
+                        shift_reviewer_id,
+                    )?;
+                    if let Some(websocket_type) = review_side_effects.websocket_type {
+                        side_effects
+                            .websocket_events
+                            .push((websocket_type, updated.clone()));
+                    }
+                    side_effects
+                        .completed_shifts
+                        .extend(review_side_effects.completed_shift);
+                    side_effects
+                        .notifications
+                        .extend(review_side_effects.notifications);
+
+                    result.updated.push(updated);
+                }
+
+                Ok((result, side_effects))
+            },
+        )?;
+
+        // events are only sent once every change has been committed
+        Notification::send_live(inbox_tx, side_effects.notifications);
+        for (notification_type, submission) in &side_effects.websocket_events {
+            WebsocketNotification::send(
+                conn,
+                notify_tx,
+                *notification_type,
+                Some(NotificationList::Arepl),
+                submission,
+            );
+        }
+        for completed_shift in &side_effects.completed_shifts {
+            WebsocketNotification::send(
+                conn,
+                notify_tx,
+                WebsocketNotificationType::ShiftCompleted,
+                None,
+                completed_shift,
+            );
+        }
+
+        Ok(result)
+    }
+
+    fn apply_one(
+        &self,
+        conn: &mut DbConnection,
+        id: Uuid,
+        reviewer_id: Uuid,
+    ) -> Result<Submission, ApiError> {
+        let target = diesel::update(submissions::table.filter(submissions::id.eq(id)));
+
+        let updated = match self.action {
+            SubmissionBulkAction::Accept | SubmissionBulkAction::Deny => {
+                let status = if self.action == SubmissionBulkAction::Accept {
+                    SubmissionStatus::Accepted
+                } else {
+                    SubmissionStatus::Denied
+                };
+                target
+                    .set((
+                        submissions::status.eq(status),
+                        submissions::reviewer_id.eq(Some(reviewer_id)),
+                        // existing notes are kept unless new ones are given
+                        self.reviewer_notes
+                            .clone()
+                            .map(|notes| submissions::reviewer_notes.eq(notes)),
+                        submissions::reason_codes.eq(self.reason_codes.clone().unwrap_or_default()),
+                    ))
+                    .returning(Submission::as_select())
+                    .get_result::<Submission>(conn)?
+            }
+            SubmissionBulkAction::Unclaim => target
+                .set((
+                    submissions::status.eq(SubmissionStatus::Pending),
+                    submissions::reviewer_id.eq::<Option<Uuid>>(None),
+                ))
+                .returning(Submission::as_select())
+                .get_result::<Submission>(conn)?,
+            SubmissionBulkAction::Lock | SubmissionBulkAction::Unlock => target
+                .set(submissions::locked.eq(self.action == SubmissionBulkAction::Lock))
+                .returning(Submission::as_select())
+                .get_result::<Submission>(conn)?,
+            SubmissionBulkAction::Prioritize | SubmissionBulkAction::Deprioritize => target
+                .set(submissions::priority.eq(self.action == SubmissionBulkAction::Prioritize))
+                .returning(Submission::as_select())
+                .get_result::<Submission>(conn)?,
+        };
+
+        Ok(updated)
+    }
+}
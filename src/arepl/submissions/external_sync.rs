@@ -0,0 +1,234 @@
+use crate::{
+    app_data::db::DbConnection,
+    arepl::submissions::{videos::SubmissionVideo, Submission, SubmissionStatus},
+    error_handler::ApiError,
+    external_sync::{
+        ExternalRecord, ExternalRecordData, ExternalSource, ExternalSyncAction, ExternalSyncChange,
+    },
+    providers::ProvidersAppState,
+    schema::arepl::{levels, submissions},
+};
+use chrono::Utc;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use diesel::prelude::*;
+impl From<&Submission> for ExternalRecordData {
+    fn from(submission: &Submission) -> Self {
+        Self {
+            video_url: submission.video_url.clone(),
+            mobile: submission.mobile,
+            completion_time: Some(submission.completion_time),
+            accepted: submission.status == SubmissionStatus::Accepted,
+        }
+    }
+}
+
+/// Brings the user's platformer submissions in line with the records of an external source.
+/// On a dry run, only returns what would change.
+pub fn sync_records(
+    conn: &mut DbConnection,
+    providers: &ProvidersAppState,
+    source: &ExternalSource,
+    user_id: Uuid,
+    records: &[ExternalRecord],
+    dry_run: bool,
+) -> Result<Vec<ExternalSyncChange>, ApiError> {
+    let mut changes = Vec::with_capacity(records.len());
+
+    for record in records {
+        let mut level_query = levels::table
+            .filter(levels::level_id.eq(record.level_id))
+            .select((levels::id, levels::name))
+            .into_boxed();
+        if let Some(two_player) = record.two_player {
+            level_query = level_query.filter(levels::two_player.eq(two_player));
+        }
+        let mut matching_levels = level_query.limit(2).load::<(Uuid, String)>(conn)?;
+        if matching_levels.len() > 1 {
+            changes.push(ExternalSyncChange::skipped(
+                record,
+                "The source does not say which version of this two player level the record is for",
+            ));
+            continue;
+        }
+        let Some((level_id, level_name)) = matching_levels.pop() else {
+            changes.push(ExternalSyncChange::skipped(
+                record,
+                "This level is not on the list",
+            ));
+            continue;
+        };
+        let Some(completion_time) = record.completion_time else {
+            changes.push(ExternalSyncChange::skipped(
+                record,
+                "This record has no completion time",
+            ));
+            continue;
+        };
+
+        let existing = submissions::table
+            .filter(submissions::submitted_by.eq(user_id))
+            .filter(submissions::level_id.eq(level_id))
+            .select(Submission::as_select())
+            .first::<Submission>(conn)
+            .optional()?;
+
+        let before = existing.as_ref().map(ExternalRecordData::from);
+        let after = ExternalRecordData {
+            video_url: record.video_url.clone(),
+            mobile: record.mobile,
+            completion_time: Some(completion_time),
+            accepted: source.auto_accept,
+        };
+
+        // only update if relevant data has changed (avoid triggering a submission history log entry)
+        let action = match &before {
+            None => ExternalSyncAction::Create,
+            Some(before)
+                if before.video_url != after.video_url
+                    || before.mobile != after.mobile
+                    || before.completion_time != after.completion_time
+                    || (source.auto_accept && !before.accepted) =>
+            {
+                ExternalSyncAction::Update
+            }
+            Some(_) => ExternalSyncAction::Unchanged,
+        };
+
+        if action == ExternalSyncAction::Update {
+            if let Some(reason) = existing
+                .as_ref()
+                .and_then(|submission| update_blocked_reason(submission, source.auto_accept))
+            {
+                changes.push(ExternalSyncChange::skipped(record, reason));
+                continue;
+            }
+        }
+
+        let (status, reviewer_notes, user_notes) = if source.auto_accept {
+            (
+                SubmissionStatus::Accepted,
+                Some(format!("Accepted via {} sync", source.name)),
+                None,
+            )
+        } else {
+            (
+                SubmissionStatus::Pending,
+                None,
+                Some(format!("Imported from {}", source.name)),
+            )
+        };
+        let video = SubmissionVideo::from(providers.identify_video(&record.video_url));
+        let video_reused = match action {
+            ExternalSyncAction::Create | ExternalSyncAction::Update if !dry_run => video
+                .is_reused(
+                    conn,
+                    existing.as_ref().map(|submission| submission.id),
+                    user_id,
+                    level_id,
+                )?,
+            _ => false,
+        };
+        let now = Utc::now();
+
+        let submission_id = match (action, &existing) {
+            _ if dry_run => existing.as_ref().map(|submission| submission.id),
+            (ExternalSyncAction::Create, _) => Some(
+                diesel::insert_into(submissions::table)
+                    .values((
+                        submissions::submitted_by.eq(user_id),
+                        submissions::level_id.eq(level_id),
+                        submissions::mobile.eq(record.mobile),
+                        submissions::custom_copy_id.eq::<Option<i32>>(None),
+                        submissions::video_url.eq(&record.video_url),
+                        (video, submissions::video_reused.eq(video_reused)),
+                        submissions::raw_url.eq::<Option<String>>(None),
+                        submissions::mod_menu.eq::<Option<String>>(Some(String::from("None"))),
+                        submissions::user_notes.eq(user_notes),
+                        submissions::priority.eq(false),
+                        submissions::status.eq(status),
+                        submissions::completion_time.eq(completion_time),
+                        submissions::reviewer_id.eq::<Option<Uuid>>(None),
+                        submissions::reviewer_notes.eq(reviewer_notes),
+                        submissions::created_at.eq(now),
+                        submissions::updated_at.eq(now),
+                    ))
+                    .returning(submissions::id)
+                    .get_result::<Uuid>(conn)?,
+            ),
+            (ExternalSyncAction::Update, Some(old)) => Some(
+                diesel::update(submissions::table.filter(submissions::id.eq(old.id)))
+                    .set((
+                        submissions::mobile.eq(record.mobile),
+                        submissions::video_url.eq(&record.video_url),
+                        (video, submissions::video_reused.eq(video_reused)),
+                        submissions::completion_time.eq(completion_time),
+                        submissions::status.eq(status),
+                        submissions::reviewer_id.eq::<Option<Uuid>>(None),
+                        submissions::reviewer_notes.eq(reviewer_notes),
+                        submissions::updated_at.eq(now),
+                    ))
+                    .returning(submissions::id)
+                    .get_result::<Uuid>(conn)?,
+            ),
+            _ => existing.as_ref().map(|submission| submission.id),
+        };
+
+        changes.push(ExternalSyncChange {
+            gd_level_id: record.level_id,
+            two_player: record.two_player,
+            level_id: Some(level_id),
+            level_name: Some(level_name),
+            action,
+            submission_id,
+            after: Some(if action == ExternalSyncAction::Unchanged {
+                before.clone().unwrap_or(after)
+            } else {
+                after
+            }),
+            before,
+            reason: None,
+        });
+    }
+
+    Ok(changes)
+}
+
+/// Why a sync may not overwrite an existing submission, if it may not.
+fn update_blocked_reason(submission: &Submission, auto_accept: bool) -> Option<&'static str> {
+    if submission.locked {
+        return Some("The submission is locked by staff");
+    }
+    match submission.status {
+        SubmissionStatus::Claimed
+        | SubmissionStatus::UnderReview
+        | SubmissionStatus::UnderConsideration => Some("The submission is being reviewed"),
+        SubmissionStatus::Accepted if !auto_accept => {
+            Some("The submission is already accepted, this source only queues submissions")
+        }
+        SubmissionStatus::Pending | SubmissionStatus::Denied | SubmissionStatus::Accepted => None,
+    }
+}
+
+/// Finds the submissions of the user for the given levels, keyed by level.
+pub fn find_submissions(
+    conn: &mut DbConnection,
+    user_id: Uuid,
+    level_ids: &[Uuid],
+) -> Result<HashMap<Uuid, (Uuid, ExternalRecordData)>, ApiError> {
+    let found = submissions::table
+        .filter(submissions::submitted_by.eq(user_id))
+        .filter(submissions::level_id.eq_any(level_ids))
+        .select(Submission::as_select())
+        .load::<Submission>(conn)?;
+    Ok(found
+        .iter()
+        .map(|submission| {
+            (
+                submission.level_id,
+                (submission.id, ExternalRecordData::from(submission)),
+            )
+        })
+        .collect())
+}
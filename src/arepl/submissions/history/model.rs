@@ -5,6 +5,7 @@ use crate::{
     error_handler::ApiError,
     roles::ReviewerVisibility,
     schema::{arepl::submission_history, users},
+    submission_reasons::{LocalizedSubmissionReason, RequestLocales, SubmissionReasonCatalogue},
     users::BaseUser,
 };
 use chrono::{DateTime, Utc};
@@ -29,6 +30,7 @@ pub struct SubmissionHistory {
     pub completion_time: Option<i64>,
     pub user_notes: Option<String>,
     pub reviewer_notes: Option<String>,
+    pub reason_codes: Vec<Option<String>>,
     pub private_reviewer_notes: Option<String>,
     pub reviewer_id: Option<Uuid>,
     pub locked: Option<bool>,
@@ -48,6 +50,7 @@ pub struct SubmissionHistoryResolved {
     pub completion_time: Option<i64>,
     pub user_notes: Option<String>,
     pub reviewer_notes: Option<String>,
+    pub reasons: Vec<LocalizedSubmissionReason>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reviewer: Option<BaseUser>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -56,13 +59,18 @@ pub struct SubmissionHistoryResolved {
 }
 
 impl SubmissionHistoryResolved {
-    pub fn from_data(history_row: (SubmissionHistory, Option<BaseUser>)) -> Self {
+    pub fn from_data(
+        history_row: (SubmissionHistory, Option<BaseUser>),
+        catalogue: &SubmissionReasonCatalogue,
+        locales: &RequestLocales,
+    ) -> Self {
         let (history, user) = history_row;
         Self {
             id: history.id,
             submission_id: history.submission_id,
             status: history.status,
             reviewer_notes: history.reviewer_notes,
+            reasons: catalogue.localize(&history.reason_codes, locales),
             reviewer: user,
             user_notes: history.user_notes,
             timestamp: history.timestamp,
@@ -81,6 +89,7 @@ impl SubmissionHistoryResolved {
         conn: &mut DbConnection,
         id: Uuid,
         authenticated: &Authenticated,
+        locales: &RequestLocales,
     ) -> Result<Vec<SubmissionHistoryResolved>, ApiError> {
         let history = submission_history::table
             .filter(submission_history::submission_id.eq(id))
@@ -93,9 +102,10 @@ impl SubmissionHistoryResolved {
             .order(submission_history::timestamp.desc())
             .load::<(SubmissionHistory, Option<BaseUser>)>(conn)?;
 
+        let catalogue = SubmissionReasonCatalogue::load(conn)?;
         let mut resolved_history = history
             .into_iter()
-            .map(SubmissionHistoryResolved::from_data)
+            .map(|row| SubmissionHistoryResolved::from_data(row, &catalogue, locales))
             .collect::<Vec<_>>();
 
         let visibility = ReviewerVisibility::new(conn, authenticated)?;
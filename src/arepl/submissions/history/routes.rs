@@ -9,6 +9,7 @@ use crate::{
     arepl::submissions::history::SubmissionHistoryResolved,
     auth::{Authenticated, UserAuth},
     error_handler::ApiError,
+    submission_reasons::RequestLocales,
 };
 
 #[utoipa::path(
@@ -32,12 +33,14 @@ async fn get_history(
     db: web::Data<Arc<DbAppState>>,
     id: web::Path<Uuid>,
     authenticated: Authenticated,
+    locales: RequestLocales,
 ) -> Result<HttpResponse, ApiError> {
     let history = web::block(move || {
         SubmissionHistoryResolved::by_submission_id(
             &mut db.connection()?,
             id.into_inner(),
             &authenticated,
+            &locales,
         )
     })
     .await??;
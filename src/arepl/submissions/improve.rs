@@ -0,0 +1,136 @@
+use crate::{
+    app_data::db::DbConnection,
+    arepl::{
+        levels::LevelStatus,
+        submissions::{patch::SubmissionPatchUser, Submission},
+    },
+    auth::Authenticated,
+    error_handler::ApiError,
+    providers::ProvidersAppState,
+    schema::arepl::{levels, records, submissions},
+    submission_requirements::{RequirementLevel, SubmissionRequirements, SubmissionRuleList},
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use diesel::prelude::*;
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct SubmissionImprovement {
+    /// Completion video URL of the faster run.
+    ///
+    /// The provider is enforced and the URL is stored in a standardized canonical form.
+    /// See [Allowed video URL types](#allowed-video-url-types).
+    pub video_url: String,
+    /// Completion time of the faster run in milliseconds.
+    pub completion_time: i64,
+    /// Whether the faster run was completed on mobile. Keeps the current value if omitted.
+    pub mobile: Option<bool>,
+    /// Raw footage URL of the faster run (optional).
+    pub raw_url: Option<String>,
+    /// The mod menu used in the faster run.
+    pub mod_menu: Option<String>,
+    /// Any additional notes left by the submitter.
+    pub user_notes: Option<String>,
+}
+
+impl SubmissionImprovement {
+    /// Resubmits an accepted submission with a faster run. The current record stays in place
+    /// until the improvement is accepted, after which its time is kept as a superseded personal best.
+    pub fn submit(
+        self,
+        id: Uuid,
+        conn: &mut DbConnection,
+        authenticated: &Authenticated,
+        providers: &ProvidersAppState,
+    ) -> Result<Submission, ApiError> {
+        let (submitted_by, completion_time, video_url) = records::table
+            .filter(records::submission_id.eq(id))
+            .select((
+                records::submitted_by,
+                records::completion_time,
+                records::video_url,
+            ))
+            .first::<(Uuid, i64, String)>(conn)
+            .optional()?
+            .ok_or_else(|| ApiError::NotFound("This submission has no record to improve"))?;
+
+        if submitted_by != authenticated.user_id {
+            return Err(ApiError::Forbidden(
+                "You can only improve your own records.",
+            ));
+        }
+
+        if self.completion_time >= completion_time {
+            return Err(ApiError::UnprocessableEntity(
+                "An improvement must be faster than your current time.",
+            ));
+        }
+
+        if providers
+            .validate_completion_video_url(&self.video_url)
+            .is_ok_and(|url| url == video_url)
+        {
+            return Err(ApiError::BadRequest(
+                "An improvement needs a new completion video.",
+            ));
+        }
+
+        // the faster run has to include everything the requirement rules ask for, like a new submission
+        let (mobile, mod_menu, custom_copy_id, status, position, tags, requires_raw_footage) =
+            submissions::table
+                .inner_join(levels::table.on(levels::id.eq(submissions::level_id)))
+                .filter(submissions::id.eq(id))
+                .select((
+                    submissions::mobile,
+                    submissions::mod_menu,
+                    submissions::custom_copy_id,
+                    levels::status,
+                    levels::position,
+                    levels::tags,
+                    levels::requires_raw_footage,
+                ))
+                .first::<(
+                    bool,
+                    Option<String>,
+                    Option<i32>,
+                    LevelStatus,
+                    Option<i32>,
+                    Vec<Option<String>>,
+                    bool,
+                )>(conn)?;
+
+        SubmissionRequirements::resolve(
+            conn,
+            SubmissionRuleList::Arepl,
+            &RequirementLevel {
+                status: (&status).into(),
+                position,
+                tags: &tags,
+                requires_raw_footage,
+            },
+            self.mobile.unwrap_or(mobile),
+        )?
+        .check(
+            self.raw_url.as_deref(),
+            self.mod_menu.as_deref().or(mod_menu.as_deref()),
+            custom_copy_id,
+        )?;
+
+        SubmissionPatchUser::patch(
+            SubmissionPatchUser {
+                mobile: self.mobile,
+                custom_copy_id: None,
+                video_url: Some(self.video_url),
+                completion_time: Some(self.completion_time),
+                raw_url: Some(self.raw_url),
+                mod_menu: self.mod_menu,
+                user_notes: Some(self.user_notes),
+            },
+            id,
+            conn,
+            authenticated,
+            providers,
+        )
+    }
+}
@@ -1,12 +1,17 @@
+pub mod bulk;
+pub mod external_sync;
 mod history;
+pub mod improve;
 mod model;
 pub mod patch;
 mod pemonlist;
 pub mod post;
-mod queue;
+pub mod queue;
+pub mod raw_probes;
 pub mod resolved;
 mod routes;
 mod status;
+pub mod videos;
 
 #[cfg(test)]
 pub mod test_utils;
@@ -1,9 +1,18 @@
 use crate::{
     app_data::db::DbConnection,
-    arepl::levels::ExtendedBaseLevel,
+    arepl::{
+        levels::{id_resolver::level_filter, ExtendedBaseLevel},
+        submissions::{
+            raw_probes::RawFootageProbe,
+            videos::{VideoDateWarning, VideoUse},
+        },
+    },
     auth::{Authenticated, Permission},
     error_handler::ApiError,
-    schema::arepl::submissions,
+    notifications::WebsocketNotificationType,
+    reviewer_conflicts::ReviewerConflicts,
+    schema::arepl::{levels, levels_created, submissions},
+    submission_reasons::{LocalizedSubmissionReason, RequestLocales},
     users::ExtendedBaseUser,
 };
 use chrono::{DateTime, Utc};
@@ -28,12 +37,14 @@ pub enum SubmissionStatus {
 }
 
 impl SubmissionStatus {
-    pub fn websocket_type(&self) -> Option<&'static str> {
+    pub fn websocket_type(&self) -> Option<WebsocketNotificationType> {
         match self {
-            SubmissionStatus::Accepted => Some("SUBMISSION_ACCEPTED"),
-            SubmissionStatus::Denied => Some("SUBMISSION_DENIED"),
-            SubmissionStatus::UnderConsideration => Some("SUBMISSION_UNDER_CONSIDERATION"),
-            SubmissionStatus::UnderReview => Some("SUBMISSION_UNDER_REVIEW"),
+            SubmissionStatus::Accepted => Some(WebsocketNotificationType::SubmissionAccepted),
+            SubmissionStatus::Denied => Some(WebsocketNotificationType::SubmissionDenied),
+            SubmissionStatus::UnderConsideration => {
+                Some(WebsocketNotificationType::SubmissionUnderConsideration)
+            }
+            SubmissionStatus::UnderReview => Some(WebsocketNotificationType::SubmissionUnderReview),
             SubmissionStatus::Claimed | SubmissionStatus::Pending => None,
         }
     }
@@ -57,6 +68,8 @@ pub struct Submission {
     /// The provider is enforced and the URL is stored in a standardized canonical form.
     /// See [Allowed video URL types](#allowed-video-url-types).
     pub video_url: String,
+    /// Publish date of the completion video reported by its provider, if known.
+    pub video_published_at: Option<DateTime<Utc>>,
     /// Completion time of the record in milliseconds.
     pub completion_time: i64,
     /// Raw footage URL (optional).
@@ -77,6 +90,9 @@ pub struct Submission {
     pub priority_at: DateTime<Utc>,
     /// Notes given by the reviewer when reviewing the record.
     pub reviewer_notes: Option<String>,
+    /// Codes of the reasons selected by the reviewer when denying the record or putting it under consideration.
+    #[schema(value_type = Vec<String>)]
+    pub reason_codes: Vec<Option<String>>,
     /// Private notes given by the reviewer when reviewing the record.
     pub private_reviewer_notes: Option<String>,
     /// Whether or not this submission has been locked by a staff member
@@ -106,6 +122,8 @@ pub struct SubmissionResolved {
     /// The provider is enforced and the URL is stored in a standardized canonical form.
     /// See [Allowed video URL types](#allowed-video-url-types).
     pub video_url: String,
+    /// Publish date of the completion video reported by its provider, if known.
+    pub video_published_at: Option<DateTime<Utc>>,
     /// Completion time of the record in milliseconds.
     pub completion_time: i64,
     /// Raw footage URL (optional).
@@ -127,6 +145,20 @@ pub struct SubmissionResolved {
     pub priority_at: DateTime<Utc>,
     /// Notes given by the reviewer when reviewing the record.
     pub reviewer_notes: Option<String>,
+    /// Reasons selected by the reviewer when denying the record or putting it under consideration.
+    pub reasons: Vec<LocalizedSubmissionReason>,
+    /// [MOD ONLY] Whether the completion video had already been submitted by another player when it was submitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video_reused: Option<bool>,
+    /// [MOD ONLY] Other submissions using the same completion video.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video_reuses: Option<Vec<VideoUse>>,
+    /// [MOD ONLY] Inconsistencies between the completion video's publish date and the level or submission.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video_date_warnings: Option<Vec<VideoDateWarning>>,
+    /// [MOD ONLY] Result of the automatic check of the raw footage, if it was probed already.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_footage_probe: Option<RawFootageProbe>,
     /// Whether or not this submission has been locked by a staff member
     pub locked: bool,
     /// Any additional notes left by the submitter.
@@ -147,6 +179,87 @@ pub struct SubmissionPage {
 
 pub type SubmissionFilter = Box<dyn BoxableExpression<submissions::table, Pg, SqlType = Bool>>;
 
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct SubmissionClaimOptions {
+    /// Comma separated levels (UUID, GD ID or position) the claimed submission must be for.
+    pub level_filter: Option<String>,
+    /// Comma separated level tags, the level of the claimed submission must have at least one of them.
+    pub tag_filter: Option<String>,
+    /// Smallest list position (inclusive) the level of the claimed submission can be placed at.
+    pub min_position: Option<i32>,
+    /// Largest list position (inclusive) the level of the claimed submission can be placed at.
+    pub max_position: Option<i32>,
+    /// Only claim mobile (or desktop) submissions.
+    pub mobile_filter: Option<bool>,
+}
+
+impl SubmissionClaimOptions {
+    fn split(value: Option<&str>) -> Vec<&str> {
+        value
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .collect()
+    }
+
+    // narrows down the claimable submissions, levels are matched through a subquery so that
+    // only the submission rows end up being locked by the claim
+    fn to_filter(&self) -> Result<Option<SubmissionFilter>, ApiError> {
+        let mut level_condition: Option<SubmissionFilter> = None;
+        for level in Self::split(self.level_filter.as_deref()) {
+            let condition: SubmissionFilter =
+                Box::new(submissions::level_id.eq_any(level_filter(level)?.select(levels::id)));
+            level_condition = Some(match level_condition.take() {
+                Some(existing) => Box::new(existing.or(condition)),
+                None => condition,
+            });
+        }
+        let tags = Self::split(self.tag_filter.as_deref())
+            .into_iter()
+            .map(|tag| Some(tag.to_owned()))
+            .collect::<Vec<_>>();
+
+        if let (Some(min), Some(max)) = (self.min_position, self.max_position) {
+            if min > max {
+                return Err(ApiError::BadRequest(
+                    "min_position cannot be greater than max_position",
+                ));
+            }
+        }
+
+        let mut filter: Option<SubmissionFilter> = None;
+        let mut and = |condition: SubmissionFilter| {
+            filter = Some(match filter.take() {
+                Some(existing) => Box::new(existing.and(condition)),
+                None => condition,
+            });
+        };
+
+        if let Some(level_condition) = level_condition {
+            and(level_condition);
+        }
+        if let Some(mobile) = self.mobile_filter {
+            and(Box::new(submissions::mobile.eq(mobile)));
+        }
+        if !tags.is_empty() || self.min_position.is_some() || self.max_position.is_some() {
+            let mut matching_levels = levels::table.select(levels::id).into_boxed();
+            if !tags.is_empty() {
+                matching_levels = matching_levels.filter(levels::tags.overlaps_with(tags));
+            }
+            if let Some(min) = self.min_position {
+                matching_levels = matching_levels.filter(levels::position.ge(min));
+            }
+            if let Some(max) = self.max_position {
+                matching_levels = matching_levels.filter(levels::position.le(max));
+            }
+            and(Box::new(submissions::level_id.eq_any(matching_levels)));
+        }
+
+        Ok(filter)
+    }
+}
+
 impl Submission {
     // filters for submissions that can be claimed by the current reviewer
     fn claimable_filter(
@@ -175,19 +288,73 @@ impl Submission {
         }
     }
 
+    // filters out submissions the reviewer has a conflict of interest with
+    fn conflict_filter(reviewer_id: Uuid, conflicts: &ReviewerConflicts) -> SubmissionFilter {
+        let mut filter: SubmissionFilter =
+            Box::new(submissions::submitted_by.ne_all(conflicts.users.clone()));
+
+        if conflicts.exclude_level_creators {
+            filter = Box::new(
+                filter
+                    .and(
+                        submissions::level_id.ne_all(
+                            levels_created::table
+                                .filter(levels_created::user_id.eq(reviewer_id))
+                                .select(levels_created::level_id),
+                        ),
+                    )
+                    .and(
+                        submissions::level_id.ne_all(
+                            levels::table
+                                .filter(levels::publisher_id.eq(reviewer_id))
+                                .select(levels::id),
+                        ),
+                    ),
+            );
+        }
+
+        filter
+    }
+
+    /// Checks whether the conflict of interest rules prevent the reviewer from reviewing this submission.
+    pub fn conflicts_with_reviewer(
+        conn: &mut DbConnection,
+        submission_id: Uuid,
+        reviewer_id: Uuid,
+        can_edit_non_self_claimed: bool,
+    ) -> Result<bool, ApiError> {
+        // reviewers who can edit submissions claimed by others are trusted to recuse themselves
+        if can_edit_non_self_claimed {
+            return Ok(false);
+        }
+        let conflicts = ReviewerConflicts::load(conn, reviewer_id)?;
+        let allowed = submissions::table
+            .filter(submissions::id.eq(submission_id))
+            .filter(Self::conflict_filter(reviewer_id, &conflicts))
+            .count()
+            .get_result::<i64>(conn)?;
+        Ok(allowed == 0)
+    }
+
     // Picks the next submission (if any) in the queue/prio queue, depending on the reviewer's permissions
     fn find_next_claimable_id(
         conn: &mut DbConnection,
         reviewer_id: Uuid,
         can_claim_raw_footage: bool,
         priority: bool,
+        options: &SubmissionClaimOptions,
+        conflicts: &ReviewerConflicts,
     ) -> Result<Option<Uuid>, ApiError> {
+        let mut filter: SubmissionFilter = Box::new(
+            Self::claimable_filter(reviewer_id, can_claim_raw_footage, priority)
+                .and(Self::conflict_filter(reviewer_id, conflicts)),
+        );
+        if let Some(options_filter) = options.to_filter()? {
+            filter = Box::new(filter.and(options_filter));
+        }
+
         let query = submissions::table
-            .filter(Self::claimable_filter(
-                reviewer_id,
-                can_claim_raw_footage,
-                priority,
-            ))
+            .filter(filter)
             .for_update()
             .skip_locked()
             .select(submissions::id);
@@ -214,16 +381,20 @@ impl Submission {
     pub fn claim_highest_priority(
         conn: &mut DbConnection,
         authenticated: &Authenticated,
+        options: &SubmissionClaimOptions,
     ) -> Result<SubmissionResolved, ApiError> {
         conn.transaction(|conn| -> Result<SubmissionResolved, ApiError> {
             let can_claim_raw_footage =
                 authenticated.has_permission(conn, Permission::SubmissionEditWithRawFootage)?;
+            let conflicts = ReviewerConflicts::load(conn, authenticated.user_id)?;
 
             let preferred_id = Self::find_next_claimable_id(
                 conn,
                 authenticated.user_id,
                 can_claim_raw_footage,
                 true,
+                options,
+                &conflicts,
             )?;
 
             let next_id = if let Some(id) = preferred_id {
@@ -233,6 +404,8 @@ impl Submission {
                 authenticated.user_id,
                 can_claim_raw_footage,
                 false,
+                options,
+                &conflicts,
             )? {
                 id
             } else {
@@ -249,7 +422,12 @@ impl Submission {
                 ))
                 .execute(conn)?;
 
-            let resolved = SubmissionResolved::find_one(conn, next_id, authenticated)?;
+            let resolved = SubmissionResolved::find_one(
+                conn,
+                next_id,
+                authenticated,
+                &RequestLocales::default(),
+            )?;
 
             Ok(resolved)
         })
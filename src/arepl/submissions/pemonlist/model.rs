@@ -1,228 +1,55 @@
-use chrono::Utc;
-use serde::Deserialize;
-
 use crate::{
-    app_data::db::DbConnection,
-    arepl::{
-        levels::ExtendedBaseLevel,
-        submissions::{Submission, SubmissionStatus},
-    },
-    auth::Authenticated,
-    error_handler::ApiError,
-    schema::{
-        arepl::{levels, submissions},
-        users,
-    },
+    app_data::db::DbAppState, arepl::submissions::Submission, error_handler::ApiError,
+    external_sync::ExternalSource, providers::ProvidersAppState, schema::arepl::submissions,
 };
+use actix_web::web;
+use std::sync::Arc;
+use uuid::Uuid;
 
 use diesel::prelude::*;
-#[derive(Debug, Deserialize)]
-pub struct PemonlistPlayer {
-    records: Vec<PemonlistRecord>,
-}
-
-#[derive(Debug, Deserialize)]
-struct PemonlistRecord {
-    formatted_time: String,
-    level: PemonlistLevelInfo,
-    mobile: bool,
-    video_id: String,
-}
+/// Name of the external source seeded for pemonlist.
+pub const PEMONLIST_SOURCE: &str = "pemonlist";
 
-#[derive(Debug, Deserialize)]
-struct PemonlistLevelInfo {
-    level_id: i32,
-}
-
-#[derive(Debug, Deserialize)]
-struct PemonlistError {
-    code: String,
-    error: bool,
-}
+pub struct PemonlistPlayer;
 
-#[derive(Debug, Deserialize)]
-#[serde(untagged)]
-enum PemonlistResponse {
-    Err(PemonlistError),
-    Ok(PemonlistPlayer),
-}
 impl PemonlistPlayer {
-    pub fn sync_with_pemonlist(
-        conn: &mut DbConnection,
-        authenticated: &Authenticated,
+    /// Syncs a user with the pemonlist source, returning the submissions matching their pemonlist records.
+    pub async fn sync_with_pemonlist(
+        db: Arc<DbAppState>,
+        providers: Arc<ProvidersAppState>,
+        user_id: Uuid,
     ) -> Result<Vec<Submission>, ApiError> {
-        let player_discord_id = users::table
-            .filter(users::id.eq(authenticated.user_id))
-            .select(users::discord_id)
-            .first::<Option<String>>(conn)?;
-
-        let Some(player_discord_id) = player_discord_id else {
-            return Err(ApiError::UnprocessableEntity(
-                "Given user does not have a discord id",
-            ));
-        };
-
-        let client = reqwest::blocking::Client::new();
-        let base_url = std::env::var("PEMONLIST_API_URL")
-            .unwrap_or_else(|_| "https://pemonlist.com/api/player".to_owned());
-        let url = format!("{}/{}", base_url.trim_end_matches('/'), player_discord_id);
-        let resp = client
-            .get(&url)
-            .send()
-            .map_err(|e| ApiError::BadGateway(e.to_string()))?;
-
-        let pemonlist_response: PemonlistResponse = resp.json().map_err(|e| {
-            ApiError::BadGateway(format!("Failed to parse data received from pemonlist: {e}"))
-        })?;
-
-        let pemonlist_data = match pemonlist_response {
-            PemonlistResponse::Err(err) if err.error && err.code == "bad_user" => {
-                return Err(ApiError::NotFound(format!(
-                    "Player {player_discord_id} not found on pemonlist"
-                )));
-            }
-            PemonlistResponse::Err(err) => {
-                return Err(ApiError::BadGateway(format!(
-                    "{}: {})",
-                    err.code, err.error
-                )));
-            }
-            PemonlistResponse::Ok(player) => player,
-        };
-
-        let mut imported = Vec::new();
-
-        for pemonlist_record in pemonlist_data.records {
-            let existing_level = levels::table
-                .filter(levels::level_id.eq(pemonlist_record.level.level_id))
-                .select(ExtendedBaseLevel::as_select())
-                .first::<ExtendedBaseLevel>(conn)
-                .optional()?;
-
-            let Some(existing_level) = existing_level else {
-                continue;
-            };
-
-            // find existing submission for this user/level in arepl.submissions
-            let existing_submission: Option<Submission> = submissions::table
-                .filter(submissions::submitted_by.eq(authenticated.user_id))
-                .filter(submissions::level_id.eq(existing_level.id))
+        let block_db = db.clone();
+        let source = web::block(move || {
+            ExternalSource::find_by_name(&mut block_db.connection()?, PEMONLIST_SOURCE)
+        })
+        .await??;
+
+        let result = ExternalSource::sync(
+            db.clone(),
+            providers,
+            source.id,
+            user_id,
+            Some(user_id),
+            false,
+        )
+        .await?;
+
+        let submission_ids = result
+            .changes
+            .iter()
+            .filter_map(|change| change.submission_id)
+            .collect::<Vec<_>>();
+        web::block(move || {
+            let mut synced = submissions::table
+                .filter(submissions::id.eq_any(&submission_ids))
                 .select(Submission::as_select())
-                .first::<Submission>(conn)
-                .optional()?;
-
-            let now = Utc::now();
-            let timestamp = Self::parse_formatted_ms(&pemonlist_record.formatted_time)?;
-
-            let video_url = format!(
-                "https://www.youtube.com/watch?v={}",
-                pemonlist_record.video_id
-            );
-
-            // ensure there is an Accepted submission with the correct data
-            // if exists, only update if relevant data has changed (avoid triggering a submission history log entry)
-            let submission = if let Some(old) = existing_submission {
-                if old.status != SubmissionStatus::Accepted
-                    || old.completion_time != timestamp
-                    || old.mobile != pemonlist_record.mobile
-                    || old.video_url != video_url
-                {
-                    diesel::update(submissions::table.filter(submissions::id.eq(old.id)))
-                        .set((
-                            submissions::mobile.eq(pemonlist_record.mobile),
-                            submissions::video_url.eq(video_url),
-                            submissions::completion_time.eq(timestamp),
-                            submissions::status.eq(SubmissionStatus::Accepted),
-                            submissions::reviewer_id.eq::<Option<uuid::Uuid>>(None),
-                            submissions::reviewer_notes.eq::<Option<String>>(Some(String::from(
-                                "Accepted via pemonlist sync",
-                            ))),
-                            submissions::updated_at.eq(now),
-                        ))
-                        .returning(Submission::as_select())
-                        .get_result::<Submission>(conn)?
-                } else {
-                    old
-                }
-            } else {
-                diesel::insert_into(submissions::table)
-                    .values((
-                        submissions::submitted_by.eq(authenticated.user_id),
-                        submissions::level_id.eq(existing_level.id),
-                        submissions::mobile.eq(pemonlist_record.mobile),
-                        submissions::custom_copy_id.eq::<Option<i32>>(None),
-                        submissions::video_url.eq(video_url),
-                        submissions::raw_url.eq::<Option<String>>(None),
-                        submissions::mod_menu.eq::<Option<String>>(Some(String::from("None"))),
-                        submissions::user_notes.eq::<Option<String>>(None),
-                        submissions::priority.eq(false),
-                        submissions::status.eq(SubmissionStatus::Accepted),
-                        submissions::completion_time.eq(timestamp),
-                        submissions::reviewer_id.eq::<Option<uuid::Uuid>>(None),
-                        submissions::reviewer_notes.eq::<Option<String>>(Some(String::from(
-                            "Accepted via pemonlist sync",
-                        ))),
-                        submissions::created_at.eq(now),
-                        submissions::updated_at.eq(now),
-                    ))
-                    .returning(Submission::as_select())
-                    .get_result::<Submission>(conn)?
-            };
-
-            imported.push(submission);
-        }
-
-        Ok(imported)
-    }
-
-    fn parse_formatted_ms(s: &str) -> Result<i64, ApiError> {
-        let (hms, millis) = s.split_once('.').unwrap_or((s, "0"));
-
-        let mut parts = hms.split(':');
-        let hours = parts
-            .next()
-            .ok_or_else(|| ApiError::BadGateway("Malformed hour timestamp"))?;
-        let minutes = parts
-            .next()
-            .ok_or_else(|| ApiError::BadGateway("Malformed minute timestamp"))?;
-        let seconds = parts
-            .next()
-            .ok_or_else(|| ApiError::BadGateway("Malformed second timestamp"))?;
-        if parts.next().is_some() {
-            return Err(ApiError::BadGateway("Malformed formatted_time"));
-        }
-
-        let hours: i64 = hours
-            .parse()
-            .map_err(|e| ApiError::BadGateway(format!("Failed to parse hours: {e}")))?;
-        let minutes: i64 = minutes
-            .parse()
-            .map_err(|e| ApiError::BadGateway(format!("Failed to parse minutes: {e}")))?;
-        let seconds: i64 = seconds
-            .parse()
-            .map_err(|e| ApiError::BadGateway(format!("Failed to parse seconds: {e}")))?;
-
-        if hours < 0 || !(0..60).contains(&minutes) || !(0..60).contains(&seconds) {
-            return Err(ApiError::BadGateway(
-                "Malformed formatted_time (out of range)",
-            ));
-        }
-
-        // normalize fraction to milliseconds
-        let milliseconds = {
-            let mut millis = millis.to_owned();
-            if millis.len() > 3 {
-                millis.truncate(3);
-            } else {
-                while millis.len() < 3 {
-                    millis.push('0');
-                }
-            }
-            millis
-                .parse::<i64>()
-                .map_err(|e| ApiError::BadGateway(format!("Failed to parse milliseconds: {e}")))?
-        };
-
-        Ok(hours * 3_600_000 + minutes * 60_000 + seconds * 1_000 + milliseconds)
+                .load::<Submission>(&mut db.connection()?)?;
+            synced.sort_by_key(|submission| {
+                submission_ids.iter().position(|id| *id == submission.id)
+            });
+            Ok(synced)
+        })
+        .await?
     }
 }
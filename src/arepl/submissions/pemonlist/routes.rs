@@ -3,6 +3,7 @@ use crate::arepl::submissions::pemonlist::PemonlistPlayer;
 use crate::arepl::submissions::Submission;
 use crate::auth::{Authenticated, UserAuth};
 use crate::error_handler::ApiError;
+use crate::providers::ProvidersAppState;
 use actix_web::{post, web, HttpResponse};
 use std::sync::Arc;
 use utoipa::OpenApi;
@@ -10,7 +11,7 @@ use utoipa::OpenApi;
 #[utoipa::path(
     post,
     summary = "[Auth]Sync with Pemonlist",
-    description = "Import and/or update platformer submissions from a Pemonlist account. The pemonlist account must be linked to the same discord account as the authenticated user.",
+    description = "Import and/or update platformer submissions from a Pemonlist account. The pemonlist account must be linked to the same discord account as the authenticated user. Shorthand for syncing with the `pemonlist` external source.",
     tag = "AREDL (P) - Submissions",
     responses(
         (status = 200, body = Vec<Submission>)
@@ -23,12 +24,15 @@ use utoipa::OpenApi;
 #[post("/sync", wrap = "UserAuth::load()")]
 async fn sync_pemonlist(
     db: web::Data<Arc<DbAppState>>,
+    providers: web::Data<Arc<ProvidersAppState>>,
     authenticated: Authenticated,
 ) -> Result<HttpResponse, ApiError> {
-    let result = web::block(move || {
-        PemonlistPlayer::sync_with_pemonlist(&mut db.connection()?, &authenticated)
-    })
-    .await??;
+    let result = PemonlistPlayer::sync_with_pemonlist(
+        db.get_ref().clone(),
+        providers.get_ref().clone(),
+        authenticated.user_id,
+    )
+    .await?;
     Ok(HttpResponse::Ok().json(result))
 }
 
@@ -6,17 +6,16 @@ use {
     },
     crate::auth::create_test_token,
     crate::{
+        external_sync::test_utils::set_test_external_source_base_url,
         test_utils::*,
         users::test_utils::{create_test_user, set_test_user_discord_id},
     },
     actix_web::test::{self, read_body_json},
     httpmock::prelude::*,
     serde_json::{json, Value},
-    serial_test::serial,
 };
 
 #[actix_web::test]
-#[serial]
 async fn sync_pemonlist() {
     let server = MockServer::start_async().await;
     let response_body = json!({
@@ -40,12 +39,13 @@ async fn sync_pemonlist() {
         })
         .await;
 
-    std::env::set_var(
-        "PEMONLIST_API_URL",
-        format!("{}/api/player", server.base_url()),
-    );
-
     let (app, db, auth, _) = init_test_app().await;
+    set_test_external_source_base_url(
+        &db,
+        "pemonlist",
+        &format!("{}/api/player", server.base_url()),
+    )
+    .await;
     let (user_id, _) = create_test_user(&db, None).await;
 
     set_test_user_discord_id(&db, user_id, "550348841396994048").await;
@@ -91,7 +91,6 @@ async fn sync_pemonlist() {
 }
 
 #[actix_web::test]
-#[serial]
 async fn sync_pemonlist_preserves_verification_flag() {
     // Mock pemonlist response
     let server = MockServer::start_async().await;
@@ -116,12 +115,13 @@ async fn sync_pemonlist_preserves_verification_flag() {
         })
         .await;
 
-    std::env::set_var(
-        "PEMONLIST_API_URL",
-        format!("{}/api/player", server.base_url()),
-    );
-
     let (app, db, auth, _) = init_test_app().await;
+    set_test_external_source_base_url(
+        &db,
+        "pemonlist",
+        &format!("{}/api/player", server.base_url()),
+    )
+    .await;
     let (user_id, _) = create_test_user(&db, None).await;
 
     set_test_user_discord_id(&db, user_id, "550348841396994048").await;
@@ -0,0 +1,5 @@
+mod model;
+#[cfg(test)]
+mod tests;
+
+pub use model::*;
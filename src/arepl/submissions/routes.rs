@@ -3,11 +3,18 @@ use crate::{
     arepl::{
         records::Record,
         submissions::{
-            patch::{SubmissionPatchMod, SubmissionPatchUser},
+            bulk::{
+                SkippedSubmission, SubmissionBulkAction, SubmissionBulkEdit, SubmissionBulkFilter,
+                SubmissionBulkResult,
+            },
+            improve::SubmissionImprovement,
+            patch::{SubmissionPatchMod, SubmissionPatchModBody, SubmissionPatchUser},
             pemonlist,
             post::{SubmissionInsert, SubmissionPostMod},
+            raw_probes::RawFootageProbe,
             resolved::{ResolvedSubmissionPage, SubmissionQueryOptions},
-            status, Submission, SubmissionPage, SubmissionResolved, SubmissionStatus,
+            status, Submission, SubmissionClaimOptions, SubmissionPage, SubmissionResolved,
+            SubmissionStatus,
         },
     },
     auth::{Authenticated, Permission, UserAuth},
@@ -15,6 +22,9 @@ use crate::{
     notifications::WebsocketNotification,
     page_helper::{PageQuery, Paginated},
     providers::ProvidersAppState,
+    submission_reasons::RequestLocales,
+    users::me::notifications::Notification,
+    utils::probe::RawFootageFlag,
 };
 use actix_web::{delete, get, patch, post, web, HttpResponse};
 use std::sync::Arc;
@@ -23,7 +33,7 @@ use tracing_actix_web::RootSpan;
 use utoipa::OpenApi;
 use uuid::Uuid;
 
-use super::{history, queue};
+use super::{history, queue, videos};
 
 #[utoipa::path(
     get,
@@ -54,6 +64,7 @@ async fn find_all(
     page_query: web::Query<PageQuery<50>>,
     options: web::Query<SubmissionQueryOptions>,
     authenticated: Authenticated,
+    locales: RequestLocales,
 ) -> Result<HttpResponse, ApiError> {
     let submissions = web::block(move || {
         ResolvedSubmissionPage::find_all(
@@ -61,6 +72,7 @@ async fn find_all(
             page_query.into_inner(),
             options.into_inner(),
             &authenticated,
+            &locales,
         )
     })
     .await??;
@@ -88,9 +100,15 @@ async fn find_one(
     db: web::Data<Arc<DbAppState>>,
     id: web::Path<Uuid>,
     authenticated: Authenticated,
+    locales: RequestLocales,
 ) -> Result<HttpResponse, ApiError> {
     let submission = web::block(move || {
-        SubmissionResolved::find_one(&mut db.connection()?, id.into_inner(), &authenticated)
+        SubmissionResolved::find_one(
+            &mut db.connection()?,
+            id.into_inner(),
+            &authenticated,
+            &locales,
+        )
     })
     .await??;
     Ok(HttpResponse::Ok().json(submission))
@@ -121,6 +139,7 @@ async fn find_me(
     page_query: web::Query<PageQuery<50>>,
     options: web::Query<SubmissionQueryOptions>,
     authenticated: Authenticated,
+    locales: RequestLocales,
 ) -> Result<HttpResponse, ApiError> {
     let submissions = web::block(move || {
         ResolvedSubmissionPage::find_own(
@@ -128,6 +147,7 @@ async fn find_me(
             page_query.into_inner(),
             options.into_inner(),
             &authenticated,
+            &locales,
         )
     })
     .await??;
@@ -157,12 +177,16 @@ async fn create(
     providers: web::Data<Arc<ProvidersAppState>>,
 ) -> Result<HttpResponse, ApiError> {
     root_span.record("body", tracing::field::debug(&body));
+    let db_clone = db.clone();
+    let providers_clone = providers.clone();
     let created = web::block(move || {
         let conn = &mut db.connection()?;
         authenticated.ensure_not_banned(conn)?;
         Submission::create(conn, body.into_inner(), &authenticated, providers.as_ref())
     })
     .await??;
+    let created =
+        Submission::update_video_published_at(db_clone, created, providers_clone.get_ref()).await;
     Ok(HttpResponse::Created().json(created))
 }
 
@@ -181,34 +205,42 @@ async fn create(
     params(
         ("id" = Uuid, description = "The ID of the submission")
     ),
-    request_body = SubmissionPatchMod,
+    request_body = SubmissionPatchModBody,
 )]
 #[patch("/{id}", wrap = "UserAuth::load()")]
 async fn patch(
     db: web::Data<Arc<DbAppState>>,
     id: web::Path<Uuid>,
-    body: web::Json<SubmissionPatchMod>,
+    body: web::Json<SubmissionPatchModBody>,
     authenticated: Authenticated,
     root_span: RootSpan,
     notify_tx: web::Data<broadcast::Sender<WebsocketNotification>>,
+    inbox_tx: web::Data<broadcast::Sender<Notification>>,
     providers: web::Data<Arc<ProvidersAppState>>,
 ) -> Result<HttpResponse, ApiError> {
     root_span.record("body", tracing::field::debug(&body));
     let db_clone = db.clone();
     let providers_clone = providers.clone();
+    let inbox_tx_clone = inbox_tx.clone();
+    let SubmissionPatchModBody {
+        patch: body,
+        use_video_date,
+    } = body.into_inner();
+    let video_changed = body.video_url.is_some();
     let patched = web::block(move || {
         let conn = &mut db.connection()?;
         if authenticated.has_permission(conn, Permission::SubmissionReview)? {
             SubmissionPatchMod::patch(
-                body.into_inner(),
+                body,
                 id.into_inner(),
                 conn,
                 &authenticated,
                 &notify_tx,
+                &inbox_tx,
                 &providers,
             )
         } else {
-            let user_patch = SubmissionPatchMod::downgrade(body.into_inner());
+            let user_patch = SubmissionPatchMod::downgrade(body);
             SubmissionPatchUser::patch(
                 user_patch,
                 id.into_inner(),
@@ -220,17 +252,119 @@ async fn patch(
     })
     .await??;
 
+    let patched = if video_changed {
+        Submission::update_video_published_at(db_clone.clone(), patched, providers_clone.get_ref())
+            .await
+    } else {
+        patched
+    };
+
     // if the status submission is changed to accepted, trigger other actions (timestamp update, badges, bounties, etc)
     if patched.status == SubmissionStatus::Accepted {
-        Record::post_accept_actions(db_clone, &patched, providers_clone);
+        Record::post_accept_actions(
+            db_clone,
+            &patched,
+            providers_clone,
+            inbox_tx_clone,
+            use_video_date,
+        );
     }
     Ok(HttpResponse::Ok().json(patched))
 }
 
+#[utoipa::path(
+    post,
+    summary = "[Auth]Submit a faster time",
+    description = "Resubmit one of your accepted submissions with a faster run. Your current record is kept until the new run is accepted, and stays in your personal best history afterwards.",
+    tag = "AREDL (P) - Submissions",
+    responses(
+        (status = 200, body = Submission)
+    ),
+    security(
+        ("access_token" = []),
+        ("api_key" = []),
+    ),
+    params(
+        ("id" = Uuid, description = "The ID of the submission")
+    ),
+    request_body = SubmissionImprovement,
+)]
+#[post("/{id}/improve", wrap = "UserAuth::load()")]
+async fn improve(
+    db: web::Data<Arc<DbAppState>>,
+    id: web::Path<Uuid>,
+    body: web::Json<SubmissionImprovement>,
+    authenticated: Authenticated,
+    root_span: RootSpan,
+    providers: web::Data<Arc<ProvidersAppState>>,
+) -> Result<HttpResponse, ApiError> {
+    root_span.record("body", tracing::field::debug(&body));
+    let db_clone = db.clone();
+    let providers_clone = providers.clone();
+    let improved = web::block(move || {
+        let conn = &mut db.connection()?;
+        body.into_inner()
+            .submit(id.into_inner(), conn, &authenticated, &providers)
+    })
+    .await??;
+    let improved =
+        Submission::update_video_published_at(db_clone, improved, providers_clone.get_ref()).await;
+    Ok(HttpResponse::Ok().json(improved))
+}
+
+#[utoipa::path(
+    post,
+    summary = "[Staff]Bulk edit submissions",
+    description = "Apply the same action to a list of submissions, or to every submission matching a filter, in a single transaction. Submissions the action cannot be applied to are skipped and returned with the reason.",
+    tag = "AREDL (P) - Submissions",
+    responses(
+        (status = 200, body = SubmissionBulkResult)
+    ),
+    security(
+        ("access_token" = []),
+        ("api_key" = []),
+    ),
+    request_body = SubmissionBulkEdit,
+)]
+#[post("/bulk", wrap = "UserAuth::require(Permission::SubmissionBulkEdit)")]
+async fn bulk(
+    db: web::Data<Arc<DbAppState>>,
+    body: web::Json<SubmissionBulkEdit>,
+    authenticated: Authenticated,
+    root_span: RootSpan,
+    notify_tx: web::Data<broadcast::Sender<WebsocketNotification>>,
+    inbox_tx: web::Data<broadcast::Sender<Notification>>,
+    providers: web::Data<Arc<ProvidersAppState>>,
+) -> Result<HttpResponse, ApiError> {
+    root_span.record("body", tracing::field::debug(&body));
+    let action = body.action;
+    let db_clone = db.clone();
+    let inbox_tx_clone = inbox_tx.clone();
+    let result = web::block(move || {
+        body.into_inner()
+            .apply(&mut db.connection()?, &authenticated, &notify_tx, &inbox_tx)
+    })
+    .await??;
+
+    // already accepted submissions are skipped, so every updated one was just accepted
+    if action == SubmissionBulkAction::Accept {
+        for accepted in &result.updated {
+            Record::post_accept_actions(
+                db_clone.clone(),
+                accepted,
+                providers.clone(),
+                inbox_tx_clone.clone(),
+                None,
+            );
+        }
+    }
+    Ok(HttpResponse::Ok().json(result))
+}
+
 #[utoipa::path(
     get,
     summary = "[Staff]Claim a submission",
-    description = "Claim the next submission to be checked. Alternates between priority and non-priority submissions when possible.",
+    description = "Claim the next submission to be checked. Alternates between priority and non-priority submissions when possible. The claimed submission can be restricted to specific levels, tags, positions or platforms.",
     tag = "AREDL (P) - Submissions",
     responses(
         (status = 200, body = SubmissionResolved)
@@ -239,14 +373,22 @@ async fn patch(
         ("access_token" = []),
         ("api_key" = []),
     ),
+    params(
+        ("level_filter" = Option<String>, Query, description = "Only claim submissions for these comma separated levels (UUID, GD ID or position)"),
+        ("tag_filter" = Option<String>, Query, description = "Only claim submissions for levels with at least one of these comma separated tags"),
+        ("min_position" = Option<i32>, Query, description = "Only claim submissions for levels placed at this position or below"),
+        ("max_position" = Option<i32>, Query, description = "Only claim submissions for levels placed at this position or above"),
+        ("mobile_filter" = Option<bool>, Query, description = "Only claim mobile/desktop submissions"),
+    ),
 )]
 #[get("/claim", wrap = "UserAuth::require(Permission::SubmissionReview)")]
 async fn claim(
     db: web::Data<Arc<DbAppState>>,
     authenticated: Authenticated,
+    options: web::Query<SubmissionClaimOptions>,
 ) -> Result<HttpResponse, ApiError> {
     let patched = web::block(move || {
-        Submission::claim_highest_priority(&mut db.connection()?, &authenticated)
+        Submission::claim_highest_priority(&mut db.connection()?, &authenticated, &options)
     })
     .await??;
 
@@ -289,6 +431,7 @@ async fn delete(
         (path = "/pemonlist", api=pemonlist::ApiDoc),
         (path = "/", api=history::ApiDoc),
         (path = "/", api=queue::ApiDoc),
+        (path = "/", api=videos::ApiDoc),
         (path = "/status", api=status::ApiDoc),
     ),
     components(
@@ -299,12 +442,21 @@ async fn delete(
             SubmissionStatus,
             Record,
             SubmissionPatchMod,
+            SubmissionPatchModBody,
             SubmissionPatchUser,
+            SubmissionImprovement,
             SubmissionInsert,
             SubmissionPage,
             SubmissionQueryOptions,
             SubmissionResolved,
             ResolvedSubmissionPage,
+            SubmissionBulkAction,
+            SubmissionBulkFilter,
+            SubmissionBulkEdit,
+            SubmissionBulkResult,
+            SkippedSubmission,
+            RawFootageProbe,
+            RawFootageFlag,
         )
     ),
     paths(
@@ -314,6 +466,8 @@ async fn delete(
         claim,
         create,
         patch,
+        improve,
+        bulk,
         delete,
     )
 )]
@@ -323,12 +477,15 @@ pub fn init_routes(config: &mut web::ServiceConfig) {
         web::scope("/submissions")
             .service(claim)
             .service(find_me)
+            .service(bulk)
             .configure(pemonlist::init_routes)
             .configure(status::init_routes)
             .configure(history::init_routes)
             .configure(queue::init_routes)
+            .configure(videos::init_routes)
             .service(find_one)
             .service(patch)
+            .service(improve)
             .service(delete)
             .service(create)
             .service(find_all),
@@ -0,0 +1,342 @@
+use crate::{
+    app_data::db::DbConnection,
+    error_handler::ApiError,
+    schema::{
+        arepl::{levels, submission_closure_levels, submission_closures},
+        users,
+    },
+    users::BaseUser,
+};
+use chrono::{DateTime, Utc};
+use diesel::{pg::Pg, Selectable};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use diesel::prelude::*;
+#[derive(Queryable, Selectable, Debug)]
+#[diesel(table_name = submission_closures, check_for_backend(Pg))]
+struct SubmissionClosureRow {
+    id: Uuid,
+    reason: String,
+    starts_at: DateTime<Utc>,
+    ends_at: Option<DateTime<Utc>>,
+    created_by: Option<Uuid>,
+    created_at: DateTime<Utc>,
+    lifted_by: Option<Uuid>,
+    lifted_at: Option<DateTime<Utc>>,
+    applies_to_all: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct SubmissionClosure {
+    /// Internal UUID of the closure.
+    pub id: Uuid,
+    /// Why submissions are closed, shown to submitters.
+    pub reason: String,
+    /// Whether the closure applies to every level.
+    pub applies_to_all: bool,
+    /// Internal UUIDs of the levels the closure applies to. Empty if it applies to every level.
+    pub level_ids: Vec<Uuid>,
+    /// Timestamp of when submissions close.
+    pub starts_at: DateTime<Utc>,
+    /// Timestamp of when submissions open again. Closures without an end last until they are lifted.
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct SubmissionClosureFull {
+    /// Internal UUID of the closure.
+    pub id: Uuid,
+    /// Why submissions are closed, shown to submitters.
+    pub reason: String,
+    /// Whether the closure applies to every level.
+    pub applies_to_all: bool,
+    /// Internal UUIDs of the levels the closure applies to. Empty if it applies to every level.
+    pub level_ids: Vec<Uuid>,
+    /// Timestamp of when submissions close.
+    pub starts_at: DateTime<Utc>,
+    /// Timestamp of when submissions open again. Closures without an end last until they are lifted.
+    pub ends_at: Option<DateTime<Utc>>,
+    /// The moderator that created this closure, if their account still exists.
+    pub created_by: Option<BaseUser>,
+    /// Timestamp of when this closure was created.
+    pub created_at: DateTime<Utc>,
+    /// The moderator that lifted this closure early, if any.
+    pub lifted_by: Option<BaseUser>,
+    /// Timestamp of when this closure was lifted early, if it was.
+    pub lifted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct SubmissionClosureCreate {
+    /// Why submissions are closed, shown to submitters.
+    pub reason: String,
+    /// Internal UUIDs of the levels to close submissions for, must not be empty if given. Closes submissions for every level if omitted.
+    pub level_ids: Option<Vec<Uuid>>,
+    /// Timestamp of when submissions close. Defaults to now.
+    pub starts_at: Option<DateTime<Utc>>,
+    /// Timestamp of when submissions open again. Lasts until lifted if omitted.
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+impl SubmissionClosureRow {
+    fn load_level_ids(
+        conn: &mut DbConnection,
+        closure_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Vec<Uuid>>, ApiError> {
+        let rows = submission_closure_levels::table
+            .filter(submission_closure_levels::closure_id.eq_any(closure_ids))
+            .select((
+                submission_closure_levels::closure_id,
+                submission_closure_levels::level_id,
+            ))
+            .load::<(Uuid, Uuid)>(conn)?;
+        Ok(rows
+            .into_iter()
+            .fold(HashMap::new(), |mut map, (closure_id, level_id)| {
+                map.entry(closure_id)
+                    .or_insert_with(Vec::new)
+                    .push(level_id);
+                map
+            }))
+    }
+}
+
+impl SubmissionClosure {
+    fn from_row(row: SubmissionClosureRow, level_ids: &mut HashMap<Uuid, Vec<Uuid>>) -> Self {
+        Self {
+            level_ids: level_ids.remove(&row.id).unwrap_or_default(),
+            id: row.id,
+            reason: row.reason,
+            applies_to_all: row.applies_to_all,
+            starts_at: row.starts_at,
+            ends_at: row.ends_at,
+        }
+    }
+
+    /// Finds the closure currently preventing submissions for the level, if any.
+    pub fn find_active_for_level(
+        conn: &mut DbConnection,
+        level_id: Uuid,
+    ) -> Result<Option<Self>, ApiError> {
+        let now = Utc::now();
+        let row = submission_closures::table
+            .filter(submission_closures::lifted_at.is_null())
+            .filter(submission_closures::starts_at.le(now))
+            .filter(
+                submission_closures::ends_at
+                    .is_null()
+                    .or(submission_closures::ends_at.gt(now)),
+            )
+            .filter(
+                submission_closures::id
+                    .eq_any(
+                        submission_closure_levels::table
+                            .filter(submission_closure_levels::level_id.eq(level_id))
+                            .select(submission_closure_levels::closure_id),
+                    )
+                    .or(submission_closures::applies_to_all.eq(true)),
+            )
+            .order(submission_closures::starts_at.desc())
+            .select(SubmissionClosureRow::as_select())
+            .first::<SubmissionClosureRow>(conn)
+            .optional()?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let mut level_ids = SubmissionClosureRow::load_level_ids(conn, &[row.id])?;
+        Ok(Some(Self::from_row(row, &mut level_ids)))
+    }
+
+    /// Lists the closures that are in effect or scheduled, soonest first.
+    pub fn find_current(conn: &mut DbConnection) -> Result<Vec<Self>, ApiError> {
+        let now = Utc::now();
+        let rows = submission_closures::table
+            .filter(submission_closures::lifted_at.is_null())
+            .filter(
+                submission_closures::ends_at
+                    .is_null()
+                    .or(submission_closures::ends_at.gt(now)),
+            )
+            .order(submission_closures::starts_at.asc())
+            .select(SubmissionClosureRow::as_select())
+            .load::<SubmissionClosureRow>(conn)?;
+
+        let ids = rows.iter().map(|row| row.id).collect::<Vec<_>>();
+        let mut level_ids = SubmissionClosureRow::load_level_ids(conn, &ids)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| Self::from_row(row, &mut level_ids))
+            .collect())
+    }
+
+    /// Error returned to submitters while the closure is in effect.
+    pub fn error_message(&self) -> String {
+        format!(
+            "Submissions for this level are currently closed: {}",
+            self.reason
+        )
+    }
+}
+
+impl SubmissionClosureFull {
+    pub fn create(
+        conn: &mut DbConnection,
+        closure: SubmissionClosureCreate,
+        moderator: Uuid,
+    ) -> Result<Self, ApiError> {
+        let SubmissionClosureCreate {
+            reason,
+            level_ids,
+            starts_at,
+            ends_at,
+        } = closure;
+
+        let reason = reason.trim().to_owned();
+        if reason.is_empty() {
+            return Err(ApiError::BadRequest("A reason must be given."));
+        }
+        let starts_at = starts_at.unwrap_or_else(Utc::now);
+        if let Some(ends_at) = ends_at {
+            if ends_at <= starts_at {
+                return Err(ApiError::BadRequest(
+                    "The closure must end after it starts.",
+                ));
+            }
+            if ends_at <= Utc::now() {
+                return Err(ApiError::BadRequest("The closure must end in the future."));
+            }
+        }
+
+        // only a missing list closes every level, an empty selection is most likely a mistake
+        let applies_to_all = level_ids.is_none();
+        let mut level_ids = level_ids.unwrap_or_default();
+        if !applies_to_all && level_ids.is_empty() {
+            return Err(ApiError::BadRequest(
+                "At least one level must be given, leave the levels out to close the whole list.",
+            ));
+        }
+        level_ids.sort_unstable();
+        level_ids.dedup();
+
+        conn.transaction(|conn| -> Result<Self, ApiError> {
+            let existing = levels::table
+                .filter(levels::id.eq_any(&level_ids))
+                .count()
+                .get_result::<i64>(conn)?;
+            if usize::try_from(existing).ok() != Some(level_ids.len()) {
+                return Err(ApiError::NotFound("Could not find every level."));
+            }
+
+            let row = diesel::insert_into(submission_closures::table)
+                .values((
+                    submission_closures::reason.eq(reason),
+                    submission_closures::starts_at.eq(starts_at),
+                    submission_closures::ends_at.eq(ends_at),
+                    submission_closures::created_by.eq(moderator),
+                    submission_closures::applies_to_all.eq(applies_to_all),
+                ))
+                .returning(SubmissionClosureRow::as_select())
+                .get_result::<SubmissionClosureRow>(conn)?;
+
+            diesel::insert_into(submission_closure_levels::table)
+                .values(
+                    level_ids
+                        .iter()
+                        .map(|level_id| {
+                            (
+                                submission_closure_levels::closure_id.eq(row.id),
+                                submission_closure_levels::level_id.eq(*level_id),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .execute(conn)?;
+
+            Self::from_rows(conn, vec![row])?
+                .pop()
+                .ok_or_else(|| ApiError::InternalServerError("Failed to load the closure"))
+        })
+    }
+
+    /// Lifts the closure early, opening submissions again.
+    pub fn lift(conn: &mut DbConnection, id: Uuid, moderator: Uuid) -> Result<Self, ApiError> {
+        conn.transaction(|conn| {
+            // locked so that concurrent lifts cannot both pass the check and overwrite each other
+            let row = submission_closures::table
+                .filter(submission_closures::id.eq(id))
+                .select(SubmissionClosureRow::as_select())
+                .for_update()
+                .first::<SubmissionClosureRow>(conn)?;
+
+            if row.lifted_at.is_some() {
+                return Err(ApiError::Conflict("This closure has already been lifted."));
+            }
+            if row.ends_at.is_some_and(|ends_at| ends_at <= Utc::now()) {
+                return Err(ApiError::Conflict("This closure has already ended."));
+            }
+
+            let row = diesel::update(submission_closures::table)
+                .filter(submission_closures::id.eq(id))
+                .set((
+                    submission_closures::lifted_by.eq(moderator),
+                    submission_closures::lifted_at.eq(Utc::now()),
+                ))
+                .returning(SubmissionClosureRow::as_select())
+                .get_result::<SubmissionClosureRow>(conn)?;
+
+            Self::from_rows(conn, vec![row])?
+                .pop()
+                .ok_or_else(|| ApiError::InternalServerError("Failed to load the closure"))
+        })
+    }
+
+    /// Lists every closure ever created, including lifted and ended ones, most recent first.
+    pub fn find_all(conn: &mut DbConnection) -> Result<Vec<Self>, ApiError> {
+        let rows = submission_closures::table
+            .order(submission_closures::created_at.desc())
+            .select(SubmissionClosureRow::as_select())
+            .load::<SubmissionClosureRow>(conn)?;
+        Self::from_rows(conn, rows)
+    }
+
+    fn from_rows(
+        conn: &mut DbConnection,
+        rows: Vec<SubmissionClosureRow>,
+    ) -> Result<Vec<Self>, ApiError> {
+        let ids = rows.iter().map(|row| row.id).collect::<Vec<_>>();
+        let mut level_ids = SubmissionClosureRow::load_level_ids(conn, &ids)?;
+
+        let user_ids = rows
+            .iter()
+            .flat_map(|row| row.created_by.into_iter().chain(row.lifted_by))
+            .collect::<Vec<_>>();
+        let users = users::table
+            .filter(users::id.eq_any(&user_ids))
+            .select(BaseUser::as_select())
+            .load::<BaseUser>(conn)?
+            .into_iter()
+            .map(|user| (user.id, user))
+            .collect::<HashMap<_, _>>();
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(Self {
+                    level_ids: level_ids.remove(&row.id).unwrap_or_default(),
+                    created_by: row.created_by.and_then(|id| users.get(&id).cloned()),
+                    lifted_by: row.lifted_by.and_then(|id| users.get(&id).cloned()),
+                    id: row.id,
+                    reason: row.reason,
+                    applies_to_all: row.applies_to_all,
+                    starts_at: row.starts_at,
+                    ends_at: row.ends_at,
+                    created_at: row.created_at,
+                    lifted_at: row.lifted_at,
+                })
+            })
+            .collect()
+    }
+}
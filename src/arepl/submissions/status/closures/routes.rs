@@ -0,0 +1,136 @@
+use crate::{
+    app_data::db::DbAppState,
+    arepl::submissions::status::closures::{
+        SubmissionClosure, SubmissionClosureCreate, SubmissionClosureFull,
+    },
+    auth::{Authenticated, Permission, UserAuth},
+    cache_control::CacheController,
+    error_handler::ApiError,
+};
+use actix_web::{delete, get, post, web, HttpResponse};
+use std::sync::Arc;
+use utoipa::OpenApi;
+use uuid::Uuid;
+
+#[utoipa::path(
+    get,
+    summary = "Get submission closures",
+    description = "List the closures that currently prevent submitting records, as well as the scheduled ones, soonest first. Closures without levels apply to the whole list.",
+    tag = "AREDL (P) - Submissions",
+    responses(
+        (status = 200, body = [SubmissionClosure])
+    ),
+)]
+#[get("", wrap = "CacheController::public_with_max_age(60)")]
+async fn find_current(db: web::Data<Arc<DbAppState>>) -> Result<HttpResponse, ApiError> {
+    let closures =
+        web::block(move || SubmissionClosure::find_current(&mut db.connection()?)).await??;
+    Ok(HttpResponse::Ok().json(closures))
+}
+
+#[utoipa::path(
+    get,
+    summary = "[Staff]Get submission closure history",
+    description = "Get a log of every submission closure, including lifted and ended ones, along with who created and lifted them.",
+    tag = "AREDL (P) - Submissions",
+    responses(
+        (status = 200, body = [SubmissionClosureFull])
+    ),
+    security(
+        ("access_token" = []),
+        ("api_key" = []),
+    ),
+)]
+#[get(
+    "/history",
+    wrap = "UserAuth::require(Permission::SubmissionStatusManage)"
+)]
+async fn find_all(db: web::Data<Arc<DbAppState>>) -> Result<HttpResponse, ApiError> {
+    let closures =
+        web::block(move || SubmissionClosureFull::find_all(&mut db.connection()?)).await??;
+    Ok(HttpResponse::Ok().json(closures))
+}
+
+#[utoipa::path(
+    post,
+    summary = "[Staff]Close submissions",
+    description = "Close submissions for a set of levels, or for the whole list if no level is given. The closure can be scheduled to start later and to end on its own.",
+    tag = "AREDL (P) - Submissions",
+    request_body = SubmissionClosureCreate,
+    responses(
+        (status = 200, body = SubmissionClosureFull)
+    ),
+    security(
+        ("access_token" = []),
+        ("api_key" = []),
+    ),
+)]
+#[post("", wrap = "UserAuth::require(Permission::SubmissionStatusManage)")]
+async fn create(
+    db: web::Data<Arc<DbAppState>>,
+    closure: web::Json<SubmissionClosureCreate>,
+    authenticated: Authenticated,
+) -> Result<HttpResponse, ApiError> {
+    let closure = web::block(move || {
+        SubmissionClosureFull::create(
+            &mut db.connection()?,
+            closure.into_inner(),
+            authenticated.user_id,
+        )
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(closure))
+}
+
+#[utoipa::path(
+    delete,
+    summary = "[Staff]Lift a submission closure",
+    description = "Lift a closure before it ends, opening submissions again. The closure is kept in the history.",
+    tag = "AREDL (P) - Submissions",
+    params(
+        ("id" = Uuid, description = "The internal UUID of the closure")
+    ),
+    responses(
+        (status = 200, body = SubmissionClosureFull)
+    ),
+    security(
+        ("access_token" = []),
+        ("api_key" = []),
+    ),
+)]
+#[delete(
+    "/{id}",
+    wrap = "UserAuth::require(Permission::SubmissionStatusManage)"
+)]
+async fn lift(
+    db: web::Data<Arc<DbAppState>>,
+    id: web::Path<Uuid>,
+    authenticated: Authenticated,
+) -> Result<HttpResponse, ApiError> {
+    let closure = web::block(move || {
+        SubmissionClosureFull::lift(
+            &mut db.connection()?,
+            id.into_inner(),
+            authenticated.user_id,
+        )
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(closure))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    components(schemas(SubmissionClosure, SubmissionClosureCreate, SubmissionClosureFull)),
+    paths(find_current, find_all, create, lift)
+)]
+pub struct ApiDoc;
+
+pub fn init_routes(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/closures")
+            .service(find_current)
+            .service(find_all)
+            .service(create)
+            .service(lift),
+    );
+}
@@ -0,0 +1,331 @@
+#[cfg(test)]
+use {
+    crate::{
+        arepl::{
+            levels::test_utils::create_test_level,
+            submissions::{
+                test_utils::{create_test_submission, set_test_submission_status},
+                SubmissionStatus,
+            },
+        },
+        auth::{create_test_token, Permission},
+        schema::arepl::levels,
+        test_utils::{assert_error_response, init_test_app},
+        users::test_utils::create_test_user,
+    },
+    actix_http::StatusCode,
+    actix_web::test::{self, read_body_json},
+    chrono::{Duration, Utc},
+    diesel::prelude::*,
+    serde_json::{json, Value},
+    uuid::Uuid,
+};
+
+#[cfg(test)]
+fn submission_body(level_id: Uuid, video_id: &str) -> Value {
+    json!({
+        "level_id": level_id,
+        "video_url": format!("https://youtube.com/watch?v={video_id}"),
+        "raw_url": "https://raw.com",
+        "mobile": false,
+        "completion_time": 1500
+    })
+}
+
+#[actix_web::test]
+async fn level_closure_blocks_submissions() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (moderator, _) = create_test_user(&db, Some(Permission::SubmissionStatusManage)).await;
+    let moderator_token = create_test_token(moderator, &auth.jwt_encoding_key).unwrap();
+    let (user, _) = create_test_user(&db, None).await;
+    let token = create_test_token(user, &auth.jwt_encoding_key).unwrap();
+    let closed_level = create_test_level(&db).await;
+    let open_level = create_test_level(&db).await;
+
+    let req = test::TestRequest::post()
+        .uri("/arepl/submissions/status/closures")
+        .insert_header(("Authorization", format!("Bearer {moderator_token}")))
+        .set_json(json!({
+            "reason": "The verification is being disputed.",
+            "level_ids": [closed_level, closed_level]
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let closure: Value = read_body_json(resp).await;
+    assert_eq!(closure["level_ids"], json!([closed_level]));
+    assert_eq!(closure["applies_to_all"], false);
+    assert_eq!(closure["created_by"]["id"], json!(moderator));
+
+    let req = test::TestRequest::post()
+        .uri("/arepl/submissions/")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(submission_body(closed_level, "closedlvl01"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_error_response!(
+        resp,
+        StatusCode::FORBIDDEN,
+        Some(
+            "Submissions for this level are currently closed: The verification is being disputed."
+        ),
+    );
+
+    let req = test::TestRequest::post()
+        .uri("/arepl/submissions/")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(submission_body(open_level, "openlvl0001"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    let req = test::TestRequest::get()
+        .uri("/arepl/submissions/status/closures")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let closures: Value = read_body_json(resp).await;
+    assert_eq!(closures.as_array().unwrap().len(), 1);
+    assert_eq!(closures[0]["id"], closure["id"]);
+}
+
+#[actix_web::test]
+async fn scheduled_closure_does_not_block_yet() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (moderator, _) = create_test_user(&db, Some(Permission::SubmissionStatusManage)).await;
+    let moderator_token = create_test_token(moderator, &auth.jwt_encoding_key).unwrap();
+    let (user, _) = create_test_user(&db, None).await;
+    let token = create_test_token(user, &auth.jwt_encoding_key).unwrap();
+    let level = create_test_level(&db).await;
+
+    let req = test::TestRequest::post()
+        .uri("/arepl/submissions/status/closures")
+        .insert_header(("Authorization", format!("Bearer {moderator_token}")))
+        .set_json(json!({
+            "reason": "List update",
+            "starts_at": Utc::now() + Duration::hours(1),
+            "ends_at": Utc::now() + Duration::hours(2)
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    let req = test::TestRequest::get()
+        .uri("/arepl/submissions/status/closures")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    let closures: Value = read_body_json(resp).await;
+    assert_eq!(closures.as_array().unwrap().len(), 1);
+
+    let req = test::TestRequest::post()
+        .uri("/arepl/submissions/")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(submission_body(level, "scheduled01"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+}
+
+#[actix_web::test]
+async fn lift_list_closure() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (moderator, _) = create_test_user(&db, Some(Permission::SubmissionStatusManage)).await;
+    let moderator_token = create_test_token(moderator, &auth.jwt_encoding_key).unwrap();
+    let (user, _) = create_test_user(&db, None).await;
+    let token = create_test_token(user, &auth.jwt_encoding_key).unwrap();
+    let level = create_test_level(&db).await;
+
+    let req = test::TestRequest::post()
+        .uri("/arepl/submissions/status/closures")
+        .insert_header(("Authorization", format!("Bearer {moderator_token}")))
+        .set_json(json!({ "reason": "Maintenance" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let closure: Value = read_body_json(resp).await;
+    assert_eq!(closure["applies_to_all"], true);
+    let closure_id = closure["id"].as_str().unwrap();
+
+    let req = test::TestRequest::post()
+        .uri("/arepl/submissions/")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(submission_body(level, "listclosed1"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_error_response!(
+        resp,
+        StatusCode::FORBIDDEN,
+        Some("Submissions for this level are currently closed: Maintenance"),
+    );
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/arepl/submissions/status/closures/{closure_id}"))
+        .insert_header(("Authorization", format!("Bearer {moderator_token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/arepl/submissions/status/closures/{closure_id}"))
+        .insert_header(("Authorization", format!("Bearer {moderator_token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_error_response!(
+        resp,
+        StatusCode::CONFLICT,
+        Some("This closure has already been lifted.")
+    );
+
+    let req = test::TestRequest::post()
+        .uri("/arepl/submissions/")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(submission_body(level, "listopen001"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    let req = test::TestRequest::get()
+        .uri("/arepl/submissions/status/closures/history")
+        .insert_header(("Authorization", format!("Bearer {moderator_token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let history: Value = read_body_json(resp).await;
+    let entry = history
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|entry| entry["id"] == closure["id"])
+        .expect("Lifted closure should be in the history");
+    assert_eq!(entry["lifted_by"]["id"], json!(moderator));
+    assert!(!entry["lifted_at"].is_null());
+}
+
+#[actix_web::test]
+async fn level_closure_stays_scoped_without_levels() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (moderator, _) = create_test_user(&db, Some(Permission::SubmissionStatusManage)).await;
+    let moderator_token = create_test_token(moderator, &auth.jwt_encoding_key).unwrap();
+    let (user, _) = create_test_user(&db, None).await;
+    let token = create_test_token(user, &auth.jwt_encoding_key).unwrap();
+    let removed_level = create_test_level(&db).await;
+    let level = create_test_level(&db).await;
+
+    let req = test::TestRequest::post()
+        .uri("/arepl/submissions/status/closures")
+        .insert_header(("Authorization", format!("Bearer {moderator_token}")))
+        .set_json(json!({ "reason": "Rerate in progress", "level_ids": [removed_level] }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    diesel::delete(levels::table.filter(levels::id.eq(removed_level)))
+        .execute(&mut db.connection().unwrap())
+        .unwrap();
+
+    let req = test::TestRequest::post()
+        .uri("/arepl/submissions/")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(submission_body(level, "scopedopen1"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(
+        resp.status().is_success(),
+        "A closure whose levels were removed should not close the whole list, status is {}",
+        resp.status()
+    );
+}
+
+#[actix_web::test]
+async fn closure_blocks_resubmissions() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (moderator, _) = create_test_user(&db, Some(Permission::SubmissionStatusManage)).await;
+    let moderator_token = create_test_token(moderator, &auth.jwt_encoding_key).unwrap();
+    let (user, _) = create_test_user(&db, None).await;
+    let token = create_test_token(user, &auth.jwt_encoding_key).unwrap();
+    let level = create_test_level(&db).await;
+    let submission = create_test_submission(level, user, &db).await;
+    set_test_submission_status(&db, submission, SubmissionStatus::Denied);
+
+    let req = test::TestRequest::post()
+        .uri("/arepl/submissions/status/closures")
+        .insert_header(("Authorization", format!("Bearer {moderator_token}")))
+        .set_json(json!({ "reason": "Rerate in progress", "level_ids": [level] }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    let req = test::TestRequest::patch()
+        .uri(&format!("/arepl/submissions/{submission}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"video_url": "https://www.youtube.com/watch?v=closure1111"}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_error_response!(
+        resp,
+        StatusCode::BAD_REQUEST,
+        Some("Only pending submissions can be edited while submissions for this level are closed: Rerate in progress"),
+    );
+}
+
+#[actix_web::test]
+async fn create_closure_validation() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (moderator, _) = create_test_user(&db, Some(Permission::SubmissionStatusManage)).await;
+    let moderator_token = create_test_token(moderator, &auth.jwt_encoding_key).unwrap();
+    let (reviewer, _) = create_test_user(&db, Some(Permission::SubmissionReview)).await;
+    let reviewer_token = create_test_token(reviewer, &auth.jwt_encoding_key).unwrap();
+
+    let req = test::TestRequest::post()
+        .uri("/arepl/submissions/status/closures")
+        .insert_header(("Authorization", format!("Bearer {reviewer_token}")))
+        .set_json(json!({ "reason": "Maintenance" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let cases = [
+        (
+            json!({ "reason": "  " }),
+            StatusCode::BAD_REQUEST,
+            "A reason must be given.",
+        ),
+        (
+            json!({
+                "reason": "Maintenance",
+                "starts_at": Utc::now() + Duration::hours(2),
+                "ends_at": Utc::now() + Duration::hours(1)
+            }),
+            StatusCode::BAD_REQUEST,
+            "The closure must end after it starts.",
+        ),
+        (
+            json!({
+                "reason": "Maintenance",
+                "starts_at": Utc::now() - Duration::hours(2),
+                "ends_at": Utc::now() - Duration::hours(1)
+            }),
+            StatusCode::BAD_REQUEST,
+            "The closure must end in the future.",
+        ),
+        (
+            json!({ "reason": "Maintenance", "level_ids": [Uuid::new_v4()] }),
+            StatusCode::NOT_FOUND,
+            "Could not find every level.",
+        ),
+        (
+            json!({ "reason": "Maintenance", "level_ids": [] }),
+            StatusCode::BAD_REQUEST,
+            "At least one level must be given, leave the levels out to close the whole list.",
+        ),
+    ];
+    for (body, status, message) in cases {
+        let req = test::TestRequest::post()
+            .uri("/arepl/submissions/status/closures")
+            .insert_header(("Authorization", format!("Bearer {moderator_token}")))
+            .set_json(body)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_error_response!(resp, status, Some(message));
+    }
+}
@@ -1,3 +1,4 @@
+pub mod closures;
 mod model;
 mod routes;
 
@@ -1,6 +1,6 @@
 use crate::{
     app_data::db::DbAppState,
-    arepl::submissions::status::{SubmissionsEnabled, SubmissionsEnabledFull},
+    arepl::submissions::status::{closures, SubmissionsEnabled, SubmissionsEnabledFull},
     auth::{Authenticated, Permission, UserAuth},
     cache_control::CacheController,
     error_handler::ApiError,
@@ -128,6 +128,9 @@ async fn get_history(db: web::Data<Arc<DbAppState>>) -> Result<HttpResponse, Api
 
 #[derive(OpenApi)]
 #[openapi(
+    nest(
+        (path = "/closures", api=closures::ApiDoc),
+    ),
     components(schemas(SubmissionsEnabled, SubmissionsEnabledFull)),
     paths(get_status, get_status_full, enable, disable, get_history)
 )]
@@ -139,6 +142,7 @@ pub fn init_routes(config: &mut web::ServiceConfig) {
             .service(get_status_full)
             .service(get_history)
             .service(enable)
-            .service(disable),
+            .service(disable)
+            .configure(closures::init_routes),
     );
 }
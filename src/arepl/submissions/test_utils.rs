@@ -44,6 +44,7 @@ pub async fn insert_history_entry(
         id: Uuid::new_v4(),
         submission_id,
         reviewer_notes: None,
+        reason_codes: Vec::new(),
         status,
         completion_time: Some(1_000_000),
         video_url: Some("https://video.com".to_owned()),
@@ -85,6 +86,18 @@ pub fn set_test_submission_reviewer(
         .expect("Failed to set test arepl submission reviewer");
 }
 
+#[cfg(test)]
+pub fn set_test_submission_reviewer_notes(
+    db: &Arc<DbAppState>,
+    submission_id: Uuid,
+    reviewer_notes: Option<&str>,
+) {
+    diesel::update(submissions::table.filter(submissions::id.eq(submission_id)))
+        .set(submissions::reviewer_notes.eq(reviewer_notes.map(str::to_owned)))
+        .execute(&mut db.connection().unwrap())
+        .expect("Failed to set test arepl submission reviewer notes");
+}
+
 #[cfg(test)]
 pub fn set_test_submission_reviewer_with_private_notes(
     db: &Arc<DbAppState>,
@@ -169,6 +182,18 @@ pub fn set_test_submissions_raw_url(
         .expect("Failed to set test arepl submissions raw URL");
 }
 
+#[cfg(test)]
+pub fn set_test_submission_created_at(
+    db: &Arc<DbAppState>,
+    submission_id: Uuid,
+    created_at: DateTime<Utc>,
+) {
+    diesel::update(submissions::table.filter(submissions::id.eq(submission_id)))
+        .set(submissions::created_at.eq(created_at))
+        .execute(&mut db.connection().unwrap())
+        .expect("Failed to set test arepl submission created_at");
+}
+
 #[cfg(test)]
 pub fn get_test_submission(db: &Arc<DbAppState>, submission_id: Uuid) -> Submission {
     get_test_submission_optional(db, submission_id).expect("Failed to fetch test arepl submission")
@@ -312,3 +337,44 @@ pub async fn create_two_test_submissions_with_different_timestamps(
 
     (sub_a, sub_b)
 }
+
+/// Records `count` accepted reviews by the reviewer at the given time and refreshes the submission stats.
+#[cfg(test)]
+pub async fn create_test_reviews(
+    db: &Arc<DbAppState>,
+    reviewer_id: Uuid,
+    count: usize,
+    timestamp: DateTime<Utc>,
+) {
+    let level_id = create_test_level(db).await;
+    let submission_id = create_test_submission(level_id, Uuid::new_v4(), db).await;
+    set_test_submission_status(db, submission_id, SubmissionStatus::Accepted);
+    // only the entries inserted below should count as reviews
+    diesel::delete(
+        submission_history::table.filter(submission_history::submission_id.eq(submission_id)),
+    )
+    .execute(&mut db.connection().unwrap())
+    .expect("Failed to clear test arepl submission history");
+    for _ in 0..count {
+        insert_history_entry(
+            submission_id,
+            Some(reviewer_id),
+            SubmissionStatus::Accepted,
+            db,
+        )
+        .await;
+    }
+    set_history_timestamp(db, submission_id, timestamp);
+    diesel::sql_query("REFRESH MATERIALIZED VIEW arepl.submission_stats")
+        .execute(&mut db.connection().unwrap())
+        .expect("Failed to refresh submission stats");
+}
+
+#[cfg(test)]
+pub fn test_submission_history_count(db: &Arc<DbAppState>, submission_id: Uuid) -> i64 {
+    submission_history::table
+        .filter(submission_history::submission_id.eq(submission_id))
+        .count()
+        .get_result(&mut db.connection().unwrap())
+        .expect("Failed to count test arepl submission history")
+}
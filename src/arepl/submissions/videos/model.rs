@@ -0,0 +1,336 @@
+use crate::{
+    app_data::db::{DbAppState, DbConnection},
+    arepl::{
+        levels::BaseLevel,
+        submissions::{Submission, SubmissionStatus},
+    },
+    error_handler::ApiError,
+    providers::{model::VideoIdentity, ProvidersAppState},
+    schema::{
+        arepl::{levels, position_history, records, submissions},
+        users,
+    },
+    users::BaseUser,
+};
+use actix_web::web;
+use chrono::{DateTime, Utc};
+use diesel::dsl::min;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use diesel::prelude::*;
+/// Provider and content ID of a submission's completion video, copied to its record once accepted.
+#[derive(Debug, Clone, Default, Insertable, AsChangeset)]
+#[diesel(table_name = submissions, treat_none_as_null = true, check_for_backend(Pg))]
+pub struct SubmissionVideo {
+    pub video_provider: Option<String>,
+    pub video_content_id: Option<String>,
+    // cleared whenever the video changes, until the provider is asked again
+    pub video_published_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct VideoUse {
+    /// Internal UUID of the submission.
+    pub submission_id: Uuid,
+    /// Internal UUID of the record, if the submission was accepted.
+    pub record_id: Option<Uuid>,
+    /// Level the video was submitted for.
+    pub level: BaseLevel,
+    /// User who submitted the video.
+    pub submitted_by: BaseUser,
+    /// The status of the submission.
+    pub status: SubmissionStatus,
+    /// Completion video URL of the submission.
+    pub video_url: String,
+    /// Timestamp of when the submission was created.
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct VideoUses {
+    /// Provider the video is hosted on.
+    pub provider: String,
+    /// ID of the video on its provider.
+    pub content_id: String,
+    /// Submissions using this video, oldest first.
+    pub uses: Vec<VideoUse>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema, PartialEq)]
+pub enum VideoDateWarningKind {
+    /// The video was published before the level was first placed on the list.
+    BeforeLevelPlacement,
+    /// The video was published after the submission was made.
+    AfterSubmission,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct VideoDateWarning {
+    /// What the publish date is inconsistent with.
+    pub kind: VideoDateWarningKind,
+    /// Publish date of the completion video.
+    pub video_published_at: DateTime<Utc>,
+    /// Date of the level placement or submission the video was compared to.
+    pub compared_to: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct VideoUsesQuery {
+    /// URL of the video, in any form its provider accepts.
+    pub url: String,
+}
+
+impl From<Option<VideoIdentity>> for SubmissionVideo {
+    fn from(identity: Option<VideoIdentity>) -> Self {
+        match identity {
+            Some(identity) => Self {
+                video_provider: Some(identity.provider.to_string()),
+                video_content_id: Some(identity.content_id),
+                video_published_at: None,
+            },
+            None => Self::default(),
+        }
+    }
+}
+
+impl SubmissionVideo {
+    /// Whether the video was already used by another submission, either from another player or
+    /// for another level, unless that submission was denied. `submission_id` is the submission
+    /// being checked, if it exists yet.
+    /// Reused videos are flagged for reviewers to look into rather than rejected.
+    pub fn is_reused(
+        &self,
+        conn: &mut DbConnection,
+        submission_id: Option<Uuid>,
+        submitted_by: Uuid,
+        level_id: Uuid,
+    ) -> Result<bool, ApiError> {
+        let (Some(provider), Some(content_id)) = (&self.video_provider, &self.video_content_id)
+        else {
+            return Ok(false);
+        };
+
+        Ok(diesel::select(diesel::dsl::exists(
+            submissions::table
+                .filter(submissions::video_provider.eq(provider))
+                .filter(submissions::video_content_id.eq(content_id))
+                .filter(submissions::id.nullable().is_distinct_from(submission_id))
+                .filter(
+                    submissions::submitted_by
+                        .ne(submitted_by)
+                        .or(submissions::level_id.ne(level_id)),
+                )
+                .filter(submissions::status.ne(SubmissionStatus::Denied)),
+        ))
+        .get_result::<bool>(conn)?)
+    }
+
+    /// Asks the provider when the video was published, failures are logged and treated as unknown.
+    pub async fn fetch_published_at(
+        providers: &ProvidersAppState,
+        video_url: &str,
+    ) -> Option<DateTime<Utc>> {
+        let result = async {
+            let matched = providers.parse_url(video_url)?;
+            let metadata = providers.fetch_metadata(&matched).await?;
+            Ok::<_, ApiError>(metadata.and_then(|metadata| metadata.published_at))
+        }
+        .await;
+
+        match result {
+            Ok(published_at) => published_at,
+            Err(e) => {
+                tracing::warn!(
+                    error = %e.error_message,
+                    %video_url,
+                    "Failed to fetch completion video publish date"
+                );
+                None
+            }
+        }
+    }
+}
+
+impl Submission {
+    /// Stores the publish date of the completion video, if its provider knows it.
+    ///
+    /// The submission has already been saved at this point, so failures are logged and the
+    /// submission is returned without the date.
+    pub async fn update_video_published_at(
+        db: web::Data<Arc<DbAppState>>,
+        submission: Self,
+        providers: &ProvidersAppState,
+    ) -> Self {
+        let Some(published_at) =
+            SubmissionVideo::fetch_published_at(providers, &submission.video_url).await
+        else {
+            return submission;
+        };
+
+        let submission_id = submission.id;
+        let video_url = submission.video_url.clone();
+        let result = web::block(move || {
+            // the video may have been changed while its provider was being asked
+            diesel::update(submissions::table)
+                .filter(submissions::id.eq(submission_id))
+                .filter(submissions::video_url.eq(video_url))
+                .set(submissions::video_published_at.eq(published_at))
+                .returning(Submission::as_select())
+                .get_result::<Submission>(&mut db.connection()?)
+                .optional()
+                .map_err(ApiError::from)
+        })
+        .await
+        .map_err(ApiError::from)
+        .and_then(|result| result);
+
+        match result {
+            Ok(updated) => updated.unwrap_or(submission),
+            Err(e) => {
+                tracing::warn!(
+                    error = %e.error_message,
+                    ?submission_id,
+                    "Failed to store completion video publish date"
+                );
+                submission
+            }
+        }
+    }
+}
+
+impl VideoDateWarning {
+    /// Compares the publish date of a submission's video to when its level was placed and when it was submitted.
+    pub fn find(conn: &mut DbConnection, submission_id: Uuid) -> Result<Vec<Self>, ApiError> {
+        let (published_at, level_id, created_at) = submissions::table
+            .filter(submissions::id.eq(submission_id))
+            .select((
+                submissions::video_published_at,
+                submissions::level_id,
+                submissions::created_at,
+            ))
+            .first::<(Option<DateTime<Utc>>, Uuid, DateTime<Utc>)>(conn)?;
+
+        let Some(published_at) = published_at else {
+            return Ok(Vec::new());
+        };
+
+        let placed_at = position_history::table
+            .filter(position_history::affected_level.eq(level_id))
+            .filter(position_history::new_position.is_not_null())
+            .select(min(position_history::created_at))
+            .first::<Option<DateTime<Utc>>>(conn)?;
+
+        let mut warnings = Vec::new();
+        if let Some(placed_at) = placed_at.filter(|placed_at| published_at < *placed_at) {
+            warnings.push(Self {
+                kind: VideoDateWarningKind::BeforeLevelPlacement,
+                video_published_at: published_at,
+                compared_to: placed_at,
+            });
+        }
+        if published_at > created_at {
+            warnings.push(Self {
+                kind: VideoDateWarningKind::AfterSubmission,
+                video_published_at: published_at,
+                compared_to: created_at,
+            });
+        }
+        Ok(warnings)
+    }
+}
+
+impl VideoUse {
+    fn find_all(
+        conn: &mut DbConnection,
+        provider: &str,
+        content_id: &str,
+        exclude: Option<Uuid>,
+    ) -> Result<Vec<Self>, ApiError> {
+        let mut query = submissions::table
+            .inner_join(levels::table.on(levels::id.eq(submissions::level_id)))
+            .inner_join(users::table.on(users::id.eq(submissions::submitted_by)))
+            .left_join(records::table)
+            .filter(submissions::video_provider.eq(provider))
+            .filter(submissions::video_content_id.eq(content_id))
+            .into_boxed();
+        if let Some(exclude) = exclude {
+            query = query.filter(submissions::id.ne(exclude));
+        }
+
+        let rows = query
+            .order(submissions::created_at.asc())
+            .select((
+                submissions::id,
+                records::id.nullable(),
+                BaseLevel::as_select(),
+                BaseUser::as_select(),
+                submissions::status,
+                submissions::video_url,
+                submissions::created_at,
+            ))
+            .load::<(
+                Uuid,
+                Option<Uuid>,
+                BaseLevel,
+                BaseUser,
+                SubmissionStatus,
+                String,
+                DateTime<Utc>,
+            )>(conn)?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(submission_id, record_id, level, submitted_by, status, video_url, created_at)| {
+                    Self {
+                        submission_id,
+                        record_id,
+                        level,
+                        submitted_by,
+                        status,
+                        video_url,
+                        created_at,
+                    }
+                },
+            )
+            .collect())
+    }
+
+    /// Lists the other submissions using the same completion video as the given one.
+    pub fn find_reuses(
+        conn: &mut DbConnection,
+        submission_id: Uuid,
+    ) -> Result<Vec<Self>, ApiError> {
+        let (provider, content_id) = submissions::table
+            .filter(submissions::id.eq(submission_id))
+            .select((submissions::video_provider, submissions::video_content_id))
+            .first::<(Option<String>, Option<String>)>(conn)?;
+
+        match (provider, content_id) {
+            (Some(provider), Some(content_id)) => {
+                Self::find_all(conn, &provider, &content_id, Some(submission_id))
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+}
+
+impl VideoUses {
+    pub fn find_by_url(
+        conn: &mut DbConnection,
+        providers: &ProvidersAppState,
+        url: &str,
+    ) -> Result<Self, ApiError> {
+        let identity = providers.parse_url(url)?.identity();
+        let provider = identity.provider.to_string();
+        let uses = VideoUse::find_all(conn, &provider, &identity.content_id, None)?;
+        Ok(Self {
+            provider,
+            content_id: identity.content_id,
+            uses,
+        })
+    }
+}
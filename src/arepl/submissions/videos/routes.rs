@@ -0,0 +1,58 @@
+use crate::{
+    app_data::db::DbAppState,
+    arepl::submissions::videos::{
+        VideoDateWarning, VideoDateWarningKind, VideoUse, VideoUses, VideoUsesQuery,
+    },
+    auth::{Permission, UserAuth},
+    error_handler::ApiError,
+    providers::ProvidersAppState,
+};
+use actix_web::{get, web, HttpResponse};
+use std::sync::Arc;
+use utoipa::OpenApi;
+
+#[utoipa::path(
+    get,
+    summary = "[Staff]Find submissions by video",
+    description = "List every submission, along with its record if accepted, that uses the given video. Videos are matched by their provider and ID, so timestamps, playlists and other URL forms of the same video are included.",
+    tag = "AREDL (P) - Submissions",
+    params(
+        ("url" = String, Query, description = "URL of the video"),
+    ),
+    responses(
+        (status = 200, body = VideoUses)
+    ),
+    security(
+        ("access_token" = ["SubmissionReview"]),
+        ("api_key" = ["SubmissionReview"]),
+    ),
+)]
+#[get("videos", wrap = "UserAuth::require(Permission::SubmissionReview)")]
+async fn find_by_video(
+    db: web::Data<Arc<DbAppState>>,
+    providers: web::Data<Arc<ProvidersAppState>>,
+    query: web::Query<VideoUsesQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let uses = web::block(move || {
+        VideoUses::find_by_url(&mut db.connection()?, providers.get_ref(), &query.url)
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(uses))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    components(schemas(
+        VideoDateWarning,
+        VideoDateWarningKind,
+        VideoUse,
+        VideoUses,
+        VideoUsesQuery
+    )),
+    paths(find_by_video)
+)]
+pub struct ApiDoc;
+
+pub fn init_routes(config: &mut web::ServiceConfig) {
+    config.service(find_by_video);
+}
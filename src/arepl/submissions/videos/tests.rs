@@ -0,0 +1,401 @@
+#[cfg(test)]
+use {
+    crate::{
+        arepl::{
+            levels::test_utils::create_test_level,
+            records::test_utils::get_test_record_for_level_and_user,
+            submissions::{
+                test_utils::{set_test_submission_status, test_submission_history_count},
+                SubmissionStatus,
+            },
+        },
+        auth::{create_test_token, oauth::OAuthProvider, Permission},
+        providers::{
+            context::{google::new_google_context, ProviderContext},
+            list::youtube::YouTubeProvider,
+            model::{Provider, ProviderRegistry},
+            test_utils::{
+                clear_oauth_env, mock_google_token_endpoint, mock_youtube_videos_endpoint,
+                seed_oauth_token, set_oauth_env,
+            },
+            ProvidersAppState,
+        },
+        test_utils::{init_test_app, init_test_app_with_providers},
+        users::test_utils::{create_test_full_reviewer, create_test_user},
+    },
+    actix_http::StatusCode,
+    actix_web::test::{self, read_body_json},
+    chrono::{DateTime, Utc},
+    httpmock::MockServer,
+    serde_json::{json, Value},
+    serial_test::serial,
+    std::sync::Arc,
+    tokio::time::{sleep, Duration},
+    url::form_urlencoded::Serializer,
+    uuid::Uuid,
+};
+
+#[cfg(test)]
+fn submission_body(level_id: Uuid, video_url: &str) -> Value {
+    json!({
+        "level_id": level_id,
+        "video_url": video_url,
+        "raw_url": "https://raw.com",
+        "mobile": false,
+        "completion_time": 1500
+    })
+}
+
+#[cfg(test)]
+fn videos_uri(video_url: &str) -> String {
+    let query = Serializer::new(String::new())
+        .append_pair("url", video_url)
+        .finish();
+    format!("/arepl/submissions/videos?{query}")
+}
+
+#[actix_web::test]
+async fn submit_video_reused_by_another_player() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (reviewer, _) = create_test_user(&db, Some(Permission::SubmissionReview)).await;
+    let reviewer_token = create_test_token(reviewer, &auth.jwt_encoding_key).unwrap();
+    let (owner, _) = create_test_user(&db, None).await;
+    let owner_token = create_test_token(owner, &auth.jwt_encoding_key).unwrap();
+    let (other, _) = create_test_user(&db, None).await;
+    let other_token = create_test_token(other, &auth.jwt_encoding_key).unwrap();
+    let (late, _) = create_test_user(&db, None).await;
+    let late_token = create_test_token(late, &auth.jwt_encoding_key).unwrap();
+    let first_level = create_test_level(&db).await;
+    let second_level = create_test_level(&db).await;
+
+    let submit = |token: &str, level: Uuid, video_url: &str| {
+        test::TestRequest::post()
+            .uri("/arepl/submissions/")
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .set_json(submission_body(level, video_url))
+            .to_request()
+    };
+    let view = |id: &Value| {
+        test::TestRequest::get()
+            .uri(&format!("/arepl/submissions/{}", id.as_str().unwrap()))
+            .insert_header(("Authorization", format!("Bearer {reviewer_token}")))
+            .to_request()
+    };
+
+    let resp = test::call_service(
+        &app,
+        submit(
+            &owner_token,
+            first_level,
+            "https://youtu.be/reusedvid01?t=30",
+        ),
+    )
+    .await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let original: Value = read_body_json(resp).await;
+
+    let resp = test::call_service(&app, view(&original["id"])).await;
+    let body: Value = read_body_json(resp).await;
+    assert_eq!(body["video_reused"], false);
+
+    // the same video behind a differently written URL is still recognized
+    let resp = test::call_service(
+        &app,
+        submit(
+            &other_token,
+            first_level,
+            "https://www.youtube.com/watch?v=reusedvid01&list=PL123",
+        ),
+    )
+    .await;
+    assert!(
+        resp.status().is_success(),
+        "Reused videos should be flagged for reviewers, not rejected, status is {}",
+        resp.status()
+    );
+    let reused: Value = read_body_json(resp).await;
+
+    let resp = test::call_service(&app, view(&reused["id"])).await;
+    let body: Value = read_body_json(resp).await;
+    assert_eq!(body["video_reused"], true);
+
+    // the same player using one video for another level is flagged too
+    let resp = test::call_service(
+        &app,
+        submit(
+            &owner_token,
+            second_level,
+            "https://www.youtube.com/shorts/reusedvid01",
+        ),
+    )
+    .await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let second: Value = read_body_json(resp).await;
+
+    let resp = test::call_service(&app, view(&second["id"])).await;
+    let body: Value = read_body_json(resp).await;
+    assert_eq!(body["video_reused"], true);
+
+    // uses of the video that were denied don't count
+    for submission in [&original, &reused, &second] {
+        let id = submission["id"].as_str().unwrap().parse::<Uuid>().unwrap();
+        set_test_submission_status(&db, id, SubmissionStatus::Denied);
+    }
+
+    let resp = test::call_service(
+        &app,
+        submit(&late_token, first_level, "https://youtu.be/reusedvid01"),
+    )
+    .await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let late_submission: Value = read_body_json(resp).await;
+
+    let resp = test::call_service(&app, view(&late_submission["id"])).await;
+    let body: Value = read_body_json(resp).await;
+    assert_eq!(body["video_reused"], false);
+}
+
+#[actix_web::test]
+async fn patch_to_video_reused_by_another_player() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (reviewer, _) = create_test_user(&db, Some(Permission::SubmissionReview)).await;
+    let reviewer_token = create_test_token(reviewer, &auth.jwt_encoding_key).unwrap();
+    let (owner, _) = create_test_user(&db, None).await;
+    let owner_token = create_test_token(owner, &auth.jwt_encoding_key).unwrap();
+    let (other, _) = create_test_user(&db, None).await;
+    let other_token = create_test_token(other, &auth.jwt_encoding_key).unwrap();
+    let level = create_test_level(&db).await;
+
+    let req = test::TestRequest::post()
+        .uri("/arepl/submissions/")
+        .insert_header(("Authorization", format!("Bearer {owner_token}")))
+        .set_json(submission_body(level, "https://youtu.be/patchreuse1"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    let req = test::TestRequest::post()
+        .uri("/arepl/submissions/")
+        .insert_header(("Authorization", format!("Bearer {other_token}")))
+        .set_json(submission_body(level, "https://youtu.be/patchother1"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let submission: Value = read_body_json(resp).await;
+    let submission_uri = format!("/arepl/submissions/{}", submission["id"].as_str().unwrap());
+
+    let req = test::TestRequest::patch()
+        .uri(&submission_uri)
+        .insert_header(("Authorization", format!("Bearer {other_token}")))
+        .set_json(json!({"video_url": "https://www.youtube.com/watch?v=patchreuse1&t=12"}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    let req = test::TestRequest::get()
+        .uri(&submission_uri)
+        .insert_header(("Authorization", format!("Bearer {reviewer_token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    let body: Value = read_body_json(resp).await;
+    assert_eq!(body["video_reused"], true);
+
+    let req = test::TestRequest::get()
+        .uri(&submission_uri)
+        .insert_header(("Authorization", format!("Bearer {other_token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    let body: Value = read_body_json(resp).await;
+    assert!(body.get("video_reused").is_none());
+}
+
+#[actix_web::test]
+async fn find_submissions_by_video() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (reviewer, _) = create_test_user(&db, Some(Permission::SubmissionReview)).await;
+    let reviewer_token = create_test_token(reviewer, &auth.jwt_encoding_key).unwrap();
+    let (user, _) = create_test_user(&db, None).await;
+    let token = create_test_token(user, &auth.jwt_encoding_key).unwrap();
+    let first_level = create_test_level(&db).await;
+    let second_level = create_test_level(&db).await;
+
+    let mut ids = Vec::new();
+    for level in [first_level, second_level] {
+        let req = test::TestRequest::post()
+            .uri("/arepl/submissions/")
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .set_json(submission_body(level, "https://youtu.be/sharedvid01"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success(), "status is {}", resp.status());
+        let submission: Value = read_body_json(resp).await;
+        ids.push(submission["id"].as_str().unwrap().parse::<Uuid>().unwrap());
+    }
+    set_test_submission_status(&db, ids[0], SubmissionStatus::Accepted);
+
+    let req = test::TestRequest::get()
+        .uri(&videos_uri(
+            "https://m.youtube.com/watch?v=sharedvid01&t=1m",
+        ))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let req = test::TestRequest::get()
+        .uri(&videos_uri(
+            "https://m.youtube.com/watch?v=sharedvid01&t=1m",
+        ))
+        .insert_header(("Authorization", format!("Bearer {reviewer_token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: Value = read_body_json(resp).await;
+    assert_eq!(body["provider"], "YouTube");
+    assert_eq!(body["content_id"], "sharedvid01");
+    let uses = body["uses"].as_array().unwrap();
+    assert_eq!(uses.len(), 2);
+    let accepted = uses
+        .iter()
+        .find(|entry| entry["submission_id"] == json!(ids[0]))
+        .expect("Accepted submission should be listed");
+    assert!(!accepted["record_id"].is_null());
+    assert_eq!(accepted["submitted_by"]["id"], json!(user));
+    assert_eq!(accepted["level"]["id"], json!(first_level));
+
+    // the reviewer view of a submission lists the other uses of its video
+    let req = test::TestRequest::get()
+        .uri(&format!("/arepl/submissions/{}", ids[1]))
+        .insert_header(("Authorization", format!("Bearer {reviewer_token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: Value = read_body_json(resp).await;
+    let reuses = body["video_reuses"].as_array().unwrap();
+    assert_eq!(reuses.len(), 1);
+    assert_eq!(reuses[0]["submission_id"], json!(ids[0]));
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/arepl/submissions/{}", ids[1]))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: Value = read_body_json(resp).await;
+    assert!(body.get("video_reuses").is_none());
+
+    let req = test::TestRequest::get()
+        .uri(&videos_uri("https://example.com/video"))
+        .insert_header(("Authorization", format!("Bearer {reviewer_token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[actix_web::test]
+#[serial]
+async fn video_publish_date_checks() {
+    clear_oauth_env(OAuthProvider::Google);
+    let server = MockServer::start_async().await;
+    set_oauth_env(OAuthProvider::Google, &server.base_url());
+    mock_google_token_endpoint(&server, 3600, "test_access").await;
+    let old_mock =
+        mock_youtube_videos_endpoint(&server, "oldvideo001", "2009-10-25T06:57:33Z").await;
+    mock_youtube_videos_endpoint(&server, "futurevid01", "2099-01-01T00:00:00Z").await;
+    let google_auth = new_google_context()
+        .await
+        .expect("Failed to create Google OAuth context");
+    let providers_app_state = Arc::new(ProvidersAppState::new(
+        ProviderRegistry::new(vec![Arc::new(YouTubeProvider) as Arc<dyn Provider>]),
+        ProviderContext {
+            http: reqwest::Client::new(),
+            db: None,
+            discord_auth: None,
+            google_auth: Some(Arc::new(google_auth)),
+            patreon_auth: None,
+            twitch_auth: None,
+        },
+    ));
+    let (app, db, auth, _) = init_test_app_with_providers(providers_app_state).await;
+    seed_oauth_token(&db, OAuthProvider::Google, Some("refresh_a"));
+    let (moderator, _) = create_test_full_reviewer(&db).await;
+    let moderator_token = create_test_token(moderator, &auth.jwt_encoding_key).unwrap();
+    let (user, _) = create_test_user(&db, None).await;
+    let token = create_test_token(user, &auth.jwt_encoding_key).unwrap();
+    let old_level = create_test_level(&db).await;
+    let future_level = create_test_level(&db).await;
+
+    let mut ids = Vec::new();
+    for (level, video_url) in [
+        (old_level, "https://youtu.be/oldvideo001"),
+        (future_level, "https://youtu.be/futurevid01"),
+    ] {
+        let req = test::TestRequest::post()
+            .uri("/arepl/submissions/")
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .set_json(submission_body(level, video_url))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success(), "status is {}", resp.status());
+        let submission: Value = read_body_json(resp).await;
+        ids.push(submission["id"].as_str().unwrap().to_owned());
+    }
+
+    // storing the publish date is not an edit of the submission
+    let submission_id = ids[0].parse::<Uuid>().unwrap();
+    assert_eq!(test_submission_history_count(&db, submission_id), 1);
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/arepl/submissions/{}", ids[0]))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    let body: Value = read_body_json(resp).await;
+    assert_eq!(
+        body["video_published_at"]
+            .as_str()
+            .unwrap()
+            .parse::<DateTime<Utc>>()
+            .unwrap(),
+        "2009-10-25T06:57:33Z".parse::<DateTime<Utc>>().unwrap()
+    );
+    assert!(body.get("video_date_warnings").is_none());
+
+    for (id, kind) in [
+        (&ids[0], "BeforeLevelPlacement"),
+        (&ids[1], "AfterSubmission"),
+    ] {
+        let req = test::TestRequest::get()
+            .uri(&format!("/arepl/submissions/{id}"))
+            .insert_header(("Authorization", format!("Bearer {moderator_token}")))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        let body: Value = read_body_json(resp).await;
+        let warnings = body["video_date_warnings"].as_array().unwrap();
+        assert_eq!(warnings.len(), 1, "warnings are {warnings:?}");
+        assert_eq!(warnings[0]["kind"], kind);
+    }
+
+    // accepting reuses the stored publish date instead of asking the provider again
+    let req = test::TestRequest::patch()
+        .uri(&format!("/arepl/submissions/{}", ids[0]))
+        .insert_header(("Authorization", format!("Bearer {moderator_token}")))
+        .set_json(json!({ "status": "Accepted", "use_video_date": true }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    let expected = "2009-10-25T06:57:33Z".parse::<DateTime<Utc>>().unwrap();
+    let mut achieved_at = None;
+    for _ in 0..40 {
+        achieved_at = Some(get_test_record_for_level_and_user(&db, old_level, user).achieved_at);
+        if achieved_at == Some(expected) {
+            break;
+        }
+        sleep(Duration::from_millis(50)).await;
+    }
+    assert_eq!(achieved_at, Some(expected));
+    assert_eq!(old_mock.calls_async().await, 1);
+
+    clear_oauth_env(OAuthProvider::Google);
+}
@@ -0,0 +1,384 @@
+use crate::app_data::db::DbConnection;
+use crate::arepl::levels::ExtendedBaseLevel;
+use crate::clans::Clan;
+use crate::error_handler::ApiError;
+use crate::page_helper::{PageQuery, Paginated};
+use crate::schema::{
+    arepl::{levels, records, world_record_changes, world_records},
+    clan_members, clans, users,
+};
+use crate::users::{user_filter, ExtendedBaseUser};
+use chrono::{DateTime, Utc};
+use diesel::pg::Pg;
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use diesel::prelude::*;
+#[derive(Serialize, Deserialize, Queryable, Selectable, Debug, ToSchema)]
+#[diesel(table_name = world_records, check_for_backend(Pg))]
+pub struct WorldRecord {
+    /// Internal UUID of the level.
+    pub level_id: Uuid,
+    /// Internal UUID of the world record.
+    pub record_id: Uuid,
+    /// Internal UUID of the world record holder.
+    pub submitted_by: Uuid,
+    /// Completion time of the world record in milliseconds.
+    pub completion_time: i64,
+    /// Timestamp of when the world record was achieved.
+    pub achieved_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct ResolvedWorldRecord {
+    /// Level the world record is on.
+    pub level: ExtendedBaseLevel,
+    /// Internal UUID of the world record.
+    pub record_id: Uuid,
+    /// User holding the world record.
+    pub holder: ExtendedBaseUser,
+    /// Completion time of the world record in milliseconds.
+    pub completion_time: i64,
+    /// Timestamp of when the world record was achieved.
+    pub achieved_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, DbEnum, Clone, Copy, PartialEq)]
+#[ExistingTypePath = "crate::schema::arepl::sql_types::WorldRecordChangeKind"]
+#[DbValueStyle = "PascalCase"]
+pub enum WorldRecordChangeKind {
+    /// The first world record of a level.
+    Set,
+    /// A faster record took over the world record.
+    Beaten,
+    /// The world record stopped counting, the next fastest record took over if there is one.
+    Removed,
+}
+
+#[derive(Serialize, Deserialize, Queryable, Selectable, Debug, ToSchema)]
+#[diesel(table_name = world_record_changes, check_for_backend(Pg))]
+pub struct WorldRecordChange {
+    /// Internal UUID of the change.
+    pub id: Uuid,
+    /// Internal UUID of the level.
+    pub level_id: Uuid,
+    /// What happened to the world record.
+    pub kind: WorldRecordChangeKind,
+    /// Internal UUID of the new world record. Null if no record is left or it was deleted.
+    pub record_id: Option<Uuid>,
+    /// Completion time of the new world record in milliseconds, if any.
+    pub completion_time: Option<i64>,
+    /// Internal UUID of the previous world record, if any and still existing.
+    pub previous_record_id: Option<Uuid>,
+    /// Completion time of the previous world record in milliseconds, if any.
+    pub previous_completion_time: Option<i64>,
+    /// Timestamp of when the change was detected.
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct ResolvedWorldRecordChange {
+    /// Internal UUID of the change.
+    pub id: Uuid,
+    /// Level the world record is on.
+    pub level: ExtendedBaseLevel,
+    /// What happened to the world record.
+    pub kind: WorldRecordChangeKind,
+    /// User who holds the new world record, if any and the record still exists.
+    pub holder: Option<ExtendedBaseUser>,
+    /// Completion time of the new world record in milliseconds, if any.
+    pub completion_time: Option<i64>,
+    /// User who held the world record before, if any and the record still exists.
+    pub previous_holder: Option<ExtendedBaseUser>,
+    /// Completion time of the previous world record in milliseconds, if any.
+    pub previous_completion_time: Option<i64>,
+    /// How many milliseconds faster the new world record is, if it beat the previous one.
+    pub improvement: Option<i64>,
+    /// Timestamp of when the change was detected.
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct WorldRecordChangePage {
+    data: Vec<ResolvedWorldRecordChange>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct WorldRecordChangeQueryOptions {
+    pub level_filter: Option<Uuid>,
+    /// Only list changes where this user set or lost the world record.
+    pub user_filter: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct UserWorldRecordsEntry {
+    /// Rank of the user, sorted by count of world records held.
+    pub rank: i64,
+    /// This entry's user.
+    pub user: ExtendedBaseUser,
+    /// Count of world records the user currently holds.
+    pub world_records: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct CountryWorldRecordsEntry {
+    /// Rank of the country, sorted by count of world records held.
+    pub rank: i64,
+    /// The country. Uses the ISO 3166-1 numeric country code.
+    pub country: i32,
+    /// Count of world records the country's players currently hold.
+    pub world_records: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct ClanWorldRecordsEntry {
+    /// Rank of the clan, sorted by count of world records held.
+    pub rank: i64,
+    /// The clan.
+    pub clan: Clan,
+    /// Count of world records the clan's members currently hold.
+    pub world_records: i64,
+}
+
+impl WorldRecord {
+    pub fn find(conn: &mut DbConnection, level_id: Uuid) -> Result<ResolvedWorldRecord, ApiError> {
+        let (world_record, level, holder) = world_records::table
+            .filter(world_records::level_id.eq(level_id))
+            .inner_join(levels::table)
+            .inner_join(users::table)
+            .select((
+                WorldRecord::as_select(),
+                ExtendedBaseLevel::as_select(),
+                ExtendedBaseUser::as_select(),
+            ))
+            .first::<(WorldRecord, ExtendedBaseLevel, ExtendedBaseUser)>(conn)
+            .optional()?
+            .ok_or_else(|| ApiError::NotFound("This level has no world record"))?;
+
+        Ok(ResolvedWorldRecord::from_data(&world_record, level, holder))
+    }
+
+    pub fn find_all(conn: &mut DbConnection) -> Result<Vec<ResolvedWorldRecord>, ApiError> {
+        let world_records = world_records::table
+            .inner_join(levels::table)
+            .inner_join(users::table)
+            .order(levels::position.asc().nulls_last())
+            .select((
+                WorldRecord::as_select(),
+                ExtendedBaseLevel::as_select(),
+                ExtendedBaseUser::as_select(),
+            ))
+            .load::<(WorldRecord, ExtendedBaseLevel, ExtendedBaseUser)>(conn)?;
+
+        Ok(world_records
+            .into_iter()
+            .map(|(world_record, level, holder)| {
+                ResolvedWorldRecord::from_data(&world_record, level, holder)
+            })
+            .collect())
+    }
+}
+
+impl ResolvedWorldRecord {
+    fn from_data(
+        world_record: &WorldRecord,
+        level: ExtendedBaseLevel,
+        holder: ExtendedBaseUser,
+    ) -> Self {
+        Self {
+            level,
+            record_id: world_record.record_id,
+            holder,
+            completion_time: world_record.completion_time,
+            achieved_at: world_record.achieved_at,
+        }
+    }
+}
+
+impl ResolvedWorldRecordChange {
+    pub fn find_all<const D: i64>(
+        conn: &mut DbConnection,
+        page_query: PageQuery<D>,
+        options: &WorldRecordChangeQueryOptions,
+    ) -> Result<Paginated<WorldRecordChangePage>, ApiError> {
+        let build_filtered = || {
+            let mut q = world_record_changes::table.into_boxed::<Pg>();
+            if let Some(level) = options.level_filter {
+                q = q.filter(world_record_changes::level_id.eq(level));
+            }
+            if let Some(user) = &options.user_filter {
+                let user_records = || {
+                    records::table
+                        .filter(records::submitted_by.eq_any(user_filter(user).select(users::id)))
+                        .select(records::id)
+                };
+                q = q.filter(
+                    world_record_changes::record_id
+                        .assume_not_null()
+                        .eq_any(user_records())
+                        .or(world_record_changes::previous_record_id
+                            .assume_not_null()
+                            .eq_any(user_records())),
+                );
+            }
+            q
+        };
+
+        let total_count: i64 = build_filtered().count().get_result(conn)?;
+
+        let changes = build_filtered()
+            .inner_join(levels::table)
+            .order(world_record_changes::created_at.desc())
+            .limit(page_query.per_page())
+            .offset(page_query.offset())
+            .select((
+                WorldRecordChange::as_select(),
+                ExtendedBaseLevel::as_select(),
+            ))
+            .load::<(WorldRecordChange, ExtendedBaseLevel)>(conn)?;
+
+        let record_ids = changes
+            .iter()
+            .flat_map(|(change, _)| [change.record_id, change.previous_record_id])
+            .flatten()
+            .collect::<Vec<_>>();
+        let holders = records::table
+            .filter(records::id.eq_any(record_ids))
+            .inner_join(users::table)
+            .select((records::id, ExtendedBaseUser::as_select()))
+            .load::<(Uuid, ExtendedBaseUser)>(conn)?
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+        let holder = |record_id: Option<Uuid>| holders.get(&record_id?).cloned();
+
+        let data = changes
+            .into_iter()
+            .map(|(change, level)| Self {
+                id: change.id,
+                level,
+                kind: change.kind,
+                holder: holder(change.record_id),
+                completion_time: change.completion_time,
+                previous_holder: holder(change.previous_record_id),
+                previous_completion_time: change.previous_completion_time,
+                improvement: match (
+                    change.kind,
+                    change.completion_time,
+                    change.previous_completion_time,
+                ) {
+                    (WorldRecordChangeKind::Beaten, Some(time), Some(previous)) => {
+                        Some(previous - time)
+                    }
+                    _ => None,
+                },
+                created_at: change.created_at,
+            })
+            .collect();
+
+        Ok(Paginated::from_data(
+            page_query,
+            total_count,
+            WorldRecordChangePage { data },
+        ))
+    }
+}
+
+/// Ranks entries already sorted by count, entries with the same count share a rank.
+fn rank_by_count<T>(entries: Vec<(T, i64)>) -> Vec<(i64, T, i64)> {
+    let mut ranked = Vec::with_capacity(entries.len());
+    let mut rank = 0;
+    let mut previous_count = None;
+    for (position, (entry, count)) in (1..).zip(entries) {
+        if previous_count != Some(count) {
+            rank = position;
+            previous_count = Some(count);
+        }
+        ranked.push((rank, entry, count));
+    }
+    ranked
+}
+
+impl UserWorldRecordsEntry {
+    pub fn find_all(conn: &mut DbConnection) -> Result<Vec<Self>, ApiError> {
+        let entries = world_records::table
+            .inner_join(users::table)
+            .group_by(users::id)
+            .select((
+                ExtendedBaseUser::as_select(),
+                diesel::dsl::count(world_records::record_id),
+            ))
+            .order((
+                diesel::dsl::count(world_records::record_id).desc(),
+                users::global_name.asc(),
+            ))
+            .load::<(ExtendedBaseUser, i64)>(conn)?;
+
+        Ok(rank_by_count(entries)
+            .into_iter()
+            .map(|(rank, user, world_records)| Self {
+                rank,
+                user,
+                world_records,
+            })
+            .collect())
+    }
+}
+
+impl CountryWorldRecordsEntry {
+    pub fn find_all(conn: &mut DbConnection) -> Result<Vec<Self>, ApiError> {
+        let entries = world_records::table
+            .inner_join(users::table)
+            .filter(users::country.is_not_null())
+            .group_by(users::country)
+            .select((
+                users::country.assume_not_null(),
+                diesel::dsl::count(world_records::record_id),
+            ))
+            .order((
+                diesel::dsl::count(world_records::record_id).desc(),
+                users::country.asc(),
+            ))
+            .load::<(i32, i64)>(conn)?;
+
+        Ok(rank_by_count(entries)
+            .into_iter()
+            .map(|(rank, country, world_records)| Self {
+                rank,
+                country,
+                world_records,
+            })
+            .collect())
+    }
+}
+
+impl ClanWorldRecordsEntry {
+    pub fn find_all(conn: &mut DbConnection) -> Result<Vec<Self>, ApiError> {
+        let entries = world_records::table
+            .inner_join(
+                clan_members::table.on(clan_members::user_id.eq(world_records::submitted_by)),
+            )
+            .inner_join(clans::table.on(clans::id.eq(clan_members::clan_id)))
+            .group_by(clans::id)
+            .select((
+                Clan::as_select(),
+                diesel::dsl::count(world_records::record_id),
+            ))
+            .order((
+                diesel::dsl::count(world_records::record_id).desc(),
+                clans::global_name.asc(),
+            ))
+            .load::<(Clan, i64)>(conn)?;
+
+        Ok(rank_by_count(entries)
+            .into_iter()
+            .map(|(rank, clan, world_records)| Self {
+                rank,
+                clan,
+                world_records,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,180 @@
+use crate::app_data::db::DbAppState;
+use crate::arepl::levels::id_resolver::resolve_level_id;
+use crate::arepl::world_records::{
+    ClanWorldRecordsEntry, CountryWorldRecordsEntry, ResolvedWorldRecord,
+    ResolvedWorldRecordChange, UserWorldRecordsEntry, WorldRecord, WorldRecordChangeKind,
+    WorldRecordChangePage, WorldRecordChangeQueryOptions,
+};
+use crate::cache_control::CacheController;
+use crate::error_handler::ApiError;
+use crate::page_helper::{PageQuery, Paginated};
+use actix_web::{get, web, HttpResponse};
+use std::sync::Arc;
+use utoipa::OpenApi;
+
+#[utoipa::path(
+    get,
+    summary = "List world records",
+    description = "List the current world record of every level that has one, in list order",
+    tag = "AREDL (P) - World Records",
+    responses(
+        (status = 200, body = [ResolvedWorldRecord])
+    ),
+)]
+#[get("", wrap = "CacheController::public_with_max_age(900)")]
+async fn find_all(db: web::Data<Arc<DbAppState>>) -> Result<HttpResponse, ApiError> {
+    let world_records = web::block(move || WorldRecord::find_all(&mut db.connection()?)).await??;
+    Ok(HttpResponse::Ok().json(world_records))
+}
+
+#[utoipa::path(
+    get,
+    summary = "World record changelog",
+    description = "List every time a world record was set, newest first, with the time it beat",
+    tag = "AREDL (P) - World Records",
+    params(
+        ("page" = Option<i64>, Query, description = "The page of the changelog to fetch"),
+        ("per_page" = Option<i64>, Query, description = "The number of entries to fetch per page"),
+        ("level_filter" = Option<Uuid>, Query, description = "Only list changes on this level"),
+        ("user_filter" = Option<String>, Query, description = "Only list changes where this user set or lost the world record"),
+    ),
+    responses(
+        (status = 200, body = Paginated<WorldRecordChangePage>)
+    ),
+)]
+#[get("/changelog", wrap = "CacheController::public_with_max_age(900)")]
+async fn changelog(
+    db: web::Data<Arc<DbAppState>>,
+    page_query: web::Query<PageQuery<20>>,
+    options: web::Query<WorldRecordChangeQueryOptions>,
+) -> Result<HttpResponse, ApiError> {
+    let changes = web::block(move || {
+        ResolvedWorldRecordChange::find_all(
+            &mut db.connection()?,
+            page_query.into_inner(),
+            &options.into_inner(),
+        )
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(changes))
+}
+
+#[utoipa::path(
+    get,
+    summary = "World record leaderboard (users)",
+    description = "Rank players by the number of world records they currently hold",
+    tag = "AREDL (P) - World Records",
+    responses(
+        (status = 200, body = [UserWorldRecordsEntry])
+    ),
+)]
+#[get(
+    "/leaderboard/users",
+    wrap = "CacheController::public_with_max_age(900)"
+)]
+async fn leaderboard_users(db: web::Data<Arc<DbAppState>>) -> Result<HttpResponse, ApiError> {
+    let entries =
+        web::block(move || UserWorldRecordsEntry::find_all(&mut db.connection()?)).await??;
+    Ok(HttpResponse::Ok().json(entries))
+}
+
+#[utoipa::path(
+    get,
+    summary = "World record leaderboard (countries)",
+    description = "Rank countries by the number of world records their players currently hold",
+    tag = "AREDL (P) - World Records",
+    responses(
+        (status = 200, body = [CountryWorldRecordsEntry])
+    ),
+)]
+#[get(
+    "/leaderboard/countries",
+    wrap = "CacheController::public_with_max_age(900)"
+)]
+async fn leaderboard_countries(db: web::Data<Arc<DbAppState>>) -> Result<HttpResponse, ApiError> {
+    let entries =
+        web::block(move || CountryWorldRecordsEntry::find_all(&mut db.connection()?)).await??;
+    Ok(HttpResponse::Ok().json(entries))
+}
+
+#[utoipa::path(
+    get,
+    summary = "World record leaderboard (clans)",
+    description = "Rank clans by the number of world records their members currently hold",
+    tag = "AREDL (P) - World Records",
+    responses(
+        (status = 200, body = [ClanWorldRecordsEntry])
+    ),
+)]
+#[get(
+    "/leaderboard/clans",
+    wrap = "CacheController::public_with_max_age(900)"
+)]
+async fn leaderboard_clans(db: web::Data<Arc<DbAppState>>) -> Result<HttpResponse, ApiError> {
+    let entries =
+        web::block(move || ClanWorldRecordsEntry::find_all(&mut db.connection()?)).await??;
+    Ok(HttpResponse::Ok().json(entries))
+}
+
+#[utoipa::path(
+    get,
+    summary = "Get a level's world record",
+    description = "Get the current world record of a level",
+    tag = "AREDL (P) - World Records",
+    params(
+        ("level_id" = String, description = "Level ID (Can be internal UUID, or GD ID. For the latter, add a _2p suffix to target the 2p version)"),
+    ),
+    responses(
+        (status = 200, body = ResolvedWorldRecord)
+    ),
+)]
+#[get("/{level_id}", wrap = "CacheController::public_with_max_age(900)")]
+async fn find(
+    db: web::Data<Arc<DbAppState>>,
+    level_id: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let world_record = web::block(move || {
+        let conn = &mut db.connection()?;
+        let level_id = resolve_level_id(conn, level_id.into_inner().as_str())?;
+        WorldRecord::find(conn, level_id)
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(world_record))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    tags(
+        (name = "AREDL (P) - World Records", description = "Fastest times on AREDL Platformer levels"),
+    ),
+    components(schemas(
+        ResolvedWorldRecord,
+        ResolvedWorldRecordChange,
+        WorldRecordChangeKind,
+        WorldRecordChangePage,
+        UserWorldRecordsEntry,
+        CountryWorldRecordsEntry,
+        ClanWorldRecordsEntry,
+    )),
+    paths(
+        find_all,
+        changelog,
+        leaderboard_users,
+        leaderboard_countries,
+        leaderboard_clans,
+        find
+    )
+)]
+pub struct ApiDoc;
+
+pub fn init_routes(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/world-records")
+            .service(find_all)
+            .service(changelog)
+            .service(leaderboard_users)
+            .service(leaderboard_countries)
+            .service(leaderboard_clans)
+            .service(find),
+    );
+}
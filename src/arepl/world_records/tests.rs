@@ -0,0 +1,186 @@
+#[cfg(test)]
+use {
+    crate::{
+        arepl::{
+            levels::{
+                test_utils::{create_test_level, set_test_level_status},
+                LevelStatus,
+            },
+            records::test_utils::{create_test_record, create_test_record_with_completion_time},
+        },
+        auth::{create_test_token, Permission},
+        clans::test_utils::{create_test_clan, create_test_clan_member},
+        test_utils::*,
+        users::{
+            badges::UserBadge,
+            test_utils::{create_test_user, set_test_user_ban_level, set_test_user_country},
+        },
+    },
+    actix_http::Request,
+    actix_web::{
+        body::BoxBody,
+        dev::{Service, ServiceResponse},
+        test::{self, read_body_json},
+        Error,
+    },
+    uuid::Uuid,
+};
+
+#[cfg(test)]
+async fn get_test_changelog(
+    app: &impl Service<Request, Response = ServiceResponse<BoxBody>, Error = Error>,
+    level_id: Uuid,
+) -> Vec<serde_json::Value> {
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "/arepl/world-records/changelog?level_filter={level_id}"
+        ))
+        .to_request();
+    let res = test::call_service(app, req).await;
+    assert!(res.status().is_success(), "status is {}", res.status());
+    let body: serde_json::Value = read_body_json(res).await;
+    body["data"].as_array().unwrap().clone()
+}
+
+#[actix_web::test]
+async fn world_record_changelog_tracks_beaten_times() {
+    let (app, db, _, _) = init_test_app().await;
+    let level_id = create_test_level(&db).await;
+    let (first, _) = create_test_user(&db, None).await;
+    let (second, _) = create_test_user(&db, None).await;
+
+    create_test_record_with_completion_time(&db, first, level_id, 60_000).await;
+    let second_record =
+        create_test_record_with_completion_time(&db, second, level_id, 45_000).await;
+    // slower records don't change the world record
+    let (slower, _) = create_test_user(&db, None).await;
+    create_test_record_with_completion_time(&db, slower, level_id, 50_000).await;
+
+    let changes = get_test_changelog(&app, level_id).await;
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0]["kind"], "Beaten");
+    assert_eq!(changes[0]["holder"]["id"], second.to_string());
+    assert_eq!(changes[0]["previous_holder"]["id"], first.to_string());
+    assert_eq!(changes[0]["improvement"], 15_000);
+    assert_eq!(changes[1]["kind"], "Set");
+    assert!(changes[1]["previous_holder"].is_null());
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/arepl/world-records/{level_id}"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert!(res.status().is_success(), "status is {}", res.status());
+    let body: serde_json::Value = read_body_json(res).await;
+    assert_eq!(body["record_id"], second_record.to_string());
+    assert_eq!(body["completion_time"], 45_000);
+}
+
+#[actix_web::test]
+async fn world_record_changelog_logs_removals() {
+    let (app, db, auth, _) = init_test_app().await;
+    let level_id = create_test_level(&db).await;
+    let (first, _) = create_test_user(&db, None).await;
+    let (second, _) = create_test_user(&db, None).await;
+    let (moderator, _) = create_test_user(&db, Some(Permission::RecordModify)).await;
+    let token = create_test_token(moderator, &auth.jwt_encoding_key).unwrap();
+
+    create_test_record_with_completion_time(&db, first, level_id, 60_000).await;
+    let second_record =
+        create_test_record_with_completion_time(&db, second, level_id, 45_000).await;
+
+    // a banned holder loses the world record without it being beaten
+    set_test_user_ban_level(&db, second, 3).await;
+    let changes = get_test_changelog(&app, level_id).await;
+    assert_eq!(changes.len(), 3);
+    assert_eq!(changes[0]["kind"], "Removed");
+    assert_eq!(changes[0]["holder"]["id"], first.to_string());
+    assert_eq!(changes[0]["previous_holder"]["id"], second.to_string());
+    assert!(changes[0]["improvement"].is_null());
+
+    set_test_user_ban_level(&db, second, 0).await;
+    let changes = get_test_changelog(&app, level_id).await;
+    assert_eq!(changes.len(), 4);
+    assert_eq!(changes[0]["kind"], "Beaten");
+    assert_eq!(changes[0]["holder"]["id"], second.to_string());
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/arepl/records/{second_record}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert!(res.status().is_success(), "status is {}", res.status());
+
+    let changes = get_test_changelog(&app, level_id).await;
+    assert_eq!(changes.len(), 5);
+    assert_eq!(changes[0]["kind"], "Removed");
+    assert_eq!(changes[0]["holder"]["id"], first.to_string());
+    assert!(changes[0]["previous_holder"].is_null());
+    assert_eq!(changes[0]["previous_completion_time"], 45_000);
+    assert!(changes[0]["improvement"].is_null());
+
+    // removed levels have no world record
+    set_test_level_status(&db, level_id, LevelStatus::Removed, None).await;
+    let changes = get_test_changelog(&app, level_id).await;
+    assert_eq!(changes.len(), 6);
+    assert_eq!(changes[0]["kind"], "Removed");
+    assert!(changes[0]["holder"].is_null());
+    assert_eq!(changes[0]["previous_holder"]["id"], first.to_string());
+}
+
+#[actix_web::test]
+async fn banned_players_do_not_hold_the_fastest_time() {
+    let (_, db, _, _) = init_test_app().await;
+    let level_id = create_test_level(&db).await;
+    let (holder, _) = create_test_user(&db, None).await;
+    let (banned, _) = create_test_user(&db, None).await;
+
+    create_test_record_with_completion_time(&db, holder, level_id, 60_000).await;
+    create_test_record_with_completion_time(&db, banned, level_id, 45_000).await;
+    set_test_user_ban_level(&db, banned, 3).await;
+
+    UserBadge::update_user_badges(&mut db.connection().unwrap(), holder).unwrap();
+    let badges = UserBadge::find_all(&mut db.connection().unwrap(), holder).unwrap();
+    assert!(badges
+        .iter()
+        .any(|badge| badge.badge_code == "platformer.fastest_time"));
+}
+
+#[actix_web::test]
+async fn world_record_leaderboards() {
+    let (app, db, _, _) = init_test_app().await;
+    let (holder, _) = create_test_user(&db, None).await;
+    set_test_user_country(&db, holder, Some(826)).await;
+    let clan_id = create_test_clan(&db).await;
+    create_test_clan_member(&db, clan_id, holder, 0).await;
+
+    for _ in 0..3 {
+        let level_id = create_test_level(&db).await;
+        create_test_record(&db, holder, level_id).await;
+    }
+
+    for (path, key, expected) in [
+        ("users", "user", serde_json::json!(holder.to_string())),
+        ("countries", "country", serde_json::json!(826)),
+        ("clans", "clan", serde_json::json!(clan_id.to_string())),
+    ] {
+        let req = test::TestRequest::get()
+            .uri(&format!("/arepl/world-records/leaderboard/{path}"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert!(res.status().is_success(), "status is {}", res.status());
+        let body: serde_json::Value = read_body_json(res).await;
+        let entry = body
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|entry| entry[key].get("id").unwrap_or(&entry[key]) == &expected)
+            .unwrap_or_else(|| panic!("{path} leaderboard is missing the holder"));
+        assert_eq!(entry["world_records"], 3);
+    }
+
+    UserBadge::update_user_badges(&mut db.connection().unwrap(), holder).unwrap();
+    let badges = UserBadge::find_all(&mut db.connection().unwrap(), holder).unwrap();
+    assert!(badges
+        .iter()
+        .any(|badge| badge.badge_code == "platformer.world_records.3"));
+}
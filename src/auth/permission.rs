@@ -69,6 +69,18 @@ pub enum Permission {
     ExternalConnectionsManage,
     /// Allows editing weekly, monthly, event and bounty levels
     BountyManage,
+    /// Allows registering and editing outgoing webhooks
+    WebhookManage,
+    /// Allows editing the catalogue of reasons reviewers select when denying submissions
+    SubmissionReasonManage,
+    /// Allows accepting, denying, unclaiming, locking or re-prioritising many submissions at once
+    SubmissionBulkEdit,
+    /// Allows editing which conflict of interest rules apply to reviewers
+    ReviewerConflictManage,
+    /// Allows editing the rules deciding what submissions must include
+    SubmissionRequirementManage,
+    /// Allows editing the external lists records are imported from, and seeing every sync
+    ExternalSyncManage,
 }
 
 pub fn get_highest_role_privilege_level(conn: &mut DbConnection, user_id: Uuid) -> i32 {
@@ -4,7 +4,7 @@ use crate::app_data::db::DbConnection;
 use crate::clans::{Clan, ClanInvite, ClanMember};
 use crate::error_handler::ApiError;
 use crate::schema::{clan_invites, clan_members, clans, users};
-use crate::users::me::notifications::{Notification, NotificationType};
+use crate::users::me::notifications::{Notification, NotificationPayload, NotificationType};
 use crate::users::ExtendedBaseUser;
 use chrono::{DateTime, Utc};
 use diesel::pg::Pg;
@@ -223,10 +223,11 @@ impl ClanMember {
 }
 
 impl ClanInvite {
+    /// Creates the invite, returning it along with the notification sent to the invited user.
     pub fn create(
         conn: &mut DbConnection,
         invite: ClanInviteCreate,
-    ) -> Result<ClanInvite, ApiError> {
+    ) -> Result<(ClanInvite, Option<Notification>), ApiError> {
         let user_in_clan = clan_members::table
             .filter(clan_members::user_id.eq(invite.user_id))
             .select(clan_members::user_id)
@@ -245,18 +246,25 @@ impl ClanInvite {
             .filter(clans::id.eq(invite.clan_id))
             .first::<Clan>(conn)?;
 
-        let result = conn.transaction::<_, ApiError, _>(|connection| {
+        conn.transaction::<_, ApiError, _>(|connection| {
             let invite = insert_into(clan_invites::table)
                 .values(invite)
                 .returning(ClanInvite::as_select())
                 .get_result(connection)?;
 
             let content = format!("{} invited you to join {}", invited_by, clan.global_name);
-            Notification::create(connection, invite.user_id, content, NotificationType::Info)?;
-
-            Ok(invite)
-        })?;
-
-        Ok(result)
+            let notification = Notification::create(
+                connection,
+                invite.user_id,
+                content,
+                NotificationType::Info,
+                Some(NotificationPayload::ClanInvite {
+                    invite_id: invite.id,
+                    clan_id: invite.clan_id,
+                }),
+            )?;
+
+            Ok((invite, notification))
+        })
     }
 }
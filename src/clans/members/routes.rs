@@ -7,8 +7,10 @@ use crate::clans::{
     Clan, ClanCreate, ClanInvite, ClanListQueryOptions, ClanMember, ClanPage, ClanUpdate,
 };
 use crate::error_handler::ApiError;
+use crate::users::me::notifications::Notification;
 use actix_web::{delete, get, patch, post, web, HttpResponse};
 use std::sync::Arc;
+use tokio::sync::broadcast;
 use tracing_actix_web::RootSpan;
 use utoipa::OpenApi;
 use uuid::Uuid;
@@ -171,23 +173,23 @@ async fn invite(
     user: web::Json<ClanMemberInvite>,
     authenticated: Authenticated,
     root_span: RootSpan,
+    inbox_tx: web::Data<broadcast::Sender<Notification>>,
 ) -> Result<HttpResponse, ApiError> {
     root_span.record("body", tracing::field::debug(&user));
-    let result = web::block(move || {
+    let (result, notification) = web::block(move || {
         let conn = &mut db.connection()?;
         authenticated.ensure_has_clan_permission(conn, *clan_id, 1)?;
-        let invite = ClanInvite::create(
+        ClanInvite::create(
             conn,
             ClanInviteCreate {
                 clan_id: *clan_id,
                 user_id: user.user_id,
                 invited_by: authenticated.user_id,
             },
-        )?;
-
-        Ok::<ClanInvite, ApiError>(invite)
+        )
     })
     .await??;
+    Notification::send_live(&inbox_tx, notification);
     Ok(HttpResponse::Ok().json(result))
 }
 
@@ -1,6 +1,7 @@
 use crate::{
-    aredl, arepl, auth, clans, get_optional_secret, health, notifications, roles, shifts, users,
-    utils,
+    aredl, arepl, auth, clans, external_sync, get_optional_secret, health, notifications,
+    reviewer_conflicts, roles, shifts, submission_reasons, submission_requirements, users, utils,
+    webhooks,
 };
 use serde_json::json;
 use utoipa::openapi::extensions::Extensions;
@@ -41,6 +42,11 @@ You can find below each category of endpoints and their description:
 | **Clans - Members** | Endpoints for fetching, inviting and managing members of a clan |
 | **Shifts** | Staff endpoints to fetch and manage staff shifts for reviewing records |
 | **Notifications** | Endpoints for opening a web socket to receive real time data from the API |
+| **Webhooks** | Staff endpoints to register and manage HTTPS endpoints receiving the same events as the notifications websocket |
+| **Submission Reasons** | Endpoints to fetch and manage the reasons reviewers select when denying a submission |
+| **Reviewer Conflicts** | Endpoints to manage the conflict of interest rules applied when reviewing submissions |
+| **Submission Requirements** | Endpoints to fetch and manage the rules deciding what a submission must include |
+| **External Sync** | Endpoints to import records from other lists, and to configure the lists records are imported from |
 | **Health** | Endpoints for checking whether the API is online or not |
 
 In addition to that, endpoints are also categorized by the type of authentication they require:
@@ -130,6 +136,28 @@ Recognized providers include all providers listed above plus:
     - `https://mega.nz/#!<id>!<key>`
     - Normalized to: `https://mega.nz/file/<id>#<key>`
 
+- **Streamable** (hosts: `streamable.com`, `www.streamable.com`)
+    - `https://streamable.com/<id>`
+    - `https://streamable.com/e/<id>`
+    - `https://streamable.com/o/<id>`
+    - Normalized to: `https://streamable.com/<id>`
+
+- **Dropbox** (hosts: `dropbox.com`, `www.dropbox.com`, `dl.dropboxusercontent.com`)
+    - `https://www.dropbox.com/s/<id>/<name>`
+    - `https://www.dropbox.com/scl/fi/<id>/<name>?rlkey=<key>`
+    - Normalized to: `https://www.dropbox.com/s/<id>/<name>` or `https://www.dropbox.com/scl/fi/<id>/<name>?rlkey=<key>`
+
+- **OneDrive** (hosts: `1drv.ms`, `onedrive.live.com`)
+    - `https://1drv.ms/<type>/<token>`
+    - `https://onedrive.live.com/?resid=<id>&authkey=<key>`
+    - `https://onedrive.live.com/redir?resid=<id>&authkey=<key>`
+    - `https://onedrive.live.com/embed?resid=<id>&authkey=<key>`
+    - Normalized to: `https://1drv.ms/<type>/<token>` or `https://onedrive.live.com/redir?resid=<id>&authkey=<key>`
+
+- **Direct file** (any other host, HTTPS only)
+    - `https://<host>/<path>.<ext>` where `<ext>` is one of `mp4`, `mkv`, `mov`, `webm`, `avi`, `flv`, `m4v`, `ts`
+    - Normalized to: the same URL without its fragment
+
 ## Privilege level and Permissions
 Staff endpoints require specific permissions to be accessed.
 
@@ -160,6 +188,11 @@ Permissions are explicit grants on roles. A role can inherit grants from another
 		(path = "/health", api = health::ApiDoc),
         (path = "/shifts", api=shifts::ApiDoc),
         (path = "/utils", api=utils::ApiDoc),
+        (path = "/webhooks", api=webhooks::ApiDoc),
+        (path = "/submission-reasons", api=submission_reasons::ApiDoc),
+        (path = "/reviewer-conflicts", api=reviewer_conflicts::ApiDoc),
+        (path = "/submission-requirements", api=submission_requirements::ApiDoc),
+        (path = "/external-sources", api=external_sync::ApiDoc),
 	)
 )]
 struct MainApiDoc;
@@ -0,0 +1,279 @@
+use crate::{
+    error_handler::ApiError,
+    external_sync::{ExternalImporterKind, ExternalSource},
+};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+/// How completion times are written by an external list.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExternalTimeFormat {
+    /// A number of milliseconds, e.g. `104405100`.
+    #[default]
+    Milliseconds,
+    /// A `hours:minutes:seconds.fraction` string, e.g. `29:00:05.100`.
+    Formatted,
+}
+
+/// Where each field of a record is found in the responses of an external list.
+/// Fields are located with JSON pointers (RFC 6901), e.g. `/level/level_id`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct ExternalFieldMapping {
+    /// Pointer to the array of records in a player response.
+    pub records: String,
+    /// Pointer to the Geometry Dash ID of the level, within a record.
+    pub level_id: String,
+    /// Pointer to whether the record is for the two player version of the level. When not set, records are matched on the level ID alone.
+    pub two_player: Option<String>,
+    /// Pointer to the completion time, within a record. Required for platformer lists.
+    pub completion_time: Option<String>,
+    /// How the completion time is written.
+    #[serde(default)]
+    pub completion_time_format: ExternalTimeFormat,
+    /// Pointer to the completion video, within a record.
+    pub video_url: String,
+    /// Template building the video URL, `{}` being replaced by the mapped value. The value is used as is when not set.
+    pub video_url_template: Option<String>,
+    /// Pointer to whether the record was completed on mobile. Defaults to `false` when not set.
+    pub mobile: Option<String>,
+}
+
+/// A completion listed by an external list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalRecord {
+    /// Geometry Dash ID of the level.
+    pub level_id: i32,
+    /// Whether the record is for the two player version of the level, if the source says.
+    pub two_player: Option<bool>,
+    /// Completion time in milliseconds, if the source lists one.
+    pub completion_time: Option<i64>,
+    pub video_url: String,
+    pub mobile: bool,
+}
+
+/// Reads the records of a player from an external list.
+pub trait ExternalImporter: Sync {
+    /// URL listing the records of a player.
+    fn player_url(&self, source: &ExternalSource, external_id: &str) -> String {
+        format!("{}/{external_id}", source.base_url.trim_end_matches('/'))
+    }
+
+    /// Extracts the records of a player from a response of the list.
+    fn parse_player(
+        &self,
+        mapping: &ExternalFieldMapping,
+        external_id: &str,
+        status: StatusCode,
+        body: &Value,
+    ) -> Result<Vec<ExternalRecord>, ApiError>;
+}
+
+/// Importer for lists answering with plain JSON, relying on the HTTP status for errors.
+pub struct JsonImporter;
+
+impl ExternalImporter for JsonImporter {
+    fn parse_player(
+        &self,
+        mapping: &ExternalFieldMapping,
+        external_id: &str,
+        status: StatusCode,
+        body: &Value,
+    ) -> Result<Vec<ExternalRecord>, ApiError> {
+        if status == StatusCode::NOT_FOUND {
+            return Err(ApiError::NotFound(format!(
+                "Player {external_id} not found on this list"
+            )));
+        }
+        if !status.is_success() {
+            return Err(ApiError::BadGateway(format!(
+                "The list responded with status {status}"
+            )));
+        }
+        mapping.extract(body)
+    }
+}
+
+/// Importer for [Pemonlist](https://pemonlist.com), which reports errors in the response body.
+pub struct PemonlistImporter;
+
+#[derive(Debug, Deserialize)]
+struct PemonlistError {
+    code: String,
+    error: bool,
+}
+
+impl ExternalImporter for PemonlistImporter {
+    fn parse_player(
+        &self,
+        mapping: &ExternalFieldMapping,
+        external_id: &str,
+        status: StatusCode,
+        body: &Value,
+    ) -> Result<Vec<ExternalRecord>, ApiError> {
+        if let Ok(err) = PemonlistError::deserialize(body) {
+            if err.error && err.code == "bad_user" {
+                return Err(ApiError::NotFound(format!(
+                    "Player {external_id} not found on pemonlist"
+                )));
+            }
+            return Err(ApiError::BadGateway(format!(
+                "Pemonlist responded with an error: {}",
+                err.code
+            )));
+        }
+        if !status.is_success() {
+            return Err(ApiError::BadGateway(format!(
+                "Pemonlist responded with status {status}"
+            )));
+        }
+        mapping.extract(body)
+    }
+}
+
+impl ExternalImporterKind {
+    pub fn importer(self) -> &'static dyn ExternalImporter {
+        match self {
+            Self::Pemonlist => &PemonlistImporter,
+            Self::Json => &JsonImporter,
+        }
+    }
+}
+
+impl ExternalFieldMapping {
+    pub fn extract(&self, body: &Value) -> Result<Vec<ExternalRecord>, ApiError> {
+        body.pointer(&self.records)
+            .and_then(Value::as_array)
+            .ok_or_else(|| {
+                ApiError::BadGateway(format!(
+                    "The response has no list of records at {}",
+                    self.records
+                ))
+            })?
+            .iter()
+            .map(|record| self.extract_record(record))
+            .collect()
+    }
+
+    fn extract_record(&self, record: &Value) -> Result<ExternalRecord, ApiError> {
+        let level_id = field_i64(record, &self.level_id)?;
+        let level_id = i32::try_from(level_id).map_err(|error| {
+            ApiError::BadGateway(format!("Invalid level ID {level_id}: {error}"))
+        })?;
+
+        let completion_time = self
+            .completion_time
+            .as_deref()
+            .map(|pointer| match self.completion_time_format {
+                ExternalTimeFormat::Milliseconds => field_i64(record, pointer),
+                ExternalTimeFormat::Formatted => parse_formatted_ms(field_str(record, pointer)?),
+            })
+            .transpose()?;
+
+        let video = field_str(record, &self.video_url)?;
+        let video_url = match &self.video_url_template {
+            Some(template) => template.replace("{}", video),
+            None => video.to_owned(),
+        };
+
+        Ok(ExternalRecord {
+            level_id,
+            two_player: self
+                .two_player
+                .as_deref()
+                .map(|pointer| optional_field_bool(record, Some(pointer)))
+                .transpose()?,
+            completion_time,
+            video_url,
+            mobile: optional_field_bool(record, self.mobile.as_deref())?,
+        })
+    }
+}
+
+fn field<'a>(record: &'a Value, pointer: &str) -> Result<&'a Value, ApiError> {
+    record
+        .pointer(pointer)
+        .ok_or_else(|| ApiError::BadGateway(format!("A record has no value at {pointer}")))
+}
+
+fn field_i64(record: &Value, pointer: &str) -> Result<i64, ApiError> {
+    let value = field(record, pointer)?;
+    value
+        .as_i64()
+        .or_else(|| value.as_str()?.parse().ok())
+        .ok_or_else(|| ApiError::BadGateway(format!("The value at {pointer} is not an integer")))
+}
+
+fn field_str<'a>(record: &'a Value, pointer: &str) -> Result<&'a str, ApiError> {
+    field(record, pointer)?
+        .as_str()
+        .ok_or_else(|| ApiError::BadGateway(format!("The value at {pointer} is not a string")))
+}
+
+fn optional_field_bool(record: &Value, pointer: Option<&str>) -> Result<bool, ApiError> {
+    let Some(pointer) = pointer else {
+        return Ok(false);
+    };
+    field(record, pointer)?
+        .as_bool()
+        .ok_or_else(|| ApiError::BadGateway(format!("The value at {pointer} is not a boolean")))
+}
+
+fn parse_formatted_ms(s: &str) -> Result<i64, ApiError> {
+    let (hms, millis) = s.split_once('.').unwrap_or((s, "0"));
+
+    let mut parts = hms.split(':');
+    let hours = parts
+        .next()
+        .ok_or_else(|| ApiError::BadGateway("Malformed hour timestamp"))?;
+    let minutes = parts
+        .next()
+        .ok_or_else(|| ApiError::BadGateway("Malformed minute timestamp"))?;
+    let seconds = parts
+        .next()
+        .ok_or_else(|| ApiError::BadGateway("Malformed second timestamp"))?;
+    if parts.next().is_some() {
+        return Err(ApiError::BadGateway("Malformed formatted_time"));
+    }
+
+    let hours: i64 = hours
+        .parse()
+        .map_err(|e| ApiError::BadGateway(format!("Failed to parse hours: {e}")))?;
+    let minutes: i64 = minutes
+        .parse()
+        .map_err(|e| ApiError::BadGateway(format!("Failed to parse minutes: {e}")))?;
+    let seconds: i64 = seconds
+        .parse()
+        .map_err(|e| ApiError::BadGateway(format!("Failed to parse seconds: {e}")))?;
+
+    if hours < 0 || !(0..60).contains(&minutes) || !(0..60).contains(&seconds) {
+        return Err(ApiError::BadGateway(
+            "Malformed formatted_time (out of range)",
+        ));
+    }
+
+    if !millis.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(ApiError::BadGateway(
+            "Malformed formatted_time (fraction is not a number)",
+        ));
+    }
+
+    // normalize fraction to milliseconds
+    let milliseconds = {
+        let mut millis = millis.to_owned();
+        if millis.len() > 3 {
+            millis.truncate(3);
+        } else {
+            while millis.len() < 3 {
+                millis.push('0');
+            }
+        }
+        millis
+            .parse::<i64>()
+            .map_err(|e| ApiError::BadGateway(format!("Failed to parse milliseconds: {e}")))?
+    };
+
+    Ok(hours * 3_600_000 + minutes * 60_000 + seconds * 1_000 + milliseconds)
+}
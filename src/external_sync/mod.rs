@@ -0,0 +1,15 @@
+mod importers;
+mod model;
+mod routes;
+mod subscriptions;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(test)]
+pub mod test_utils;
+
+pub use importers::*;
+pub use model::*;
+pub use routes::{init_routes, ApiDoc};
+pub use subscriptions::*;
@@ -0,0 +1,664 @@
+use crate::{
+    app_data::db::{DbAppState, DbConnection},
+    aredl, arepl,
+    error_handler::ApiError,
+    external_sync::{ExternalFieldMapping, ExternalRecord},
+    page_helper::{PageQuery, Paginated},
+    providers::ProvidersAppState,
+    schema::{external_sources, external_sync_logs, users},
+};
+use actix_web::web;
+use chrono::{DateTime, Utc};
+use diesel::{pg::Pg, AsChangeset, Queryable, Selectable};
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use diesel::prelude::*;
+
+/// How long a source may take to answer before the sync is given up.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(20);
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema, DbEnum)]
+#[ExistingTypePath = "crate::schema::sql_types::ExternalImporter"]
+#[DbValueStyle = "snake_case"]
+#[serde(rename_all = "lowercase")]
+pub enum ExternalImporterKind {
+    /// Pemonlist, reporting errors in the response body.
+    Pemonlist,
+    /// Any list answering with JSON, reporting errors through HTTP status codes.
+    Json,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema, DbEnum)]
+#[ExistingTypePath = "crate::schema::sql_types::ExternalSyncList"]
+#[DbValueStyle = "snake_case"]
+#[serde(rename_all = "lowercase")]
+pub enum ExternalSyncList {
+    Aredl,
+    Arepl,
+}
+
+#[derive(Serialize, Deserialize, Selectable, Queryable, Debug, Clone, ToSchema)]
+#[diesel(table_name = external_sources, check_for_backend(Pg))]
+pub struct ExternalSource {
+    /// Internal UUID of the source.
+    pub id: Uuid,
+    /// Unique name of the source, e.g. `pemonlist`.
+    pub name: String,
+    /// How responses of the source are read.
+    pub importer: ExternalImporterKind,
+    /// Base URL of the source. The player's Discord ID is appended to it to list their records.
+    pub base_url: String,
+    /// Where each field of a record is found in the responses of the source.
+    #[schema(value_type = ExternalFieldMapping)]
+    pub field_mapping: serde_json::Value,
+    /// Whether imported records are accepted directly, or queued as pending submissions.
+    pub auto_accept: bool,
+    /// The list imported records are submitted to.
+    pub target_list: ExternalSyncList,
+    /// Whether players can currently sync with this source.
+    pub enabled: bool,
+    /// Timestamp of when this source was created.
+    pub created_at: DateTime<Utc>,
+    /// Timestamp of when this source was last updated.
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct ExternalSourceCreate {
+    /// Unique name of the source.
+    pub name: String,
+    /// How responses of the source are read.
+    pub importer: ExternalImporterKind,
+    /// Base URL of the source.
+    pub base_url: String,
+    /// Where each field of a record is found in the responses of the source.
+    pub field_mapping: ExternalFieldMapping,
+    /// Whether imported records are accepted directly. Defaults to `false`.
+    pub auto_accept: Option<bool>,
+    /// The list imported records are submitted to.
+    pub target_list: ExternalSyncList,
+    /// Whether players can sync with this source. Defaults to `true`.
+    pub enabled: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, ToSchema)]
+pub struct ExternalSourcePatch {
+    /// Unique name of the source.
+    pub name: Option<String>,
+    /// How responses of the source are read.
+    pub importer: Option<ExternalImporterKind>,
+    /// Base URL of the source.
+    pub base_url: Option<String>,
+    /// Where each field of a record is found in the responses of the source.
+    pub field_mapping: Option<ExternalFieldMapping>,
+    /// Whether imported records are accepted directly.
+    pub auto_accept: Option<bool>,
+    /// The list imported records are submitted to.
+    pub target_list: Option<ExternalSyncList>,
+    /// Whether players can sync with this source.
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Default, AsChangeset)]
+#[diesel(table_name = external_sources)]
+struct ExternalSourceChangeset {
+    name: Option<String>,
+    importer: Option<ExternalImporterKind>,
+    base_url: Option<String>,
+    field_mapping: Option<serde_json::Value>,
+    auto_accept: Option<bool>,
+    target_list: Option<ExternalSyncList>,
+    enabled: Option<bool>,
+}
+
+/// The data of a submission, as compared by a sync.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct ExternalRecordData {
+    /// Video link of the completion.
+    pub video_url: String,
+    /// Whether the completion was done on mobile.
+    pub mobile: bool,
+    /// Completion time in milliseconds, on platformer lists.
+    pub completion_time: Option<i64>,
+    /// Whether the submission is accepted.
+    pub accepted: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+pub enum ExternalSyncAction {
+    /// A new submission is created.
+    Create,
+    /// The existing submission is updated.
+    Update,
+    /// The existing submission already matches the source.
+    Unchanged,
+    /// The record cannot be imported, see the reason.
+    Skipped,
+    /// The record was imported by a previous sync but is no longer listed by the source.
+    /// The submission is left untouched for staff to review.
+    Removed,
+}
+
+/// What a sync does, or would do on a dry run, with one record of the source.
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct ExternalSyncChange {
+    /// Geometry Dash ID of the level.
+    pub gd_level_id: i32,
+    /// Whether the record is for the two player version of the level, if the source says.
+    pub two_player: Option<bool>,
+    /// Internal UUID of the level, if it is on the list.
+    pub level_id: Option<Uuid>,
+    /// Name of the level, if it is on the list.
+    pub level_name: Option<String>,
+    pub action: ExternalSyncAction,
+    /// Internal UUID of the submission. Not set for submissions a dry run would create.
+    pub submission_id: Option<Uuid>,
+    /// The submission before the sync, if there was one.
+    pub before: Option<ExternalRecordData>,
+    /// The submission after the sync. Not set for skipped records.
+    pub after: Option<ExternalRecordData>,
+    /// Why the record was skipped.
+    pub reason: Option<String>,
+}
+
+impl ExternalSyncChange {
+    pub fn skipped(record: &ExternalRecord, reason: &str) -> Self {
+        Self {
+            gd_level_id: record.level_id,
+            two_player: record.two_player,
+            level_id: None,
+            level_name: None,
+            action: ExternalSyncAction::Skipped,
+            submission_id: None,
+            before: None,
+            after: None,
+            reason: Some(reason.to_owned()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Selectable, Queryable, Debug, ToSchema)]
+#[diesel(table_name = external_sync_logs, check_for_backend(Pg))]
+pub struct ExternalSyncLog {
+    /// Internal UUID of the sync.
+    pub id: Uuid,
+    /// Internal UUID of the source that was synced.
+    pub source_id: Uuid,
+    /// Internal UUID of the user whose records were synced.
+    pub user_id: Uuid,
+    /// Internal UUID of the user who started the sync. `null` for scheduled syncs.
+    pub triggered_by: Option<Uuid>,
+    /// Whether this was a dry run, which did not write anything.
+    pub dry_run: bool,
+    /// Whether the source could be read.
+    pub succeeded: bool,
+    /// Count of submissions created.
+    pub created_count: i32,
+    /// Count of submissions updated.
+    pub updated_count: i32,
+    /// Count of submissions already matching the source.
+    pub unchanged_count: i32,
+    /// Count of records that could not be imported.
+    pub skipped_count: i32,
+    /// Count of previously imported records no longer listed by the source.
+    pub removed_count: i32,
+    /// What was done with each record of the source.
+    #[schema(value_type = Vec<ExternalSyncChange>)]
+    pub changes: serde_json::Value,
+    /// Why the source could not be read, for failed syncs.
+    pub error: Option<String>,
+    /// Timestamp of the sync.
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct ExternalSyncLogPage {
+    data: Vec<ExternalSyncLog>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, ToSchema)]
+pub struct ExternalSyncLogQueryOptions {
+    pub source_id: Option<Uuid>,
+    pub user_id: Option<Uuid>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, ToSchema)]
+pub struct ExternalSyncQuery {
+    /// Only compute what the sync would do, without writing anything. Defaults to `false`.
+    pub dry_run: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct ExternalSyncResult {
+    /// Internal UUID of the sync log entry.
+    pub log_id: Uuid,
+    /// Whether this was a dry run, which did not write anything.
+    pub dry_run: bool,
+    /// What was done, or would be done on a dry run, with each record of the source.
+    pub changes: Vec<ExternalSyncChange>,
+}
+
+impl ExternalSyncResult {
+    pub fn count(&self, action: ExternalSyncAction) -> usize {
+        self.changes
+            .iter()
+            .filter(|change| change.action == action)
+            .count()
+    }
+}
+
+fn validate_base_url(base_url: &str) -> Result<String, ApiError> {
+    let parsed = Url::parse(base_url.trim())
+        .map_err(|_err| ApiError::BadRequest("The base URL is not a valid URL."))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err(ApiError::BadRequest("The base URL must use HTTP or HTTPS."));
+    }
+    Ok(base_url.trim().to_owned())
+}
+
+fn validate_mapping(
+    mapping: &ExternalFieldMapping,
+    target_list: ExternalSyncList,
+) -> Result<(), ApiError> {
+    if target_list == ExternalSyncList::Arepl && mapping.completion_time.is_none() {
+        return Err(ApiError::BadRequest(
+            "Sources feeding the platformer list must map the completion time.",
+        ));
+    }
+    Ok(())
+}
+
+impl ExternalSource {
+    pub fn find_all(conn: &mut DbConnection) -> Result<Vec<Self>, ApiError> {
+        Ok(external_sources::table
+            .order(external_sources::name.asc())
+            .select(ExternalSource::as_select())
+            .load::<ExternalSource>(conn)?)
+    }
+
+    pub fn find(conn: &mut DbConnection, id: Uuid) -> Result<Self, ApiError> {
+        external_sources::table
+            .filter(external_sources::id.eq(id))
+            .select(ExternalSource::as_select())
+            .first::<ExternalSource>(conn)
+            .optional()?
+            .ok_or_else(|| ApiError::NotFound("Could not find this source"))
+    }
+
+    pub fn find_by_name(conn: &mut DbConnection, name: &str) -> Result<Self, ApiError> {
+        external_sources::table
+            .filter(external_sources::name.eq(name))
+            .select(ExternalSource::as_select())
+            .first::<ExternalSource>(conn)
+            .optional()?
+            .ok_or_else(|| ApiError::NotFound(format!("Could not find the {name} source")))
+    }
+
+    pub fn create(conn: &mut DbConnection, source: ExternalSourceCreate) -> Result<Self, ApiError> {
+        let base_url = validate_base_url(&source.base_url)?;
+        validate_mapping(&source.field_mapping, source.target_list)?;
+        let field_mapping = serde_json::to_value(&source.field_mapping)
+            .map_err(|error| ApiError::BadRequest(format!("Invalid field mapping: {error}")))?;
+
+        let source = diesel::insert_into(external_sources::table)
+            .values((
+                external_sources::name.eq(source.name),
+                external_sources::importer.eq(source.importer),
+                external_sources::base_url.eq(base_url),
+                external_sources::field_mapping.eq(field_mapping),
+                external_sources::auto_accept.eq(source.auto_accept.unwrap_or(false)),
+                external_sources::target_list.eq(source.target_list),
+                external_sources::enabled.eq(source.enabled.unwrap_or(true)),
+            ))
+            .returning(ExternalSource::as_select())
+            .get_result::<ExternalSource>(conn)?;
+        Ok(source)
+    }
+
+    pub fn patch(
+        conn: &mut DbConnection,
+        id: Uuid,
+        patch: ExternalSourcePatch,
+    ) -> Result<Self, ApiError> {
+        conn.transaction(|connection| -> Result<Self, ApiError> {
+            let current = Self::find(connection, id)?;
+            let target_list = patch.target_list.unwrap_or(current.target_list);
+            let mapping = match patch.field_mapping {
+                Some(mapping) => mapping,
+                None => current.mapping()?,
+            };
+            validate_mapping(&mapping, target_list)?;
+
+            let changeset = ExternalSourceChangeset {
+                name: patch.name,
+                importer: patch.importer,
+                base_url: patch
+                    .base_url
+                    .as_deref()
+                    .map(validate_base_url)
+                    .transpose()?,
+                field_mapping: Some(serde_json::to_value(&mapping).map_err(|error| {
+                    ApiError::BadRequest(format!("Invalid field mapping: {error}"))
+                })?),
+                auto_accept: patch.auto_accept,
+                target_list: patch.target_list,
+                enabled: patch.enabled,
+            };
+
+            let source = diesel::update(external_sources::table)
+                .filter(external_sources::id.eq(id))
+                .set((changeset, external_sources::updated_at.eq(Utc::now())))
+                .returning(ExternalSource::as_select())
+                .get_result::<ExternalSource>(connection)?;
+            Ok(source)
+        })
+    }
+
+    pub fn delete(conn: &mut DbConnection, id: Uuid) -> Result<(), ApiError> {
+        let deleted = diesel::delete(external_sources::table)
+            .filter(external_sources::id.eq(id))
+            .execute(conn)?;
+        if deleted == 0 {
+            return Err(ApiError::NotFound("Could not find this source"));
+        }
+        Ok(())
+    }
+
+    pub fn mapping(&self) -> Result<ExternalFieldMapping, ApiError> {
+        serde_json::from_value(self.field_mapping.clone()).map_err(|error| {
+            ApiError::InternalServerError(format!(
+                "The field mapping of the {} source is invalid: {error}",
+                self.name
+            ))
+        })
+    }
+
+    /// Reads the records of a player from the source.
+    async fn fetch(
+        &self,
+        providers: &ProvidersAppState,
+        external_id: &str,
+    ) -> Result<Vec<ExternalRecord>, ApiError> {
+        let importer = self.importer.importer();
+        let mapping = self.mapping()?;
+        let response = providers
+            .context
+            .http
+            .get(importer.player_url(self, external_id))
+            .timeout(FETCH_TIMEOUT)
+            .send()
+            .await
+            .map_err(|error| {
+                ApiError::BadGateway(format!("Failed to reach {}: {error}", self.name))
+            })?;
+        let status = response.status();
+        let body = response
+            .json::<serde_json::Value>()
+            .await
+            .map_err(|error| {
+                ApiError::BadGateway(format!(
+                    "Failed to parse data received from {}: {error}",
+                    self.name
+                ))
+            })?;
+        importer.parse_player(&mapping, external_id, status, &body)
+    }
+
+    /// Imports the records a user has on this source into the target list.
+    /// Players are identified on external lists by their linked Discord account.
+    pub async fn sync(
+        db: Arc<DbAppState>,
+        providers: Arc<ProvidersAppState>,
+        source_id: Uuid,
+        user_id: Uuid,
+        triggered_by: Option<Uuid>,
+        dry_run: bool,
+    ) -> Result<ExternalSyncResult, ApiError> {
+        let block_db = db.clone();
+        let (source, external_id) = web::block(move || {
+            let conn = &mut block_db.connection()?;
+            let source = Self::find(conn, source_id)?;
+            if !source.enabled {
+                return Err(ApiError::UnprocessableEntity("This source is disabled"));
+            }
+            let external_id = users::table
+                .filter(users::id.eq(user_id))
+                .select(users::discord_id)
+                .first::<Option<String>>(conn)?
+                .ok_or_else(|| {
+                    ApiError::UnprocessableEntity("Given user does not have a discord id")
+                })?;
+            Ok((source, external_id))
+        })
+        .await??;
+
+        let fetched = source.fetch(&providers, &external_id).await;
+
+        web::block(move || {
+            let conn = &mut db.connection()?;
+            let records = match fetched {
+                Ok(records) => records,
+                Err(error) => {
+                    ExternalSyncLog::create_failed(
+                        conn,
+                        &source,
+                        user_id,
+                        triggered_by,
+                        dry_run,
+                        &error.error_message,
+                    )?;
+                    return Err(error);
+                }
+            };
+
+            conn.transaction(|connection| -> Result<ExternalSyncResult, ApiError> {
+                let mut changes = match source.target_list {
+                    ExternalSyncList::Aredl => aredl::submissions::external_sync::sync_records(
+                        connection, &providers, &source, user_id, &records, dry_run,
+                    )?,
+                    ExternalSyncList::Arepl => arepl::submissions::external_sync::sync_records(
+                        connection, &providers, &source, user_id, &records, dry_run,
+                    )?,
+                };
+                let removed = Self::removed_changes(connection, &source, user_id, &changes)?;
+                changes.extend(removed);
+                let log = ExternalSyncLog::create(
+                    connection,
+                    &source,
+                    user_id,
+                    triggered_by,
+                    dry_run,
+                    &changes,
+                )?;
+                Ok(ExternalSyncResult {
+                    log_id: log.id,
+                    dry_run,
+                    changes,
+                })
+            })
+        })
+        .await?
+    }
+
+    /// Lists the records imported by the previous sync of the user that the source no longer lists,
+    /// as long as the user still has a submission for them.
+    fn removed_changes(
+        conn: &mut DbConnection,
+        source: &ExternalSource,
+        user_id: Uuid,
+        changes: &[ExternalSyncChange],
+    ) -> Result<Vec<ExternalSyncChange>, ApiError> {
+        let previous = external_sync_logs::table
+            .filter(external_sync_logs::source_id.eq(source.id))
+            .filter(external_sync_logs::user_id.eq(user_id))
+            .filter(external_sync_logs::succeeded.eq(true))
+            .filter(external_sync_logs::dry_run.eq(false))
+            .order(external_sync_logs::created_at.desc())
+            .select(external_sync_logs::changes)
+            .first::<serde_json::Value>(conn)
+            .optional()?;
+        let Some(previous) = previous else {
+            return Ok(Vec::new());
+        };
+        let previous: Vec<ExternalSyncChange> =
+            serde_json::from_value(previous).map_err(|error| {
+                ApiError::InternalServerError(format!(
+                    "Failed to read the previous sync changes: {error}"
+                ))
+            })?;
+
+        let listed = changes
+            .iter()
+            .filter_map(|change| change.level_id)
+            .collect::<HashSet<_>>();
+        let mut seen = HashSet::new();
+        let missing = previous
+            .into_iter()
+            .filter(|change| change.action != ExternalSyncAction::Skipped)
+            .filter(|change| {
+                change
+                    .level_id
+                    .is_some_and(|level_id| !listed.contains(&level_id) && seen.insert(level_id))
+            })
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let level_ids = missing
+            .iter()
+            .filter_map(|change| change.level_id)
+            .collect::<Vec<_>>();
+        let submissions = match source.target_list {
+            ExternalSyncList::Aredl => {
+                aredl::submissions::external_sync::find_submissions(conn, user_id, &level_ids)?
+            }
+            ExternalSyncList::Arepl => {
+                arepl::submissions::external_sync::find_submissions(conn, user_id, &level_ids)?
+            }
+        };
+
+        Ok(missing
+            .into_iter()
+            .filter_map(|change| {
+                let (submission_id, data) = submissions.get(&change.level_id?)?;
+                Some(ExternalSyncChange {
+                    action: ExternalSyncAction::Removed,
+                    submission_id: Some(*submission_id),
+                    before: Some(data.clone()),
+                    after: Some(data.clone()),
+                    reason: Some(format!(
+                        "This record is no longer listed by {}",
+                        source.name
+                    )),
+                    ..change
+                })
+            })
+            .collect())
+    }
+}
+
+fn count_action(changes: &[ExternalSyncChange], action: ExternalSyncAction) -> i32 {
+    let count = changes
+        .iter()
+        .filter(|change| change.action == action)
+        .count();
+    i32::try_from(count).unwrap_or(i32::MAX)
+}
+
+impl ExternalSyncLog {
+    fn create(
+        conn: &mut DbConnection,
+        source: &ExternalSource,
+        user_id: Uuid,
+        triggered_by: Option<Uuid>,
+        dry_run: bool,
+        changes: &[ExternalSyncChange],
+    ) -> Result<Self, ApiError> {
+        let changes_json = serde_json::to_value(changes).map_err(|error| {
+            ApiError::InternalServerError(format!("Failed to serialize sync changes: {error}"))
+        })?;
+        let log = diesel::insert_into(external_sync_logs::table)
+            .values((
+                external_sync_logs::source_id.eq(source.id),
+                external_sync_logs::user_id.eq(user_id),
+                external_sync_logs::triggered_by.eq(triggered_by),
+                external_sync_logs::dry_run.eq(dry_run),
+                external_sync_logs::succeeded.eq(true),
+                external_sync_logs::created_count
+                    .eq(count_action(changes, ExternalSyncAction::Create)),
+                external_sync_logs::updated_count
+                    .eq(count_action(changes, ExternalSyncAction::Update)),
+                external_sync_logs::unchanged_count
+                    .eq(count_action(changes, ExternalSyncAction::Unchanged)),
+                external_sync_logs::skipped_count
+                    .eq(count_action(changes, ExternalSyncAction::Skipped)),
+                external_sync_logs::removed_count
+                    .eq(count_action(changes, ExternalSyncAction::Removed)),
+                external_sync_logs::changes.eq(changes_json),
+            ))
+            .returning(ExternalSyncLog::as_select())
+            .get_result::<ExternalSyncLog>(conn)?;
+        Ok(log)
+    }
+
+    fn create_failed(
+        conn: &mut DbConnection,
+        source: &ExternalSource,
+        user_id: Uuid,
+        triggered_by: Option<Uuid>,
+        dry_run: bool,
+        error: &str,
+    ) -> Result<Self, ApiError> {
+        let log = diesel::insert_into(external_sync_logs::table)
+            .values((
+                external_sync_logs::source_id.eq(source.id),
+                external_sync_logs::user_id.eq(user_id),
+                external_sync_logs::triggered_by.eq(triggered_by),
+                external_sync_logs::dry_run.eq(dry_run),
+                external_sync_logs::succeeded.eq(false),
+                external_sync_logs::error.eq(error),
+            ))
+            .returning(ExternalSyncLog::as_select())
+            .get_result::<ExternalSyncLog>(conn)?;
+        Ok(log)
+    }
+
+    pub fn find_all<const D: i64>(
+        conn: &mut DbConnection,
+        page_query: PageQuery<D>,
+        options: &ExternalSyncLogQueryOptions,
+    ) -> Result<Paginated<ExternalSyncLogPage>, ApiError> {
+        let build_filtered = || {
+            let mut q = external_sync_logs::table.into_boxed::<Pg>();
+            if let Some(source_id) = options.source_id {
+                q = q.filter(external_sync_logs::source_id.eq(source_id));
+            }
+            if let Some(user_id) = options.user_id {
+                q = q.filter(external_sync_logs::user_id.eq(user_id));
+            }
+            q
+        };
+
+        let total_count: i64 = build_filtered().count().get_result(conn)?;
+        let data = build_filtered()
+            .order(external_sync_logs::created_at.desc())
+            .limit(page_query.per_page())
+            .offset(page_query.offset())
+            .select(ExternalSyncLog::as_select())
+            .load::<ExternalSyncLog>(conn)?;
+
+        Ok(Paginated::from_data(
+            page_query,
+            total_count,
+            ExternalSyncLogPage { data },
+        ))
+    }
+}
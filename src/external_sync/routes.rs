@@ -0,0 +1,332 @@
+use crate::{
+    app_data::db::DbAppState,
+    auth::{Authenticated, Permission, UserAuth},
+    error_handler::ApiError,
+    external_sync::{
+        ExternalFieldMapping, ExternalImporterKind, ExternalRecordData, ExternalSource,
+        ExternalSourceCreate, ExternalSourcePatch, ExternalSyncAction, ExternalSyncChange,
+        ExternalSyncList, ExternalSyncLog, ExternalSyncLogPage, ExternalSyncLogQueryOptions,
+        ExternalSyncQuery, ExternalSyncResult, ExternalSyncSubscription,
+        ExternalSyncSubscriptionResolved, ExternalTimeFormat,
+    },
+    page_helper::{PageQuery, Paginated},
+    providers::ProvidersAppState,
+};
+use actix_web::{delete, get, patch, post, web, HttpResponse};
+use std::sync::Arc;
+use tracing_actix_web::RootSpan;
+use utoipa::OpenApi;
+use uuid::Uuid;
+
+#[utoipa::path(
+    get,
+    summary = "List external sources",
+    description = "Lists the external lists records can be imported from.",
+    tag = "External Sync",
+    responses(
+        (status = 200, body = Vec<ExternalSource>)
+    ),
+)]
+#[get("")]
+async fn find_all_sources(db: web::Data<Arc<DbAppState>>) -> Result<HttpResponse, ApiError> {
+    let sources = web::block(move || ExternalSource::find_all(&mut db.connection()?)).await??;
+    Ok(HttpResponse::Ok().json(sources))
+}
+
+#[utoipa::path(
+    post,
+    summary = "[Staff]Create an external source",
+    description = "Adds a new list records can be imported from.",
+    tag = "External Sync",
+    request_body = ExternalSourceCreate,
+    responses(
+        (status = 200, body = ExternalSource)
+    ),
+    security(
+        ("access_token" = ["ExternalSyncManage"]),
+        ("api_key" = ["ExternalSyncManage"]),
+    ),
+)]
+#[post("", wrap = "UserAuth::require(Permission::ExternalSyncManage)")]
+async fn create_source(
+    db: web::Data<Arc<DbAppState>>,
+    body: web::Json<ExternalSourceCreate>,
+    root_span: RootSpan,
+) -> Result<HttpResponse, ApiError> {
+    root_span.record("body", tracing::field::debug(&body));
+    let source =
+        web::block(move || ExternalSource::create(&mut db.connection()?, body.into_inner()))
+            .await??;
+    Ok(HttpResponse::Ok().json(source))
+}
+
+#[utoipa::path(
+    get,
+    summary = "[Staff]List syncs",
+    description = "Lists past syncs, newest first, along with what they did with each imported record.",
+    tag = "External Sync",
+    params(
+        ("page" = Option<i64>, Query, description = "The page of the log to fetch"),
+        ("per_page" = Option<i64>, Query, description = "The number of entries to fetch per page"),
+        ("source_id" = Option<Uuid>, Query, description = "Only list syncs of this source"),
+        ("user_id" = Option<Uuid>, Query, description = "Only list syncs of this user's records"),
+    ),
+    responses(
+        (status = 200, body = Paginated<ExternalSyncLogPage>)
+    ),
+    security(
+        ("access_token" = ["ExternalSyncManage"]),
+        ("api_key" = ["ExternalSyncManage"]),
+    ),
+)]
+#[get("/logs", wrap = "UserAuth::require(Permission::ExternalSyncManage)")]
+async fn find_logs(
+    db: web::Data<Arc<DbAppState>>,
+    page_query: web::Query<PageQuery<20>>,
+    options: web::Query<ExternalSyncLogQueryOptions>,
+) -> Result<HttpResponse, ApiError> {
+    let logs = web::block(move || {
+        ExternalSyncLog::find_all(
+            &mut db.connection()?,
+            page_query.into_inner(),
+            &options.into_inner(),
+        )
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(logs))
+}
+
+#[utoipa::path(
+    get,
+    summary = "[Auth]List my sync subscriptions",
+    description = "Lists the sources the authenticated user's records are periodically synced from, along with the outcome of the last scheduled sync.",
+    tag = "External Sync",
+    responses(
+        (status = 200, body = Vec<ExternalSyncSubscriptionResolved>)
+    ),
+    security(
+        ("access_token" = []),
+        ("api_key" = []),
+    ),
+)]
+#[get("/subscriptions", wrap = "UserAuth::load()")]
+async fn find_subscriptions(
+    db: web::Data<Arc<DbAppState>>,
+    authenticated: Authenticated,
+) -> Result<HttpResponse, ApiError> {
+    let subscriptions = web::block(move || {
+        ExternalSyncSubscription::find_all_for_user(&mut db.connection()?, authenticated.user_id)
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(subscriptions))
+}
+
+#[utoipa::path(
+    patch,
+    summary = "[Staff]Edit an external source",
+    description = "Edits a source. Records imported before are not affected.",
+    tag = "External Sync",
+    request_body = ExternalSourcePatch,
+    params(
+        ("id" = Uuid, description = "Internal UUID of the source"),
+    ),
+    responses(
+        (status = 200, body = ExternalSource)
+    ),
+    security(
+        ("access_token" = ["ExternalSyncManage"]),
+        ("api_key" = ["ExternalSyncManage"]),
+    ),
+)]
+#[patch("/{id}", wrap = "UserAuth::require(Permission::ExternalSyncManage)")]
+async fn patch_source(
+    db: web::Data<Arc<DbAppState>>,
+    id: web::Path<Uuid>,
+    body: web::Json<ExternalSourcePatch>,
+    root_span: RootSpan,
+) -> Result<HttpResponse, ApiError> {
+    root_span.record("body", tracing::field::debug(&body));
+    let source = web::block(move || {
+        ExternalSource::patch(&mut db.connection()?, id.into_inner(), body.into_inner())
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(source))
+}
+
+#[utoipa::path(
+    delete,
+    summary = "[Staff]Delete an external source",
+    description = "Deletes a source along with its sync log. Records imported before are not affected.",
+    tag = "External Sync",
+    params(
+        ("id" = Uuid, description = "Internal UUID of the source"),
+    ),
+    responses(
+        (status = 204)
+    ),
+    security(
+        ("access_token" = ["ExternalSyncManage"]),
+        ("api_key" = ["ExternalSyncManage"]),
+    ),
+)]
+#[delete("/{id}", wrap = "UserAuth::require(Permission::ExternalSyncManage)")]
+async fn delete_source(
+    db: web::Data<Arc<DbAppState>>,
+    id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    web::block(move || ExternalSource::delete(&mut db.connection()?, id.into_inner())).await??;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    post,
+    summary = "[Auth]Sync with an external source",
+    description = "Imports and/or updates the authenticated user's submissions from their records on an external list. The account on the external list must be linked to the same discord account as the authenticated user. Use `dry_run` to see what would change without writing anything.",
+    tag = "External Sync",
+    params(
+        ("id" = Uuid, description = "Internal UUID of the source"),
+        ("dry_run" = Option<bool>, Query, description = "Only compute what the sync would do"),
+    ),
+    responses(
+        (status = 200, body = ExternalSyncResult)
+    ),
+    security(
+        ("access_token" = []),
+        ("api_key" = []),
+    ),
+)]
+#[post("/{id}/sync", wrap = "UserAuth::load()")]
+async fn sync(
+    db: web::Data<Arc<DbAppState>>,
+    providers: web::Data<Arc<ProvidersAppState>>,
+    id: web::Path<Uuid>,
+    query: web::Query<ExternalSyncQuery>,
+    authenticated: Authenticated,
+) -> Result<HttpResponse, ApiError> {
+    let result = ExternalSource::sync(
+        db.get_ref().clone(),
+        providers.get_ref().clone(),
+        id.into_inner(),
+        authenticated.user_id,
+        Some(authenticated.user_id),
+        query.dry_run.unwrap_or(false),
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+#[utoipa::path(
+    post,
+    summary = "[Auth]Subscribe to scheduled syncs",
+    description = "Opts the authenticated user in to having their records periodically synced from this source. Records removed from the source are reported in the sync log, but their submissions are kept.",
+    tag = "External Sync",
+    params(
+        ("id" = Uuid, description = "Internal UUID of the source"),
+    ),
+    responses(
+        (status = 200, body = ExternalSyncSubscription)
+    ),
+    security(
+        ("access_token" = []),
+        ("api_key" = []),
+    ),
+)]
+#[post("/{id}/subscription", wrap = "UserAuth::load()")]
+async fn subscribe(
+    db: web::Data<Arc<DbAppState>>,
+    id: web::Path<Uuid>,
+    authenticated: Authenticated,
+) -> Result<HttpResponse, ApiError> {
+    let subscription = web::block(move || {
+        ExternalSyncSubscription::subscribe(
+            &mut db.connection()?,
+            authenticated.user_id,
+            id.into_inner(),
+        )
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(subscription))
+}
+
+#[utoipa::path(
+    delete,
+    summary = "[Auth]Unsubscribe from scheduled syncs",
+    description = "Stops periodically syncing the authenticated user's records from this source.",
+    tag = "External Sync",
+    params(
+        ("id" = Uuid, description = "Internal UUID of the source"),
+    ),
+    responses(
+        (status = 204)
+    ),
+    security(
+        ("access_token" = []),
+        ("api_key" = []),
+    ),
+)]
+#[delete("/{id}/subscription", wrap = "UserAuth::load()")]
+async fn unsubscribe(
+    db: web::Data<Arc<DbAppState>>,
+    id: web::Path<Uuid>,
+    authenticated: Authenticated,
+) -> Result<HttpResponse, ApiError> {
+    web::block(move || {
+        ExternalSyncSubscription::unsubscribe(
+            &mut db.connection()?,
+            authenticated.user_id,
+            id.into_inner(),
+        )
+    })
+    .await??;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    tags(
+        (name = "External Sync", description = "Endpoints to import records from other lists, and to configure the lists records are imported from")
+    ),
+    components(schemas(
+        ExternalSource,
+        ExternalSourceCreate,
+        ExternalSourcePatch,
+        ExternalImporterKind,
+        ExternalSyncList,
+        ExternalFieldMapping,
+        ExternalTimeFormat,
+        ExternalSyncLog,
+        ExternalSyncResult,
+        ExternalSyncChange,
+        ExternalSyncAction,
+        ExternalRecordData,
+        ExternalSyncSubscription,
+        ExternalSyncSubscriptionResolved,
+    )),
+    paths(
+        find_all_sources,
+        create_source,
+        find_logs,
+        find_subscriptions,
+        patch_source,
+        delete_source,
+        sync,
+        subscribe,
+        unsubscribe
+    )
+)]
+pub struct ApiDoc;
+
+pub fn init_routes(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/external-sources")
+            .service(find_all_sources)
+            .service(create_source)
+            .service(find_logs)
+            .service(find_subscriptions)
+            .service(patch_source)
+            .service(delete_source)
+            .service(sync)
+            .service(subscribe)
+            .service(unsubscribe),
+    );
+}
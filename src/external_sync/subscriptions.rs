@@ -0,0 +1,267 @@
+use crate::{
+    app_data::db::{DbAppState, DbConnection},
+    error_handler::ApiError,
+    external_sync::{ExternalSource, ExternalSyncAction, ExternalSyncLog},
+    providers::ProvidersAppState,
+    schema::{external_sources, external_sync_logs, external_sync_subscriptions, users},
+};
+use actix_web::web;
+use chrono::{DateTime, Utc};
+use diesel::{pg::Pg, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use diesel::prelude::*;
+#[derive(Serialize, Deserialize, Selectable, Queryable, Debug, ToSchema)]
+#[diesel(table_name = external_sync_subscriptions, check_for_backend(Pg))]
+pub struct ExternalSyncSubscription {
+    /// Internal UUID of the subscribed user.
+    pub user_id: Uuid,
+    /// Internal UUID of the source the user's records are synced from.
+    pub source_id: Uuid,
+    /// Timestamp of the last scheduled sync.
+    pub last_synced_at: Option<DateTime<Utc>>,
+    /// Internal UUID of the log entry of the last scheduled sync.
+    pub last_log_id: Option<Uuid>,
+    /// Timestamp of when the user subscribed.
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct ExternalSyncSubscriptionResolved {
+    /// The source the user's records are synced from.
+    pub source: ExternalSource,
+    /// Timestamp of the last scheduled sync.
+    pub last_synced_at: Option<DateTime<Utc>>,
+    /// The outcome of the last scheduled sync.
+    pub last_sync: Option<ExternalSyncLog>,
+    /// Timestamp of when the user subscribed.
+    pub created_at: DateTime<Utc>,
+}
+
+/// The outcome of the scheduled sync of one subscribed user.
+#[derive(Debug)]
+pub struct ExternalSyncOutcome {
+    pub user_id: Uuid,
+    pub source_name: String,
+    pub created: usize,
+    pub updated: usize,
+    pub removed: usize,
+    /// Why the sync failed, for failed syncs.
+    pub error: Option<String>,
+}
+
+impl ExternalSyncSubscription {
+    pub fn find_all_for_user(
+        conn: &mut DbConnection,
+        user_id: Uuid,
+    ) -> Result<Vec<ExternalSyncSubscriptionResolved>, ApiError> {
+        let subscriptions = external_sync_subscriptions::table
+            .inner_join(
+                external_sources::table
+                    .on(external_sources::id.eq(external_sync_subscriptions::source_id)),
+            )
+            .left_join(
+                external_sync_logs::table.on(external_sync_logs::id
+                    .nullable()
+                    .eq(external_sync_subscriptions::last_log_id)),
+            )
+            .filter(external_sync_subscriptions::user_id.eq(user_id))
+            .order(external_sources::name.asc())
+            .select((
+                ExternalSyncSubscription::as_select(),
+                ExternalSource::as_select(),
+                Option::<ExternalSyncLog>::as_select(),
+            ))
+            .load::<(
+                ExternalSyncSubscription,
+                ExternalSource,
+                Option<ExternalSyncLog>,
+            )>(conn)?;
+
+        Ok(subscriptions
+            .into_iter()
+            .map(
+                |(subscription, source, last_sync)| ExternalSyncSubscriptionResolved {
+                    source,
+                    last_synced_at: subscription.last_synced_at,
+                    last_sync,
+                    created_at: subscription.created_at,
+                },
+            )
+            .collect())
+    }
+
+    pub fn subscribe(
+        conn: &mut DbConnection,
+        user_id: Uuid,
+        source_id: Uuid,
+    ) -> Result<Self, ApiError> {
+        let source = ExternalSource::find(conn, source_id)?;
+        if !source.enabled {
+            return Err(ApiError::UnprocessableEntity("This source is disabled"));
+        }
+        let discord_id = users::table
+            .filter(users::id.eq(user_id))
+            .select(users::discord_id)
+            .first::<Option<String>>(conn)?;
+        if discord_id.is_none() {
+            return Err(ApiError::UnprocessableEntity(
+                "Given user does not have a discord id",
+            ));
+        }
+
+        diesel::insert_into(external_sync_subscriptions::table)
+            .values((
+                external_sync_subscriptions::user_id.eq(user_id),
+                external_sync_subscriptions::source_id.eq(source_id),
+            ))
+            .on_conflict_do_nothing()
+            .execute(conn)?;
+
+        Ok(external_sync_subscriptions::table
+            .filter(external_sync_subscriptions::user_id.eq(user_id))
+            .filter(external_sync_subscriptions::source_id.eq(source_id))
+            .select(ExternalSyncSubscription::as_select())
+            .first::<ExternalSyncSubscription>(conn)?)
+    }
+
+    pub fn unsubscribe(
+        conn: &mut DbConnection,
+        user_id: Uuid,
+        source_id: Uuid,
+    ) -> Result<(), ApiError> {
+        let deleted = diesel::delete(external_sync_subscriptions::table)
+            .filter(external_sync_subscriptions::user_id.eq(user_id))
+            .filter(external_sync_subscriptions::source_id.eq(source_id))
+            .execute(conn)?;
+        if deleted == 0 {
+            return Err(ApiError::NotFound("You are not subscribed to this source"));
+        }
+        Ok(())
+    }
+
+    /// Syncs up to `limit` subscribed users with their sources, least recently synced first.
+    /// Subscriptions to disabled sources are skipped.
+    pub async fn sync_all(
+        db: &Arc<DbAppState>,
+        providers: &Arc<ProvidersAppState>,
+        limit: i64,
+    ) -> Result<Vec<ExternalSyncOutcome>, ApiError> {
+        let block_db = db.clone();
+        let due = web::block(move || {
+            Ok::<_, ApiError>(
+                external_sync_subscriptions::table
+                    .inner_join(
+                        external_sources::table
+                            .on(external_sources::id.eq(external_sync_subscriptions::source_id)),
+                    )
+                    .filter(external_sources::enabled.eq(true))
+                    .order(
+                        external_sync_subscriptions::last_synced_at
+                            .asc()
+                            .nulls_first(),
+                    )
+                    .limit(limit)
+                    .select((
+                        external_sync_subscriptions::user_id,
+                        external_sync_subscriptions::source_id,
+                        external_sources::name,
+                    ))
+                    .load::<(Uuid, Uuid, String)>(&mut block_db.connection()?)?,
+            )
+        })
+        .await??;
+
+        let mut outcomes = Vec::with_capacity(due.len());
+        for (user_id, source_id, source_name) in due {
+            let started_at = Utc::now();
+            let result = ExternalSource::sync(
+                db.clone(),
+                providers.clone(),
+                source_id,
+                user_id,
+                None,
+                false,
+            )
+            .await;
+
+            let log_id = result.as_ref().ok().map(|result| result.log_id);
+            let block_db = db.clone();
+            let recorded = web::block(move || {
+                Self::record_sync(
+                    &mut block_db.connection()?,
+                    user_id,
+                    source_id,
+                    log_id,
+                    started_at,
+                )
+            })
+            .await
+            .map_err(ApiError::from)
+            .and_then(|recorded| recorded);
+            if let Err(error) = recorded {
+                tracing::warn!(
+                    error = %error.error_message,
+                    ?user_id,
+                    source = %source_name,
+                    "Failed to record scheduled external sync"
+                );
+            }
+
+            outcomes.push(match result {
+                Ok(result) => ExternalSyncOutcome {
+                    user_id,
+                    source_name,
+                    created: result.count(ExternalSyncAction::Create),
+                    updated: result.count(ExternalSyncAction::Update),
+                    removed: result.count(ExternalSyncAction::Removed),
+                    error: None,
+                },
+                Err(error) => ExternalSyncOutcome {
+                    user_id,
+                    source_name,
+                    created: 0,
+                    updated: 0,
+                    removed: 0,
+                    error: Some(error.error_message),
+                },
+            });
+        }
+        Ok(outcomes)
+    }
+
+    /// Stores the outcome of a scheduled sync. Failed syncs have no result to read the log entry from,
+    /// so the one written since the sync started is used, if the source could be reached at all.
+    fn record_sync(
+        conn: &mut DbConnection,
+        user_id: Uuid,
+        source_id: Uuid,
+        log_id: Option<Uuid>,
+        started_at: DateTime<Utc>,
+    ) -> Result<(), ApiError> {
+        let log_id = match log_id {
+            Some(log_id) => Some(log_id),
+            None => external_sync_logs::table
+                .filter(external_sync_logs::source_id.eq(source_id))
+                .filter(external_sync_logs::user_id.eq(user_id))
+                .filter(external_sync_logs::created_at.ge(started_at))
+                .order(external_sync_logs::created_at.desc())
+                .select(external_sync_logs::id)
+                .first::<Uuid>(conn)
+                .optional()?,
+        };
+
+        diesel::update(external_sync_subscriptions::table)
+            .filter(external_sync_subscriptions::user_id.eq(user_id))
+            .filter(external_sync_subscriptions::source_id.eq(source_id))
+            .set((
+                external_sync_subscriptions::last_synced_at.eq(Utc::now()),
+                external_sync_subscriptions::last_log_id.eq(log_id),
+            ))
+            .execute(conn)?;
+        Ok(())
+    }
+}
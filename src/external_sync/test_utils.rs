@@ -0,0 +1,70 @@
+#[cfg(test)]
+use {
+    crate::{
+        app_data::db::DbAppState,
+        external_sync::{ExternalImporterKind, ExternalSyncList},
+        schema::external_sources,
+    },
+    diesel::prelude::*,
+    serde_json::json,
+    std::sync::Arc,
+    uuid::Uuid,
+};
+
+/// Points a seeded source, such as `pemonlist`, to a mock server.
+#[cfg(test)]
+pub async fn set_test_external_source_base_url(db: &Arc<DbAppState>, name: &str, base_url: &str) {
+    diesel::update(external_sources::table)
+        .filter(external_sources::name.eq(name))
+        .set(external_sources::base_url.eq(base_url))
+        .execute(&mut db.connection().unwrap())
+        .expect("Failed to update test external source");
+}
+
+/// Creates a JSON source for the classic list, reading `{"data": {"completions": [{"level": 1, "video": "..."}]}}` responses.
+#[cfg(test)]
+pub async fn create_test_json_source(
+    db: &Arc<DbAppState>,
+    base_url: &str,
+    auto_accept: bool,
+) -> Uuid {
+    diesel::insert_into(external_sources::table)
+        .values((
+            external_sources::name.eq(format!("test-{}", Uuid::new_v4())),
+            external_sources::importer.eq(ExternalImporterKind::Json),
+            external_sources::base_url.eq(base_url),
+            external_sources::field_mapping.eq(json!({
+                "records": "/data/completions",
+                "level_id": "/level",
+                "video_url": "/video",
+                "mobile": "/mobile",
+            })),
+            external_sources::auto_accept.eq(auto_accept),
+            external_sources::target_list.eq(ExternalSyncList::Aredl),
+        ))
+        .returning(external_sources::id)
+        .get_result::<Uuid>(&mut db.connection().unwrap())
+        .expect("Failed to create test external source")
+}
+
+/// Creates a JSON source for the platformer list, reading `{"data": {"completions": [{"level": 1, "video": "...", "time": "0:01:02.345"}]}}` responses.
+#[cfg(test)]
+pub async fn create_test_platformer_json_source(db: &Arc<DbAppState>, base_url: &str) -> Uuid {
+    diesel::insert_into(external_sources::table)
+        .values((
+            external_sources::name.eq(format!("test-{}", Uuid::new_v4())),
+            external_sources::importer.eq(ExternalImporterKind::Json),
+            external_sources::base_url.eq(base_url),
+            external_sources::field_mapping.eq(json!({
+                "records": "/data/completions",
+                "level_id": "/level",
+                "video_url": "/video",
+                "completion_time": "/time",
+                "completion_time_format": "formatted",
+            })),
+            external_sources::target_list.eq(ExternalSyncList::Arepl),
+        ))
+        .returning(external_sources::id)
+        .get_result::<Uuid>(&mut db.connection().unwrap())
+        .expect("Failed to create test external source")
+}
@@ -0,0 +1,536 @@
+#[cfg(test)]
+use {
+    crate::{
+        aredl::{
+            levels::test_utils::{
+                create_test_level, get_test_level, set_test_level_gd_id, set_test_level_two_player,
+            },
+            submissions::{
+                test_utils::{
+                    create_test_submission, get_test_submission, set_test_submission_locked,
+                    set_test_submission_status,
+                },
+                SubmissionStatus,
+            },
+        },
+        auth::{create_test_token, Permission},
+        external_sync::{
+            test_utils::{create_test_json_source, create_test_platformer_json_source},
+            ExternalSyncSubscription,
+        },
+        providers::init_app_state as providers_init_app_state,
+        test_utils::*,
+        users::test_utils::{create_test_user, set_test_user_discord_id},
+    },
+    actix_web::test::{self, read_body_json},
+    httpmock::prelude::*,
+    serde_json::json,
+};
+
+#[actix_web::test]
+async fn json_source_dry_run_then_queue_as_pending() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, None).await;
+    set_test_user_discord_id(&db, user_id, "123456789").await;
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+    let level_id = create_test_level(&db).await;
+    let gd_id = get_test_level(&db, level_id).await.level_id;
+
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(GET).path("/players/123456789");
+            then.status(200).json_body(json!({
+                "data": {
+                    "completions": [
+                        {"level": gd_id, "video": "https://www.youtube.com/watch?v=dQw4w9WgXcQ", "mobile": true},
+                        {"level": 999_999_999, "video": "https://www.youtube.com/watch?v=abcdefghijk", "mobile": false},
+                    ]
+                }
+            }));
+        })
+        .await;
+    let source_id =
+        create_test_json_source(&db, &format!("{}/players", server.base_url()), false).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/external-sources/{source_id}/sync?dry_run=true"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert!(res.status().is_success(), "status is {}", res.status());
+    let body: serde_json::Value = read_body_json(res).await;
+    assert_eq!(body["dry_run"], true);
+    let changes = body["changes"].as_array().unwrap();
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0]["action"], "Create");
+    assert_eq!(changes[0]["level_id"], level_id.to_string());
+    assert_eq!(changes[0]["after"]["mobile"], true);
+    assert_eq!(changes[0]["after"]["accepted"], false);
+    assert!(changes[0]["submission_id"].is_null());
+    assert_eq!(changes[1]["action"], "Skipped");
+
+    // the dry run did not create anything
+    let req = test::TestRequest::post()
+        .uri(&format!("/external-sources/{source_id}/sync"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert!(res.status().is_success(), "status is {}", res.status());
+    let body: serde_json::Value = read_body_json(res).await;
+    assert_eq!(body["changes"][0]["action"], "Create");
+    let submission_id = body["changes"][0]["submission_id"].as_str().unwrap();
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/aredl/submissions/{submission_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert!(res.status().is_success(), "status is {}", res.status());
+    let submission: serde_json::Value = read_body_json(res).await;
+    assert_eq!(submission["status"], "Pending");
+    assert_eq!(submission["mobile"], true);
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/external-sources/{source_id}/sync"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    let body: serde_json::Value = read_body_json(res).await;
+    assert_eq!(body["changes"][0]["action"], "Unchanged");
+    assert_eq!(body["changes"][0]["submission_id"], submission_id);
+}
+
+#[actix_web::test]
+async fn failed_syncs_are_logged() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, None).await;
+    set_test_user_discord_id(&db, user_id, "987654321").await;
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+    let (staff_id, _) = create_test_user(&db, Some(Permission::ExternalSyncManage)).await;
+    let staff_token = create_test_token(staff_id, &auth.jwt_encoding_key).unwrap();
+
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(GET).path("/players/987654321");
+            then.status(500)
+                .json_body(json!({"message": "internal error"}));
+        })
+        .await;
+    let source_id =
+        create_test_json_source(&db, &format!("{}/players", server.base_url()), true).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/external-sources/{source_id}/sync"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 502);
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/external-sources/logs?source_id={source_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 403);
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/external-sources/logs?source_id={source_id}"))
+        .insert_header(("Authorization", format!("Bearer {staff_token}")))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert!(res.status().is_success(), "status is {}", res.status());
+    let body: serde_json::Value = read_body_json(res).await;
+    let logs = body["data"].as_array().unwrap();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0]["user_id"], user_id.to_string());
+    assert_eq!(logs[0]["succeeded"], false);
+    assert!(logs[0]["error"].as_str().unwrap().contains("500"));
+}
+
+#[actix_web::test]
+async fn create_and_patch_source() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (staff_id, _) = create_test_user(&db, Some(Permission::ExternalSyncManage)).await;
+    let token = create_test_token(staff_id, &auth.jwt_encoding_key).unwrap();
+
+    let mapping = json!({
+        "records": "/records",
+        "level_id": "/level_id",
+        "video_url": "/video",
+    });
+
+    // platformer sources must map completion times
+    let req = test::TestRequest::post()
+        .uri("/external-sources")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({
+            "name": "otherlist",
+            "importer": "json",
+            "base_url": "https://example.com/api/players",
+            "field_mapping": mapping,
+            "target_list": "arepl",
+        }))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 400);
+
+    let req = test::TestRequest::post()
+        .uri("/external-sources")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({
+            "name": "otherlist",
+            "importer": "json",
+            "base_url": "https://example.com/api/players",
+            "field_mapping": mapping,
+            "target_list": "aredl",
+        }))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert!(res.status().is_success(), "status is {}", res.status());
+    let source: serde_json::Value = read_body_json(res).await;
+    assert_eq!(source["auto_accept"], false);
+    assert_eq!(source["enabled"], true);
+    let source_id = source["id"].as_str().unwrap();
+
+    let req = test::TestRequest::patch()
+        .uri(&format!("/external-sources/{source_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"target_list": "arepl"}))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 400);
+
+    let req = test::TestRequest::patch()
+        .uri(&format!("/external-sources/{source_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"auto_accept": true, "enabled": false}))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert!(res.status().is_success(), "status is {}", res.status());
+    let source: serde_json::Value = read_body_json(res).await;
+    assert_eq!(source["auto_accept"], true);
+    assert_eq!(source["enabled"], false);
+    assert_eq!(source["field_mapping"]["records"], "/records");
+
+    let req = test::TestRequest::get()
+        .uri("/external-sources")
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    let sources: serde_json::Value = read_body_json(res).await;
+    let names = sources
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|source| source["name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert!(names.contains(&"pemonlist"));
+    assert!(names.contains(&"otherlist"));
+}
+
+#[actix_web::test]
+async fn scheduled_sync_reports_records_removed_upstream() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, None).await;
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+    let level_id = create_test_level(&db).await;
+    let gd_id = get_test_level(&db, level_id).await.level_id;
+
+    let server = MockServer::start_async().await;
+    let listed = server
+        .mock_async(|when, then| {
+            when.method(GET).path("/players/24680");
+            then.status(200).json_body(json!({
+                "data": {
+                    "completions": [
+                        {"level": gd_id, "video": "https://www.youtube.com/watch?v=dQw4w9WgXcQ", "mobile": false},
+                    ]
+                }
+            }));
+        })
+        .await;
+    let source_id =
+        create_test_json_source(&db, &format!("{}/players", server.base_url()), true).await;
+
+    // users without a discord account cannot be synced
+    let req = test::TestRequest::post()
+        .uri(&format!("/external-sources/{source_id}/subscription"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 422);
+
+    set_test_user_discord_id(&db, user_id, "24680").await;
+    let req = test::TestRequest::post()
+        .uri(&format!("/external-sources/{source_id}/subscription"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert!(res.status().is_success(), "status is {}", res.status());
+
+    let providers = providers_init_app_state(db.clone()).await;
+    let outcomes = ExternalSyncSubscription::sync_all(&db, &providers, 10)
+        .await
+        .unwrap();
+    assert_eq!(outcomes.len(), 1);
+    assert_eq!(outcomes[0].user_id, user_id);
+    assert_eq!(outcomes[0].created, 1);
+    assert!(outcomes[0].error.is_none());
+
+    listed.delete_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(GET).path("/players/24680");
+            then.status(200)
+                .json_body(json!({"data": {"completions": []}}));
+        })
+        .await;
+
+    let outcomes = ExternalSyncSubscription::sync_all(&db, &providers, 10)
+        .await
+        .unwrap();
+    assert_eq!(outcomes[0].created, 0);
+    assert_eq!(outcomes[0].removed, 1);
+
+    let req = test::TestRequest::get()
+        .uri("/external-sources/subscriptions")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert!(res.status().is_success(), "status is {}", res.status());
+    let subscriptions: serde_json::Value = read_body_json(res).await;
+    let subscriptions = subscriptions.as_array().unwrap();
+    assert_eq!(subscriptions.len(), 1);
+    let last_sync = &subscriptions[0]["last_sync"];
+    assert_eq!(last_sync["removed_count"], 1);
+    assert!(last_sync["triggered_by"].is_null());
+    let removed = &last_sync["changes"][0];
+    assert_eq!(removed["action"], "Removed");
+    assert_eq!(removed["level_id"], level_id.to_string());
+
+    // the submission is kept for staff to review
+    let submission_id = removed["submission_id"].as_str().unwrap();
+    let req = test::TestRequest::get()
+        .uri(&format!("/aredl/submissions/{submission_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert!(res.status().is_success(), "status is {}", res.status());
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/external-sources/{source_id}/subscription"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 204);
+    let outcomes = ExternalSyncSubscription::sync_all(&db, &providers, 10)
+        .await
+        .unwrap();
+    assert!(outcomes.is_empty());
+}
+
+#[actix_web::test]
+async fn records_match_two_player_levels_by_level_id() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, None).await;
+    set_test_user_discord_id(&db, user_id, "246813579").await;
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+
+    let two_player_level = create_test_level(&db).await;
+    set_test_level_two_player(&db, two_player_level, true).await;
+    let two_player_gd_id = get_test_level(&db, two_player_level).await.level_id;
+
+    // both versions of this level are on the list
+    let solo_level = create_test_level(&db).await;
+    let shared_gd_id = get_test_level(&db, solo_level).await.level_id;
+    let shared_level = create_test_level(&db).await;
+    set_test_level_two_player(&db, shared_level, true).await;
+    set_test_level_gd_id(&db, shared_level, shared_gd_id).await;
+
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(GET).path("/players/246813579");
+            then.status(200).json_body(json!({
+                "data": {
+                    "completions": [
+                        {"level": two_player_gd_id, "video": "https://www.youtube.com/watch?v=dQw4w9WgXcQ", "mobile": false},
+                        {"level": shared_gd_id, "video": "https://www.youtube.com/watch?v=abcdefghijk", "mobile": false},
+                    ]
+                }
+            }));
+        })
+        .await;
+    let source_id =
+        create_test_json_source(&db, &format!("{}/players", server.base_url()), false).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/external-sources/{source_id}/sync?dry_run=true"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert!(res.status().is_success(), "status is {}", res.status());
+    let body: serde_json::Value = read_body_json(res).await;
+    let changes = body["changes"].as_array().unwrap();
+    assert_eq!(changes[0]["action"], "Create");
+    assert_eq!(changes[0]["level_id"], two_player_level.to_string());
+    assert!(changes[0]["two_player"].is_null());
+    assert_eq!(changes[1]["action"], "Skipped");
+}
+
+#[actix_web::test]
+async fn malformed_formatted_times_fail_the_sync() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, None).await;
+    set_test_user_discord_id(&db, user_id, "135792468").await;
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(GET).path("/players/135792468");
+            then.status(200).json_body(json!({
+                "data": {
+                    "completions": [
+                        {"level": 1, "video": "https://www.youtube.com/watch?v=dQw4w9WgXcQ", "time": "0:01:02.12é"},
+                    ]
+                }
+            }));
+        })
+        .await;
+    let source_id =
+        create_test_platformer_json_source(&db, &format!("{}/players", server.base_url())).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/external-sources/{source_id}/sync"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 502);
+}
+
+#[actix_web::test]
+async fn sync_leaves_reviewed_and_accepted_submissions_alone() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, None).await;
+    set_test_user_discord_id(&db, user_id, "112233445").await;
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+
+    let mut submissions = Vec::new();
+    let mut completions = Vec::new();
+    for (status, locked) in [
+        (SubmissionStatus::Claimed, false),
+        (SubmissionStatus::UnderReview, false),
+        (SubmissionStatus::Pending, true),
+        (SubmissionStatus::Accepted, false),
+    ] {
+        let level_id = create_test_level(&db).await;
+        let submission_id = create_test_submission(level_id, user_id, &db).await;
+        set_test_submission_status(&db, submission_id, status.clone());
+        set_test_submission_locked(&db, submission_id, locked);
+        submissions.push((submission_id, status));
+        completions.push(json!({
+            "level": get_test_level(&db, level_id).await.level_id,
+            "video": "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+            "mobile": false,
+        }));
+    }
+
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(GET).path("/players/112233445");
+            then.status(200)
+                .json_body(json!({ "data": { "completions": completions } }));
+        })
+        .await;
+    let source_id =
+        create_test_json_source(&db, &format!("{}/players", server.base_url()), false).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/external-sources/{source_id}/sync"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert!(res.status().is_success(), "status is {}", res.status());
+    let body: serde_json::Value = read_body_json(res).await;
+    let changes = body["changes"].as_array().unwrap();
+    assert_eq!(changes.len(), submissions.len());
+    for change in changes {
+        assert_eq!(change["action"], "Skipped");
+    }
+
+    // nothing was reset to pending
+    for (submission_id, status) in submissions {
+        let submission = get_test_submission(&db, submission_id);
+        assert_eq!(submission.status, status);
+        assert_ne!(
+            submission.video_url,
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ"
+        );
+    }
+}
+
+#[actix_web::test]
+async fn synced_submissions_flag_reused_videos() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, None).await;
+    set_test_user_discord_id(&db, user_id, "1122334455").await;
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+    let (other_id, _) = create_test_user(&db, None).await;
+    let other_token = create_test_token(other_id, &auth.jwt_encoding_key).unwrap();
+    let (reviewer_id, _) = create_test_user(&db, Some(Permission::SubmissionReview)).await;
+    let reviewer_token = create_test_token(reviewer_id, &auth.jwt_encoding_key).unwrap();
+    let level_id = create_test_level(&db).await;
+    let gd_id = get_test_level(&db, level_id).await.level_id;
+
+    let req = test::TestRequest::post()
+        .uri("/aredl/submissions/")
+        .insert_header(("Authorization", format!("Bearer {other_token}")))
+        .set_json(json!({
+            "level_id": level_id,
+            "video_url": "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+            "raw_url": "https://raw.com",
+            "mobile": false
+        }))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert!(res.status().is_success(), "status is {}", res.status());
+
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(GET).path("/players/1122334455");
+            then.status(200).json_body(json!({
+                "data": {
+                    "completions": [
+                        {"level": gd_id, "video": "https://youtu.be/dQw4w9WgXcQ", "mobile": false},
+                    ]
+                }
+            }));
+        })
+        .await;
+    let source_id =
+        create_test_json_source(&db, &format!("{}/players", server.base_url()), false).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/external-sources/{source_id}/sync"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert!(res.status().is_success(), "status is {}", res.status());
+    let body: serde_json::Value = read_body_json(res).await;
+    assert_eq!(body["changes"][0]["action"], "Create");
+    let submission_id = body["changes"][0]["submission_id"].as_str().unwrap();
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/aredl/submissions/{submission_id}"))
+        .insert_header(("Authorization", format!("Bearer {reviewer_token}")))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert!(res.status().is_success(), "status is {}", res.status());
+    let submission: serde_json::Value = read_body_json(res).await;
+    assert_eq!(submission["status"], "Pending");
+    assert_eq!(submission["video_reused"], true);
+}
@@ -20,23 +20,33 @@ mod auth;
 mod cache_control;
 mod clans;
 mod docs;
+mod external_sync;
 mod health;
 mod notifications;
 mod page_helper;
+mod reviewer_conflicts;
 mod roles;
 mod scheduled;
 mod shifts;
+mod submission_reasons;
+mod submission_requirements;
 mod users;
 mod utils;
+mod webhooks;
 
 use crate::app_data::{auth as auth_data, db};
+use crate::aredl::submissions::queue::QueueThroughputCache as AredlQueueThroughputCache;
+use crate::arepl::submissions::queue::QueueThroughputCache as AreplQueueThroughputCache;
 use crate::cache_control::CacheController;
 use crate::docs::ApiDoc;
 use crate::error_handler::{ConfigError, StartupError};
 use crate::scheduled::{
-    data_cleaner::start_data_cleaner, refresh_discord_avatars::start_discord_avatars_refresher,
+    data_cleaner::start_data_cleaner, dead_link_scanner::start_dead_link_scanner,
+    external_sync::start_external_sync, raw_footage_probe::start_raw_footage_prober,
+    refresh_discord_avatars::start_discord_avatars_refresher,
     refresh_level_data::start_level_data_refresher, refresh_matviews::start_matviews_refresher,
     shifts_creator::start_recurrent_shift_creator, sync_patreon_plus::start_patreon_plus_sync,
+    webhook_delivery::start_webhook_delivery,
 };
 use actix_cors::Cors;
 use actix_http::StatusCode;
@@ -52,11 +62,13 @@ use listenfd::ListenFd;
 use notifications::WebsocketNotification;
 use std::env;
 use std::fs;
+use std::sync::Arc;
 use tokio::sync::broadcast;
 use tracing::Span;
 use tracing_actix_web::{root_span, DefaultRootSpanBuilder, RootSpanBuilder, TracingLogger};
 use tracing_subscriber::fmt::format::FmtSpan;
 use tracing_subscriber::EnvFilter;
+use users::me::notifications::Notification;
 use utoipa::OpenApi as _;
 use utoipa_rapidoc::RapiDoc;
 
@@ -88,6 +100,7 @@ async fn main() -> Result<(), StartupError> {
     tracing::info!("Initializing...");
 
     let (notify_tx, _notify_rx) = broadcast::channel::<WebsocketNotification>(100);
+    let (inbox_tx, _inbox_rx) = broadcast::channel::<Notification>(256);
 
     let prometheus = PrometheusMetricsBuilder::new("api")
         .endpoint("/metrics")
@@ -96,6 +109,10 @@ async fn main() -> Result<(), StartupError> {
         .build()
         .map_err(|error| StartupError::Init(format!("Failed to start Prometheus: {error}")))?;
 
+    providers::metrics::register_metrics(&prometheus.registry).map_err(|error| {
+        StartupError::Init(format!("Failed to register provider metrics: {error}"))
+    })?;
+
     let db_app_state = db::init_app_state()?;
 
     let auth_app_state = auth_data::init_app_state()?;
@@ -104,6 +121,9 @@ async fn main() -> Result<(), StartupError> {
 
     db_app_state.run_pending_migrations()?;
 
+    let aredl_queue_throughput = Arc::new(AredlQueueThroughputCache::default());
+    let arepl_queue_throughput = Arc::new(AreplQueueThroughputCache::default());
+
     start_matviews_refresher(db_app_state.clone()).await?;
 
     start_data_cleaner(db_app_state.clone(), notify_tx.clone()).await?;
@@ -116,6 +136,19 @@ async fn main() -> Result<(), StartupError> {
 
     start_patreon_plus_sync(db_app_state.clone(), providers_app_state.clone()).await?;
 
+    start_webhook_delivery(db_app_state.clone()).await?;
+
+    start_raw_footage_prober(db_app_state.clone(), providers_app_state.clone()).await?;
+    start_dead_link_scanner(
+        db_app_state.clone(),
+        providers_app_state.clone(),
+        notify_tx.clone(),
+        inbox_tx.clone(),
+    )
+    .await?;
+
+    start_external_sync(db_app_state.clone(), providers_app_state.clone()).await?;
+
     let mut listenfd = ListenFd::from_env();
     let mut server = HttpServer::new(move || {
         let cors = Cors::permissive();
@@ -154,6 +187,9 @@ async fn main() -> Result<(), StartupError> {
                     .app_data(web::Data::new(db_app_state.clone()))
                     .app_data(web::Data::new(providers_app_state.clone()))
                     .app_data(web::Data::new(notify_tx.clone()))
+                    .app_data(web::Data::new(inbox_tx.clone()))
+                    .app_data(web::Data::new(aredl_queue_throughput.clone()))
+                    .app_data(web::Data::new(arepl_queue_throughput.clone()))
                     .wrap(CacheController::default_no_store())
                     .wrap(NormalizePath::trim())
                     .wrap(TracingLogger::<AppRootSpanBuilder>::new())
@@ -167,7 +203,12 @@ async fn main() -> Result<(), StartupError> {
                     .configure(notifications::init_routes)
                     .configure(health::init_routes)
                     .configure(shifts::init_routes)
-                    .configure(utils::init_routes),
+                    .configure(utils::init_routes)
+                    .configure(webhooks::init_routes)
+                    .configure(submission_reasons::init_routes)
+                    .configure(reviewer_conflicts::init_routes)
+                    .configure(submission_requirements::init_routes)
+                    .configure(external_sync::init_routes),
             )
             .service(
                 RapiDoc::with_openapi("/openapi.json", ApiDoc::openapi())
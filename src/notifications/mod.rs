@@ -1,6 +1,8 @@
 mod model;
 mod routes;
+mod subscription;
 mod tests;
 
 pub use model::*;
 pub use routes::{init_routes, ApiDoc};
+pub use subscription::*;
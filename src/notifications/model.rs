@@ -1,20 +1,125 @@
+use crate::app_data::db::DbConnection;
+use crate::error_handler::ApiError;
+use crate::schema::notification_events;
+use crate::webhooks::Webhook;
+use chrono::{DateTime, Utc};
+use diesel::dsl::max;
+use diesel::pg::Pg;
+use diesel::sql_types::BigInt;
 use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter, EnumString};
 use tokio::sync::broadcast;
 use utoipa::ToSchema;
 
-#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+use diesel::prelude::*;
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    ToSchema,
+    Display,
+    EnumString,
+    EnumIter,
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+/// Catalogue of every event type that can be emitted on the notifications websocket.
+pub enum WebsocketNotificationType {
+    /// A new submission was created.
+    SubmissionCreated,
+    /// A submission was accepted.
+    SubmissionAccepted,
+    /// A submission was denied.
+    SubmissionDenied,
+    /// A submission was put under consideration.
+    SubmissionUnderConsideration,
+    /// A submission was put under review.
+    SubmissionUnderReview,
+    /// A shift reached its target count.
+    ShiftCompleted,
+    /// The daily recurring shifts were created.
+    ShiftsCreated,
+    /// Running shifts expired before being completed.
+    ShiftsMissed,
+    /// A new level was placed on a list.
+    LevelPlaced,
+    /// A level was raised on a list.
+    LevelRaised,
+    /// A level was lowered on a list.
+    LevelLowered,
+    /// Two adjacent levels swapped positions.
+    LevelSwapped,
+    /// A level was moved to the legacy list.
+    LevelMovedToLegacy,
+    /// A level was moved back to the main list from the legacy list.
+    LevelMovedFromLegacy,
+    /// A level was removed from a list.
+    LevelRemoved,
+    /// The video of an accepted record is no longer available.
+    RecordVideoUnavailable,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    ToSchema,
+    Display,
+    EnumString,
+    EnumIter,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+/// The list a notification is related to.
+pub enum NotificationList {
+    Aredl,
+    Arepl,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, Queryable, Selectable)]
+#[diesel(table_name = notification_events, check_for_backend(Pg))]
 pub struct WebsocketNotification {
+    /// Monotonically increasing sequence ID of this event. Pass the last received one as `since` when reconnecting to replay missed events.
+    /// 0 for events that could not be stored, these can't be replayed.
+    pub id: i64,
+    /// The type of the event, e.g. `SUBMISSION_ACCEPTED`. See [WebsocketNotificationType] for the full catalogue.
     pub notification_type: String,
+    /// The payload of the event. Its shape depends on the notification type.
     pub data: serde_json::Value,
+    /// Timestamp of when this event was emitted.
+    pub created_at: DateTime<Utc>,
+    /// The list this event is related to (`aredl` or `arepl`), if any.
+    pub list: Option<String>,
 }
 
 impl WebsocketNotification {
+    /// Maximum amount of events loaded at once when replaying missed events.
+    pub const REPLAY_BATCH_SIZE: i64 = 500;
+    /// Advisory lock serializing the sequence ids of stored events.
+    const SEQUENCE_LOCK_KEY: i64 = 0x6e6f_7469_6679;
+
+    /// Whether this event was stored and can be replayed.
+    pub fn is_stored(&self) -> bool {
+        self.id > 0
+    }
+
     pub fn send<T: Serialize>(
+        conn: &mut DbConnection,
         notify_tx: &broadcast::Sender<WebsocketNotification>,
-        notification_type: impl Into<String>,
+        notification_type: WebsocketNotificationType,
+        list: Option<NotificationList>,
         data: &T,
     ) {
-        let notification_type = notification_type.into();
         let data = match serde_json::to_value(data) {
             Ok(data) => data,
             Err(error) => {
@@ -25,16 +130,84 @@ impl WebsocketNotification {
             }
         };
 
-        match notify_tx.send(WebsocketNotification {
-            notification_type: notification_type.clone(),
-            data,
-        }) {
-            Ok(_) => {}
+        // persist first so that the event gets its sequence id and can be replayed by clients that missed it.
+        // ids are handed out under a lock held until commit, so events become visible in id order and a
+        // replay never skips an event committed after a later one
+        let persisted = conn.transaction(|conn| {
+            diesel::sql_query("SELECT pg_advisory_xact_lock($1);")
+                .bind::<BigInt, _>(Self::SEQUENCE_LOCK_KEY)
+                .execute(conn)?;
+            diesel::insert_into(notification_events::table)
+                .values((
+                    notification_events::notification_type.eq(notification_type.to_string()),
+                    notification_events::data.eq(&data),
+                    notification_events::list.eq(list.map(|list| list.to_string())),
+                ))
+                .returning(WebsocketNotification::as_select())
+                .get_result::<WebsocketNotification>(conn)
+        });
+
+        // live receivers still get the event if it could not be stored
+        let notification = match persisted {
+            Ok(notification) => {
+                if let Err(error) = Webhook::enqueue_deliveries(conn, &notification) {
+                    tracing::error!(
+                        "Failed to queue webhook deliveries for {notification_type} notification: {error}"
+                    );
+                }
+                notification
+            }
             Err(error) => {
                 tracing::error!(
-                    "Failed to send {notification_type} websocket notification: {error}"
+                    "Failed to persist {notification_type} websocket notification: {error}"
                 );
+                WebsocketNotification {
+                    id: 0,
+                    notification_type: notification_type.to_string(),
+                    data,
+                    created_at: Utc::now(),
+                    list: list.map(|list| list.to_string()),
+                }
             }
+        };
+
+        // no receivers is not an error, the event can still be replayed later
+        if notify_tx.receiver_count() == 0 {
+            return;
+        }
+
+        if let Err(error) = notify_tx.send(notification) {
+            tracing::error!("Failed to send {notification_type} websocket notification: {error}");
         }
     }
+
+    /// Returns the sequence id of the latest emitted event, or 0 if none were emitted yet.
+    pub fn latest_id(conn: &mut DbConnection) -> Result<i64, ApiError> {
+        let latest = notification_events::table
+            .select(max(notification_events::id))
+            .first::<Option<i64>>(conn)?;
+        Ok(latest.unwrap_or(0))
+    }
+
+    /// Finds the events emitted after the given sequence id, oldest first.
+    pub fn find_since(
+        conn: &mut DbConnection,
+        since: i64,
+        limit: i64,
+    ) -> Result<Vec<WebsocketNotification>, ApiError> {
+        let events = notification_events::table
+            .filter(notification_events::id.gt(since))
+            .order(notification_events::id.asc())
+            .limit(limit)
+            .select(WebsocketNotification::as_select())
+            .load::<WebsocketNotification>(conn)?;
+        Ok(events)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WebsocketQueryOptions {
+    pub since: Option<i64>,
+    pub topics: Option<String>,
+    pub lists: Option<String>,
 }
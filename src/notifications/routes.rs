@@ -1,21 +1,94 @@
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
 use actix_web::{get, web, Error, HttpRequest, HttpResponse};
-use actix_ws::{handle, Message};
+use actix_ws::{handle, Message, Session};
 use futures_util::StreamExt as _;
+use serde::Serialize;
 use tokio::{sync::broadcast, time::interval};
 use utoipa::OpenApi;
 
 use crate::{
+    app_data::db::DbAppState,
     auth::{Permission, UserAuth},
-    notifications::WebsocketNotification,
+    error_handler::ApiError,
+    notifications::{
+        NotificationFilter, NotificationList, SubscriptionRequest, SubscriptionResponse,
+        WebsocketNotification, WebsocketNotificationType, WebsocketQueryOptions,
+    },
 };
 
+// Sends every persisted event after `last_id` matching the filter to the session, returns the id of the last
+// replayed event, or None if the session should be closed.
+async fn replay_since(
+    db: &Arc<DbAppState>,
+    session: &mut Session,
+    filter: &NotificationFilter,
+    mut last_id: i64,
+) -> Option<i64> {
+    loop {
+        let db = db.clone();
+        let events = match web::block(move || {
+            WebsocketNotification::find_since(
+                &mut db.connection()?,
+                last_id,
+                WebsocketNotification::REPLAY_BATCH_SIZE,
+            )
+        })
+        .await
+        {
+            Ok(events) => events,
+            Err(error) => Err(ApiError::from(error)),
+        };
+
+        let events = match events {
+            Ok(events) => events,
+            Err(error) => {
+                tracing::error!("Failed to load websocket notifications to replay: {error}");
+                return None;
+            }
+        };
+
+        let batch_len = events.len();
+
+        for event in events {
+            last_id = event.id;
+            if !filter.matches(&event) {
+                continue;
+            }
+            if !send_frame(session, &event).await {
+                return None;
+            }
+        }
+
+        if i64::try_from(batch_len).unwrap_or(i64::MAX) < WebsocketNotification::REPLAY_BATCH_SIZE {
+            return Some(last_id);
+        }
+    }
+}
+
+// Returns false if the session is closed
+async fn send_frame<T: Serialize>(session: &mut Session, frame: &T) -> bool {
+    let text = match serde_json::to_string(frame) {
+        Ok(text) => text,
+        Err(error) => {
+            tracing::error!("failed to serialize WebSocket frame: {error}");
+            return true;
+        }
+    };
+
+    session.text(text).await.is_ok()
+}
+
 #[utoipa::path(
     get,
     summary = "[Staff]Subscribe to notifications",
-    description = "Upgrades the HTTP connection to a WebSocket. This websocker will receive notifications about events such as accepted/denied submissions. This is mainly used by the discord bot.",
+    description = "Upgrades the HTTP connection to a WebSocket. This websocket will receive notifications about events such as accepted/denied submissions. This is mainly used by the discord bot.\n\nEvery notification carries a monotonically increasing `id`. When reconnecting, pass the last received `id` as `since` to first replay every event that was missed before switching to live delivery.",
     tag = "Notifications",
+    params(
+        ("since" = Option<i64>, Query, description = "Replay all the events emitted after this event id before delivering live events"),
+        ("topics" = Option<String>, Query, description = "Comma separated event types to subscribe to initially, e.g. `SUBMISSION_*,SHIFT_COMPLETED`"),
+        ("lists" = Option<String>, Query, description = "Comma separated lists to subscribe to initially, `aredl` or `arepl`"),
+    ),
     responses(
         (status = 101, description = "Switching Protocols to WebSocket"),
         (status = 401, description = "Unauthorized / invalid or missing token"),
@@ -33,66 +106,107 @@ use crate::{
 async fn notifications_websocket(
     req: HttpRequest,
     stream: web::Payload,
+    options: web::Query<WebsocketQueryOptions>,
+    db: web::Data<Arc<DbAppState>>,
     notify_tx: web::Data<broadcast::Sender<WebsocketNotification>>,
 ) -> Result<HttpResponse, Error> {
+    let db = db.get_ref().clone();
+    let options = options.into_inner();
+    let mut filter =
+        NotificationFilter::from_query(options.topics.as_deref(), options.lists.as_deref())?;
+    let since = if let Some(since) = options.since {
+        since
+    } else {
+        let db = db.clone();
+        web::block(move || WebsocketNotification::latest_id(&mut db.connection()?)).await??
+    };
+
     let (res, mut session, mut msg_stream) = handle(&req, stream)?;
+    // subscribe before replaying so that no event gets lost in between
     let mut rx = notify_tx.subscribe();
     let mut heartbeat = interval(Duration::from_secs(30));
 
     actix_rt::spawn(async move {
+        let Some(mut last_id) = replay_since(&db, &mut session, &filter, since).await else {
+            return;
+        };
+        // events up to this id were already delivered by a replay
+        let mut replayed_until = last_id;
+
         loop {
             tokio::select! {
                 _ = heartbeat.tick() => {
-                     if session.ping(&[]).await.is_err() {
-                    break;
-                }
+                    if session.ping(&[]).await.is_err() {
+                        break;
+                    }
                 }
 
-               message = msg_stream.next() => {
-                match message {
-                    Some(Ok(Message::Ping(payload))) => {
-                        if session.pong(&payload).await.is_err() {
+                message = msg_stream.next() => {
+                    match message {
+                        Some(Ok(Message::Ping(payload))) => {
+                            if session.pong(&payload).await.is_err() {
+                                break;
+                            }
+                        }
+                        Some(Ok(Message::Close(reason))) => {
+                            if let Err(error) = session.close(reason).await {
+                                tracing::debug!("Failed to close WebSocket session: {error}");
+                            }
                             break;
                         }
-                    }
-                    Some(Ok(Message::Close(reason))) => {
-                        if let Err(error) = session.close(reason).await {
-                            tracing::debug!("Failed to close WebSocket session: {error}");
+                        Some(Ok(Message::Text(text))) => {
+                            let response = match serde_json::from_str::<SubscriptionRequest>(&text) {
+                                Ok(request) => filter.apply(&request),
+                                Err(error) => SubscriptionResponse::Error {
+                                    message: format!("Invalid subscription request: {error}"),
+                                    unknown: Vec::new(),
+                                },
+                            };
+                            if !send_frame(&mut session, &response).await {
+                                break;
+                            }
                         }
-                        break;
-                    }
-                    Some(Ok(Message::Pong(_) | _))  => {}
-                    Some(Err(error)) => {
-                        tracing::debug!("WebSocket protocol error: {error}");
-                        break;
+                        Some(Ok(Message::Pong(_) | _))  => {}
+                        Some(Err(error)) => {
+                            tracing::debug!("WebSocket protocol error: {error}");
+                            break;
+                        }
+                        None => break,
                     }
-                    None => break,
                 }
-            }
-            notification = rx.recv() => {
-                match notification {
-                    Ok(notification) => {
-                        let text = match serde_json::to_string(&notification) {
-                            Ok(text) => text,
-                            Err(error) => {
-                                tracing::error!(
-                                    "failed to serialize WebSocket notification: {error}"
-                                );
+
+                notification = rx.recv() => {
+                    match notification {
+                        Ok(notification) => {
+                            if notification.is_stored() {
+                                // already delivered while replaying
+                                if notification.id <= replayed_until {
+                                    continue;
+                                }
+                                last_id = last_id.max(notification.id);
+                            }
+                            if !filter.matches(&notification) {
                                 continue;
                             }
-                        };
 
-                        if session.text(text).await.is_err() {
-                            break;
+                            if !send_frame(&mut session, &notification).await {
+                                break;
+                            }
                         }
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            tracing::warn!(
+                                "WebSocket subscriber skipped {skipped} notifications, replaying them"
+                            );
+                            match replay_since(&db, &mut session, &filter, last_id).await {
+                                Some(replayed_id) => {
+                                    last_id = replayed_id;
+                                    replayed_until = replayed_id;
+                                }
+                                None => break,
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
                     }
-                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
-                        tracing::warn!(
-                            "WebSocket subscriber skipped {skipped} notifications"
-                        );
-                    }
-                    Err(broadcast::error::RecvError::Closed) => break,
-                }
                 }
             }
         }
@@ -102,7 +216,13 @@ async fn notifications_websocket(
 
 #[derive(OpenApi)]
 #[openapi(
-    components(schemas(WebsocketNotification)),
+    components(schemas(
+        WebsocketNotification,
+        WebsocketNotificationType,
+        NotificationList,
+        SubscriptionRequest,
+        SubscriptionResponse
+    )),
     paths(notifications_websocket)
 )]
 pub struct ApiDoc;
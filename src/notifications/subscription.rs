@@ -0,0 +1,202 @@
+use crate::error_handler::ApiError;
+use crate::notifications::{NotificationList, WebsocketNotification, WebsocketNotificationType};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use strum::IntoEnumIterator as _;
+use utoipa::ToSchema;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SubscriptionAction {
+    /// Start receiving the given topics and lists.
+    Subscribe,
+    /// Stop receiving the given topics and lists.
+    Unsubscribe,
+}
+
+/// Frame sent by a client over the notifications websocket to change what it receives.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SubscriptionRequest {
+    pub action: SubscriptionAction,
+    /// Event types, e.g. `SUBMISSION_ACCEPTED`. A trailing `*` matches every type starting with
+    /// that prefix (`SUBMISSION_*`, `SHIFT_*`, `LEVEL_*`), a lone `*` matches all types.
+    #[serde(default)]
+    pub topics: Vec<String>,
+    /// Lists, either `aredl` or `arepl`.
+    #[serde(default)]
+    pub lists: Vec<String>,
+}
+
+/// Frame sent by the server in response to a [SubscriptionRequest].
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SubscriptionResponse {
+    /// The request was applied.
+    Ack {
+        action: SubscriptionAction,
+        /// Every event type the client is now subscribed to.
+        topics: Vec<WebsocketNotificationType>,
+        /// Every list the client is now subscribed to.
+        lists: Vec<NotificationList>,
+    },
+    /// The request was rejected and the subscription was left unchanged.
+    Error {
+        message: String,
+        /// Topics or lists that could not be resolved.
+        unknown: Vec<String>,
+    },
+}
+
+/// Selects the events delivered to a websocket client.
+/// Clients receive every topic and every list until they subscribe to specific ones.
+#[derive(Clone, Debug, Default)]
+pub struct NotificationFilter {
+    topics: Option<HashSet<WebsocketNotificationType>>,
+    lists: Option<HashSet<NotificationList>>,
+}
+
+fn resolve_topic(topic: &str) -> Vec<WebsocketNotificationType> {
+    let topic = topic.trim().to_uppercase();
+    match topic.strip_suffix('*') {
+        Some(prefix) => {
+            // `SHIFT_*` also covers the `SHIFTS_` events
+            let prefix = prefix.trim_end_matches('_');
+            WebsocketNotificationType::iter()
+                .filter(|notification_type| notification_type.to_string().starts_with(prefix))
+                .collect()
+        }
+        None => topic.parse().into_iter().collect(),
+    }
+}
+
+fn resolve_topics(
+    topics: &[String],
+    unknown: &mut Vec<String>,
+) -> HashSet<WebsocketNotificationType> {
+    let mut resolved = HashSet::new();
+    for topic in topics {
+        let matched = resolve_topic(topic);
+        if matched.is_empty() {
+            unknown.push(topic.clone());
+        }
+        resolved.extend(matched);
+    }
+    resolved
+}
+
+fn resolve_lists(lists: &[String], unknown: &mut Vec<String>) -> HashSet<NotificationList> {
+    let mut resolved = HashSet::new();
+    for list in lists {
+        match list.trim().to_lowercase().parse() {
+            Ok(list) => {
+                resolved.insert(list);
+            }
+            Err(_) => unknown.push(list.clone()),
+        }
+    }
+    resolved
+}
+
+fn split_query(value: Option<&str>) -> Vec<String> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+impl NotificationFilter {
+    /// Builds the initial filter from the comma separated `topics` and `lists` query parameters.
+    pub fn from_query(topics: Option<&str>, lists: Option<&str>) -> Result<Self, ApiError> {
+        let mut filter = NotificationFilter::default();
+        let request = SubscriptionRequest {
+            action: SubscriptionAction::Subscribe,
+            topics: split_query(topics),
+            lists: split_query(lists),
+        };
+        match filter.apply(&request) {
+            SubscriptionResponse::Ack { .. } => Ok(filter),
+            SubscriptionResponse::Error { unknown, .. } => Err(ApiError::BadRequest(format!(
+                "Unknown topics or lists: {}",
+                unknown.join(", ")
+            ))),
+        }
+    }
+
+    /// Applies a subscription request, leaving the filter unchanged if any topic or list is unknown.
+    pub fn apply(&mut self, request: &SubscriptionRequest) -> SubscriptionResponse {
+        let mut unknown = Vec::new();
+        let topics = resolve_topics(&request.topics, &mut unknown);
+        let lists = resolve_lists(&request.lists, &mut unknown);
+
+        if !unknown.is_empty() {
+            return SubscriptionResponse::Error {
+                message: format!("Unknown topics or lists: {}", unknown.join(", ")),
+                unknown,
+            };
+        }
+
+        match request.action {
+            SubscriptionAction::Subscribe => {
+                // the first subscription replaces the default of receiving everything
+                if !topics.is_empty() {
+                    self.topics.get_or_insert_with(HashSet::new).extend(topics);
+                }
+                if !lists.is_empty() {
+                    self.lists.get_or_insert_with(HashSet::new).extend(lists);
+                }
+            }
+            SubscriptionAction::Unsubscribe => {
+                if !topics.is_empty() {
+                    self.topics
+                        .get_or_insert_with(|| WebsocketNotificationType::iter().collect())
+                        .retain(|topic| !topics.contains(topic));
+                }
+                if !lists.is_empty() {
+                    self.lists
+                        .get_or_insert_with(|| NotificationList::iter().collect())
+                        .retain(|list| !lists.contains(list));
+                }
+            }
+        }
+
+        SubscriptionResponse::Ack {
+            action: request.action,
+            topics: WebsocketNotificationType::iter()
+                .filter(|topic| self.has_topic(*topic))
+                .collect(),
+            lists: NotificationList::iter()
+                .filter(|list| self.has_list(*list))
+                .collect(),
+        }
+    }
+
+    fn has_topic(&self, topic: WebsocketNotificationType) -> bool {
+        self.topics
+            .as_ref()
+            .is_none_or(|topics| topics.contains(&topic))
+    }
+
+    fn has_list(&self, list: NotificationList) -> bool {
+        self.lists
+            .as_ref()
+            .is_none_or(|lists| lists.contains(&list))
+    }
+
+    /// Whether the notification should be delivered. Events that are not tied to a list
+    /// (e.g. shifts) are delivered regardless of the subscribed lists.
+    pub fn matches(&self, notification: &WebsocketNotification) -> bool {
+        let topic_matches = match notification.notification_type.parse() {
+            Ok(topic) => self.has_topic(topic),
+            Err(_) => self.topics.is_none(),
+        };
+        let list_matches = match notification.list.as_deref().map(str::parse) {
+            None => true,
+            Some(Ok(list)) => self.has_list(list),
+            Some(Err(_)) => self.lists.is_none(),
+        };
+        topic_matches && list_matches
+    }
+}
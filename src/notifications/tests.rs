@@ -2,11 +2,16 @@
 use {
     crate::{
         auth::{create_test_token, Permission},
+        notifications::{
+            NotificationFilter, NotificationList, SubscriptionAction, SubscriptionRequest,
+            SubscriptionResponse, WebsocketNotification, WebsocketNotificationType,
+        },
         test_utils::init_test_app,
         users::test_utils::create_test_user,
     },
     actix_http::StatusCode,
     actix_web::{http::header, test},
+    diesel::RunQueryDsl as _,
     serde_json::json,
 };
 
@@ -45,16 +50,347 @@ async fn websocket_success() {
     );
 }
 
+#[actix_web::test]
+async fn websocket_with_since_success() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, Some(Permission::NotificationsSubscribe)).await;
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+
+    let req = ws_request("/notifications/websocket?since=0")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(
+        resp.status().as_u16(),
+        StatusCode::SWITCHING_PROTOCOLS.as_u16()
+    );
+}
+
+#[actix_web::test]
+async fn websocket_invalid_since() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, Some(Permission::NotificationsSubscribe)).await;
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+
+    let req = ws_request("/notifications/websocket?since=abc")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status().as_u16(), StatusCode::BAD_REQUEST.as_u16());
+}
+
 #[actix_web::test]
 async fn notification_broadcast() {
-    let (_app, _conn, _auth, notify_tx) = init_test_app().await;
+    let (_app, db, _auth, notify_tx) = init_test_app().await;
     let mut rx = notify_tx.subscribe();
 
-    let note = crate::notifications::WebsocketNotification {
-        notification_type: "test".into(),
-        data: json!({"hello": 1}),
-    };
-    notify_tx.send(note.clone()).unwrap();
+    WebsocketNotification::send(
+        &mut db.connection().unwrap(),
+        &notify_tx,
+        WebsocketNotificationType::SubmissionCreated,
+        Some(NotificationList::Aredl),
+        &json!({"hello": 1}),
+    );
     let received = rx.recv().await.unwrap();
-    assert_eq!(received.notification_type, note.notification_type);
+    assert_eq!(received.notification_type, "SUBMISSION_CREATED");
+    assert_eq!(received.list.as_deref(), Some("aredl"));
+    assert_eq!(received.data, json!({"hello": 1}));
+}
+
+#[actix_web::test]
+async fn notification_is_broadcast_when_it_cannot_be_stored() {
+    let (_app, db, _auth, notify_tx) = init_test_app().await;
+    let mut rx = notify_tx.subscribe();
+    let conn = &mut db.connection().unwrap();
+
+    // the test transaction is already open, so make every insert fail inside it
+    diesel::sql_query(
+        "ALTER TABLE notification_events ADD CONSTRAINT fail CHECK (false) NOT VALID;",
+    )
+    .execute(conn)
+    .unwrap();
+    WebsocketNotification::send(
+        conn,
+        &notify_tx,
+        WebsocketNotificationType::ShiftsMissed,
+        None,
+        &json!({"hello": 1}),
+    );
+
+    let received = rx.try_recv().unwrap();
+    assert_eq!(received.notification_type, "SHIFTS_MISSED");
+    assert!(!received.is_stored());
+}
+
+#[actix_web::test]
+async fn notification_is_persisted_without_subscribers() {
+    let (_app, db, _auth, notify_tx) = init_test_app().await;
+    let conn = &mut db.connection().unwrap();
+
+    let before = WebsocketNotification::latest_id(conn).unwrap();
+    WebsocketNotification::send(
+        conn,
+        &notify_tx,
+        WebsocketNotificationType::ShiftsCreated,
+        None,
+        &json!({"hello": 1}),
+    );
+
+    let events = WebsocketNotification::find_since(conn, before, 10).unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].notification_type, "SHIFTS_CREATED");
+    assert_eq!(events[0].list, None);
+    assert_eq!(events[0].data, json!({"hello": 1}));
+    assert_eq!(
+        WebsocketNotification::latest_id(conn).unwrap(),
+        events[0].id
+    );
+}
+
+#[actix_web::test]
+async fn notification_replay_since_cursor() {
+    let (_app, db, _auth, notify_tx) = init_test_app().await;
+    let mut rx = notify_tx.subscribe();
+
+    for i in 0..3 {
+        WebsocketNotification::send(
+            &mut db.connection().unwrap(),
+            &notify_tx,
+            WebsocketNotificationType::SubmissionAccepted,
+            Some(NotificationList::Arepl),
+            &json!({ "i": i }),
+        );
+    }
+    let first = rx.recv().await.unwrap();
+    let second = rx.recv().await.unwrap();
+    let third = rx.recv().await.unwrap();
+    assert!(first.id < second.id && second.id < third.id);
+
+    let conn = &mut db.connection().unwrap();
+
+    let replayed = WebsocketNotification::find_since(conn, first.id, 10).unwrap();
+    let replayed_ids = replayed.iter().map(|event| event.id).collect::<Vec<_>>();
+    assert_eq!(replayed_ids, vec![second.id, third.id]);
+    assert_eq!(replayed[0].data, json!({ "i": 1 }));
+
+    let limited = WebsocketNotification::find_since(conn, first.id, 1).unwrap();
+    assert_eq!(limited.len(), 1);
+    assert_eq!(limited[0].id, second.id);
+
+    assert!(WebsocketNotification::find_since(conn, third.id, 10)
+        .unwrap()
+        .is_empty());
+}
+
+#[cfg(test)]
+fn test_notification(
+    notification_type: WebsocketNotificationType,
+    list: Option<NotificationList>,
+) -> WebsocketNotification {
+    WebsocketNotification {
+        id: 1,
+        notification_type: notification_type.to_string(),
+        data: json!({}),
+        created_at: chrono::Utc::now(),
+        list: list.map(|list| list.to_string()),
+    }
+}
+
+#[cfg(test)]
+fn subscription_request(
+    action: SubscriptionAction,
+    topics: &[&str],
+    lists: &[&str],
+) -> SubscriptionRequest {
+    SubscriptionRequest {
+        action,
+        topics: topics.iter().map(|topic| (*topic).to_owned()).collect(),
+        lists: lists.iter().map(|list| (*list).to_owned()).collect(),
+    }
+}
+
+#[actix_web::test]
+async fn websocket_with_topics_success() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, Some(Permission::NotificationsSubscribe)).await;
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+
+    let req =
+        ws_request("/notifications/websocket?topics=SUBMISSION_*,SHIFT_COMPLETED&lists=arepl")
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(
+        resp.status().as_u16(),
+        StatusCode::SWITCHING_PROTOCOLS.as_u16()
+    );
+}
+
+#[actix_web::test]
+async fn websocket_unknown_topic() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, Some(Permission::NotificationsSubscribe)).await;
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+
+    let req = ws_request("/notifications/websocket?topics=NOT_A_TOPIC")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status().as_u16(), StatusCode::BAD_REQUEST.as_u16());
+}
+
+#[actix_web::test]
+async fn subscription_filters_topics_and_lists() {
+    let mut filter = NotificationFilter::default();
+    assert!(filter.matches(&test_notification(
+        WebsocketNotificationType::LevelRaised,
+        Some(NotificationList::Aredl)
+    )));
+
+    let response = filter.apply(&subscription_request(
+        SubscriptionAction::Subscribe,
+        &["SUBMISSION_*"],
+        &["arepl"],
+    ));
+    let SubscriptionResponse::Ack { topics, lists, .. } = response else {
+        panic!("expected an ack, got {response:?}");
+    };
+    assert_eq!(
+        topics,
+        vec![
+            WebsocketNotificationType::SubmissionCreated,
+            WebsocketNotificationType::SubmissionAccepted,
+            WebsocketNotificationType::SubmissionDenied,
+            WebsocketNotificationType::SubmissionUnderConsideration,
+            WebsocketNotificationType::SubmissionUnderReview,
+        ]
+    );
+    assert_eq!(lists, vec![NotificationList::Arepl]);
+
+    assert!(filter.matches(&test_notification(
+        WebsocketNotificationType::SubmissionAccepted,
+        Some(NotificationList::Arepl)
+    )));
+    assert!(!filter.matches(&test_notification(
+        WebsocketNotificationType::SubmissionAccepted,
+        Some(NotificationList::Aredl)
+    )));
+    assert!(!filter.matches(&test_notification(
+        WebsocketNotificationType::ShiftsCreated,
+        None
+    )));
+
+    filter.apply(&subscription_request(
+        SubscriptionAction::Subscribe,
+        &["SHIFT_*"],
+        &[],
+    ));
+    // shifts are not tied to a list
+    for notification_type in [
+        WebsocketNotificationType::ShiftCompleted,
+        WebsocketNotificationType::ShiftsCreated,
+        WebsocketNotificationType::ShiftsMissed,
+    ] {
+        assert!(filter.matches(&test_notification(notification_type, None)));
+    }
+
+    filter.apply(&subscription_request(
+        SubscriptionAction::Unsubscribe,
+        &["SUBMISSION_DENIED"],
+        &[],
+    ));
+    assert!(!filter.matches(&test_notification(
+        WebsocketNotificationType::SubmissionDenied,
+        Some(NotificationList::Arepl)
+    )));
+}
+
+#[actix_web::test]
+async fn subscription_documented_shift_pattern() {
+    let mut filter = NotificationFilter::default();
+    let response = filter.apply(&subscription_request(
+        SubscriptionAction::Subscribe,
+        &["SHIFT_*"],
+        &[],
+    ));
+    let SubscriptionResponse::Ack { topics, .. } = response else {
+        panic!("expected an ack, got {response:?}");
+    };
+    assert_eq!(
+        topics,
+        vec![
+            WebsocketNotificationType::ShiftCompleted,
+            WebsocketNotificationType::ShiftsCreated,
+            WebsocketNotificationType::ShiftsMissed,
+        ]
+    );
+
+    let mut filter = NotificationFilter::default();
+    filter.apply(&subscription_request(
+        SubscriptionAction::Subscribe,
+        &["SHIFTS_*"],
+        &[],
+    ));
+    assert!(filter.matches(&test_notification(
+        WebsocketNotificationType::ShiftsMissed,
+        None
+    )));
+    assert!(!filter.matches(&test_notification(
+        WebsocketNotificationType::ShiftCompleted,
+        None
+    )));
+}
+
+#[actix_web::test]
+async fn subscription_unsubscribe_from_default() {
+    let mut filter = NotificationFilter::default();
+    filter.apply(&subscription_request(
+        SubscriptionAction::Unsubscribe,
+        &["LEVEL_*"],
+        &["aredl"],
+    ));
+
+    assert!(!filter.matches(&test_notification(
+        WebsocketNotificationType::LevelPlaced,
+        Some(NotificationList::Arepl)
+    )));
+    assert!(!filter.matches(&test_notification(
+        WebsocketNotificationType::SubmissionCreated,
+        Some(NotificationList::Aredl)
+    )));
+    assert!(filter.matches(&test_notification(
+        WebsocketNotificationType::SubmissionCreated,
+        Some(NotificationList::Arepl)
+    )));
+}
+
+#[actix_web::test]
+async fn subscription_unknown_topic_is_rejected() {
+    let mut filter = NotificationFilter::default();
+    let response = filter.apply(&subscription_request(
+        SubscriptionAction::Subscribe,
+        &["SUBMISSION_ACCEPTED", "NOT_A_TOPIC", "FOO_*"],
+        &["gdl"],
+    ));
+    let SubscriptionResponse::Error { unknown, .. } = response else {
+        panic!("expected an error, got {response:?}");
+    };
+    assert_eq!(unknown, vec!["NOT_A_TOPIC", "FOO_*", "gdl"]);
+
+    // the subscription is left unchanged
+    assert!(filter.matches(&test_notification(
+        WebsocketNotificationType::ShiftCompleted,
+        None
+    )));
+
+    let frame = serde_json::to_value(SubscriptionResponse::Error {
+        message: "error".to_owned(),
+        unknown: vec![],
+    })
+    .unwrap();
+    assert_eq!(frame["type"], "error");
 }
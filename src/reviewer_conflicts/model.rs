@@ -0,0 +1,210 @@
+use crate::{
+    app_data::db::DbConnection,
+    error_handler::ApiError,
+    schema::{clan_members, reviewer_conflict_settings, reviewer_conflicts, users},
+    users::BaseUser,
+};
+use chrono::{DateTime, Utc};
+use diesel::{pg::Pg, AsChangeset, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use diesel::prelude::*;
+#[derive(Serialize, Deserialize, Selectable, Queryable, Debug, Clone, ToSchema)]
+#[diesel(table_name = reviewer_conflict_settings, check_for_backend(Pg))]
+pub struct ReviewerConflictSettings {
+    /// Whether reviewers are prevented from reviewing submissions of members of their clans.
+    pub exclude_clanmates: bool,
+    /// Whether reviewers are prevented from reviewing submissions of users they flagged as a conflict.
+    pub exclude_flagged_users: bool,
+    /// Whether reviewers are prevented from reviewing submissions for levels they created or published.
+    pub exclude_level_creators: bool,
+    /// Internal UUID of the staff member who last edited these settings.
+    pub updated_by: Option<Uuid>,
+    /// Timestamp of when these settings were last edited.
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, ToSchema, AsChangeset)]
+#[diesel(table_name = reviewer_conflict_settings)]
+pub struct ReviewerConflictSettingsPatch {
+    /// Whether reviewers are prevented from reviewing submissions of members of their clans.
+    pub exclude_clanmates: Option<bool>,
+    /// Whether reviewers are prevented from reviewing submissions of users they flagged as a conflict.
+    pub exclude_flagged_users: Option<bool>,
+    /// Whether reviewers are prevented from reviewing submissions for levels they created or published.
+    pub exclude_level_creators: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct ReviewerConflict {
+    /// The flagged user.
+    pub user: BaseUser,
+    /// Why the reviewer flagged this user, only visible to the reviewer.
+    pub note: Option<String>,
+    /// Timestamp of when the user was flagged.
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct ReviewerConflictCreate {
+    /// Internal UUID of the user to flag.
+    pub user_id: Uuid,
+    /// Why this user is a conflict of interest.
+    pub note: Option<String>,
+}
+
+/// Users and levels a reviewer should not review submissions for, resolved from the current settings.
+#[derive(Debug, Default)]
+pub struct ReviewerConflicts {
+    /// Submitters the reviewer has a conflict of interest with.
+    pub users: Vec<Uuid>,
+    /// Whether submissions for levels the reviewer created or published are excluded.
+    pub exclude_level_creators: bool,
+}
+
+impl ReviewerConflictSettings {
+    pub fn get(conn: &mut DbConnection) -> Result<Self, ApiError> {
+        let settings = reviewer_conflict_settings::table
+            .select(ReviewerConflictSettings::as_select())
+            .first::<ReviewerConflictSettings>(conn)?;
+        Ok(settings)
+    }
+
+    pub fn patch(
+        conn: &mut DbConnection,
+        patch: ReviewerConflictSettingsPatch,
+        moderator_id: Uuid,
+    ) -> Result<Self, ApiError> {
+        let settings = diesel::update(reviewer_conflict_settings::table)
+            .set((
+                patch,
+                reviewer_conflict_settings::updated_by.eq(moderator_id),
+                reviewer_conflict_settings::updated_at.eq(Utc::now()),
+            ))
+            .returning(ReviewerConflictSettings::as_select())
+            .get_result::<ReviewerConflictSettings>(conn)?;
+        Ok(settings)
+    }
+}
+
+impl ReviewerConflict {
+    pub fn find_all(conn: &mut DbConnection, reviewer_id: Uuid) -> Result<Vec<Self>, ApiError> {
+        let conflicts = reviewer_conflicts::table
+            .inner_join(users::table.on(users::id.eq(reviewer_conflicts::user_id)))
+            .filter(reviewer_conflicts::reviewer_id.eq(reviewer_id))
+            .order(reviewer_conflicts::created_at.desc())
+            .select((
+                BaseUser::as_select(),
+                reviewer_conflicts::note,
+                reviewer_conflicts::created_at,
+            ))
+            .load::<(BaseUser, Option<String>, DateTime<Utc>)>(conn)?;
+
+        Ok(conflicts
+            .into_iter()
+            .map(|(user, note, created_at)| ReviewerConflict {
+                user,
+                note,
+                created_at,
+            })
+            .collect())
+    }
+
+    pub fn create(
+        conn: &mut DbConnection,
+        reviewer_id: Uuid,
+        conflict: ReviewerConflictCreate,
+    ) -> Result<Self, ApiError> {
+        let ReviewerConflictCreate { user_id, note } = conflict;
+        if user_id == reviewer_id {
+            return Err(ApiError::BadRequest(
+                "You cannot flag yourself as a conflict of interest.",
+            ));
+        }
+
+        let user = users::table
+            .filter(users::id.eq(user_id))
+            .select(BaseUser::as_select())
+            .first::<BaseUser>(conn)?;
+
+        let (note, created_at) = diesel::insert_into(reviewer_conflicts::table)
+            .values((
+                reviewer_conflicts::reviewer_id.eq(reviewer_id),
+                reviewer_conflicts::user_id.eq(user_id),
+                reviewer_conflicts::note.eq(&note),
+            ))
+            .on_conflict((reviewer_conflicts::reviewer_id, reviewer_conflicts::user_id))
+            .do_update()
+            .set(reviewer_conflicts::note.eq(&note))
+            .returning((reviewer_conflicts::note, reviewer_conflicts::created_at))
+            .get_result::<(Option<String>, DateTime<Utc>)>(conn)?;
+
+        Ok(ReviewerConflict {
+            user,
+            note,
+            created_at,
+        })
+    }
+
+    pub fn delete(
+        conn: &mut DbConnection,
+        reviewer_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<(), ApiError> {
+        let deleted = diesel::delete(
+            reviewer_conflicts::table
+                .filter(reviewer_conflicts::reviewer_id.eq(reviewer_id))
+                .filter(reviewer_conflicts::user_id.eq(user_id)),
+        )
+        .execute(conn)?;
+
+        if deleted == 0 {
+            return Err(ApiError::NotFound(
+                "This user is not flagged as a conflict.",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl ReviewerConflicts {
+    pub fn load(conn: &mut DbConnection, reviewer_id: Uuid) -> Result<Self, ApiError> {
+        let settings = ReviewerConflictSettings::get(conn)?;
+        let mut users = Vec::new();
+
+        if settings.exclude_clanmates {
+            let clans = clan_members::table
+                .filter(clan_members::user_id.eq(reviewer_id))
+                .select(clan_members::clan_id)
+                .load::<Uuid>(conn)?;
+            if !clans.is_empty() {
+                users.extend(
+                    clan_members::table
+                        .filter(clan_members::clan_id.eq_any(clans))
+                        .filter(clan_members::user_id.ne(reviewer_id))
+                        .select(clan_members::user_id)
+                        .load::<Uuid>(conn)?,
+                );
+            }
+        }
+
+        if settings.exclude_flagged_users {
+            users.extend(
+                reviewer_conflicts::table
+                    .filter(reviewer_conflicts::reviewer_id.eq(reviewer_id))
+                    .select(reviewer_conflicts::user_id)
+                    .load::<Uuid>(conn)?,
+            );
+        }
+
+        users.sort_unstable();
+        users.dedup();
+
+        Ok(Self {
+            users,
+            exclude_level_creators: settings.exclude_level_creators,
+        })
+    }
+}
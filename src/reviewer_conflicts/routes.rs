@@ -0,0 +1,187 @@
+use crate::{
+    app_data::db::DbAppState,
+    auth::{Authenticated, Permission, UserAuth},
+    error_handler::ApiError,
+    reviewer_conflicts::{
+        ReviewerConflict, ReviewerConflictCreate, ReviewerConflictSettings,
+        ReviewerConflictSettingsPatch,
+    },
+};
+use actix_web::{delete, get, patch, post, web, HttpResponse};
+use std::sync::Arc;
+use tracing_actix_web::RootSpan;
+use utoipa::OpenApi;
+use uuid::Uuid;
+
+#[utoipa::path(
+    get,
+    summary = "[Staff]Get conflict of interest settings",
+    description = "Get which conflict of interest rules currently prevent reviewers from claiming or editing submissions.",
+    tag = "Reviewer Conflicts",
+    responses(
+        (status = 200, body = ReviewerConflictSettings)
+    ),
+    security(
+        ("access_token" = ["SubmissionReview"]),
+        ("api_key" = ["SubmissionReview"]),
+    ),
+)]
+#[get("/settings", wrap = "UserAuth::require(Permission::SubmissionReview)")]
+async fn get_settings(db: web::Data<Arc<DbAppState>>) -> Result<HttpResponse, ApiError> {
+    let settings =
+        web::block(move || ReviewerConflictSettings::get(&mut db.connection()?)).await??;
+    Ok(HttpResponse::Ok().json(settings))
+}
+
+#[utoipa::path(
+    patch,
+    summary = "[Staff]Edit conflict of interest settings",
+    description = "Enable or disable conflict of interest rules. These apply to claiming submissions, and to editing them for reviewers who can only edit submissions they claimed.",
+    tag = "Reviewer Conflicts",
+    request_body = ReviewerConflictSettingsPatch,
+    responses(
+        (status = 200, body = ReviewerConflictSettings)
+    ),
+    security(
+        ("access_token" = ["ReviewerConflictManage"]),
+        ("api_key" = ["ReviewerConflictManage"]),
+    ),
+)]
+#[patch(
+    "/settings",
+    wrap = "UserAuth::require(Permission::ReviewerConflictManage)"
+)]
+async fn patch_settings(
+    db: web::Data<Arc<DbAppState>>,
+    body: web::Json<ReviewerConflictSettingsPatch>,
+    authenticated: Authenticated,
+    root_span: RootSpan,
+) -> Result<HttpResponse, ApiError> {
+    root_span.record("body", tracing::field::debug(&body));
+    let settings = web::block(move || {
+        ReviewerConflictSettings::patch(
+            &mut db.connection()?,
+            body.into_inner(),
+            authenticated.user_id,
+        )
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(settings))
+}
+
+#[utoipa::path(
+    get,
+    summary = "[Staff]List own flagged users",
+    description = "List the users the logged in reviewer flagged as a conflict of interest.",
+    tag = "Reviewer Conflicts",
+    responses(
+        (status = 200, body = Vec<ReviewerConflict>)
+    ),
+    security(
+        ("access_token" = ["SubmissionReview"]),
+        ("api_key" = ["SubmissionReview"]),
+    ),
+)]
+#[get("/@me", wrap = "UserAuth::require(Permission::SubmissionReview)")]
+async fn find_own(
+    db: web::Data<Arc<DbAppState>>,
+    authenticated: Authenticated,
+) -> Result<HttpResponse, ApiError> {
+    let conflicts = web::block(move || {
+        ReviewerConflict::find_all(&mut db.connection()?, authenticated.user_id)
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(conflicts))
+}
+
+#[utoipa::path(
+    post,
+    summary = "[Staff]Flag a user as a conflict",
+    description = "Flag a user as a conflict of interest, so that their submissions are never given to the logged in reviewer. Flagging an already flagged user updates the note.",
+    tag = "Reviewer Conflicts",
+    request_body = ReviewerConflictCreate,
+    responses(
+        (status = 200, body = ReviewerConflict)
+    ),
+    security(
+        ("access_token" = ["SubmissionReview"]),
+        ("api_key" = ["SubmissionReview"]),
+    ),
+)]
+#[post("/@me", wrap = "UserAuth::require(Permission::SubmissionReview)")]
+async fn create(
+    db: web::Data<Arc<DbAppState>>,
+    body: web::Json<ReviewerConflictCreate>,
+    authenticated: Authenticated,
+    root_span: RootSpan,
+) -> Result<HttpResponse, ApiError> {
+    root_span.record("body", tracing::field::debug(&body));
+    let conflict = web::block(move || {
+        ReviewerConflict::create(
+            &mut db.connection()?,
+            authenticated.user_id,
+            body.into_inner(),
+        )
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(conflict))
+}
+
+#[utoipa::path(
+    delete,
+    summary = "[Staff]Unflag a user",
+    description = "Remove a user from the logged in reviewer's conflicts of interest.",
+    tag = "Reviewer Conflicts",
+    params(
+        ("user_id" = Uuid, description = "Internal UUID of the flagged user"),
+    ),
+    responses(
+        (status = 204)
+    ),
+    security(
+        ("access_token" = ["SubmissionReview"]),
+        ("api_key" = ["SubmissionReview"]),
+    ),
+)]
+#[delete(
+    "/@me/{user_id}",
+    wrap = "UserAuth::require(Permission::SubmissionReview)"
+)]
+async fn delete(
+    db: web::Data<Arc<DbAppState>>,
+    user_id: web::Path<Uuid>,
+    authenticated: Authenticated,
+) -> Result<HttpResponse, ApiError> {
+    web::block(move || {
+        ReviewerConflict::delete(
+            &mut db.connection()?,
+            authenticated.user_id,
+            user_id.into_inner(),
+        )
+    })
+    .await??;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    components(schemas(
+        ReviewerConflictSettings,
+        ReviewerConflictSettingsPatch,
+        ReviewerConflict,
+        ReviewerConflictCreate,
+    )),
+    paths(get_settings, patch_settings, find_own, create, delete)
+)]
+pub struct ApiDoc;
+
+pub fn init_routes(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/reviewer-conflicts")
+            .service(get_settings)
+            .service(patch_settings)
+            .service(find_own)
+            .service(create)
+            .service(delete),
+    );
+}
@@ -0,0 +1,14 @@
+#[cfg(test)]
+use {
+    crate::{app_data::db::DbAppState, schema::reviewer_conflict_settings},
+    diesel::prelude::*,
+    std::sync::Arc,
+};
+
+#[cfg(test)]
+pub async fn set_test_exclude_level_creators(db: &Arc<DbAppState>, exclude: bool) {
+    diesel::update(reviewer_conflict_settings::table)
+        .set(reviewer_conflict_settings::exclude_level_creators.eq(exclude))
+        .execute(&mut db.connection().unwrap())
+        .expect("Failed to update test reviewer conflict settings");
+}
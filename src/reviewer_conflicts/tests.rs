@@ -0,0 +1,248 @@
+#[cfg(test)]
+use {
+    crate::{
+        aredl::{
+            levels::test_utils::{create_test_level, create_test_level_with_publisher},
+            submissions::{
+                test_utils::{
+                    create_test_submission, set_test_submission_raw_url,
+                    set_test_submission_reviewer, set_test_submission_status,
+                },
+                SubmissionStatus,
+            },
+        },
+        arepl,
+        auth::{create_test_token, Permission},
+        clans::test_utils::{create_test_clan, create_test_clan_member},
+        reviewer_conflicts::test_utils::set_test_exclude_level_creators,
+        test_utils::*,
+        users::test_utils::{
+            create_test_full_reviewer, create_test_hidden_reviewer, create_test_user,
+            create_test_user_with_permissions,
+        },
+    },
+    actix_http::StatusCode,
+    actix_web::test::{self, read_body_json},
+    serde_json::{json, Value},
+    serial_test::serial,
+};
+
+#[actix_web::test]
+async fn flag_and_unflag_users() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (reviewer_id, _) = create_test_full_reviewer(&db).await;
+    let token = create_test_token(reviewer_id, &auth.jwt_encoding_key).unwrap();
+    let (user_id, _) = create_test_user(&db, None).await;
+
+    let req = test::TestRequest::post()
+        .uri("/reviewer-conflicts/@me")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"user_id": user_id, "note": "My brother"}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    let req = test::TestRequest::get()
+        .uri("/reviewer-conflicts/@me")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: Value = read_body_json(resp).await;
+    assert_eq!(body[0]["user"]["id"], user_id.to_string());
+    assert_eq!(body[0]["note"], "My brother");
+
+    let req = test::TestRequest::post()
+        .uri("/reviewer-conflicts/@me")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"user_id": reviewer_id}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    for expected in [StatusCode::NO_CONTENT, StatusCode::NOT_FOUND] {
+        let req = test::TestRequest::delete()
+            .uri(&format!("/reviewer-conflicts/@me/{user_id}"))
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), expected);
+    }
+}
+
+#[actix_web::test]
+async fn claim_skips_flagged_users_and_clanmates() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (reviewer_id, _) = create_test_full_reviewer(&db).await;
+    let token = create_test_token(reviewer_id, &auth.jwt_encoding_key).unwrap();
+    let (flagged_id, _) = create_test_user(&db, None).await;
+    let (clanmate_id, _) = create_test_user(&db, None).await;
+    let clan_id = create_test_clan(&db).await;
+    create_test_clan_member(&db, clan_id, reviewer_id, 0).await;
+    create_test_clan_member(&db, clan_id, clanmate_id, 0).await;
+
+    let flagged_level = create_test_level(&db).await;
+    let clanmate_level = create_test_level(&db).await;
+    create_test_submission(flagged_level, flagged_id, &db).await;
+    create_test_submission(clanmate_level, clanmate_id, &db).await;
+    let arepl_level = arepl::levels::test_utils::create_test_level(&db).await;
+    arepl::submissions::test_utils::create_test_submission(arepl_level, clanmate_id, &db).await;
+
+    let req = test::TestRequest::post()
+        .uri("/reviewer-conflicts/@me")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"user_id": flagged_id}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    for uri in [
+        format!("/aredl/submissions/claim?level_filter={flagged_level},{clanmate_level}"),
+        format!("/arepl/submissions/claim?level_filter={arepl_level}"),
+    ] {
+        let req = test::TestRequest::get()
+            .uri(&uri)
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND, "{uri}");
+    }
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/reviewer-conflicts/@me/{flagged_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    test::call_service(&app, req).await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "/aredl/submissions/claim?level_filter={flagged_level},{clanmate_level}"
+        ))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: Value = read_body_json(resp).await;
+    assert_eq!(body["submitted_by"]["id"], flagged_id.to_string());
+}
+
+#[actix_web::test]
+#[serial]
+async fn level_creator_exclusion_is_configurable() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (reviewer_id, _) = create_test_full_reviewer(&db).await;
+    let token = create_test_token(reviewer_id, &auth.jwt_encoding_key).unwrap();
+    let (admin_id, _) = create_test_user(&db, Some(Permission::ReviewerConflictManage)).await;
+    let admin_token = create_test_token(admin_id, &auth.jwt_encoding_key).unwrap();
+    let (user_id, _) = create_test_user(&db, None).await;
+    let level_id = create_test_level_with_publisher(&db, reviewer_id).await;
+    create_test_submission(level_id, user_id, &db).await;
+
+    let req = test::TestRequest::patch()
+        .uri("/reviewer-conflicts/settings")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"exclude_level_creators": true}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let req = test::TestRequest::patch()
+        .uri("/reviewer-conflicts/settings")
+        .insert_header(("Authorization", format!("Bearer {admin_token}")))
+        .set_json(json!({"exclude_level_creators": true}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: Value = read_body_json(resp).await;
+    assert_eq!(body["exclude_level_creators"], true);
+    assert_eq!(body["updated_by"], admin_id.to_string());
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/aredl/submissions/claim?level_filter={level_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    set_test_exclude_level_creators(&db, false).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/aredl/submissions/claim?level_filter={level_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+}
+
+#[actix_web::test]
+async fn conflicts_block_edits_by_non_privileged_reviewers() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (reviewer_id, _) = create_test_hidden_reviewer(&db).await;
+    let token = create_test_token(reviewer_id, &auth.jwt_encoding_key).unwrap();
+    let (user_id, _) = create_test_user(&db, None).await;
+    let level_id = create_test_level(&db).await;
+    let submission_id = create_test_submission(level_id, user_id, &db).await;
+    set_test_submission_raw_url(&db, submission_id, None);
+    set_test_submission_status(&db, submission_id, SubmissionStatus::Claimed);
+    set_test_submission_reviewer(&db, submission_id, Some(reviewer_id));
+
+    // the conflict is flagged after the submission was claimed
+    let req = test::TestRequest::post()
+        .uri("/reviewer-conflicts/@me")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"user_id": user_id}))
+        .to_request();
+    test::call_service(&app, req).await;
+
+    let req = test::TestRequest::patch()
+        .uri(&format!("/aredl/submissions/{submission_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"status": "Accepted"}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+async fn conflicts_skip_bulk_actions_by_non_privileged_reviewers() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (reviewer_id, _) = create_test_user_with_permissions(
+        &db,
+        &[Permission::SubmissionReview, Permission::SubmissionBulkEdit],
+    )
+    .await;
+    let token = create_test_token(reviewer_id, &auth.jwt_encoding_key).unwrap();
+    let (flagged_id, _) = create_test_user(&db, None).await;
+    let (user_id, _) = create_test_user(&db, None).await;
+    let level_id = create_test_level(&db).await;
+    let flagged_submission = create_test_submission(level_id, flagged_id, &db).await;
+    let submission = create_test_submission(level_id, user_id, &db).await;
+    // both are claimed so the flagged one can only be skipped because of the conflict
+    for id in [flagged_submission, submission] {
+        set_test_submission_raw_url(&db, id, None);
+        set_test_submission_status(&db, id, SubmissionStatus::Claimed);
+        set_test_submission_reviewer(&db, id, Some(reviewer_id));
+    }
+
+    let req = test::TestRequest::post()
+        .uri("/reviewer-conflicts/@me")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"user_id": flagged_id}))
+        .to_request();
+    test::call_service(&app, req).await;
+
+    let req = test::TestRequest::post()
+        .uri("/aredl/submissions/bulk")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"action": "Deny", "ids": [flagged_submission, submission]}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: Value = read_body_json(resp).await;
+    assert_eq!(body["updated"].as_array().unwrap().len(), 1);
+    assert_eq!(body["updated"][0]["id"], submission.to_string());
+    assert_eq!(body["skipped"][0]["id"], flagged_submission.to_string());
+    assert_eq!(
+        body["skipped"][0]["reason"],
+        "You have a conflict of interest with this submission."
+    );
+}
@@ -1,6 +1,6 @@
 use crate::app_data::db::DbAppState;
 use crate::error_handler::StartupError;
-use crate::notifications::WebsocketNotification;
+use crate::notifications::{WebsocketNotification, WebsocketNotificationType};
 use crate::scheduled::{sleep_until_next, startup_schedule};
 use crate::schema::shifts;
 use crate::shifts::Shift;
@@ -35,14 +35,31 @@ pub async fn start_data_cleaner(
 
                 tracing::info!("Cleaning old notifications");
 
+                // unread notifications are kept longer so that they are not lost while a user is away
                 if let Err(error) = diesel::sql_query(
-                    "DELETE FROM notifications WHERE created_at < NOW() - INTERVAL '1 month'",
+                    "DELETE FROM notifications \
+                 WHERE (read_at IS NOT NULL AND created_at < NOW() - INTERVAL '1 month') \
+                    OR created_at < NOW() - INTERVAL '3 months'",
                 )
                 .execute(conn)
                 {
                     tracing::error!("Failed to clean notifications {error}");
                 }
 
+                // events still waiting to be delivered to a webhook are kept, deleting them would drop the delivery
+                if let Err(error) = diesel::sql_query(
+                    "DELETE FROM notification_events e \
+                 WHERE e.created_at < NOW() - INTERVAL '1 month' \
+                   AND NOT EXISTS ( \
+                       SELECT 1 FROM webhook_deliveries d \
+                       WHERE d.event_id = e.id AND d.status = 'Pending' \
+                   )",
+                )
+                .execute(conn)
+                {
+                    tracing::error!("Failed to clean websocket notification events {error}");
+                }
+
                 tracing::info!("Cleaning stale submissions claims");
 
                 if let Err(error) = diesel::sql_query(
@@ -96,7 +113,13 @@ pub async fn start_data_cleaner(
                     "aredl": aredl_expired_shifts,
                 });
 
-                WebsocketNotification::send(&notify_tx, "SHIFTS_MISSED", &missed_shifts_payload);
+                WebsocketNotification::send(
+                    conn,
+                    &notify_tx,
+                    WebsocketNotificationType::ShiftsMissed,
+                    None,
+                    &missed_shifts_payload,
+                );
 
                 tracing::info!("Cleaned data successfully");
             }
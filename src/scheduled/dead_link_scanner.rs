@@ -0,0 +1,66 @@
+use crate::app_data::db::DbAppState;
+use crate::aredl::records::video_checks::RecordVideoCheck as AredlRecordVideoCheck;
+use crate::arepl::records::video_checks::RecordVideoCheck as AreplRecordVideoCheck;
+use crate::error_handler::StartupError;
+use crate::get_optional_secret;
+use crate::notifications::WebsocketNotification;
+use crate::providers::ProvidersAppState;
+use crate::scheduled::{parse_startup_schedule, sleep_until_next};
+use crate::users::me::notifications::Notification;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tokio::task;
+
+/// How many record videos of each list are checked per run.
+const CHECK_BATCH_SIZE: i64 = 50;
+
+pub async fn start_dead_link_scanner(
+    db: Arc<DbAppState>,
+    providers: Arc<ProvidersAppState>,
+    notify_tx: broadcast::Sender<WebsocketNotification>,
+    inbox_tx: broadcast::Sender<Notification>,
+) -> Result<(), StartupError> {
+    let Some(schedule_config) =
+        get_optional_secret("RECORD_VIDEO_CHECK_SCHEDULE").filter(|value| !value.is_empty())
+    else {
+        tracing::info!("RECORD_VIDEO_CHECK_SCHEDULE not set, record videos are not checked");
+        return Ok(());
+    };
+    let schedule = parse_startup_schedule("RECORD_VIDEO_CHECK_SCHEDULE", &schedule_config)?;
+
+    task::spawn(async move {
+        loop {
+            match AredlRecordVideoCheck::check_due(
+                &db,
+                &providers,
+                &notify_tx,
+                &inbox_tx,
+                CHECK_BATCH_SIZE,
+            )
+            .await
+            {
+                Ok(0) => {}
+                Ok(checked) => tracing::info!("Checked videos of {checked} AREDL records"),
+                Err(error) => tracing::error!("Failed to check AREDL record videos: {error}"),
+            }
+
+            match AreplRecordVideoCheck::check_due(
+                &db,
+                &providers,
+                &notify_tx,
+                &inbox_tx,
+                CHECK_BATCH_SIZE,
+            )
+            .await
+            {
+                Ok(0) => {}
+                Ok(checked) => tracing::info!("Checked videos of {checked} AREPL records"),
+                Err(error) => tracing::error!("Failed to check AREPL record videos: {error}"),
+            }
+
+            sleep_until_next(&schedule).await;
+        }
+    });
+
+    Ok(())
+}
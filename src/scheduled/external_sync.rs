@@ -0,0 +1,64 @@
+use crate::app_data::db::DbAppState;
+use crate::error_handler::StartupError;
+use crate::external_sync::ExternalSyncSubscription;
+use crate::get_optional_secret;
+use crate::providers::ProvidersAppState;
+use crate::scheduled::{parse_startup_schedule, sleep_until_next};
+use std::sync::Arc;
+use tokio::task;
+
+/// Maximum amount of subscriptions synced in a single run, the least recently synced go first.
+const SYNC_BATCH_SIZE: i64 = 50;
+
+pub async fn start_external_sync(
+    db: Arc<DbAppState>,
+    providers: Arc<ProvidersAppState>,
+) -> Result<(), StartupError> {
+    let Some(schedule_config) =
+        get_optional_secret("EXTERNAL_SYNC_SCHEDULE").filter(|value| !value.is_empty())
+    else {
+        tracing::info!("EXTERNAL_SYNC_SCHEDULE not set, subscribed users are not synced");
+        return Ok(());
+    };
+    let schedule = parse_startup_schedule("EXTERNAL_SYNC_SCHEDULE", &schedule_config)?;
+
+    task::spawn(async move {
+        loop {
+            match ExternalSyncSubscription::sync_all(&db, &providers, SYNC_BATCH_SIZE).await {
+                Ok(outcomes) => {
+                    for outcome in &outcomes {
+                        if let Some(error) = &outcome.error {
+                            tracing::warn!(
+                                "Failed to sync {} with {}: {error}",
+                                outcome.user_id,
+                                outcome.source_name
+                            );
+                        } else {
+                            tracing::info!(
+                                "Synced {} with {}: {} created, {} updated, {} removed upstream",
+                                outcome.user_id,
+                                outcome.source_name,
+                                outcome.created,
+                                outcome.updated,
+                                outcome.removed
+                            );
+                        }
+                    }
+                    let failed = outcomes
+                        .iter()
+                        .filter(|outcome| outcome.error.is_some())
+                        .count();
+                    tracing::info!(
+                        "Synced {} external list subscriptions, {failed} failed",
+                        outcomes.len()
+                    );
+                }
+                Err(error) => tracing::error!("Failed to sync external lists: {error}"),
+            }
+
+            sleep_until_next(&schedule).await;
+        }
+    });
+
+    Ok(())
+}
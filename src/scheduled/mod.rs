@@ -1,9 +1,13 @@
 pub mod data_cleaner;
+pub mod dead_link_scanner;
+pub mod external_sync;
+pub mod raw_footage_probe;
 pub mod refresh_discord_avatars;
 pub mod refresh_level_data;
 pub mod refresh_matviews;
 pub mod shifts_creator;
 pub mod sync_patreon_plus;
+pub mod webhook_delivery;
 
 use crate::error_handler::{ConfigError, StartupError};
 use crate::get_secret;
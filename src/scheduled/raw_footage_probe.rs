@@ -0,0 +1,45 @@
+use crate::app_data::db::DbAppState;
+use crate::aredl::submissions::raw_probes::RawFootageProbe as AredlRawFootageProbe;
+use crate::arepl::submissions::raw_probes::RawFootageProbe as AreplRawFootageProbe;
+use crate::error_handler::StartupError;
+use crate::get_optional_secret;
+use crate::providers::ProvidersAppState;
+use crate::scheduled::{parse_startup_schedule, sleep_until_next};
+use std::sync::Arc;
+use tokio::task;
+
+/// How many submissions of each list are probed per run.
+const PROBE_BATCH_SIZE: i64 = 20;
+
+pub async fn start_raw_footage_prober(
+    db: Arc<DbAppState>,
+    providers: Arc<ProvidersAppState>,
+) -> Result<(), StartupError> {
+    let Some(schedule_config) =
+        get_optional_secret("RAW_FOOTAGE_PROBE_SCHEDULE").filter(|value| !value.is_empty())
+    else {
+        tracing::info!("RAW_FOOTAGE_PROBE_SCHEDULE not set, raw footage probing is disabled");
+        return Ok(());
+    };
+    let schedule = parse_startup_schedule("RAW_FOOTAGE_PROBE_SCHEDULE", &schedule_config)?;
+
+    task::spawn(async move {
+        loop {
+            match AredlRawFootageProbe::probe_unchecked(&db, &providers, PROBE_BATCH_SIZE).await {
+                Ok(0) => {}
+                Ok(probed) => tracing::info!("Probed raw footage of {probed} AREDL submissions"),
+                Err(error) => tracing::error!("Failed to probe AREDL raw footage: {error}"),
+            }
+
+            match AreplRawFootageProbe::probe_unchecked(&db, &providers, PROBE_BATCH_SIZE).await {
+                Ok(0) => {}
+                Ok(probed) => tracing::info!("Probed raw footage of {probed} AREPL submissions"),
+                Err(error) => tracing::error!("Failed to probe AREPL raw footage: {error}"),
+            }
+
+            sleep_until_next(&schedule).await;
+        }
+    });
+
+    Ok(())
+}
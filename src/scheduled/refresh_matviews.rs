@@ -1,4 +1,8 @@
 use crate::app_data::db::DbAppState;
+use crate::aredl::leaderboard::take_leaderboard_snapshots as take_aredl_leaderboard_snapshots;
+use crate::aredl::submissions::queue::SubmissionQueueSnapshot as AredlQueueSnapshot;
+use crate::arepl::leaderboard::take_leaderboard_snapshots as take_arepl_leaderboard_snapshots;
+use crate::arepl::submissions::queue::SubmissionQueueSnapshot as AreplQueueSnapshot;
 use crate::error_handler::{ApiError, StartupError};
 use crate::scheduled::{sleep_until_next, startup_schedule};
 use crate::schema::matview_refresh_log;
@@ -27,6 +31,7 @@ pub async fn start_matviews_refresher(db: Arc<DbAppState>) -> Result<(), Startup
         "country_created_levels",
         "clans_created_levels",
         "submission_stats",
+        "submission_reason_stats",
         "record_totals",
         "submission_totals",
     ];
@@ -75,6 +80,34 @@ pub async fn start_matviews_refresher(db: Arc<DbAppState>) -> Result<(), Startup
                 }
             }
 
+            // the day's last standings are kept to track rank changes
+            if let Err(e) = db
+                .connection()
+                .and_then(|mut conn| take_aredl_leaderboard_snapshots(&mut conn))
+            {
+                tracing::error!("Failed to snapshot the AREDL leaderboards: {}", e);
+            }
+            if let Err(e) = db
+                .connection()
+                .and_then(|mut conn| take_arepl_leaderboard_snapshots(&mut conn))
+            {
+                tracing::error!("Failed to snapshot the AREPL leaderboards: {}", e);
+            }
+
+            // queue depth is recorded on the same schedule to build its history
+            if let Err(e) = db
+                .connection()
+                .and_then(|mut conn| AredlQueueSnapshot::take(&mut conn))
+            {
+                tracing::error!("Failed to snapshot the AREDL submission queue: {}", e);
+            }
+            if let Err(e) = db
+                .connection()
+                .and_then(|mut conn| AreplQueueSnapshot::take(&mut conn))
+            {
+                tracing::error!("Failed to snapshot the AREPL submission queue: {}", e);
+            }
+
             sleep_until_next(&schedule).await;
         }
     });
@@ -1,7 +1,7 @@
 use crate::{
     app_data::db::DbAppState,
     error_handler::StartupError,
-    notifications::WebsocketNotification,
+    notifications::{WebsocketNotification, WebsocketNotificationType},
     scheduled::{sleep_until_next, startup_schedule},
     shifts::RecurringShift,
 };
@@ -21,15 +21,28 @@ pub async fn start_recurrent_shift_creator(
             tracing::info!("Creating today’s recurring shifts");
 
             let today: NaiveDate = Utc::now().date_naive();
-            match db
-                .connection()
-                .and_then(|mut conn| RecurringShift::create_shifts(&mut conn, today))
             {
-                Ok(new_shifts) => {
-                    WebsocketNotification::send(&notify_tx, "SHIFTS_CREATED", &new_shifts);
-                }
-                Err(e) => {
-                    tracing::error!("Failed to create shifts for {}: {}", today, e);
+                let conn = &mut match db.connection() {
+                    Ok(c) => c,
+                    Err(e) => {
+                        tracing::error!("DB connection failed: {e}");
+                        continue;
+                    }
+                };
+
+                match RecurringShift::create_shifts(conn, today) {
+                    Ok(new_shifts) => {
+                        WebsocketNotification::send(
+                            conn,
+                            &notify_tx,
+                            WebsocketNotificationType::ShiftsCreated,
+                            None,
+                            &new_shifts,
+                        );
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to create shifts for {}: {}", today, e);
+                    }
                 }
             }
 
@@ -0,0 +1,33 @@
+use crate::app_data::db::DbAppState;
+use crate::error_handler::StartupError;
+use crate::get_optional_secret;
+use crate::scheduled::{parse_startup_schedule, sleep_until_next};
+use crate::webhooks::{deliver_due_webhooks, DeliveryClient};
+use std::sync::Arc;
+use tokio::task;
+
+pub async fn start_webhook_delivery(db: Arc<DbAppState>) -> Result<(), StartupError> {
+    let Some(schedule_config) =
+        get_optional_secret("WEBHOOK_DELIVERY_SCHEDULE").filter(|value| !value.is_empty())
+    else {
+        tracing::info!("WEBHOOK_DELIVERY_SCHEDULE not set, webhook delivery is disabled");
+        return Ok(());
+    };
+    let schedule = parse_startup_schedule("WEBHOOK_DELIVERY_SCHEDULE", &schedule_config)?;
+
+    let client = DeliveryClient::default();
+
+    task::spawn(async move {
+        loop {
+            match deliver_due_webhooks(&db, &client).await {
+                Ok(0) => {}
+                Ok(attempted) => tracing::info!("Attempted {attempted} webhook deliveries"),
+                Err(error) => tracing::error!("Failed to deliver webhooks: {error}"),
+            }
+
+            sleep_until_next(&schedule).await;
+        }
+    });
+
+    Ok(())
+}
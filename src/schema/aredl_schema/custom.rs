@@ -3,7 +3,8 @@
 
 use crate::schema::aredl::{
     bounty_completed, level_custom_copies, level_notes, levels, levels_created, pack_levels,
-    pack_tiers, packs, records, submission_history, submissions, submissions_enabled,
+    pack_tiers, packs, record_video_checks, records, submission_history, submissions,
+    submissions_enabled,
 };
 use crate::schema::{clan_members, clans, users};
 
@@ -24,6 +25,7 @@ diesel::allow_tables_to_appear_in_same_query!(levels_created, clan_members);
 diesel::allow_tables_to_appear_in_same_query!(records, users);
 diesel::allow_tables_to_appear_in_same_query!(records, clans);
 diesel::allow_tables_to_appear_in_same_query!(records, clan_members);
+diesel::allow_tables_to_appear_in_same_query!(record_video_checks, users);
 diesel::allow_tables_to_appear_in_same_query!(submission_history, users);
 diesel::allow_tables_to_appear_in_same_query!(bounty_completed, users);
 
@@ -261,6 +263,16 @@ diesel::table! {
 diesel::joinable!(submission_stats -> users (reviewer_id));
 diesel::allow_tables_to_appear_in_same_query!(submission_stats, users);
 
+diesel::table! {
+    aredl.submission_reason_stats (day, reviewer_id, reason_code) {
+        day -> Date,
+        reviewer_id -> Nullable<Uuid>,
+        reason_code -> Text,
+        denied -> Int8,
+        under_consideration -> Int8,
+    }
+}
+
 diesel::table! {
     aredl.submission_stats_leaderboard (reviewer_id) {
         reviewer_id -> Uuid,
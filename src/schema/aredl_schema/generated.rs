@@ -33,6 +33,10 @@ pub mod aredl {
         #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
         #[diesel(postgres_type(name = "submission_status"))]
         pub struct SubmissionStatus;
+
+        #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+        #[diesel(postgres_type(name = "video_availability"))]
+        pub struct VideoAvailability;
     }
 
     diesel::table! {
@@ -60,6 +64,30 @@ pub mod aredl {
         }
     }
 
+    diesel::table! {
+        aredl.clans_leaderboard_snapshots (clan_id, snapshot_date) {
+            clan_id -> Uuid,
+            snapshot_date -> Date,
+            rank -> Int4,
+            extremes_rank -> Int4,
+            hardest_rank -> Int4,
+            level_points -> Int4,
+            extremes -> Int4,
+        }
+    }
+
+    diesel::table! {
+        aredl.country_leaderboard_snapshots (country, snapshot_date) {
+            country -> Int4,
+            snapshot_date -> Date,
+            rank -> Int4,
+            extremes_rank -> Int4,
+            hardest_rank -> Int4,
+            level_points -> Int4,
+            extremes -> Int4,
+        }
+    }
+
     diesel::table! {
         aredl.guideline_updates (id) {
             id -> Uuid,
@@ -194,6 +222,20 @@ pub mod aredl {
         }
     }
 
+    diesel::table! {
+        use diesel::sql_types::*;
+        use super::sql_types::VideoAvailability;
+
+        aredl.record_video_checks (record_id) {
+            record_id -> Uuid,
+            video_url -> Text,
+            availability -> VideoAvailability,
+            error -> Nullable<Text>,
+            unavailable_since -> Nullable<Timestamptz>,
+            checked_at -> Timestamptz,
+        }
+    }
+
     diesel::table! {
         aredl.records (id) {
             id -> Uuid,
@@ -207,6 +249,29 @@ pub mod aredl {
             hide_video -> Bool,
             submission_id -> Uuid,
             achieved_at -> Timestamptz,
+            video_provider -> Nullable<Text>,
+            video_content_id -> Nullable<Text>,
+        }
+    }
+
+    diesel::table! {
+        aredl.submission_closure_levels (closure_id, level_id) {
+            closure_id -> Uuid,
+            level_id -> Uuid,
+        }
+    }
+
+    diesel::table! {
+        aredl.submission_closures (id) {
+            id -> Uuid,
+            reason -> Text,
+            starts_at -> Timestamptz,
+            ends_at -> Nullable<Timestamptz>,
+            created_by -> Nullable<Uuid>,
+            created_at -> Timestamptz,
+            lifted_by -> Nullable<Uuid>,
+            lifted_at -> Nullable<Timestamptz>,
+            applies_to_all -> Bool,
         }
     }
 
@@ -230,6 +295,37 @@ pub mod aredl {
             priority -> Nullable<Bool>,
             private_reviewer_notes -> Nullable<Text>,
             locked -> Nullable<Bool>,
+            reason_codes -> Array<Nullable<Text>>,
+        }
+    }
+
+    diesel::table! {
+        aredl.submission_queue_snapshots (taken_at) {
+            taken_at -> Timestamptz,
+            regular_submissions_in_queue -> Int8,
+            regular_submissions_without_raw_in_queue -> Int8,
+            priority_submissions_in_queue -> Int8,
+            priority_submissions_without_raw_in_queue -> Int8,
+            uc_submissions -> Int8,
+        }
+    }
+
+    diesel::table! {
+        aredl.submission_raw_probes (submission_id) {
+            submission_id -> Uuid,
+            raw_url -> Text,
+            error -> Nullable<Text>,
+            duration_seconds -> Nullable<Float8>,
+            width -> Nullable<Int4>,
+            height -> Nullable<Int4>,
+            fps -> Nullable<Float8>,
+            container -> Nullable<Text>,
+            file_created_at -> Nullable<Timestamptz>,
+            reencoded -> Nullable<Bool>,
+            flags -> Array<Nullable<Text>>,
+            probed_at -> Timestamptz,
+            attempts -> Int4,
+            retry_at -> Nullable<Timestamptz>,
         }
     }
 
@@ -256,6 +352,11 @@ pub mod aredl {
             updated_at -> Timestamptz,
             private_reviewer_notes -> Nullable<Text>,
             locked -> Bool,
+            reason_codes -> Array<Nullable<Text>>,
+            video_provider -> Nullable<Text>,
+            video_content_id -> Nullable<Text>,
+            video_reused -> Bool,
+            video_published_at -> Nullable<Timestamptz>,
         }
     }
 
@@ -268,17 +369,38 @@ pub mod aredl {
         }
     }
 
+    diesel::table! {
+        aredl.user_leaderboard_snapshots (user_id, snapshot_date) {
+            user_id -> Uuid,
+            snapshot_date -> Date,
+            rank -> Int4,
+            raw_rank -> Int4,
+            extremes_rank -> Int4,
+            hardest_rank -> Int4,
+            country_rank -> Int4,
+            total_points -> Int4,
+            pack_points -> Int4,
+            extremes -> Int4,
+        }
+    }
+
     diesel::joinable!(bounties -> levels (level_id));
     diesel::joinable!(bounty_completed -> bounties (bounty_id));
     diesel::joinable!(level_custom_copies -> levels (level_id));
     diesel::joinable!(level_notes -> levels (level_id));
     diesel::joinable!(level_updates -> levels (level_id));
+    diesel::joinable!(record_video_checks -> records (record_id));
     diesel::joinable!(records -> submissions (submission_id));
+    diesel::joinable!(submission_closure_levels -> levels (level_id));
+    diesel::joinable!(submission_closure_levels -> submission_closures (closure_id));
     diesel::joinable!(submission_history -> submissions (submission_id));
+    diesel::joinable!(submission_raw_probes -> submissions (submission_id));
 
     diesel::allow_tables_to_appear_in_same_query!(
         bounties,
         bounty_completed,
+        clans_leaderboard_snapshots,
+        country_leaderboard_snapshots,
         guideline_updates,
         last_gddl_update,
         level_custom_copies,
@@ -290,9 +412,15 @@ pub mod aredl {
         pack_tiers,
         packs,
         position_history,
+        record_video_checks,
         records,
+        submission_closure_levels,
+        submission_closures,
         submission_history,
+        submission_queue_snapshots,
+        submission_raw_probes,
         submissions,
         submissions_enabled,
+        user_leaderboard_snapshots,
     );
 }
@@ -3,7 +3,8 @@
 
 use crate::schema::arepl::{
     bounty_completed, level_custom_copies, level_notes, levels, levels_created, pack_levels,
-    pack_tiers, packs, records, submission_history, submissions, submissions_enabled,
+    pack_tiers, packs, personal_bests, record_video_checks, records, submission_history,
+    submissions, submissions_enabled, world_record_changes,
 };
 use crate::schema::{clan_members, clans, users};
 
@@ -24,6 +25,8 @@ diesel::allow_tables_to_appear_in_same_query!(levels_created, clan_members);
 diesel::allow_tables_to_appear_in_same_query!(records, users);
 diesel::allow_tables_to_appear_in_same_query!(records, clans);
 diesel::allow_tables_to_appear_in_same_query!(records, clan_members);
+diesel::allow_tables_to_appear_in_same_query!(personal_bests, users);
+diesel::allow_tables_to_appear_in_same_query!(record_video_checks, users);
 diesel::allow_tables_to_appear_in_same_query!(submission_history, users);
 diesel::allow_tables_to_appear_in_same_query!(bounty_completed, users);
 
@@ -262,6 +265,16 @@ diesel::table! {
 diesel::joinable!(submission_stats -> users (reviewer_id));
 diesel::allow_tables_to_appear_in_same_query!(submission_stats, users);
 
+diesel::table! {
+    arepl.submission_reason_stats (day, reviewer_id, reason_code) {
+        day -> Date,
+        reviewer_id -> Nullable<Uuid>,
+        reason_code -> Text,
+        denied -> Int8,
+        under_consideration -> Int8,
+    }
+}
+
 diesel::table! {
     arepl.record_totals (level_id) {
         level_id -> Nullable<Uuid>,
@@ -282,3 +295,22 @@ diesel::table! {
 
 diesel::joinable!(submission_totals -> levels (level_id));
 diesel::allow_tables_to_appear_in_same_query!(submission_totals, levels);
+
+diesel::table! {
+    arepl.world_records (level_id) {
+        level_id -> Uuid,
+        record_id -> Uuid,
+        submitted_by -> Uuid,
+        completion_time -> Int8,
+        achieved_at -> Timestamptz,
+    }
+}
+
+diesel::joinable!(world_records -> levels (level_id));
+diesel::joinable!(world_records -> users (submitted_by));
+diesel::allow_tables_to_appear_in_same_query!(world_records, levels);
+diesel::allow_tables_to_appear_in_same_query!(world_records, records);
+diesel::allow_tables_to_appear_in_same_query!(world_records, users);
+diesel::allow_tables_to_appear_in_same_query!(world_records, clans);
+diesel::allow_tables_to_appear_in_same_query!(world_records, clan_members);
+diesel::allow_tables_to_appear_in_same_query!(world_record_changes, users);
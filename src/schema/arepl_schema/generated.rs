@@ -33,6 +33,14 @@ pub mod arepl {
         #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
         #[diesel(postgres_type(name = "submission_status"))]
         pub struct SubmissionStatus;
+
+        #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+        #[diesel(postgres_type(name = "video_availability"))]
+        pub struct VideoAvailability;
+
+        #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+        #[diesel(postgres_type(name = "world_record_change_kind", schema = "arepl"))]
+        pub struct WorldRecordChangeKind;
     }
 
     diesel::table! {
@@ -60,6 +68,30 @@ pub mod arepl {
         }
     }
 
+    diesel::table! {
+        arepl.clans_leaderboard_snapshots (clan_id, snapshot_date) {
+            clan_id -> Uuid,
+            snapshot_date -> Date,
+            rank -> Int4,
+            extremes_rank -> Int4,
+            hardest_rank -> Int4,
+            level_points -> Int4,
+            extremes -> Int4,
+        }
+    }
+
+    diesel::table! {
+        arepl.country_leaderboard_snapshots (country, snapshot_date) {
+            country -> Int4,
+            snapshot_date -> Date,
+            rank -> Int4,
+            extremes_rank -> Int4,
+            hardest_rank -> Int4,
+            level_points -> Int4,
+            extremes -> Int4,
+        }
+    }
+
     diesel::table! {
         arepl.last_gddl_update (id) {
             id -> Uuid,
@@ -168,6 +200,18 @@ pub mod arepl {
         }
     }
 
+    diesel::table! {
+        arepl.personal_bests (id) {
+            id -> Uuid,
+            record_id -> Uuid,
+            completion_time -> Int8,
+            video_url -> Varchar,
+            mobile -> Bool,
+            achieved_at -> Timestamptz,
+            superseded_at -> Nullable<Timestamptz>,
+        }
+    }
+
     diesel::table! {
         use diesel::sql_types::*;
         use super::sql_types::LevelStatus;
@@ -185,6 +229,20 @@ pub mod arepl {
         }
     }
 
+    diesel::table! {
+        use diesel::sql_types::*;
+        use super::sql_types::VideoAvailability;
+
+        arepl.record_video_checks (record_id) {
+            record_id -> Uuid,
+            video_url -> Text,
+            availability -> VideoAvailability,
+            error -> Nullable<Text>,
+            unavailable_since -> Nullable<Timestamptz>,
+            checked_at -> Timestamptz,
+        }
+    }
+
     diesel::table! {
         arepl.records (id) {
             id -> Uuid,
@@ -199,6 +257,29 @@ pub mod arepl {
             hide_video -> Bool,
             submission_id -> Uuid,
             achieved_at -> Timestamptz,
+            video_provider -> Nullable<Text>,
+            video_content_id -> Nullable<Text>,
+        }
+    }
+
+    diesel::table! {
+        arepl.submission_closure_levels (closure_id, level_id) {
+            closure_id -> Uuid,
+            level_id -> Uuid,
+        }
+    }
+
+    diesel::table! {
+        arepl.submission_closures (id) {
+            id -> Uuid,
+            reason -> Text,
+            starts_at -> Timestamptz,
+            ends_at -> Nullable<Timestamptz>,
+            created_by -> Nullable<Uuid>,
+            created_at -> Timestamptz,
+            lifted_by -> Nullable<Uuid>,
+            lifted_at -> Nullable<Timestamptz>,
+            applies_to_all -> Bool,
         }
     }
 
@@ -223,6 +304,37 @@ pub mod arepl {
             private_reviewer_notes -> Nullable<Text>,
             locked -> Nullable<Bool>,
             completion_time -> Nullable<Int8>,
+            reason_codes -> Array<Nullable<Text>>,
+        }
+    }
+
+    diesel::table! {
+        arepl.submission_queue_snapshots (taken_at) {
+            taken_at -> Timestamptz,
+            regular_submissions_in_queue -> Int8,
+            regular_submissions_without_raw_in_queue -> Int8,
+            priority_submissions_in_queue -> Int8,
+            priority_submissions_without_raw_in_queue -> Int8,
+            uc_submissions -> Int8,
+        }
+    }
+
+    diesel::table! {
+        arepl.submission_raw_probes (submission_id) {
+            submission_id -> Uuid,
+            raw_url -> Text,
+            error -> Nullable<Text>,
+            duration_seconds -> Nullable<Float8>,
+            width -> Nullable<Int4>,
+            height -> Nullable<Int4>,
+            fps -> Nullable<Float8>,
+            container -> Nullable<Text>,
+            file_created_at -> Nullable<Timestamptz>,
+            reencoded -> Nullable<Bool>,
+            flags -> Array<Nullable<Text>>,
+            probed_at -> Timestamptz,
+            attempts -> Int4,
+            retry_at -> Nullable<Timestamptz>,
         }
     }
 
@@ -250,6 +362,11 @@ pub mod arepl {
             completion_time -> Int8,
             private_reviewer_notes -> Nullable<Text>,
             locked -> Bool,
+            reason_codes -> Array<Nullable<Text>>,
+            video_provider -> Nullable<Text>,
+            video_content_id -> Nullable<Text>,
+            video_reused -> Bool,
+            video_published_at -> Nullable<Timestamptz>,
         }
     }
 
@@ -262,17 +379,56 @@ pub mod arepl {
         }
     }
 
+    diesel::table! {
+        arepl.user_leaderboard_snapshots (user_id, snapshot_date) {
+            user_id -> Uuid,
+            snapshot_date -> Date,
+            rank -> Int4,
+            raw_rank -> Int4,
+            extremes_rank -> Int4,
+            hardest_rank -> Int4,
+            country_rank -> Int4,
+            total_points -> Int4,
+            pack_points -> Int4,
+            extremes -> Int4,
+        }
+    }
+
+    diesel::table! {
+        use diesel::sql_types::*;
+        use super::sql_types::WorldRecordChangeKind;
+
+        arepl.world_record_changes (id) {
+            id -> Uuid,
+            level_id -> Uuid,
+            kind -> WorldRecordChangeKind,
+            record_id -> Nullable<Uuid>,
+            completion_time -> Nullable<Int8>,
+            previous_record_id -> Nullable<Uuid>,
+            previous_completion_time -> Nullable<Int8>,
+            created_at -> Timestamptz,
+        }
+    }
+
     diesel::joinable!(bounties -> levels (level_id));
     diesel::joinable!(bounty_completed -> bounties (bounty_id));
     diesel::joinable!(level_custom_copies -> levels (level_id));
     diesel::joinable!(level_notes -> levels (level_id));
     diesel::joinable!(level_updates -> levels (level_id));
+    diesel::joinable!(personal_bests -> records (record_id));
+    diesel::joinable!(record_video_checks -> records (record_id));
     diesel::joinable!(records -> submissions (submission_id));
+    diesel::joinable!(submission_closure_levels -> levels (level_id));
+    diesel::joinable!(submission_closure_levels -> submission_closures (closure_id));
     diesel::joinable!(submission_history -> submissions (submission_id));
+    diesel::joinable!(submission_raw_probes -> submissions (submission_id));
+    diesel::joinable!(world_record_changes -> levels (level_id));
 
     diesel::allow_tables_to_appear_in_same_query!(
         bounties,
         bounty_completed,
+        clans_leaderboard_snapshots,
+        country_leaderboard_snapshots,
         last_gddl_update,
         level_custom_copies,
         level_notes,
@@ -282,10 +438,18 @@ pub mod arepl {
         pack_levels,
         pack_tiers,
         packs,
+        personal_bests,
         position_history,
+        record_video_checks,
         records,
+        submission_closure_levels,
+        submission_closures,
         submission_history,
+        submission_queue_snapshots,
+        submission_raw_probes,
         submissions,
         submissions_enabled,
+        user_leaderboard_snapshots,
+        world_record_changes,
     );
 }
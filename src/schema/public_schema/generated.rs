@@ -2,6 +2,18 @@
 
 pub mod public {
     pub mod sql_types {
+        #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+        #[diesel(postgres_type(name = "external_importer"))]
+        pub struct ExternalImporter;
+
+        #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+        #[diesel(postgres_type(name = "external_sync_list"))]
+        pub struct ExternalSyncList;
+
+        #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+        #[diesel(postgres_type(name = "notification_category"))]
+        pub struct NotificationCategory;
+
         #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
         #[diesel(postgres_type(name = "notification_type"))]
         pub struct NotificationType;
@@ -14,6 +26,18 @@ pub mod public {
         #[diesel(postgres_type(name = "shift_status"))]
         pub struct ShiftStatus;
 
+        #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+        #[diesel(postgres_type(name = "submission_rule_level_status"))]
+        pub struct SubmissionRuleLevelStatus;
+
+        #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+        #[diesel(postgres_type(name = "submission_rule_list"))]
+        pub struct SubmissionRuleList;
+
+        #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+        #[diesel(postgres_type(name = "webhook_delivery_status"))]
+        pub struct WebhookDeliveryStatus;
+
         #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
         #[diesel(postgres_type(name = "weekday"))]
         pub struct Weekday;
@@ -52,6 +76,54 @@ pub mod public {
         }
     }
 
+    diesel::table! {
+        use diesel::sql_types::*;
+        use super::sql_types::ExternalImporter;
+        use super::sql_types::ExternalSyncList;
+
+        external_sources (id) {
+            id -> Uuid,
+            name -> Varchar,
+            importer -> ExternalImporter,
+            base_url -> Varchar,
+            field_mapping -> Jsonb,
+            auto_accept -> Bool,
+            target_list -> ExternalSyncList,
+            enabled -> Bool,
+            created_at -> Timestamptz,
+            updated_at -> Timestamptz,
+        }
+    }
+
+    diesel::table! {
+        external_sync_logs (id) {
+            id -> Uuid,
+            source_id -> Uuid,
+            user_id -> Uuid,
+            triggered_by -> Nullable<Uuid>,
+            dry_run -> Bool,
+            succeeded -> Bool,
+            created_count -> Int4,
+            updated_count -> Int4,
+            unchanged_count -> Int4,
+            skipped_count -> Int4,
+            changes -> Jsonb,
+            error -> Nullable<Varchar>,
+            created_at -> Timestamptz,
+            removed_count -> Int4,
+        }
+    }
+
+    diesel::table! {
+        external_sync_subscriptions (user_id, source_id) {
+            user_id -> Uuid,
+            source_id -> Uuid,
+            last_synced_at -> Nullable<Timestamptz>,
+            last_log_id -> Nullable<Uuid>,
+            created_at -> Timestamptz,
+        }
+    }
+
     diesel::table! {
         matview_refresh_log (view_name) {
             view_name -> Text,
@@ -83,9 +155,32 @@ pub mod public {
         }
     }
 
+    diesel::table! {
+        notification_events (id) {
+            id -> Int8,
+            notification_type -> Varchar,
+            data -> Jsonb,
+            created_at -> Timestamptz,
+            list -> Nullable<Varchar>,
+        }
+    }
+
+    diesel::table! {
+        use diesel::sql_types::*;
+        use super::sql_types::NotificationCategory;
+
+        notification_preferences (user_id, category) {
+            user_id -> Uuid,
+            category -> NotificationCategory,
+            muted -> Bool,
+            updated_at -> Timestamptz,
+        }
+    }
+
     diesel::table! {
         use diesel::sql_types::*;
         use super::sql_types::NotificationType;
+        use super::sql_types::NotificationCategory;
 
         notifications (id) {
             id -> Uuid,
@@ -93,6 +188,9 @@ pub mod public {
             content -> Text,
             notification_type -> NotificationType,
             created_at -> Timestamptz,
+            category -> NotificationCategory,
+            payload -> Nullable<Jsonb>,
+            read_at -> Nullable<Timestamptz>,
         }
     }
 
@@ -161,6 +259,26 @@ pub mod public {
         }
     }
 
+    diesel::table! {
+        reviewer_conflict_settings (id) {
+            id -> Bool,
+            exclude_clanmates -> Bool,
+            exclude_flagged_users -> Bool,
+            exclude_level_creators -> Bool,
+            updated_by -> Nullable<Uuid>,
+            updated_at -> Timestamptz,
+        }
+    }
+
+    diesel::table! {
+        reviewer_conflicts (reviewer_id, user_id) {
+            reviewer_id -> Uuid,
+            user_id -> Uuid,
+            note -> Nullable<Text>,
+            created_at -> Timestamptz,
+        }
+    }
+
     diesel::table! {
         role_permissions (role_id, permission) {
             role_id -> Int4,
@@ -195,6 +313,41 @@ pub mod public {
         }
     }
 
+    diesel::table! {
+        submission_reasons (code) {
+            code -> Varchar,
+            label -> Varchar,
+            translations -> Jsonb,
+            enabled -> Bool,
+            created_at -> Timestamptz,
+            updated_at -> Timestamptz,
+        }
+    }
+
+    diesel::table! {
+        use diesel::sql_types::*;
+        use super::sql_types::SubmissionRuleList;
+        use super::sql_types::SubmissionRuleLevelStatus;
+
+        submission_requirement_rules (id) {
+            id -> Uuid,
+            list -> SubmissionRuleList,
+            description -> Varchar,
+            priority -> Int4,
+            level_status -> Nullable<SubmissionRuleLevelStatus>,
+            min_position -> Nullable<Int4>,
+            max_position -> Nullable<Int4>,
+            tag -> Nullable<Varchar>,
+            mobile -> Nullable<Bool>,
+            requires_raw_footage -> Nullable<Bool>,
+            requires_mod_menu -> Nullable<Bool>,
+            requires_custom_copy -> Nullable<Bool>,
+            enabled -> Bool,
+            created_at -> Timestamptz,
+            updated_at -> Timestamptz,
+        }
+    }
+
     diesel::table! {
         user_badges (user_id, badge_code) {
             user_id -> Uuid,
@@ -233,37 +386,90 @@ pub mod public {
         }
     }
 
+    diesel::table! {
+        use diesel::sql_types::*;
+        use super::sql_types::WebhookDeliveryStatus;
+
+        webhook_deliveries (id) {
+            id -> Uuid,
+            webhook_id -> Uuid,
+            event_id -> Int8,
+            status -> WebhookDeliveryStatus,
+            attempts -> Int4,
+            next_attempt_at -> Timestamptz,
+            response_status -> Nullable<Int4>,
+            error -> Nullable<Varchar>,
+            created_at -> Timestamptz,
+            updated_at -> Timestamptz,
+        }
+    }
+
+    diesel::table! {
+        webhooks (id) {
+            id -> Uuid,
+            url -> Varchar,
+            secret -> Varchar,
+            event_types -> Array<Nullable<Text>>,
+            enabled -> Bool,
+            consecutive_failures -> Int4,
+            created_by -> Nullable<Uuid>,
+            created_at -> Timestamptz,
+            updated_at -> Timestamptz,
+        }
+    }
+
     diesel::joinable!(clan_invites -> clans (clan_id));
     diesel::joinable!(clan_members -> clans (clan_id));
     diesel::joinable!(clan_members -> users (user_id));
+    diesel::joinable!(external_sync_logs -> external_sources (source_id));
+    diesel::joinable!(external_sync_subscriptions -> external_sources (source_id));
+    diesel::joinable!(external_sync_subscriptions -> external_sync_logs (last_log_id));
+    diesel::joinable!(external_sync_subscriptions -> users (user_id));
     diesel::joinable!(merge_logs -> users (primary_user));
+    diesel::joinable!(notification_preferences -> users (user_id));
     diesel::joinable!(notifications -> users (user_id));
     diesel::joinable!(oauth_connected_accounts -> users (user_id));
     diesel::joinable!(oauth_requests -> users (user_id));
+    diesel::joinable!(reviewer_conflict_settings -> users (updated_by));
+    diesel::joinable!(reviewer_conflicts -> users (user_id));
     diesel::joinable!(role_permissions -> permissions (permission));
     diesel::joinable!(role_permissions -> roles (role_id));
     diesel::joinable!(user_badges -> users (user_id));
     diesel::joinable!(user_roles -> roles (role_id));
     diesel::joinable!(user_roles -> users (user_id));
+    diesel::joinable!(webhook_deliveries -> notification_events (event_id));
+    diesel::joinable!(webhook_deliveries -> webhooks (webhook_id));
+    diesel::joinable!(webhooks -> users (created_by));
 
     diesel::allow_tables_to_appear_in_same_query!(
         clan_invites,
         clan_members,
         clans,
+        external_sources,
+        external_sync_logs,
+        external_sync_subscriptions,
         matview_refresh_log,
         merge_logs,
         merge_requests,
+        notification_events,
+        notification_preferences,
         notifications,
         oauth_connected_accounts,
         oauth_requests,
         oauth_tokens,
         permissions,
         recurrent_shifts,
+        reviewer_conflict_settings,
+        reviewer_conflicts,
         role_permissions,
         roles,
         shifts,
+        submission_reasons,
+        submission_requirement_rules,
         user_badges,
         user_roles,
         users,
+        webhook_deliveries,
+        webhooks,
     );
 }
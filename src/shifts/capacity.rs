@@ -0,0 +1,169 @@
+use crate::{
+    app_data::db::DbConnection,
+    error_handler::ApiError,
+    schema::{recurrent_shifts, shifts},
+    shifts::{ShiftStatus, Weekday},
+};
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use diesel::prelude::*;
+/// Amount of past days the review throughput and shift capacity are averaged over.
+pub const THROUGHPUT_WINDOW_DAYS: u64 = 14;
+/// Amount of upcoming days the scheduled shift capacity is looked at.
+const UPCOMING_WINDOW_DAYS: u64 = 7;
+/// Bounds of the factor the recent throughput is scaled by to account for the upcoming shifts.
+const CAPACITY_FACTOR_BOUNDS: (f64, f64) = (0.5, 2.0);
+
+/// Scheduled review capacity, in submissions per day, of the recent past and the upcoming week.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShiftCapacity {
+    /// Average daily target count of the shifts over the last days.
+    pub past_daily: f64,
+    /// Average daily target count of the shifts scheduled for the upcoming week.
+    pub upcoming_daily: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct QueueEta {
+    /// Estimated amount of hours until the submission is reviewed.
+    pub estimated_hours: f64,
+    /// Lower bound of the estimate, assuming reviewers keep up with their busier days.
+    pub min_hours: f64,
+    /// Upper bound of the estimate, assuming reviewers keep up with their slower days.
+    pub max_hours: f64,
+    /// Estimated timestamp of when the submission is reviewed.
+    pub estimated_at: DateTime<Utc>,
+    /// Amount of submissions the estimate assumes get reviewed per day.
+    pub daily_reviews: f64,
+}
+
+impl ShiftCapacity {
+    /// Loads the scheduled capacity, optionally limited to shifts of the given reviewers.
+    ///
+    /// Days of the upcoming week for which no shifts were created yet fall back to the recurring shifts
+    /// of that weekday.
+    pub fn load(conn: &mut DbConnection, reviewers: Option<&[Uuid]>) -> Result<Self, ApiError> {
+        let now = Utc::now();
+        let today = now.date_naive();
+        let window_start = today - Days::new(THROUGHPUT_WINDOW_DAYS);
+        let upcoming_end = today + Days::new(UPCOMING_WINDOW_DAYS);
+
+        let mut shifts_query = shifts::table
+            .filter(shifts::status.ne(ShiftStatus::Excused))
+            .filter(shifts::start_at.ge(start_of_day(window_start)))
+            .filter(shifts::start_at.lt(start_of_day(upcoming_end)))
+            .select((shifts::start_at, shifts::target_count))
+            .into_boxed();
+        let mut recurring_query = recurrent_shifts::table
+            .select((recurrent_shifts::weekday, recurrent_shifts::target_count))
+            .into_boxed();
+        if let Some(reviewers) = reviewers {
+            shifts_query = shifts_query.filter(shifts::user_id.eq_any(reviewers));
+            recurring_query = recurring_query.filter(recurrent_shifts::user_id.eq_any(reviewers));
+        }
+        let scheduled = shifts_query.load::<(DateTime<Utc>, i32)>(conn)?;
+        let recurring = recurring_query.load::<(Weekday, i32)>(conn)?;
+
+        let past_total: i64 = scheduled
+            .iter()
+            .filter(|(start_at, _)| start_at.date_naive() < today)
+            .map(|(_, target_count)| i64::from(*target_count))
+            .sum();
+
+        let upcoming_total: i64 = today
+            .iter_days()
+            .take_while(|day| *day < upcoming_end)
+            .map(|day| {
+                let created = scheduled
+                    .iter()
+                    .filter(|(start_at, _)| start_at.date_naive() == day)
+                    .map(|(_, target_count)| i64::from(*target_count))
+                    .collect::<Vec<_>>();
+                if created.is_empty() {
+                    let weekday = Weekday::from(day);
+                    recurring
+                        .iter()
+                        .filter(|(template_weekday, _)| *template_weekday == weekday)
+                        .map(|(_, target_count)| i64::from(*target_count))
+                        .sum::<i64>()
+                } else {
+                    created.into_iter().sum()
+                }
+            })
+            .sum();
+
+        Ok(Self {
+            past_daily: count_to_f64(past_total) / count_to_f64(THROUGHPUT_WINDOW_DAYS),
+            upcoming_daily: count_to_f64(upcoming_total) / count_to_f64(UPCOMING_WINDOW_DAYS),
+        })
+    }
+
+    /// How much more (or less) reviewing the upcoming shifts are expected to get done compared to the recent ones.
+    pub fn factor(&self) -> f64 {
+        if self.past_daily <= 0.0 || self.upcoming_daily <= 0.0 {
+            // without shifts on both sides there is nothing to compare, the recent throughput is used as is
+            return 1.0;
+        }
+        let (min, max) = CAPACITY_FACTOR_BOUNDS;
+        (self.upcoming_daily / self.past_daily).clamp(min, max)
+    }
+}
+
+impl QueueEta {
+    /// Estimates when a submission with `ahead` submissions in front of it gets reviewed, given the amount of
+    /// submissions reviewed on each of the last days. Returns `None` if nothing was reviewed recently.
+    pub fn estimate(ahead: i64, daily_reviews: &[i64], capacity: &ShiftCapacity) -> Option<Self> {
+        if daily_reviews.is_empty() {
+            return None;
+        }
+        let mut sorted = daily_reviews.to_vec();
+        sorted.sort_unstable();
+
+        let factor = capacity.factor();
+        let mean = count_to_f64(sorted.iter().sum::<i64>()) / count_to_f64(sorted.len()) * factor;
+        if mean <= 0.0 {
+            return None;
+        }
+        let percentile = |p: usize| {
+            sorted
+                .get((sorted.len() - 1) * p / 100)
+                .copied()
+                .map_or(0.0, count_to_f64)
+                * factor
+        };
+        // a quiet quarter of the days should not make the upper bound endless
+        let slow = percentile(25).max(mean / 4.0);
+        let fast = percentile(75).max(mean);
+
+        // the submission itself also has to be reviewed
+        let remaining = count_to_f64(ahead.max(0) + 1);
+        let hours = |daily: f64| (remaining / daily * 24.0 * 10.0).round() / 10.0;
+        let estimated_hours = hours(mean);
+
+        let estimated_at = std::time::Duration::try_from_secs_f64(estimated_hours * 3600.0)
+            .ok()
+            .and_then(|duration| chrono::Duration::from_std(duration).ok())
+            .and_then(|duration| Utc::now().checked_add_signed(duration))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+
+        Some(Self {
+            estimated_hours,
+            min_hours: hours(fast),
+            max_hours: hours(slow),
+            estimated_at,
+            daily_reviews: (mean * 10.0).round() / 10.0,
+        })
+    }
+}
+
+// review counts are far below the range where converting them to floats loses precision
+fn count_to_f64<T: TryInto<u32>>(count: T) -> f64 {
+    count.try_into().map_or(f64::from(u32::MAX), f64::from)
+}
+
+fn start_of_day(day: NaiveDate) -> DateTime<Utc> {
+    day.and_time(chrono::NaiveTime::MIN).and_utc()
+}
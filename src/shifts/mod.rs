@@ -1,3 +1,4 @@
+mod capacity;
 mod model;
 mod recurring;
 mod routes;
@@ -8,6 +9,7 @@ mod tests;
 #[cfg(test)]
 pub mod test_utils;
 
+pub use capacity::*;
 pub use model::*;
 pub use recurring::*;
 pub use routes::{init_routes, ApiDoc};
@@ -77,3 +77,18 @@ pub fn test_shifts_for_user(db: &Arc<DbAppState>, user_id: Uuid) -> Vec<Shift> {
         .load(&mut db.connection().unwrap())
         .expect("Failed to load test shifts for user")
 }
+
+#[cfg(test)]
+pub fn set_test_shift_start_at(
+    db: &Arc<DbAppState>,
+    shift_id: Uuid,
+    start_at: chrono::DateTime<Utc>,
+) {
+    diesel::update(shifts::table.filter(shifts::id.eq(shift_id)))
+        .set((
+            shifts::start_at.eq(start_at),
+            shifts::end_at.eq(start_at + chrono::Duration::hours(4)),
+        ))
+        .execute(&mut db.connection().unwrap())
+        .expect("Failed to set test shift start");
+}
@@ -0,0 +1,251 @@
+use crate::{app_data::db::DbConnection, error_handler::ApiError, schema::submission_reasons};
+use actix_web::{
+    dev::Payload,
+    http::header::{AcceptLanguage, Header as _, Preference},
+    FromRequest, HttpRequest,
+};
+use chrono::{DateTime, Utc};
+use diesel::{pg::Pg, AsChangeset, Queryable};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::future::{ready, Ready};
+use utoipa::ToSchema;
+
+use diesel::prelude::*;
+#[derive(Serialize, Deserialize, Selectable, Queryable, Debug, Clone, ToSchema)]
+#[diesel(table_name = submission_reasons, check_for_backend(Pg))]
+pub struct SubmissionReason {
+    /// Unique code of the reason, e.g. `missing_clicks`.
+    pub code: String,
+    /// Default text of the reason, shown when no translation matches the requested language.
+    pub label: String,
+    /// Translated texts of the reason, keyed by language tag (e.g. `fr`, `pt-BR`).
+    #[schema(value_type = BTreeMap<String, String>)]
+    pub translations: serde_json::Value,
+    /// Whether reviewers can currently select this reason. Disabled reasons are still shown on past reviews.
+    pub enabled: bool,
+    /// Timestamp of when this reason was created.
+    pub created_at: DateTime<Utc>,
+    /// Timestamp of when this reason was last updated.
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct SubmissionReasonCreate {
+    /// Unique code of the reason. Only lowercase letters, digits and underscores are allowed.
+    pub code: String,
+    /// Default text of the reason.
+    pub label: String,
+    /// Translated texts of the reason, keyed by language tag.
+    pub translations: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct SubmissionReasonPatch {
+    /// Default text of the reason.
+    pub label: Option<String>,
+    /// Translated texts of the reason, keyed by language tag. Replaces all the existing translations.
+    pub translations: Option<BTreeMap<String, String>>,
+    /// Whether reviewers can select this reason.
+    pub enabled: Option<bool>,
+}
+
+#[derive(AsChangeset, Debug)]
+#[diesel(table_name = submission_reasons)]
+struct SubmissionReasonChangeset {
+    label: Option<String>,
+    translations: Option<serde_json::Value>,
+    enabled: Option<bool>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct LocalizedSubmissionReason {
+    /// Code of the reason.
+    pub code: String,
+    /// Text of the reason, in the language requested through the `Accept-Language` header if available.
+    pub text: String,
+}
+
+/// Languages accepted by the client through the `Accept-Language` header, most preferred first.
+#[derive(Debug, Clone, Default)]
+pub struct RequestLocales(Vec<String>);
+
+impl FromRequest for RequestLocales {
+    type Error = ApiError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        // a malformed header is not worth failing the request over, the default texts are used instead
+        let locales = AcceptLanguage::parse(req)
+            .map(|header| {
+                header
+                    .ranked()
+                    .into_iter()
+                    .filter_map(Preference::into_item)
+                    .map(|tag| tag.to_string().to_lowercase())
+                    .collect()
+            })
+            .unwrap_or_default();
+        ready(Ok(Self(locales)))
+    }
+}
+
+impl RequestLocales {
+    pub fn new(locales: &[&str]) -> Self {
+        Self(locales.iter().map(|locale| locale.to_lowercase()).collect())
+    }
+}
+
+/// Every known reason, used to turn the codes stored on submissions into localized texts.
+pub struct SubmissionReasonCatalogue(HashMap<String, SubmissionReason>);
+
+impl SubmissionReasonCatalogue {
+    pub fn load(conn: &mut DbConnection) -> Result<Self, ApiError> {
+        let reasons = submission_reasons::table
+            .select(SubmissionReason::as_select())
+            .load::<SubmissionReason>(conn)?;
+        Ok(Self(
+            reasons
+                .into_iter()
+                .map(|reason| (reason.code.clone(), reason))
+                .collect(),
+        ))
+    }
+
+    pub fn localize(
+        &self,
+        codes: &[Option<String>],
+        locales: &RequestLocales,
+    ) -> Vec<LocalizedSubmissionReason> {
+        codes
+            .iter()
+            .flatten()
+            .map(|code| LocalizedSubmissionReason {
+                code: code.clone(),
+                text: self
+                    .0
+                    .get(code)
+                    .map_or_else(|| code.clone(), |reason| reason.localized_text(locales)),
+            })
+            .collect()
+    }
+}
+
+fn validate_code(code: &str) -> Result<(), ApiError> {
+    if code.is_empty()
+        || code.len() > 64
+        || !code
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    {
+        return Err(ApiError::BadRequest(
+            "Reason codes must be 1 to 64 characters long and only contain lowercase letters, digits and underscores.",
+        ));
+    }
+    Ok(())
+}
+
+fn translations_to_db(translations: BTreeMap<String, String>) -> serde_json::Value {
+    // language tags are matched case-insensitively, so they are stored lowercased
+    serde_json::Value::Object(
+        translations
+            .into_iter()
+            .map(|(locale, text)| (locale.to_lowercase(), serde_json::Value::String(text)))
+            .collect(),
+    )
+}
+
+impl SubmissionReason {
+    pub fn find_all(conn: &mut DbConnection) -> Result<Vec<Self>, ApiError> {
+        let reasons = submission_reasons::table
+            .order(submission_reasons::code.asc())
+            .select(SubmissionReason::as_select())
+            .load::<SubmissionReason>(conn)?;
+        Ok(reasons)
+    }
+
+    pub fn create(
+        conn: &mut DbConnection,
+        reason: SubmissionReasonCreate,
+    ) -> Result<Self, ApiError> {
+        validate_code(&reason.code)?;
+
+        let reason = diesel::insert_into(submission_reasons::table)
+            .values((
+                submission_reasons::code.eq(reason.code),
+                submission_reasons::label.eq(reason.label),
+                submission_reasons::translations
+                    .eq(translations_to_db(reason.translations.unwrap_or_default())),
+            ))
+            .returning(SubmissionReason::as_select())
+            .get_result::<SubmissionReason>(conn)?;
+        Ok(reason)
+    }
+
+    pub fn patch(
+        conn: &mut DbConnection,
+        code: &str,
+        patch: SubmissionReasonPatch,
+    ) -> Result<Self, ApiError> {
+        let reason = diesel::update(submission_reasons::table)
+            .filter(submission_reasons::code.eq(code))
+            .set(SubmissionReasonChangeset {
+                label: patch.label,
+                translations: patch.translations.map(translations_to_db),
+                enabled: patch.enabled,
+                updated_at: Utc::now(),
+            })
+            .returning(SubmissionReason::as_select())
+            .get_result::<SubmissionReason>(conn)?;
+        Ok(reason)
+    }
+
+    /// Checks that every code exists and can currently be selected, and removes duplicates.
+    pub fn validate_codes(
+        conn: &mut DbConnection,
+        codes: &[Option<String>],
+    ) -> Result<Vec<Option<String>>, ApiError> {
+        let mut validated: Vec<String> = Vec::with_capacity(codes.len());
+        for code in codes {
+            let Some(code) = code else {
+                return Err(ApiError::BadRequest("Reason codes cannot be null."));
+            };
+            if !validated.contains(code) {
+                validated.push(code.clone());
+            }
+        }
+
+        let enabled = submission_reasons::table
+            .filter(submission_reasons::code.eq_any(&validated))
+            .filter(submission_reasons::enabled.eq(true))
+            .select(submission_reasons::code)
+            .load::<String>(conn)?;
+
+        if let Some(unknown) = validated.iter().find(|code| !enabled.contains(code)) {
+            return Err(ApiError::BadRequest(format!(
+                "Unknown or disabled reason code: {unknown}"
+            )));
+        }
+
+        Ok(validated.into_iter().map(Some).collect())
+    }
+
+    /// Picks the translation matching the requested languages, first by exact tag then by primary language.
+    pub fn localized_text(&self, locales: &RequestLocales) -> String {
+        let translation = |locale: &str| {
+            self.translations
+                .get(locale)
+                .and_then(serde_json::Value::as_str)
+        };
+
+        locales
+            .0
+            .iter()
+            .find_map(|locale| {
+                translation(locale).or_else(|| translation(locale.split_once('-')?.0))
+            })
+            .unwrap_or(&self.label)
+            .to_owned()
+    }
+}
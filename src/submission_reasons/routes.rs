@@ -0,0 +1,110 @@
+use crate::{
+    app_data::db::DbAppState,
+    auth::{Permission, UserAuth},
+    error_handler::ApiError,
+    submission_reasons::{
+        LocalizedSubmissionReason, SubmissionReason, SubmissionReasonCreate, SubmissionReasonPatch,
+    },
+};
+use actix_web::{get, patch, post, web, HttpResponse};
+use std::sync::Arc;
+use tracing_actix_web::RootSpan;
+use utoipa::OpenApi;
+
+#[utoipa::path(
+    get,
+    summary = "List submission reasons",
+    description = "Lists the reasons reviewers can select when denying a submission or putting it under consideration, along with their translations. Disabled reasons can no longer be selected but are still shown on past reviews.",
+    tag = "Submission Reasons",
+    responses(
+        (status = 200, body = Vec<SubmissionReason>)
+    ),
+)]
+#[get("")]
+async fn find_all_reasons(db: web::Data<Arc<DbAppState>>) -> Result<HttpResponse, ApiError> {
+    let reasons = web::block(move || SubmissionReason::find_all(&mut db.connection()?)).await??;
+    Ok(HttpResponse::Ok().json(reasons))
+}
+
+#[utoipa::path(
+    post,
+    summary = "[Staff]Create a submission reason",
+    description = "Adds a new reason to the catalogue.",
+    tag = "Submission Reasons",
+    request_body = SubmissionReasonCreate,
+    responses(
+        (status = 200, body = SubmissionReason)
+    ),
+    security(
+        ("access_token" = ["SubmissionReasonManage"]),
+        ("api_key" = ["SubmissionReasonManage"]),
+    ),
+)]
+#[post("", wrap = "UserAuth::require(Permission::SubmissionReasonManage)")]
+async fn create_reason(
+    db: web::Data<Arc<DbAppState>>,
+    body: web::Json<SubmissionReasonCreate>,
+    root_span: RootSpan,
+) -> Result<HttpResponse, ApiError> {
+    root_span.record("body", tracing::field::debug(&body));
+    let reason =
+        web::block(move || SubmissionReason::create(&mut db.connection()?, body.into_inner()))
+            .await??;
+    Ok(HttpResponse::Ok().json(reason))
+}
+
+#[utoipa::path(
+    patch,
+    summary = "[Staff]Edit a submission reason",
+    description = "Edits the texts of a reason, or enables/disables it. Reasons cannot be deleted since past reviews reference them, disable them instead.",
+    tag = "Submission Reasons",
+    request_body = SubmissionReasonPatch,
+    params(
+        ("code" = String, description = "The code of the reason"),
+    ),
+    responses(
+        (status = 200, body = SubmissionReason)
+    ),
+    security(
+        ("access_token" = ["SubmissionReasonManage"]),
+        ("api_key" = ["SubmissionReasonManage"]),
+    ),
+)]
+#[patch(
+    "/{code}",
+    wrap = "UserAuth::require(Permission::SubmissionReasonManage)"
+)]
+async fn patch_reason(
+    db: web::Data<Arc<DbAppState>>,
+    code: web::Path<String>,
+    body: web::Json<SubmissionReasonPatch>,
+    root_span: RootSpan,
+) -> Result<HttpResponse, ApiError> {
+    root_span.record("body", tracing::field::debug(&body));
+    let reason = web::block(move || {
+        SubmissionReason::patch(&mut db.connection()?, &code, body.into_inner())
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(reason))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    components(schemas(
+        SubmissionReason,
+        SubmissionReasonCreate,
+        SubmissionReasonPatch,
+        LocalizedSubmissionReason,
+    )),
+    paths(find_all_reasons, create_reason, patch_reason)
+)]
+pub struct ApiDoc;
+
+pub fn init_routes(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/submission-reasons")
+            .service(find_all_reasons)
+            .service(create_reason)
+            .service(patch_reason),
+    );
+}
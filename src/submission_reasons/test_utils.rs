@@ -0,0 +1,31 @@
+#[cfg(test)]
+use {
+    crate::{app_data::db::DbAppState, schema::submission_reasons},
+    diesel::prelude::*,
+    serde_json::json,
+    std::sync::Arc,
+    uuid::Uuid,
+};
+
+/// Creates an enabled reason with a unique code and a French translation.
+#[cfg(test)]
+pub async fn create_test_reason(db: &Arc<DbAppState>) -> String {
+    let code = format!("test_{}", Uuid::new_v4().simple());
+    diesel::insert_into(submission_reasons::table)
+        .values((
+            submission_reasons::code.eq(&code),
+            submission_reasons::label.eq("Test reason"),
+            submission_reasons::translations.eq(json!({"fr": "Raison de test"})),
+        ))
+        .execute(&mut db.connection().unwrap())
+        .expect("Failed to create test submission reason");
+    code
+}
+
+#[cfg(test)]
+pub async fn disable_test_reason(db: &Arc<DbAppState>, code: &str) {
+    diesel::update(submission_reasons::table.filter(submission_reasons::code.eq(code)))
+        .set(submission_reasons::enabled.eq(false))
+        .execute(&mut db.connection().unwrap())
+        .expect("Failed to disable test submission reason");
+}
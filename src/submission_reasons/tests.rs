@@ -0,0 +1,177 @@
+#[cfg(test)]
+use {
+    crate::{
+        aredl::{
+            levels::test_utils::create_test_level,
+            submissions::{
+                test_utils::{create_test_submission, get_test_submission},
+                SubmissionStatus,
+            },
+        },
+        auth::{create_test_token, Permission},
+        submission_reasons::test_utils::{create_test_reason, disable_test_reason},
+        test_utils::*,
+        users::test_utils::{create_test_full_reviewer, create_test_user},
+    },
+    actix_http::StatusCode,
+    actix_web::{
+        http::header,
+        test::{self, read_body_json},
+    },
+    serde_json::{json, Value},
+    uuid::Uuid,
+};
+
+#[actix_web::test]
+async fn list_submission_reasons() {
+    let (app, db, _, _) = init_test_app().await;
+    let code = create_test_reason(&db).await;
+
+    let req = test::TestRequest::get()
+        .uri("/submission-reasons")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    let body: Value = read_body_json(resp).await;
+    let reasons = body.as_array().expect("Response should be an array");
+    assert!(reasons
+        .iter()
+        .any(|reason| reason["code"] == "missing_clicks"));
+    let reason = reasons
+        .iter()
+        .find(|reason| reason["code"] == code.as_str())
+        .expect("Test reason should be listed");
+    assert_eq!(reason["translations"]["fr"], "Raison de test");
+    assert_eq!(reason["enabled"], true);
+}
+
+#[actix_web::test]
+async fn create_and_edit_submission_reason() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, Some(Permission::SubmissionReasonManage)).await;
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+    let code = format!("test_{}", Uuid::new_v4().simple());
+
+    let req = test::TestRequest::post()
+        .uri("/submission-reasons")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({
+            "code": code,
+            "label": "Wrong attempt count",
+            "translations": {"pt-BR": "Contagem de tentativas errada"}
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: Value = read_body_json(resp).await;
+    assert_eq!(body["code"], code.as_str());
+    assert_eq!(
+        body["translations"]["pt-br"], "Contagem de tentativas errada",
+        "Translation keys should be stored lowercased"
+    );
+
+    let req = test::TestRequest::patch()
+        .uri(&format!("/submission-reasons/{code}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"label": "Wrong attempts", "enabled": false}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: Value = read_body_json(resp).await;
+    assert_eq!(body["label"], "Wrong attempts");
+    assert_eq!(body["enabled"], false);
+    assert_eq!(
+        body["translations"]["pt-br"], "Contagem de tentativas errada",
+        "Translations should be left unchanged"
+    );
+}
+
+#[actix_web::test]
+async fn create_submission_reason_validation() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, Some(Permission::SubmissionReasonManage)).await;
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+    let (other_id, _) = create_test_user(&db, None).await;
+    let other_token = create_test_token(other_id, &auth.jwt_encoding_key).unwrap();
+
+    let req = test::TestRequest::post()
+        .uri("/submission-reasons")
+        .insert_header(("Authorization", format!("Bearer {other_token}")))
+        .set_json(json!({"code": "some_reason", "label": "Some reason"}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let req = test::TestRequest::post()
+        .uri("/submission-reasons")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"code": "Not A Code", "label": "Invalid"}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let req = test::TestRequest::post()
+        .uri("/submission-reasons")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"code": "missing_clicks", "label": "Duplicate"}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+}
+
+#[actix_web::test]
+async fn denial_reasons_are_localized_for_the_submitter() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, None).await;
+    let user_token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+    let (reviewer_id, _) = create_test_full_reviewer(&db).await;
+    let reviewer_token = create_test_token(reviewer_id, &auth.jwt_encoding_key).unwrap();
+    let level_id = create_test_level(&db).await;
+    let submission_id = create_test_submission(level_id, user_id, &db).await;
+    let code = create_test_reason(&db).await;
+
+    let req = test::TestRequest::patch()
+        .uri(&format!("/aredl/submissions/{submission_id}"))
+        .insert_header(("Authorization", format!("Bearer {reviewer_token}")))
+        .set_json(json!({"status": "Denied", "reason_codes": [code, code, "no_endscreen"]}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    let stored = get_test_submission(&db, submission_id);
+    assert_eq!(stored.status, SubmissionStatus::Denied);
+    assert_eq!(
+        stored.reason_codes,
+        vec![Some(code.clone()), Some("no_endscreen".to_owned())],
+        "Duplicate codes should be removed"
+    );
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/aredl/submissions/{submission_id}"))
+        .insert_header(("Authorization", format!("Bearer {user_token}")))
+        .insert_header((header::ACCEPT_LANGUAGE, "fr-CA, en;q=0.5"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: Value = read_body_json(resp).await;
+    assert_eq!(body["reasons"][0]["code"], code.as_str());
+    assert_eq!(body["reasons"][0]["text"], "Raison de test");
+    assert_eq!(
+        body["reasons"][1]["text"], "No endscreen",
+        "Reasons without a matching translation should use their default text"
+    );
+
+    // disabled reasons are still shown on past reviews
+    disable_test_reason(&db, &code).await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/aredl/submissions/{submission_id}/history"))
+        .insert_header(("Authorization", format!("Bearer {user_token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: Value = read_body_json(resp).await;
+    assert_eq!(body[0]["status"], "Denied");
+    assert_eq!(body[0]["reasons"][0]["text"], "Test reason");
+}
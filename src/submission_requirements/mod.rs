@@ -0,0 +1,11 @@
+mod model;
+mod routes;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(test)]
+pub mod test_utils;
+
+pub use model::*;
+pub use routes::{init_routes, ApiDoc};
@@ -0,0 +1,354 @@
+use crate::{
+    app_data::db::DbConnection, aredl, arepl, error_handler::ApiError,
+    schema::submission_requirement_rules,
+};
+use chrono::{DateTime, Utc};
+use diesel::{pg::Pg, AsChangeset, Queryable, Selectable};
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+use serde_with::rust::double_option;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use diesel::prelude::*;
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema, DbEnum)]
+#[ExistingTypePath = "crate::schema::sql_types::SubmissionRuleList"]
+#[DbValueStyle = "snake_case"]
+#[serde(rename_all = "lowercase")]
+pub enum SubmissionRuleList {
+    Aredl,
+    Arepl,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema, DbEnum)]
+#[ExistingTypePath = "crate::schema::sql_types::SubmissionRuleLevelStatus"]
+#[DbValueStyle = "PascalCase"]
+pub enum SubmissionRuleLevelStatus {
+    Pending,
+    MainList,
+    Legacy,
+    Removed,
+}
+
+impl From<&aredl::levels::LevelStatus> for SubmissionRuleLevelStatus {
+    fn from(status: &aredl::levels::LevelStatus) -> Self {
+        match status {
+            aredl::levels::LevelStatus::Pending => Self::Pending,
+            aredl::levels::LevelStatus::MainList => Self::MainList,
+            aredl::levels::LevelStatus::Legacy => Self::Legacy,
+            aredl::levels::LevelStatus::Removed => Self::Removed,
+        }
+    }
+}
+
+impl From<&arepl::levels::LevelStatus> for SubmissionRuleLevelStatus {
+    fn from(status: &arepl::levels::LevelStatus) -> Self {
+        match status {
+            arepl::levels::LevelStatus::Pending => Self::Pending,
+            arepl::levels::LevelStatus::MainList => Self::MainList,
+            arepl::levels::LevelStatus::Legacy => Self::Legacy,
+            arepl::levels::LevelStatus::Removed => Self::Removed,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Selectable, Queryable, Debug, Clone, ToSchema)]
+#[diesel(table_name = submission_requirement_rules, check_for_backend(Pg))]
+pub struct SubmissionRequirementRule {
+    /// Internal UUID of the rule.
+    pub id: Uuid,
+    /// The list this rule applies to.
+    pub list: SubmissionRuleList,
+    /// Explanation of the rule. Shown to submitters when a requirement of this rule is not met.
+    pub description: String,
+    /// Rules with a higher priority take precedence over lower ones for each requirement they set.
+    pub priority: i32,
+    /// Only apply to levels with this status.
+    pub level_status: Option<SubmissionRuleLevelStatus>,
+    /// Only apply to levels placed at this position or below.
+    pub min_position: Option<i32>,
+    /// Only apply to levels placed at this position or above.
+    pub max_position: Option<i32>,
+    /// Only apply to levels with this tag.
+    pub tag: Option<String>,
+    /// Only apply to mobile (`true`) or PC (`false`) submissions.
+    pub mobile: Option<bool>,
+    /// Whether raw footage is required. `null` leaves the decision to lower priority rules.
+    pub requires_raw_footage: Option<bool>,
+    /// Whether the mod menu used must be declared. `null` leaves the decision to lower priority rules.
+    pub requires_mod_menu: Option<bool>,
+    /// Whether the custom copy used must be declared. `null` leaves the decision to lower priority rules.
+    pub requires_custom_copy: Option<bool>,
+    /// Whether this rule is currently evaluated.
+    pub enabled: bool,
+    /// Timestamp of when this rule was created.
+    pub created_at: DateTime<Utc>,
+    /// Timestamp of when this rule was last updated.
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema, Insertable)]
+#[diesel(table_name = submission_requirement_rules, check_for_backend(Pg))]
+pub struct SubmissionRequirementRuleCreate {
+    /// The list this rule applies to.
+    pub list: SubmissionRuleList,
+    /// Explanation of the rule. Shown to submitters when a requirement of this rule is not met.
+    pub description: String,
+    /// Rules with a higher priority take precedence over lower ones for each requirement they set. Defaults to 0.
+    pub priority: Option<i32>,
+    /// Only apply to levels with this status.
+    pub level_status: Option<SubmissionRuleLevelStatus>,
+    /// Only apply to levels placed at this position or below.
+    pub min_position: Option<i32>,
+    /// Only apply to levels placed at this position or above.
+    pub max_position: Option<i32>,
+    /// Only apply to levels with this tag.
+    pub tag: Option<String>,
+    /// Only apply to mobile (`true`) or PC (`false`) submissions.
+    pub mobile: Option<bool>,
+    /// Whether raw footage is required.
+    pub requires_raw_footage: Option<bool>,
+    /// Whether the mod menu used must be declared.
+    pub requires_mod_menu: Option<bool>,
+    /// Whether the custom copy used must be declared.
+    pub requires_custom_copy: Option<bool>,
+    /// Whether this rule is evaluated. Defaults to `true`.
+    pub enabled: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, ToSchema, AsChangeset)]
+#[diesel(table_name = submission_requirement_rules)]
+pub struct SubmissionRequirementRulePatch {
+    /// Explanation of the rule.
+    pub description: Option<String>,
+    /// Priority of the rule.
+    pub priority: Option<i32>,
+    /// Only apply to levels with this status.
+    #[serde(default, with = "double_option")]
+    pub level_status: Option<Option<SubmissionRuleLevelStatus>>,
+    /// Only apply to levels placed at this position or below.
+    #[serde(default, with = "double_option")]
+    pub min_position: Option<Option<i32>>,
+    /// Only apply to levels placed at this position or above.
+    #[serde(default, with = "double_option")]
+    pub max_position: Option<Option<i32>>,
+    /// Only apply to levels with this tag.
+    #[serde(default, with = "double_option")]
+    pub tag: Option<Option<String>>,
+    /// Only apply to mobile (`true`) or PC (`false`) submissions.
+    #[serde(default, with = "double_option")]
+    pub mobile: Option<Option<bool>>,
+    /// Whether raw footage is required.
+    #[serde(default, with = "double_option")]
+    pub requires_raw_footage: Option<Option<bool>>,
+    /// Whether the mod menu used must be declared.
+    #[serde(default, with = "double_option")]
+    pub requires_mod_menu: Option<Option<bool>>,
+    /// Whether the custom copy used must be declared.
+    #[serde(default, with = "double_option")]
+    pub requires_custom_copy: Option<Option<bool>>,
+    /// Whether this rule is evaluated.
+    pub enabled: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct SubmissionRequirementRuleQuery {
+    pub list: Option<SubmissionRuleList>,
+}
+
+/// The level a submission is made for, as seen by the requirement rules.
+pub struct RequirementLevel<'a> {
+    pub status: SubmissionRuleLevelStatus,
+    pub position: Option<i32>,
+    pub tags: &'a [Option<String>],
+    /// Per-level raw footage flag, used when no rule decides whether raw footage is required.
+    pub requires_raw_footage: bool,
+}
+
+/// What a submission must include, along with the message shown when it is missing.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SubmissionRequirements {
+    pub raw_footage: Option<String>,
+    pub mod_menu: Option<String>,
+    pub custom_copy: Option<String>,
+}
+
+impl SubmissionRequirementRule {
+    pub fn find_all(
+        conn: &mut DbConnection,
+        list: Option<SubmissionRuleList>,
+    ) -> Result<Vec<Self>, ApiError> {
+        let mut rules = submission_requirement_rules::table
+            .order((
+                submission_requirement_rules::list.asc(),
+                submission_requirement_rules::priority.desc(),
+                submission_requirement_rules::created_at.asc(),
+            ))
+            .select(SubmissionRequirementRule::as_select())
+            .into_boxed();
+        if let Some(list) = list {
+            rules = rules.filter(submission_requirement_rules::list.eq(list));
+        }
+        Ok(rules.load::<SubmissionRequirementRule>(conn)?)
+    }
+
+    pub fn create(
+        conn: &mut DbConnection,
+        rule: SubmissionRequirementRuleCreate,
+    ) -> Result<Self, ApiError> {
+        validate_position_range(rule.min_position, rule.max_position)?;
+        let rule = diesel::insert_into(submission_requirement_rules::table)
+            .values(rule)
+            .returning(SubmissionRequirementRule::as_select())
+            .get_result::<SubmissionRequirementRule>(conn)?;
+        Ok(rule)
+    }
+
+    pub fn patch(
+        conn: &mut DbConnection,
+        id: Uuid,
+        patch: SubmissionRequirementRulePatch,
+    ) -> Result<Self, ApiError> {
+        conn.transaction(|connection| -> Result<Self, ApiError> {
+            let (min_position, max_position) = submission_requirement_rules::table
+                .filter(submission_requirement_rules::id.eq(id))
+                .select((
+                    submission_requirement_rules::min_position,
+                    submission_requirement_rules::max_position,
+                ))
+                .for_update()
+                .first::<(Option<i32>, Option<i32>)>(connection)?;
+            validate_position_range(
+                patch.min_position.unwrap_or(min_position),
+                patch.max_position.unwrap_or(max_position),
+            )?;
+
+            let rule = diesel::update(submission_requirement_rules::table)
+                .filter(submission_requirement_rules::id.eq(id))
+                .set((
+                    patch,
+                    submission_requirement_rules::updated_at.eq(Utc::now()),
+                ))
+                .returning(SubmissionRequirementRule::as_select())
+                .get_result::<SubmissionRequirementRule>(connection)?;
+            Ok(rule)
+        })
+    }
+
+    pub fn delete(conn: &mut DbConnection, id: Uuid) -> Result<(), ApiError> {
+        let deleted = diesel::delete(submission_requirement_rules::table)
+            .filter(submission_requirement_rules::id.eq(id))
+            .execute(conn)?;
+        if deleted == 0 {
+            return Err(ApiError::NotFound("Could not find this rule"));
+        }
+        Ok(())
+    }
+
+    fn matches(&self, level: &RequirementLevel, mobile: bool) -> bool {
+        self.level_status
+            .is_none_or(|status| status == level.status)
+            && self.mobile.is_none_or(|rule_mobile| rule_mobile == mobile)
+            && self
+                .min_position
+                .is_none_or(|min| level.position.is_some_and(|position| position >= min))
+            && self
+                .max_position
+                .is_none_or(|max| level.position.is_some_and(|position| position <= max))
+            && self.tag.as_ref().is_none_or(|tag| {
+                level
+                    .tags
+                    .iter()
+                    .flatten()
+                    .any(|level_tag| level_tag.eq_ignore_ascii_case(tag))
+            })
+    }
+}
+
+fn validate_position_range(min: Option<i32>, max: Option<i32>) -> Result<(), ApiError> {
+    if let (Some(min), Some(max)) = (min, max) {
+        if min > max {
+            return Err(ApiError::BadRequest(
+                "min_position cannot be greater than max_position",
+            ));
+        }
+    }
+    Ok(())
+}
+
+impl SubmissionRequirements {
+    /// Evaluates the enabled rules of a list, from the highest priority to the lowest. For each requirement,
+    /// the first matching rule that sets it decides.
+    pub fn resolve(
+        conn: &mut DbConnection,
+        list: SubmissionRuleList,
+        level: &RequirementLevel,
+        mobile: bool,
+    ) -> Result<Self, ApiError> {
+        let rules = submission_requirement_rules::table
+            .filter(submission_requirement_rules::list.eq(list))
+            .filter(submission_requirement_rules::enabled.eq(true))
+            .order((
+                submission_requirement_rules::priority.desc(),
+                submission_requirement_rules::created_at.asc(),
+            ))
+            .select(SubmissionRequirementRule::as_select())
+            .load::<SubmissionRequirementRule>(conn)?;
+
+        Ok(Self::from_rules(&rules, level, mobile))
+    }
+
+    fn from_rules(
+        rules: &[SubmissionRequirementRule],
+        level: &RequirementLevel,
+        mobile: bool,
+    ) -> Self {
+        let decide = |requirement: fn(&SubmissionRequirementRule) -> Option<bool>| {
+            rules
+                .iter()
+                .filter(|rule| rule.matches(level, mobile))
+                .find_map(|rule| requirement(rule).map(|required| (required, &rule.description)))
+        };
+
+        let raw_footage = match decide(|rule| rule.requires_raw_footage) {
+            Some((required, description)) => required.then(|| description.clone()),
+            None => level
+                .requires_raw_footage
+                .then(|| "This level requires raw footage".to_owned()),
+        };
+
+        Self {
+            raw_footage,
+            mod_menu: decide(|rule| rule.requires_mod_menu)
+                .and_then(|(required, description)| required.then(|| description.clone())),
+            custom_copy: decide(|rule| rule.requires_custom_copy)
+                .and_then(|(required, description)| required.then(|| description.clone())),
+        }
+    }
+
+    pub fn check(
+        &self,
+        raw_url: Option<&str>,
+        mod_menu: Option<&str>,
+        custom_copy_id: Option<i32>,
+    ) -> Result<(), ApiError> {
+        let missing = [
+            (
+                &self.raw_footage,
+                raw_url.is_some_and(|url| !url.is_empty()),
+            ),
+            (
+                &self.mod_menu,
+                mod_menu.is_some_and(|menu| !menu.trim().is_empty()),
+            ),
+            (&self.custom_copy, custom_copy_id.is_some()),
+        ]
+        .into_iter()
+        .find_map(|(message, provided)| message.as_ref().filter(|_| !provided));
+
+        match missing {
+            Some(message) => Err(ApiError::UnprocessableEntity(message)),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{
+    app_data::db::DbAppState,
+    auth::{Permission, UserAuth},
+    error_handler::ApiError,
+    submission_requirements::{
+        SubmissionRequirementRule, SubmissionRequirementRuleCreate, SubmissionRequirementRulePatch,
+        SubmissionRequirementRuleQuery, SubmissionRuleLevelStatus, SubmissionRuleList,
+    },
+};
+use actix_web::{delete, get, patch, post, web, HttpResponse};
+use std::sync::Arc;
+use tracing_actix_web::RootSpan;
+use utoipa::OpenApi;
+use uuid::Uuid;
+
+#[utoipa::path(
+    get,
+    summary = "List submission requirement rules",
+    description = "Lists the rules deciding whether a submission must include raw footage, the mod menu used or the custom copy used. For each requirement, the matching rule with the highest priority decides.",
+    tag = "Submission Requirements",
+    params(
+        ("list" = Option<SubmissionRuleList>, Query, description = "Only list the rules of this list"),
+    ),
+    responses(
+        (status = 200, body = Vec<SubmissionRequirementRule>)
+    ),
+)]
+#[get("")]
+async fn find_all_rules(
+    db: web::Data<Arc<DbAppState>>,
+    query: web::Query<SubmissionRequirementRuleQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let rules =
+        web::block(move || SubmissionRequirementRule::find_all(&mut db.connection()?, query.list))
+            .await??;
+    Ok(HttpResponse::Ok().json(rules))
+}
+
+#[utoipa::path(
+    post,
+    summary = "[Staff]Create a submission requirement rule",
+    description = "Adds a new rule, applied to every submission created from now on.",
+    tag = "Submission Requirements",
+    request_body = SubmissionRequirementRuleCreate,
+    responses(
+        (status = 200, body = SubmissionRequirementRule)
+    ),
+    security(
+        ("access_token" = ["SubmissionRequirementManage"]),
+        ("api_key" = ["SubmissionRequirementManage"]),
+    ),
+)]
+#[post(
+    "",
+    wrap = "UserAuth::require(Permission::SubmissionRequirementManage)"
+)]
+async fn create_rule(
+    db: web::Data<Arc<DbAppState>>,
+    body: web::Json<SubmissionRequirementRuleCreate>,
+    root_span: RootSpan,
+) -> Result<HttpResponse, ApiError> {
+    root_span.record("body", tracing::field::debug(&body));
+    let rule = web::block(move || {
+        SubmissionRequirementRule::create(&mut db.connection()?, body.into_inner())
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(rule))
+}
+
+#[utoipa::path(
+    patch,
+    summary = "[Staff]Edit a submission requirement rule",
+    description = "Edits a rule. Set a condition or requirement to `null` to remove it.",
+    tag = "Submission Requirements",
+    request_body = SubmissionRequirementRulePatch,
+    params(
+        ("id" = Uuid, description = "Internal UUID of the rule"),
+    ),
+    responses(
+        (status = 200, body = SubmissionRequirementRule)
+    ),
+    security(
+        ("access_token" = ["SubmissionRequirementManage"]),
+        ("api_key" = ["SubmissionRequirementManage"]),
+    ),
+)]
+#[patch(
+    "/{id}",
+    wrap = "UserAuth::require(Permission::SubmissionRequirementManage)"
+)]
+async fn patch_rule(
+    db: web::Data<Arc<DbAppState>>,
+    id: web::Path<Uuid>,
+    body: web::Json<SubmissionRequirementRulePatch>,
+    root_span: RootSpan,
+) -> Result<HttpResponse, ApiError> {
+    root_span.record("body", tracing::field::debug(&body));
+    let rule = web::block(move || {
+        SubmissionRequirementRule::patch(&mut db.connection()?, id.into_inner(), body.into_inner())
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(rule))
+}
+
+#[utoipa::path(
+    delete,
+    summary = "[Staff]Delete a submission requirement rule",
+    description = "Deletes a rule. Existing submissions are not affected.",
+    tag = "Submission Requirements",
+    params(
+        ("id" = Uuid, description = "Internal UUID of the rule"),
+    ),
+    responses(
+        (status = 204)
+    ),
+    security(
+        ("access_token" = ["SubmissionRequirementManage"]),
+        ("api_key" = ["SubmissionRequirementManage"]),
+    ),
+)]
+#[delete(
+    "/{id}",
+    wrap = "UserAuth::require(Permission::SubmissionRequirementManage)"
+)]
+async fn delete_rule(
+    db: web::Data<Arc<DbAppState>>,
+    id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    web::block(move || SubmissionRequirementRule::delete(&mut db.connection()?, id.into_inner()))
+        .await??;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    components(schemas(
+        SubmissionRequirementRule,
+        SubmissionRequirementRuleCreate,
+        SubmissionRequirementRulePatch,
+        SubmissionRuleList,
+        SubmissionRuleLevelStatus,
+    )),
+    paths(find_all_rules, create_rule, patch_rule, delete_rule)
+)]
+pub struct ApiDoc;
+
+pub fn init_routes(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/submission-requirements")
+            .service(find_all_rules)
+            .service(create_rule)
+            .service(patch_rule)
+            .service(delete_rule),
+    );
+}
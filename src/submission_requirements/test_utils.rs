@@ -0,0 +1,30 @@
+#[cfg(test)]
+use {
+    crate::{
+        app_data::db::DbAppState, schema::submission_requirement_rules,
+        submission_requirements::SubmissionRuleList,
+    },
+    diesel::prelude::*,
+    std::sync::Arc,
+    uuid::Uuid,
+};
+
+/// Creates a high priority rule requiring the mod menu to be declared, only applying to levels with the given tag.
+#[cfg(test)]
+pub async fn create_test_mod_menu_rule(
+    db: &Arc<DbAppState>,
+    list: SubmissionRuleList,
+    tag: &str,
+) -> Uuid {
+    diesel::insert_into(submission_requirement_rules::table)
+        .values((
+            submission_requirement_rules::list.eq(list),
+            submission_requirement_rules::description.eq("This level requires the mod menu used"),
+            submission_requirement_rules::priority.eq(100),
+            submission_requirement_rules::tag.eq(tag),
+            submission_requirement_rules::requires_mod_menu.eq(true),
+        ))
+        .returning(submission_requirement_rules::id)
+        .get_result::<Uuid>(&mut db.connection().unwrap())
+        .expect("Failed to create test submission requirement rule")
+}
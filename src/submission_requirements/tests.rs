@@ -0,0 +1,200 @@
+#[cfg(test)]
+use {
+    crate::{
+        aredl::levels::test_utils::{create_test_level, set_test_level_tags},
+        arepl,
+        auth::{create_test_token, Permission},
+        submission_requirements::{test_utils::create_test_mod_menu_rule, SubmissionRuleList},
+        test_utils::*,
+        users::test_utils::create_test_user,
+    },
+    actix_http::StatusCode,
+    actix_web::test::{self, read_body_json},
+    serde_json::{json, Value},
+    uuid::Uuid,
+};
+
+#[actix_web::test]
+async fn list_submission_requirement_rules() {
+    let (app, _, _, _) = init_test_app().await;
+
+    let req = test::TestRequest::get()
+        .uri("/submission-requirements?list=aredl")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    let body: Value = read_body_json(resp).await;
+    let rules = body.as_array().expect("Response should be an array");
+    assert!(rules.iter().all(|rule| rule["list"] == "aredl"));
+    assert!(
+        rules.iter().any(|rule| rule["level_status"] == "MainList"
+            && rule["max_position"] == 400
+            && rule["requires_raw_footage"] == true),
+        "The top 400 raw footage rule should be seeded"
+    );
+}
+
+#[actix_web::test]
+async fn create_edit_and_delete_submission_requirement_rule() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, Some(Permission::SubmissionRequirementManage)).await;
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+    let (other_id, _) = create_test_user(&db, None).await;
+    let other_token = create_test_token(other_id, &auth.jwt_encoding_key).unwrap();
+    let tag = format!("tag_{}", Uuid::new_v4().simple());
+    let rule = json!({
+        "list": "arepl",
+        "description": "Mobile submissions require raw footage",
+        "tag": tag,
+        "mobile": true,
+        "requires_raw_footage": true,
+    });
+
+    let req = test::TestRequest::post()
+        .uri("/submission-requirements")
+        .insert_header(("Authorization", format!("Bearer {other_token}")))
+        .set_json(&rule)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let req = test::TestRequest::post()
+        .uri("/submission-requirements")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(&rule)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: Value = read_body_json(resp).await;
+    let id = body["id"].as_str().unwrap().to_owned();
+    assert_eq!(body["priority"], 0);
+    assert_eq!(body["enabled"], true);
+
+    let req = test::TestRequest::patch()
+        .uri(&format!("/submission-requirements/{id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"mobile": null, "requires_mod_menu": true, "priority": 5}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: Value = read_body_json(resp).await;
+    assert_eq!(body["mobile"], Value::Null);
+    assert_eq!(body["requires_mod_menu"], true);
+    assert_eq!(body["requires_raw_footage"], true);
+    assert_eq!(body["priority"], 5);
+
+    let req = test::TestRequest::patch()
+        .uri(&format!("/submission-requirements/{id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"min_position": 50, "max_position": 10}))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    for expected in [StatusCode::NO_CONTENT, StatusCode::NOT_FOUND] {
+        let req = test::TestRequest::delete()
+            .uri(&format!("/submission-requirements/{id}"))
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), expected);
+    }
+}
+
+#[actix_web::test]
+async fn requirement_rules_apply_to_aredl_submissions() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (admin_id, _) = create_test_user(&db, Some(Permission::SubmissionRequirementManage)).await;
+    let admin_token = create_test_token(admin_id, &auth.jwt_encoding_key).unwrap();
+    let (user_id, _) = create_test_user(&db, None).await;
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+    let tag = format!("tag_{}", Uuid::new_v4().simple());
+    let level_id = create_test_level(&db).await;
+    set_test_level_tags(&db, level_id, &[&tag]).await;
+
+    // test levels are top 1, this rule takes precedence over the raw footage requirement
+    let req = test::TestRequest::post()
+        .uri("/submission-requirements")
+        .insert_header(("Authorization", format!("Bearer {admin_token}")))
+        .set_json(json!({
+            "list": "aredl",
+            "description": "This level requires the custom copy used",
+            "priority": 100,
+            "tag": tag,
+            "requires_raw_footage": false,
+            "requires_custom_copy": true,
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    let req = test::TestRequest::post()
+        .uri("/aredl/submissions")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({
+            "level_id": level_id,
+            "video_url": "https://youtube.com/watch?v=xvFZjo5PgG0",
+            "mobile": false,
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_error_response!(
+        resp,
+        StatusCode::UNPROCESSABLE_ENTITY,
+        Some("This level requires the custom copy used"),
+    );
+
+    let req = test::TestRequest::post()
+        .uri("/aredl/submissions")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({
+            "level_id": level_id,
+            "video_url": "https://youtube.com/watch?v=xvFZjo5PgG0",
+            "mobile": false,
+            "custom_copy_id": 12345,
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+}
+
+#[actix_web::test]
+async fn requirement_rules_apply_to_arepl_submissions() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, None).await;
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+    let tag = format!("tag_{}", Uuid::new_v4().simple());
+    let level_id = arepl::levels::test_utils::create_test_level(&db).await;
+    arepl::levels::test_utils::set_test_level_tags(&db, level_id, &[&tag]).await;
+    create_test_mod_menu_rule(&db, SubmissionRuleList::Arepl, &tag).await;
+
+    let mut submission = json!({
+        "level_id": level_id,
+        "video_url": "https://youtube.com/watch?v=xvFZjo5PgG0",
+        "raw_url": "https://youtube.com/watch?v=xvFZjo5PgG0",
+        "mobile": false,
+        "completion_time": 1000,
+    });
+
+    let req = test::TestRequest::post()
+        .uri("/arepl/submissions")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(&submission)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_error_response!(
+        resp,
+        StatusCode::UNPROCESSABLE_ENTITY,
+        Some("This level requires the mod menu used"),
+    );
+
+    submission["mod_menu"] = json!("Mega Hack v8");
+    let req = test::TestRequest::post()
+        .uri("/arepl/submissions")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(&submission)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+}
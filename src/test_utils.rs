@@ -22,7 +22,11 @@ use tokio::sync::broadcast;
 use tracing_actix_web::TracingLogger;
 
 use crate::{
-    app_data::db::init_test_db_state, notifications::WebsocketNotification, AppRootSpanBuilder,
+    app_data::db::init_test_db_state,
+    aredl::submissions::queue::QueueThroughputCache as AredlQueueThroughputCache,
+    arepl::submissions::queue::QueueThroughputCache as AreplQueueThroughputCache,
+    notifications::WebsocketNotification, users::me::notifications::Notification,
+    AppRootSpanBuilder,
 };
 
 pub struct BoxResponse;
@@ -83,6 +87,7 @@ pub async fn init_test_app() -> (
     let auth_app_state = auth_init_app_state().expect("Failed to initialize auth test state");
 
     let (notify_tx, _notify_rx) = broadcast::channel::<WebsocketNotification>(100);
+    let (inbox_tx, _inbox_rx) = broadcast::channel::<Notification>(256);
 
     let db_app_state = init_test_db_state();
 
@@ -93,7 +98,10 @@ pub async fn init_test_app() -> (
             .app_data(Data::new(db_app_state.clone()))
             .app_data(Data::new(auth_app_state.clone()))
             .app_data(Data::new(notify_tx.clone()))
+            .app_data(Data::new(inbox_tx.clone()))
             .app_data(Data::new(providers_app_state.clone()))
+            .app_data(Data::new(Arc::new(AredlQueueThroughputCache::default())))
+            .app_data(Data::new(Arc::new(AreplQueueThroughputCache::default())))
             .wrap(NormalizePath::trim())
             .wrap(TracingLogger::<AppRootSpanBuilder>::new())
             .wrap(BoxResponse)
@@ -105,7 +113,13 @@ pub async fn init_test_app() -> (
             .configure(crate::clans::init_routes)
             .configure(crate::notifications::init_routes)
             .configure(crate::shifts::init_routes)
-            .configure(crate::health::init_routes),
+            .configure(crate::health::init_routes)
+            .configure(crate::utils::init_routes)
+            .configure(crate::webhooks::init_routes)
+            .configure(crate::submission_reasons::init_routes)
+            .configure(crate::reviewer_conflicts::init_routes)
+            .configure(crate::submission_requirements::init_routes)
+            .configure(crate::external_sync::init_routes),
     )
     .await;
 
@@ -126,6 +140,7 @@ pub async fn init_test_app_with_providers(
     let auth_app_state = auth_init_app_state().expect("Failed to initialize auth test state");
 
     let (notify_tx, _notify_rx) = broadcast::channel::<WebsocketNotification>(100);
+    let (inbox_tx, _inbox_rx) = broadcast::channel::<Notification>(256);
 
     let db_app_state = init_test_db_state();
     let providers_app_state = match Arc::try_unwrap(providers_app_state) {
@@ -141,7 +156,10 @@ pub async fn init_test_app_with_providers(
             .app_data(Data::new(db_app_state.clone()))
             .app_data(Data::new(auth_app_state.clone()))
             .app_data(Data::new(notify_tx.clone()))
+            .app_data(Data::new(inbox_tx.clone()))
             .app_data(Data::new(providers_app_state.clone()))
+            .app_data(Data::new(Arc::new(AredlQueueThroughputCache::default())))
+            .app_data(Data::new(Arc::new(AreplQueueThroughputCache::default())))
             .wrap(NormalizePath::trim())
             .wrap(TracingLogger::<AppRootSpanBuilder>::new())
             .wrap(BoxResponse)
@@ -18,6 +18,7 @@ pub const TIERED_BADGES: &[(&str, &[&str])] = &[
         &["2000", "1000", "500", "250", "100", "50", "20"],
     ),
     ("global.pack_completion", &["3", "5", "10", "15"]),
+    ("platformer.world_records", &["3", "5", "10", "25"]),
     ("global.bounty_board.bounty", &["5", "10", "15", "20", "25"]),
     ("global.bounty_board.weekly", &["3", "7", "12", "18", "25"]),
     ("global.bounty_board.monthly", &["1", "2", "4", "6", "8"]),
@@ -5,6 +5,7 @@ use crate::users::badges::badges_list::{
     AvailableBadges, TagBadgeMode, HARDEST_PACK_TIERS, LEVEL_TAG_BADGES, NLW_TIERS,
 };
 use crate::users::badges::statistics::UserStatistics;
+use crate::users::me::notifications::{Notification, NotificationPayload, NotificationType};
 use chrono::{DateTime, Utc};
 use diesel::pg::Pg;
 use diesel::{delete, insert_into, Selectable};
@@ -47,14 +48,15 @@ impl UserBadge {
             .load::<UserBadge>(conn)?)
     }
 
+    /// Grants a badge to the user, returning the unlock notification if the badge is new.
     pub fn grant(
         conn: &mut DbConnection,
         user_id: Uuid,
         badge: UserBadgeGrant,
-    ) -> Result<Vec<Self>, ApiError> {
+    ) -> Result<Option<Notification>, ApiError> {
         Self::validate_badge_code(&badge.badge_code)?;
 
-        insert_into(user_badges::table)
+        let granted = insert_into(user_badges::table)
             .values(UserBadge {
                 user_id,
                 badge_code: badge.badge_code,
@@ -62,9 +64,9 @@ impl UserBadge {
                 unlocked_at: Utc::now(),
             })
             .on_conflict_do_nothing()
-            .execute(conn)?;
-
-        Self::find_all(conn, user_id)
+            .returning(user_badges::badge_code)
+            .get_results::<String>(conn)?;
+        Self::notify_unlocked(conn, user_id, granted)
     }
 
     pub fn remove_all(
@@ -88,7 +90,11 @@ impl UserBadge {
 }
 
 impl UserBadge {
-    pub fn update_user_badges(conn: &mut DbConnection, user_id: Uuid) -> Result<(), ApiError> {
+    /// Unlocks the badges the user now qualifies for, returning the unlock notification if there are new ones.
+    pub fn update_user_badges(
+        conn: &mut DbConnection,
+        user_id: Uuid,
+    ) -> Result<Option<Notification>, ApiError> {
         let badge_data = UserStatistics::load(conn, user_id)?.get_unlocked_badges();
         Self::insert_missing(conn, user_id, &badge_data)
     }
@@ -112,7 +118,7 @@ impl UserBadge {
         conn: &mut DbConnection,
         user_id: Uuid,
         badges: &HashMap<String, Option<String>>,
-    ) -> Result<(), ApiError> {
+    ) -> Result<Option<Notification>, ApiError> {
         let existing_codes = user_badges::table
             .filter(user_badges::user_id.eq(user_id))
             .select(user_badges::badge_code)
@@ -132,14 +138,38 @@ impl UserBadge {
             })
             .collect::<Vec<_>>();
 
-        if !new_rows.is_empty() {
-            insert_into(user_badges::table)
-                .values(&new_rows)
-                .on_conflict_do_nothing()
-                .execute(conn)?;
+        if new_rows.is_empty() {
+            return Ok(None);
         }
 
-        Ok(())
+        let unlocked = insert_into(user_badges::table)
+            .values(&new_rows)
+            .on_conflict_do_nothing()
+            .returning(user_badges::badge_code)
+            .get_results::<String>(conn)?;
+        Self::notify_unlocked(conn, user_id, unlocked)
+    }
+
+    // Sends a single inbox notification for every badge unlocked at once, so recalculations don't flood the inbox
+    fn notify_unlocked(
+        conn: &mut DbConnection,
+        user_id: Uuid,
+        mut badge_codes: Vec<String>,
+    ) -> Result<Option<Notification>, ApiError> {
+        let content = match badge_codes.len() {
+            0 => return Ok(None),
+            1 => "You unlocked a new badge!".to_owned(),
+            count => format!("You unlocked {count} new badges!"),
+        };
+        badge_codes.sort();
+
+        Notification::create(
+            conn,
+            user_id,
+            content,
+            NotificationType::Success,
+            Some(NotificationPayload::Badges { badge_codes }),
+        )
     }
 
     fn validate_badge_code(badge_code: &str) -> Result<(), ApiError> {
@@ -364,6 +394,16 @@ impl UserStatistics {
                 .levels_records
                 .iter()
                 .any(|level| level.is_fastest_time),
+            ("platformer", ["world_records", threshold]) => {
+                threshold.parse::<usize>().ok().is_some_and(|threshold| {
+                    scope_statistics
+                        .levels_records
+                        .iter()
+                        .filter(|level| level.is_fastest_time)
+                        .count()
+                        >= threshold
+                })
+            }
             ("global", ["creator"]) => !scope_statistics.created_levels.is_empty(),
             ("global", ["verifier"]) => scope_statistics
                 .levels_records
@@ -423,14 +463,16 @@ impl UserStatistics {
                     .map(|level| (level.position, level.name.as_str()))
                     .collect(),
             ),
-            ["platformer", "fastest_time"] => Self::levels_to_text(
-                self.platformer
-                    .levels_records
-                    .iter()
-                    .filter(|level| level.is_fastest_time)
-                    .map(|level| (level.position, level.name.as_str()))
-                    .collect(),
-            ),
+            ["platformer", "fastest_time"] | ["platformer", "world_records", _] => {
+                Self::levels_to_text(
+                    self.platformer
+                        .levels_records
+                        .iter()
+                        .filter(|level| level.is_fastest_time)
+                        .map(|level| (level.position, level.name.as_str()))
+                        .collect(),
+                )
+            }
             ["global", "creator"] => Self::levels_to_text(
                 self.global
                     .created_levels
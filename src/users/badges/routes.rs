@@ -1,9 +1,11 @@
 use crate::app_data::db::DbAppState;
 use crate::auth::{Authenticated, Permission, UserAuth};
 use crate::error_handler::ApiError;
+use crate::users::me::notifications::Notification;
 use crate::users::User;
 use actix_web::{delete, get, patch, post, web, HttpResponse};
 use std::sync::Arc;
+use tokio::sync::broadcast;
 use tracing_actix_web::RootSpan;
 use utoipa::OpenApi;
 
@@ -61,15 +63,17 @@ async fn sync(
     db: web::Data<Arc<DbAppState>>,
     id: web::Path<String>,
     authenticated: Authenticated,
+    inbox_tx: web::Data<broadcast::Sender<Notification>>,
 ) -> Result<HttpResponse, ApiError> {
-    let badges = web::block(move || {
+    let (badges, notification) = web::block(move || {
         let conn = &mut db.connection()?;
         let user_id = User::from_str(conn, id.into_inner().as_str())?.id;
         authenticated.ensure_has_higher_privilege_than_user(conn, user_id)?;
-        UserBadge::update_user_badges(conn, user_id)?;
-        UserBadge::find_all(conn, user_id)
+        let notification = UserBadge::update_user_badges(conn, user_id)?;
+        Ok::<_, ApiError>((UserBadge::find_all(conn, user_id)?, notification))
     })
     .await??;
+    Notification::send_live(&inbox_tx, notification);
 
     Ok(HttpResponse::Ok().json(badges))
 }
@@ -98,16 +102,18 @@ async fn grant(
     authenticated: Authenticated,
     badge: web::Json<UserBadgeGrant>,
     root_span: RootSpan,
+    inbox_tx: web::Data<broadcast::Sender<Notification>>,
 ) -> Result<HttpResponse, ApiError> {
     root_span.record("body", tracing::field::debug(&badge));
-    let badges = web::block(move || {
+    let (badges, notification) = web::block(move || {
         let conn = &mut db.connection()?;
         let user_id = User::from_str(conn, id.into_inner().as_str())?.id;
         authenticated.ensure_has_higher_privilege_than_user(conn, user_id)?;
-        let badges = UserBadge::grant(conn, user_id, badge.into_inner())?;
-        Ok::<_, ApiError>(badges)
+        let notification = UserBadge::grant(conn, user_id, badge.into_inner())?;
+        Ok::<_, ApiError>((UserBadge::find_all(conn, user_id)?, notification))
     })
     .await??;
+    Notification::send_live(&inbox_tx, notification);
 
     Ok(HttpResponse::Ok().json(badges))
 }
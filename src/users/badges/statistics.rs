@@ -274,25 +274,12 @@ impl UserListStatistics {
     }
 
     fn load_platformer(conn: &mut DbConnection, user_id: Uuid) -> Result<Self, ApiError> {
-        let fastest_time_level_ids = arepl::records::table
-            .inner_join(arepl::levels::table.on(arepl::levels::id.eq(arepl::records::level_id)))
-            .filter(
-                arepl::levels::status
-                    .eq(PlatformerLevelStatus::MainList)
-                    .or(arepl::levels::status.eq(PlatformerLevelStatus::Pending)),
-            )
-            .filter(arepl::records::is_verification.eq(false))
-            .distinct_on(arepl::records::level_id)
-            .order_by((
-                arepl::records::level_id.asc(),
-                arepl::records::completion_time.asc(),
-                arepl::records::achieved_at.asc(),
-                arepl::records::id.asc(),
-            ))
-            .select((arepl::records::level_id, arepl::records::submitted_by))
-            .load::<(Uuid, Uuid)>(conn)?
+        // same holders as the world records leaderboard, banned players don't hold fastest times
+        let fastest_time_level_ids = arepl::world_records::table
+            .filter(arepl::world_records::submitted_by.eq(user_id))
+            .select(arepl::world_records::level_id)
+            .load::<Uuid>(conn)?
             .into_iter()
-            .filter_map(|(level_id, submitted_by)| (submitted_by == user_id).then_some(level_id))
             .collect::<HashSet<_>>();
 
         let first_victor_level_ids = arepl::records::table
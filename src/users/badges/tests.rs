@@ -3,7 +3,9 @@ use {
         aredl::levels::test_utils::create_test_level_with_record,
         auth::create_test_token,
         test_utils::{assert_error_response, init_test_app},
-        users::test_utils::create_test_user,
+        users::{
+            me::notifications::test_utils::count_test_notifications, test_utils::create_test_user,
+        },
     },
     actix_http::StatusCode,
     actix_web::test::{self, read_body_json},
@@ -69,6 +71,40 @@ async fn grant_user_badge() {
     }));
 }
 
+#[actix_web::test]
+async fn granted_badge_notifies_user_once() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, None).await;
+    let (staff_id, _) = create_test_user(&db, Some(crate::auth::Permission::UserModify)).await;
+    let staff_token =
+        create_test_token(staff_id, &auth.jwt_encoding_key).expect("Failed to generate token");
+
+    for _ in 0..2 {
+        let req = test::TestRequest::patch()
+            .uri(&format!("/users/{user_id}/badges"))
+            .insert_header(("Authorization", format!("Bearer {staff_token}")))
+            .set_json(json!({
+                "badge_code": "global.level_completion.5",
+            }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+    }
+
+    assert_eq!(count_test_notifications(&db, user_id), 1);
+
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+    let req = test::TestRequest::get()
+        .uri("/users/@me/notifications?category=Badge")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(
+        body["data"][0]["payload"],
+        json!({"kind": "Badges", "badge_codes": ["global.level_completion.5"]})
+    );
+}
+
 #[actix_web::test]
 async fn remove_user_badge() {
     let (app, db, auth, _) = init_test_app().await;
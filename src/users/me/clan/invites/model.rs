@@ -4,7 +4,7 @@ use crate::clans::members::ClanMemberAdd;
 use crate::clans::{Clan, ClanInvite};
 use crate::error_handler::ApiError;
 use crate::schema::{clan_invites, clan_members, clans, users};
-use crate::users::me::notifications::{Notification, NotificationType};
+use crate::users::me::notifications::{Notification, NotificationPayload, NotificationType};
 use crate::users::BaseUser;
 use diesel::{delete, insert_into};
 use serde::{Deserialize, Serialize};
@@ -38,8 +38,8 @@ impl ClanInvite {
         conn: &mut DbConnection,
         invite_id: Uuid,
         authenticated: &Authenticated,
-    ) -> Result<(), ApiError> {
-        conn.transaction(|connection| -> Result<(), ApiError> {
+    ) -> Result<Option<Notification>, ApiError> {
+        conn.transaction(|connection| -> Result<Option<Notification>, ApiError> {
             let invite = clan_invites::table
                 .filter(clan_invites::id.eq(invite_id))
                 .select(ClanInvite::as_select())
@@ -81,11 +81,12 @@ impl ClanInvite {
                 invite.invited_by,
                 content,
                 NotificationType::Success,
-            )?;
-            Ok(())
-        })?;
-
-        Ok(())
+                Some(NotificationPayload::ClanInvite {
+                    invite_id: invite.id,
+                    clan_id: invite.clan_id,
+                }),
+            )
+        })
     }
 
     pub fn reject_invite(
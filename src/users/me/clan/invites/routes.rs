@@ -3,8 +3,10 @@ use crate::auth::{Authenticated, UserAuth};
 use crate::clans::ClanInvite;
 use crate::error_handler::ApiError;
 use crate::users::me::clan::invites::ClanInviteResolved;
+use crate::users::me::notifications::Notification;
 use actix_web::{get, post, web, HttpResponse};
 use std::sync::Arc;
+use tokio::sync::broadcast;
 use utoipa::OpenApi;
 use uuid::Uuid;
 
@@ -54,11 +56,13 @@ async fn accept(
     db: web::Data<Arc<DbAppState>>,
     invite_id: web::Path<Uuid>,
     authenticated: Authenticated,
+    inbox_tx: web::Data<broadcast::Sender<Notification>>,
 ) -> Result<HttpResponse, ApiError> {
-    web::block(move || {
+    let notification = web::block(move || {
         ClanInvite::accept_invite(&mut db.connection()?, *invite_id, &authenticated)
     })
     .await??;
+    Notification::send_live(&inbox_tx, notification);
     Ok(HttpResponse::Ok().json(()))
 }
 
@@ -1,15 +1,22 @@
 use crate::app_data::db::DbConnection;
 use crate::error_handler::ApiError;
-use crate::schema::notifications;
+use crate::notifications::NotificationList;
+use crate::page_helper::{PageQuery, Paginated};
+use crate::schema::{notification_preferences, notifications};
 use chrono::{DateTime, Utc};
 use diesel::delete;
+use diesel::pg::Pg;
 use diesel_derive_enum::DbEnum;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use strum::IntoEnumIterator as _;
+use strum_macros::EnumIter;
+use tokio::sync::broadcast;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
 use diesel::prelude::*;
-#[derive(Debug, Serialize, Deserialize, ToSchema, DbEnum)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, DbEnum)]
 #[ExistingTypePath = "crate::schema::sql_types::NotificationType"]
 #[DbValueStyle = "PascalCase"]
 pub enum NotificationType {
@@ -18,8 +25,93 @@ pub enum NotificationType {
     Failure,
 }
 
-#[derive(Debug, Serialize, Deserialize, ToSchema, Queryable, Selectable, Insertable)]
-#[diesel(table_name = notifications)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, DbEnum, EnumIter,
+)]
+#[ExistingTypePath = "crate::schema::sql_types::NotificationCategory"]
+#[DbValueStyle = "PascalCase"]
+/// What a notification is about. Each category can be muted separately.
+pub enum NotificationCategory {
+    /// Notifications that are not tied to a specific category.
+    General,
+    /// One of your submissions was accepted, denied or put under consideration.
+    SubmissionStatus,
+    /// You were invited to a clan, or someone accepted your clan invite.
+    ClanInvite,
+    /// One of your merge requests was accepted or rejected.
+    MergeRequest,
+    /// One of your records completed a bounty.
+    Bounty,
+    /// You unlocked new badges.
+    Badge,
+    /// The video of one of your records is no longer available.
+    RecordVideo,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "kind")]
+/// Structured reference to whatever caused a notification.
+pub enum NotificationPayload {
+    Submission {
+        /// Internal UUID of the submission.
+        submission_id: Uuid,
+        /// Internal UUID of the level the submission is for.
+        level_id: Uuid,
+        /// The list the submission was made on.
+        list: NotificationList,
+        /// Codes of the reasons given by the reviewer, if the submission was denied or put under consideration.
+        #[serde(default)]
+        reason_codes: Vec<String>,
+    },
+    ClanInvite {
+        /// Internal UUID of the clan invite.
+        invite_id: Uuid,
+        /// Internal UUID of the clan.
+        clan_id: Uuid,
+    },
+    MergeRequest {
+        /// Internal UUID of the merge request.
+        merge_request_id: Uuid,
+    },
+    Bounty {
+        /// Internal UUID of the bounty.
+        bounty_id: Uuid,
+        /// Internal UUID of the record that completed the bounty.
+        record_id: Uuid,
+        /// Internal UUID of the level of the bounty.
+        level_id: Uuid,
+        /// The list the bounty is on.
+        list: NotificationList,
+    },
+    Badges {
+        /// Codes of the badges that were unlocked, e.g. `global.level_completion.10`.
+        badge_codes: Vec<String>,
+    },
+    RecordVideo {
+        /// Internal UUID of the record whose video is unavailable.
+        record_id: Uuid,
+        /// Internal UUID of the level of the record.
+        level_id: Uuid,
+        /// The list the record is on.
+        list: NotificationList,
+    },
+}
+
+impl NotificationPayload {
+    pub fn category(&self) -> NotificationCategory {
+        match self {
+            Self::Submission { .. } => NotificationCategory::SubmissionStatus,
+            Self::ClanInvite { .. } => NotificationCategory::ClanInvite,
+            Self::MergeRequest { .. } => NotificationCategory::MergeRequest,
+            Self::Bounty { .. } => NotificationCategory::Bounty,
+            Self::Badges { .. } => NotificationCategory::Badge,
+            Self::RecordVideo { .. } => NotificationCategory::RecordVideo,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable, Insertable)]
+#[diesel(table_name = notifications, check_for_backend(Pg))]
 pub struct Notification {
     /// The internal UUID of the notification
     pub id: Uuid,
@@ -31,20 +123,44 @@ pub struct Notification {
     pub notification_type: NotificationType,
     /// Timestamp of when this notification was sent
     pub created_at: DateTime<Utc>,
+    /// What this notification is about
+    pub category: NotificationCategory,
+    /// Reference to whatever caused this notification, if any
+    #[schema(value_type = Option<NotificationPayload>)]
+    pub payload: Option<serde_json::Value>,
+    /// Timestamp of when this notification was marked as read, null if it is unread
+    pub read_at: Option<DateTime<Utc>>,
 }
 
-impl Notification {
-    pub fn find_all_me_notifications(
-        conn: &mut DbConnection,
-        user_id: Uuid,
-    ) -> Result<Vec<Notification>, ApiError> {
-        let notifications = notifications::table
-            .filter(notifications::user_id.eq(user_id))
-            .order(notifications::created_at.desc())
-            .load::<Notification>(conn)?;
-        Ok(notifications)
-    }
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct NotificationPage {
+    pub data: Vec<Notification>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct NotificationQueryOptions {
+    /// Only return notifications that were not read yet.
+    pub unread_only: Option<bool>,
+    /// Only return notifications of this category.
+    pub category: Option<NotificationCategory>,
+}
 
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UnreadNotificationCount {
+    /// Amount of notifications that were not read yet.
+    pub unread: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Queryable, Selectable)]
+#[diesel(table_name = notification_preferences, check_for_backend(Pg))]
+pub struct NotificationPreference {
+    /// The category this preference applies to.
+    pub category: NotificationCategory,
+    /// Whether notifications of this category are no longer sent to you.
+    pub muted: bool,
+}
+
+impl Notification {
     pub fn clear_me_notifications(conn: &mut DbConnection, user_id: Uuid) -> Result<(), ApiError> {
         conn.transaction(|connection| -> Result<(), ApiError> {
             delete(notifications::table)
@@ -57,26 +173,210 @@ impl Notification {
         Ok(())
     }
 
+    pub fn count_unread(conn: &mut DbConnection, user_id: Uuid) -> Result<i64, ApiError> {
+        let unread = notifications::table
+            .filter(notifications::user_id.eq(user_id))
+            .filter(notifications::read_at.is_null())
+            .count()
+            .get_result::<i64>(conn)?;
+        Ok(unread)
+    }
+
+    pub fn mark_read(conn: &mut DbConnection, user_id: Uuid, id: Uuid) -> Result<Self, ApiError> {
+        // notifications that were already read keep their original read timestamp
+        diesel::update(notifications::table)
+            .filter(notifications::id.eq(id))
+            .filter(notifications::user_id.eq(user_id))
+            .filter(notifications::read_at.is_null())
+            .set(notifications::read_at.eq(Utc::now()))
+            .execute(conn)?;
+
+        let notification = notifications::table
+            .filter(notifications::id.eq(id))
+            .filter(notifications::user_id.eq(user_id))
+            .select(Notification::as_select())
+            .first::<Notification>(conn)?;
+        Ok(notification)
+    }
+
+    pub fn mark_all_read(conn: &mut DbConnection, user_id: Uuid) -> Result<(), ApiError> {
+        diesel::update(notifications::table)
+            .filter(notifications::user_id.eq(user_id))
+            .filter(notifications::read_at.is_null())
+            .set(notifications::read_at.eq(Utc::now()))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    pub fn delete(conn: &mut DbConnection, user_id: Uuid, id: Uuid) -> Result<Self, ApiError> {
+        let notification = delete(notifications::table)
+            .filter(notifications::id.eq(id))
+            .filter(notifications::user_id.eq(user_id))
+            .returning(Notification::as_select())
+            .get_result::<Notification>(conn)?;
+        Ok(notification)
+    }
+
+    /// Stores a notification in the inbox of the user, nothing is stored if its category is muted.
+    /// The created notification still has to be sent with [`Notification::send_live`] once it has been committed.
     pub fn create(
         conn: &mut DbConnection,
         user_id: Uuid,
         content: String,
         notification_type: NotificationType,
-    ) -> Result<(), ApiError> {
-        conn.transaction(|connection| -> Result<(), ApiError> {
-            diesel::insert_into(notifications::table)
+        payload: Option<NotificationPayload>,
+    ) -> Result<Option<Self>, ApiError> {
+        let category = payload
+            .as_ref()
+            .map_or(NotificationCategory::General, NotificationPayload::category);
+        let payload = payload
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|error| {
+                ApiError::InternalServerError(format!(
+                    "Failed to serialize notification payload: {error}"
+                ))
+            })?;
+
+        conn.transaction(|connection| -> Result<Option<Self>, ApiError> {
+            if NotificationPreference::is_muted(connection, user_id, category)? {
+                return Ok(None);
+            }
+
+            let notification = diesel::insert_into(notifications::table)
                 .values(Notification {
                     id: Uuid::new_v4(),
                     user_id,
                     content,
                     notification_type,
                     created_at: chrono::Utc::now(),
+                    category,
+                    payload,
+                    read_at: None,
                 })
-                .execute(connection)?;
+                .returning(Notification::as_select())
+                .get_result::<Notification>(connection)?;
+
+            Ok(Some(notification))
+        })
+    }
+
+    /// Pushes notifications to the per-user websockets, this must only be called once they have been committed.
+    pub fn send_live(
+        inbox_tx: &broadcast::Sender<Notification>,
+        notifications: impl IntoIterator<Item = Self>,
+    ) {
+        // no receivers just means that nobody is connected right now
+        if inbox_tx.receiver_count() == 0 {
+            return;
+        }
+        for notification in notifications {
+            if let Err(error) = inbox_tx.send(notification) {
+                tracing::error!("Failed to send live user notification: {error}");
+            }
+        }
+    }
+}
+
+impl NotificationPage {
+    pub fn find<const D: i64>(
+        conn: &mut DbConnection,
+        user_id: Uuid,
+        page_query: PageQuery<D>,
+        options: &NotificationQueryOptions,
+    ) -> Result<Paginated<Self>, ApiError> {
+        let build_query = || {
+            let mut query = notifications::table
+                .filter(notifications::user_id.eq(user_id))
+                .into_boxed::<Pg>();
+            if options.unread_only.unwrap_or(false) {
+                query = query.filter(notifications::read_at.is_null());
+            }
+            if let Some(category) = options.category {
+                query = query.filter(notifications::category.eq(category));
+            }
+            query
+        };
+
+        let total = build_query().count().get_result::<i64>(conn)?;
 
+        let notifications = build_query()
+            .order((notifications::created_at.desc(), notifications::id.desc()))
+            .limit(page_query.per_page())
+            .offset(page_query.offset())
+            .select(Notification::as_select())
+            .load::<Notification>(conn)?;
+
+        Ok(Paginated::from_data(
+            page_query,
+            total,
+            Self {
+                data: notifications,
+            },
+        ))
+    }
+}
+
+impl NotificationPreference {
+    pub fn is_muted(
+        conn: &mut DbConnection,
+        user_id: Uuid,
+        category: NotificationCategory,
+    ) -> Result<bool, ApiError> {
+        let muted = notification_preferences::table
+            .filter(notification_preferences::user_id.eq(user_id))
+            .filter(notification_preferences::category.eq(category))
+            .select(notification_preferences::muted)
+            .first::<bool>(conn)
+            .optional()?;
+        Ok(muted.unwrap_or(false))
+    }
+
+    /// Returns the preference of every category, categories without a stored preference are not muted.
+    pub fn find_all(conn: &mut DbConnection, user_id: Uuid) -> Result<Vec<Self>, ApiError> {
+        let stored = notification_preferences::table
+            .filter(notification_preferences::user_id.eq(user_id))
+            .select(NotificationPreference::as_select())
+            .load::<NotificationPreference>(conn)?
+            .into_iter()
+            .map(|preference| (preference.category, preference.muted))
+            .collect::<HashMap<_, _>>();
+
+        Ok(NotificationCategory::iter()
+            .map(|category| Self {
+                category,
+                muted: stored.get(&category).copied().unwrap_or(false),
+            })
+            .collect())
+    }
+
+    pub fn update(
+        conn: &mut DbConnection,
+        user_id: Uuid,
+        preferences: &[Self],
+    ) -> Result<Vec<Self>, ApiError> {
+        conn.transaction(|connection| -> Result<(), ApiError> {
+            for preference in preferences {
+                diesel::insert_into(notification_preferences::table)
+                    .values((
+                        notification_preferences::user_id.eq(user_id),
+                        notification_preferences::category.eq(preference.category),
+                        notification_preferences::muted.eq(preference.muted),
+                    ))
+                    .on_conflict((
+                        notification_preferences::user_id,
+                        notification_preferences::category,
+                    ))
+                    .do_update()
+                    .set((
+                        notification_preferences::muted.eq(preference.muted),
+                        notification_preferences::updated_at.eq(Utc::now()),
+                    ))
+                    .execute(connection)?;
+            }
             Ok(())
         })?;
 
-        Ok(())
+        Self::find_all(conn, user_id)
     }
 }
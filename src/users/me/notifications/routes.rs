@@ -1,18 +1,33 @@
 use crate::app_data::db::DbAppState;
 use crate::auth::{Authenticated, UserAuth};
 use crate::error_handler::ApiError;
-use crate::users::me::notifications::Notification;
-use actix_web::{get, post, web, HttpResponse};
-use std::sync::Arc;
+use crate::page_helper::{PageQuery, Paginated};
+use crate::users::me::notifications::{
+    Notification, NotificationCategory, NotificationPage, NotificationPayload,
+    NotificationPreference, NotificationQueryOptions, UnreadNotificationCount,
+};
+use actix_web::{delete, get, patch, post, web, Error, HttpRequest, HttpResponse};
+use actix_ws::{handle, Message};
+use futures_util::StreamExt as _;
+use std::{sync::Arc, time::Duration};
+use tokio::{sync::broadcast, time::interval};
+use tracing_actix_web::RootSpan;
 use utoipa::OpenApi;
+use uuid::Uuid;
 
 #[utoipa::path(
     get,
     summary = "[Auth]Get my notifications",
-    description = "Get the list of notifications you've received",
+    description = "Get the list of notifications you've received, most recent first",
     tag = "Users - Me",
+    params(
+        ("page" = Option<i64>, Query, description = "The page number to fetch"),
+        ("per_page" = Option<i64>, Query, description = "The number of items per page"),
+        ("unread_only" = Option<bool>, Query, description = "Only return notifications that were not read yet"),
+        ("category" = Option<NotificationCategory>, Query, description = "Only return notifications of this category"),
+    ),
     responses(
-        (status = 200, body = [Notification])
+        (status = 200, body = Paginated<NotificationPage>)
     ),
 	security(
 		("access_token" = []),
@@ -22,15 +37,70 @@ use utoipa::OpenApi;
 #[get("", wrap = "UserAuth::load()")]
 async fn list(
     db: web::Data<Arc<DbAppState>>,
+    page_query: web::Query<PageQuery<20>>,
+    options: web::Query<NotificationQueryOptions>,
     authenticated: Authenticated,
 ) -> Result<HttpResponse, ApiError> {
     let result = web::block(move || {
-        Notification::find_all_me_notifications(&mut db.connection()?, authenticated.user_id)
+        NotificationPage::find(
+            &mut db.connection()?,
+            authenticated.user_id,
+            page_query.into_inner(),
+            &options.into_inner(),
+        )
     })
     .await??;
     Ok(HttpResponse::Ok().json(result))
 }
 
+#[utoipa::path(
+    get,
+    summary = "[Auth]Count my unread notifications",
+    description = "Get the amount of notifications you haven't read yet",
+    tag = "Users - Me",
+    responses(
+        (status = 200, body = UnreadNotificationCount)
+    ),
+    security(
+        ("access_token" = []),
+        ("api_key" = []),
+    )
+)]
+#[get("/unread", wrap = "UserAuth::load()")]
+async fn count_unread(
+    db: web::Data<Arc<DbAppState>>,
+    authenticated: Authenticated,
+) -> Result<HttpResponse, ApiError> {
+    let unread = web::block(move || {
+        Notification::count_unread(&mut db.connection()?, authenticated.user_id)
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(UnreadNotificationCount { unread }))
+}
+
+#[utoipa::path(
+    post,
+    summary = "[Auth]Mark all notifications as read",
+    description = "Marks all your unread notifications as read.",
+    tag = "Users - Me",
+    responses(
+        (status = 200)
+    ),
+    security(
+        ("access_token" = []),
+        ("api_key" = []),
+    )
+)]
+#[post("/read", wrap = "UserAuth::load()")]
+async fn mark_all_read(
+    db: web::Data<Arc<DbAppState>>,
+    authenticated: Authenticated,
+) -> Result<HttpResponse, ApiError> {
+    web::block(move || Notification::mark_all_read(&mut db.connection()?, authenticated.user_id))
+        .await??;
+    Ok(HttpResponse::Ok().json(()))
+}
+
 #[utoipa::path(
     post,
     summary = "[Auth]Clear all notifications",
@@ -56,10 +126,248 @@ async fn clear(
     Ok(HttpResponse::Ok().json(()))
 }
 
+#[utoipa::path(
+    post,
+    summary = "[Auth]Mark a notification as read",
+    description = "Marks one of your notifications as read.",
+    tag = "Users - Me",
+    params(
+        ("id" = Uuid, description = "The internal UUID of the notification"),
+    ),
+    responses(
+        (status = 200, body = Notification)
+    ),
+    security(
+        ("access_token" = []),
+        ("api_key" = []),
+    )
+)]
+#[post("/{id}/read", wrap = "UserAuth::load()")]
+async fn mark_read(
+    db: web::Data<Arc<DbAppState>>,
+    id: web::Path<Uuid>,
+    authenticated: Authenticated,
+) -> Result<HttpResponse, ApiError> {
+    let notification = web::block(move || {
+        Notification::mark_read(
+            &mut db.connection()?,
+            authenticated.user_id,
+            id.into_inner(),
+        )
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(notification))
+}
+
+#[utoipa::path(
+    delete,
+    summary = "[Auth]Delete a notification",
+    description = "Removes one of your notifications.",
+    tag = "Users - Me",
+    params(
+        ("id" = Uuid, description = "The internal UUID of the notification"),
+    ),
+    responses(
+        (status = 200, body = Notification)
+    ),
+    security(
+        ("access_token" = []),
+        ("api_key" = []),
+    )
+)]
+#[delete("/{id}", wrap = "UserAuth::load()")]
+async fn delete_notification(
+    db: web::Data<Arc<DbAppState>>,
+    id: web::Path<Uuid>,
+    authenticated: Authenticated,
+) -> Result<HttpResponse, ApiError> {
+    let notification = web::block(move || {
+        Notification::delete(
+            &mut db.connection()?,
+            authenticated.user_id,
+            id.into_inner(),
+        )
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(notification))
+}
+
+#[utoipa::path(
+    get,
+    summary = "[Auth]Get my notification preferences",
+    description = "Get whether each notification category is muted",
+    tag = "Users - Me",
+    responses(
+        (status = 200, body = [NotificationPreference])
+    ),
+    security(
+        ("access_token" = []),
+        ("api_key" = []),
+    )
+)]
+#[get("/preferences", wrap = "UserAuth::load()")]
+async fn find_preferences(
+    db: web::Data<Arc<DbAppState>>,
+    authenticated: Authenticated,
+) -> Result<HttpResponse, ApiError> {
+    let preferences = web::block(move || {
+        NotificationPreference::find_all(&mut db.connection()?, authenticated.user_id)
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(preferences))
+}
+
+#[utoipa::path(
+    patch,
+    summary = "[Auth]Edit my notification preferences",
+    description = "Mutes or unmutes notification categories. Notifications of a muted category are not sent to you at all. Categories that are not included are left unchanged.",
+    tag = "Users - Me",
+    request_body = [NotificationPreference],
+    responses(
+        (status = 200, body = [NotificationPreference])
+    ),
+    security(
+        ("access_token" = []),
+        ("api_key" = []),
+    )
+)]
+#[patch("/preferences", wrap = "UserAuth::load()")]
+async fn update_preferences(
+    db: web::Data<Arc<DbAppState>>,
+    body: web::Json<Vec<NotificationPreference>>,
+    authenticated: Authenticated,
+    root_span: RootSpan,
+) -> Result<HttpResponse, ApiError> {
+    root_span.record("body", tracing::field::debug(&body));
+    let preferences = web::block(move || {
+        NotificationPreference::update(&mut db.connection()?, authenticated.user_id, &body)
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(preferences))
+}
+
+#[utoipa::path(
+    get,
+    summary = "[Auth]Receive my notifications in real time",
+    description = "Upgrades the HTTP connection to a WebSocket that receives every notification sent to you as soon as it is created, such as submission status changes, clan invites, badge unlocks and merge request outcomes. Each frame is a JSON encoded notification, exactly as returned when listing your notifications. Notifications of muted categories are not sent.\n\nOnly notifications created while connected are delivered, fetch the list of your notifications to catch up after reconnecting.",
+    tag = "Users - Me",
+    responses(
+        (status = 101, description = "Switching Protocols to WebSocket"),
+        (status = 401, description = "Unauthorized / invalid or missing token"),
+    ),
+    security(
+        ("access_token" = []),
+        ("api_key" = []),
+    )
+)]
+#[get("/websocket", wrap = "UserAuth::load()")]
+async fn notifications_websocket(
+    req: HttpRequest,
+    stream: web::Payload,
+    authenticated: Authenticated,
+    inbox_tx: web::Data<broadcast::Sender<Notification>>,
+) -> Result<HttpResponse, Error> {
+    let (res, mut session, mut msg_stream) = handle(&req, stream)?;
+    let mut rx = inbox_tx.subscribe();
+    let mut heartbeat = interval(Duration::from_secs(30));
+    let user_id = authenticated.user_id;
+
+    actix_rt::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = heartbeat.tick() => {
+                    if session.ping(&[]).await.is_err() {
+                        break;
+                    }
+                }
+
+                message = msg_stream.next() => {
+                    match message {
+                        Some(Ok(Message::Ping(payload))) => {
+                            if session.pong(&payload).await.is_err() {
+                                break;
+                            }
+                        }
+                        Some(Ok(Message::Close(reason))) => {
+                            if let Err(error) = session.close(reason).await {
+                                tracing::debug!("Failed to close WebSocket session: {error}");
+                            }
+                            break;
+                        }
+                        Some(Ok(Message::Pong(_) | _)) => {}
+                        Some(Err(error)) => {
+                            tracing::debug!("WebSocket protocol error: {error}");
+                            break;
+                        }
+                        None => break,
+                    }
+                }
+
+                notification = rx.recv() => {
+                    match notification {
+                        Ok(notification) => {
+                            if notification.user_id != user_id {
+                                continue;
+                            }
+                            let text = match serde_json::to_string(&notification) {
+                                Ok(text) => text,
+                                Err(error) => {
+                                    tracing::error!("Failed to serialize notification: {error}");
+                                    continue;
+                                }
+                            };
+                            if session.text(text).await.is_err() {
+                                break;
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            // the inbox still holds the skipped notifications
+                            tracing::warn!("User notification WebSocket skipped {skipped} notifications");
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            }
+        }
+    });
+    Ok(res)
+}
+
 #[derive(OpenApi)]
-#[openapi(components(schemas(Notification)), paths(list, clear))]
+#[openapi(
+    components(schemas(
+        Notification,
+        NotificationCategory,
+        NotificationPayload,
+        NotificationPage,
+        NotificationPreference,
+        UnreadNotificationCount
+    )),
+    paths(
+        list,
+        count_unread,
+        mark_all_read,
+        clear,
+        mark_read,
+        delete_notification,
+        find_preferences,
+        update_preferences,
+        notifications_websocket
+    )
+)]
 pub struct ApiDoc;
 
 pub fn init_routes(config: &mut web::ServiceConfig) {
-    config.service(web::scope("/notifications").service(list).service(clear));
+    config.service(
+        web::scope("/notifications")
+            .service(list)
+            .service(count_unread)
+            .service(mark_all_read)
+            .service(clear)
+            .service(find_preferences)
+            .service(update_preferences)
+            .service(notifications_websocket)
+            .service(mark_read)
+            .service(delete_notification),
+    );
 }
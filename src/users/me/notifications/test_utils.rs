@@ -22,6 +22,7 @@ pub fn create_test_notification(
         user_id,
         message.to_owned(),
         notification_type,
+        None,
     )
     .expect("Failed to create test notification");
 }
@@ -34,3 +35,13 @@ pub fn count_test_notifications(db: &Arc<DbAppState>, user_id: Uuid) -> i64 {
         .get_result(&mut db.connection().unwrap())
         .expect("Failed to count test notifications")
 }
+
+#[cfg(test)]
+pub fn find_test_notification_ids(db: &Arc<DbAppState>, user_id: Uuid) -> Vec<Uuid> {
+    notifications::table
+        .filter(notifications::user_id.eq(user_id))
+        .order(notifications::created_at.desc())
+        .select(notifications::id)
+        .load(&mut db.connection().unwrap())
+        .expect("Failed to load test notifications")
+}
@@ -1,11 +1,26 @@
 #[cfg(test)]
 use {
-    super::test_utils::{count_test_notifications, create_test_notification},
+    super::test_utils::{
+        count_test_notifications, create_test_notification, find_test_notification_ids,
+    },
     crate::{
-        auth::create_test_token, test_utils::init_test_app,
-        users::me::notifications::NotificationType, users::test_utils::create_test_user,
+        auth::create_test_token,
+        notifications::NotificationList,
+        test_utils::init_test_app,
+        users::me::notifications::{
+            Notification, NotificationCategory, NotificationPayload, NotificationType,
+        },
+        users::test_utils::create_test_user,
+    },
+    actix_http::StatusCode,
+    actix_web::{
+        http::header,
+        test::{self, read_body_json},
     },
-    actix_web::test::{self, read_body_json},
+    serde_json::json,
+    strum::IntoEnumIterator as _,
+    tokio::sync::broadcast,
+    uuid::Uuid,
 };
 
 #[actix_web::test]
@@ -26,7 +41,231 @@ async fn list_notifications() {
     assert!(resp.status().is_success());
 
     let body: serde_json::Value = read_body_json(resp).await;
-    assert_eq!(body.as_array().unwrap().len(), 2);
+    assert_eq!(body["count"], 2);
+    assert_eq!(body["data"].as_array().unwrap().len(), 2);
+    assert!(body["data"][0]["read_at"].is_null());
+}
+
+#[actix_web::test]
+async fn list_notifications_paginated() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, None).await;
+
+    for message in ["One", "Two", "Three"] {
+        create_test_notification(&db, user_id, message, NotificationType::Info);
+    }
+
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+    let req = test::TestRequest::get()
+        .uri("/users/@me/notifications?per_page=2&page=2")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+
+    let body: serde_json::Value = read_body_json(resp).await;
+    assert_eq!(body["count"], 3);
+    assert_eq!(body["pages"], 2);
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+}
+
+#[actix_web::test]
+async fn mark_notification_read() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, None).await;
+
+    create_test_notification(&db, user_id, "One", NotificationType::Info);
+    create_test_notification(&db, user_id, "Two", NotificationType::Info);
+    let notification_id = find_test_notification_ids(&db, user_id)[0];
+
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+    let req = test::TestRequest::post()
+        .uri(&format!("/users/@me/notifications/{notification_id}/read"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+
+    let body: serde_json::Value = read_body_json(resp).await;
+    assert_eq!(body["id"], notification_id.to_string());
+    assert!(!body["read_at"].is_null());
+
+    let req = test::TestRequest::get()
+        .uri("/users/@me/notifications/unread")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["unread"], 1);
+
+    let req = test::TestRequest::get()
+        .uri("/users/@me/notifications?unread_only=true")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["count"], 1);
+    assert_ne!(body["data"][0]["id"], notification_id.to_string());
+}
+
+#[actix_web::test]
+async fn mark_all_notifications_read() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, None).await;
+
+    create_test_notification(&db, user_id, "One", NotificationType::Info);
+    create_test_notification(&db, user_id, "Two", NotificationType::Success);
+
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+    let req = test::TestRequest::post()
+        .uri("/users/@me/notifications/read")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+
+    let req = test::TestRequest::get()
+        .uri("/users/@me/notifications/unread")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["unread"], 0);
+    assert_eq!(count_test_notifications(&db, user_id), 2);
+}
+
+#[actix_web::test]
+async fn delete_notification() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, None).await;
+
+    create_test_notification(&db, user_id, "One", NotificationType::Info);
+    create_test_notification(&db, user_id, "Two", NotificationType::Info);
+    let notification_id = find_test_notification_ids(&db, user_id)[0];
+
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+    let req = test::TestRequest::delete()
+        .uri(&format!("/users/@me/notifications/{notification_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+
+    assert_eq!(count_test_notifications(&db, user_id), 1);
+}
+
+#[actix_web::test]
+async fn cannot_access_other_users_notification() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, None).await;
+    let (other_user_id, _) = create_test_user(&db, None).await;
+
+    create_test_notification(&db, other_user_id, "Not yours", NotificationType::Info);
+    let notification_id = find_test_notification_ids(&db, other_user_id)[0];
+
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+    let req = test::TestRequest::post()
+        .uri(&format!("/users/@me/notifications/{notification_id}/read"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/users/@me/notifications/{notification_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+
+    assert_eq!(count_test_notifications(&db, other_user_id), 1);
+}
+
+#[actix_web::test]
+async fn notification_payload_sets_category() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, None).await;
+    let submission_id = Uuid::new_v4();
+
+    Notification::create(
+        &mut db.connection().unwrap(),
+        user_id,
+        "Your submission has been accepted!".to_owned(),
+        NotificationType::Success,
+        Some(NotificationPayload::Submission {
+            submission_id,
+            level_id: Uuid::new_v4(),
+            list: NotificationList::Aredl,
+            reason_codes: Vec::new(),
+        }),
+    )
+    .unwrap();
+    create_test_notification(&db, user_id, "Unrelated", NotificationType::Info);
+
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+    let req = test::TestRequest::get()
+        .uri("/users/@me/notifications?category=SubmissionStatus")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+
+    assert_eq!(body["count"], 1);
+    let notification = &body["data"][0];
+    assert_eq!(notification["category"], "SubmissionStatus");
+    assert_eq!(notification["payload"]["kind"], "Submission");
+    assert_eq!(
+        notification["payload"]["submission_id"],
+        submission_id.to_string()
+    );
+    assert_eq!(notification["payload"]["list"], "aredl");
+}
+
+#[actix_web::test]
+async fn muted_category_is_not_delivered() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, None).await;
+
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+    let req = test::TestRequest::patch()
+        .uri("/users/@me/notifications/preferences")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!([{"category": "ClanInvite", "muted": true}]))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+
+    let body: serde_json::Value = read_body_json(resp).await;
+    let preferences = body.as_array().unwrap();
+    assert!(preferences
+        .iter()
+        .any(|preference| preference["category"] == "ClanInvite" && preference["muted"] == true));
+    assert!(preferences
+        .iter()
+        .any(|preference| preference["category"] == "Bounty" && preference["muted"] == false));
+
+    Notification::create(
+        &mut db.connection().unwrap(),
+        user_id,
+        "Someone invited you to join a clan".to_owned(),
+        NotificationType::Info,
+        Some(NotificationPayload::ClanInvite {
+            invite_id: Uuid::new_v4(),
+            clan_id: Uuid::new_v4(),
+        }),
+    )
+    .unwrap();
+    assert_eq!(count_test_notifications(&db, user_id), 0);
+
+    create_test_notification(&db, user_id, "Still delivered", NotificationType::Info);
+    assert_eq!(count_test_notifications(&db, user_id), 1);
+
+    let req = test::TestRequest::get()
+        .uri("/users/@me/notifications/preferences")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(
+        body.as_array().unwrap().len(),
+        NotificationCategory::iter().count()
+    );
 }
 
 #[actix_web::test]
@@ -48,3 +287,58 @@ async fn clear_notifications() {
     let remaining = count_test_notifications(&db, user_id);
     assert_eq!(remaining, 0);
 }
+
+#[actix_web::test]
+async fn websocket_requires_auth() {
+    let (app, _, _, _) = init_test_app().await;
+    let req = test::TestRequest::get()
+        .uri("/users/@me/notifications/websocket")
+        .insert_header((header::UPGRADE, "websocket"))
+        .insert_header((header::CONNECTION, "upgrade"))
+        .insert_header((header::SEC_WEBSOCKET_VERSION, "13"))
+        .insert_header((header::SEC_WEBSOCKET_KEY, "testkey=="))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn websocket_open_to_every_user() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, None).await;
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+
+    let req = test::TestRequest::get()
+        .uri("/users/@me/notifications/websocket")
+        .insert_header((header::UPGRADE, "websocket"))
+        .insert_header((header::CONNECTION, "upgrade"))
+        .insert_header((header::SEC_WEBSOCKET_VERSION, "13"))
+        .insert_header((header::SEC_WEBSOCKET_KEY, "testkey=="))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::SWITCHING_PROTOCOLS);
+}
+
+#[actix_web::test]
+async fn created_notification_is_delivered_live_once_sent() {
+    let (_, db, _, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, None).await;
+    let (inbox_tx, mut rx) = broadcast::channel::<Notification>(16);
+
+    let notification = Notification::create(
+        &mut db.connection().unwrap(),
+        user_id,
+        "Live".to_owned(),
+        NotificationType::Info,
+        None,
+    )
+    .expect("Failed to create notification");
+
+    // the caller sends the notification once it has been committed
+    Notification::send_live(&inbox_tx, notification);
+    let notification = rx.try_recv().expect("Failed to receive notification");
+    assert_eq!(notification.user_id, user_id);
+    assert_eq!(notification.content, "Live");
+    assert!(notification.read_at.is_none());
+}
@@ -2,10 +2,12 @@ use crate::app_data::db::DbAppState;
 use crate::auth::{Authenticated, UserAuth};
 use crate::error_handler::ApiError;
 use crate::users::badges::UserBadge;
+use crate::users::me::notifications::Notification;
 use crate::users::me::{clan, notifications, UserMeUpdate};
 use crate::users::{User, UserResolved};
 use actix_web::{get, patch, post, web, HttpResponse};
 use std::sync::Arc;
+use tokio::sync::broadcast;
 use tracing_actix_web::RootSpan;
 use utoipa::OpenApi;
 
@@ -88,13 +90,18 @@ async fn update(
 async fn sync(
     db: web::Data<Arc<DbAppState>>,
     authenticated: Authenticated,
+    inbox_tx: web::Data<broadcast::Sender<Notification>>,
 ) -> Result<HttpResponse, ApiError> {
-    let badges = web::block(move || {
+    let (badges, notification) = web::block(move || {
         let conn = &mut db.connection()?;
-        UserBadge::update_user_badges(conn, authenticated.user_id)?;
-        UserBadge::find_all(conn, authenticated.user_id)
+        let notification = UserBadge::update_user_badges(conn, authenticated.user_id)?;
+        Ok::<_, ApiError>((
+            UserBadge::find_all(conn, authenticated.user_id)?,
+            notification,
+        ))
     })
     .await??;
+    Notification::send_live(&inbox_tx, notification);
 
     Ok(HttpResponse::Ok().json(badges))
 }
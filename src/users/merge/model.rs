@@ -3,6 +3,7 @@ use crate::error_handler::ApiError;
 use crate::page_helper::{PageQuery, Paginated};
 use crate::schema::merge_logs;
 use crate::users::badges::UserBadge;
+use crate::users::me::notifications::Notification;
 use chrono::{DateTime, Utc};
 use diesel::pg::Pg;
 use diesel::sql_types::Uuid as DieselUuid;
@@ -30,17 +31,17 @@ pub struct MergeLog {
     pub merged_at: DateTime<Utc>,
 }
 
+/// Merges the secondary user into the primary one, returning the notification of the badges the merge unlocked, if any.
 pub fn merge_users(
     conn: &mut DbConnection,
     primary_user: Uuid,
     secondary_user: Uuid,
-) -> Result<(), ApiError> {
+) -> Result<Option<Notification>, ApiError> {
     diesel::sql_query("SELECT merge_users($1, $2);")
         .bind::<DieselUuid, _>(primary_user)
         .bind::<DieselUuid, _>(secondary_user)
         .execute(conn)?;
-    UserBadge::update_user_badges(conn, primary_user)?;
-    Ok(())
+    UserBadge::update_user_badges(conn, primary_user)
 }
 
 #[derive(Serialize, Deserialize, Debug, ToSchema)]
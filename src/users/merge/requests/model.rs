@@ -9,7 +9,7 @@ use crate::app_data::db::DbConnection;
 use crate::error_handler::ApiError;
 use crate::page_helper::{PageQuery, Paginated};
 use crate::schema::{merge_requests, users};
-use crate::users::me::notifications::{Notification, NotificationType};
+use crate::users::me::notifications::{Notification, NotificationPayload, NotificationType};
 use crate::users::merge::merge_users;
 use crate::users::{user_filter, BaseUser};
 
@@ -250,43 +250,57 @@ impl MergeRequest {
         Ok(result)
     }
 
-    pub fn accept(conn: &mut DbConnection, id: Uuid) -> Result<(), ApiError> {
+    /// Merges the users of the request and deletes it, returning the notifications sent to the primary user.
+    pub fn accept(conn: &mut DbConnection, id: Uuid) -> Result<Vec<Notification>, ApiError> {
         let merge_request = merge_requests::table
             .filter(merge_requests::id.eq(id))
             .select(MergeRequest::as_select())
             .get_result::<MergeRequest>(conn)?;
-        merge_users(
+        let badge_notification = merge_users(
             conn,
             merge_request.primary_user,
             merge_request.secondary_user,
         )?;
 
-        Notification::create(
+        let accepted_notification = Notification::create(
             conn,
             merge_request.primary_user,
             "Your merge request has been accepted!".to_owned(),
             NotificationType::Success,
+            Some(NotificationPayload::MergeRequest {
+                merge_request_id: merge_request.id,
+            }),
         )?;
 
         diesel::delete(merge_requests::table)
             .filter(merge_requests::id.eq(id))
             .execute(conn)?;
-        Ok(())
+        Ok(badge_notification
+            .into_iter()
+            .chain(accepted_notification)
+            .collect())
     }
 
-    pub fn reject(conn: &mut DbConnection, id: Uuid) -> Result<MergeRequest, ApiError> {
+    /// Marks the request as rejected, returning it along with the notification sent to the primary user.
+    pub fn reject(
+        conn: &mut DbConnection,
+        id: Uuid,
+    ) -> Result<(MergeRequest, Option<Notification>), ApiError> {
         let result = diesel::update(merge_requests::table)
             .set(merge_requests::is_rejected.eq(true))
             .filter(merge_requests::id.eq(id))
             .returning(Self::as_select())
             .get_result(conn)?;
 
-        Notification::create(
+        let notification = Notification::create(
             conn,
             result.primary_user,
             "Your merge request has been rejected.".to_owned(),
             NotificationType::Failure,
+            Some(NotificationPayload::MergeRequest {
+                merge_request_id: result.id,
+            }),
         )?;
-        Ok(result)
+        Ok((result, notification))
     }
 }
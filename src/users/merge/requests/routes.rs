@@ -3,6 +3,7 @@ use crate::auth::Authenticated;
 use crate::auth::{Permission, UserAuth};
 use crate::error_handler::ApiError;
 use crate::page_helper::{PageQuery, Paginated};
+use crate::users::me::notifications::Notification;
 use crate::users::merge::requests::{
     MergeRequest, MergeRequestPage, MergeRequestQueryOptions, MergeRequestUpsert,
     ResolvedMergeRequest,
@@ -11,6 +12,7 @@ use actix_web::web;
 use actix_web::{get, post, HttpResponse, Result};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use tokio::sync::broadcast;
 use tracing_actix_web::RootSpan;
 use utoipa::{OpenApi, ToSchema};
 use uuid::Uuid;
@@ -161,8 +163,11 @@ async fn create(
 async fn accept(
     db: web::Data<Arc<DbAppState>>,
     id: web::Path<Uuid>,
+    inbox_tx: web::Data<broadcast::Sender<Notification>>,
 ) -> Result<HttpResponse, ApiError> {
-    web::block(move || MergeRequest::accept(&mut db.connection()?, id.into_inner())).await??;
+    let notifications =
+        web::block(move || MergeRequest::accept(&mut db.connection()?, id.into_inner())).await??;
+    Notification::send_live(&inbox_tx, notifications);
 
     Ok(HttpResponse::Ok().json(()))
 }
@@ -187,9 +192,11 @@ async fn accept(
 async fn reject(
     db: web::Data<Arc<DbAppState>>,
     id: web::Path<Uuid>,
+    inbox_tx: web::Data<broadcast::Sender<Notification>>,
 ) -> Result<HttpResponse, ApiError> {
-    let result =
+    let (result, notification) =
         web::block(move || MergeRequest::reject(&mut db.connection()?, id.into_inner())).await??;
+    Notification::send_live(&inbox_tx, notification);
 
     Ok(HttpResponse::Ok().json(result))
 }
@@ -2,6 +2,7 @@ use crate::app_data::db::DbAppState;
 use crate::auth::{Permission, UserAuth};
 use crate::error_handler::ApiError;
 use crate::page_helper::{PageQuery, Paginated};
+use crate::users::me::notifications::Notification;
 use crate::users::merge::requests;
 use crate::users::merge::MergeLogPage;
 use crate::users::merge::{merge_users, MergeLog};
@@ -10,6 +11,7 @@ use actix_web::web;
 use actix_web::{get, post, HttpResponse, Result};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use tokio::sync::broadcast;
 use tracing_actix_web::RootSpan;
 use utoipa::{OpenApi, ToSchema};
 use uuid::Uuid;
@@ -42,9 +44,10 @@ async fn direct_merge(
     db: web::Data<Arc<DbAppState>>,
     options: web::Json<DirectMergeOptions>,
     root_span: RootSpan,
+    inbox_tx: web::Data<broadcast::Sender<Notification>>,
 ) -> Result<HttpResponse, ApiError> {
     root_span.record("body", tracing::field::debug(&options));
-    web::block(move || {
+    let notification = web::block(move || {
         merge_users(
             &mut db.connection()?,
             options.primary_user,
@@ -52,6 +55,7 @@ async fn direct_merge(
         )
     })
     .await??;
+    Notification::send_live(&inbox_tx, notification);
     Ok(HttpResponse::Ok().json(()))
 }
 
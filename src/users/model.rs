@@ -41,7 +41,7 @@ pub struct BaseUser {
     pub global_name: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Queryable, Selectable, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, Queryable, Selectable, ToSchema, Clone)]
 #[diesel(table_name=users, check_for_backend(Pg))]
 pub struct ExtendedBaseUser {
     /// Internal UUID of the user.
@@ -1,5 +1,6 @@
 pub(crate) mod patreon;
-mod probe;
+pub(crate) mod probe;
+mod providers;
 mod routes;
 
 pub use routes::{init_routes, ApiDoc};
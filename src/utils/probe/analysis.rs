@@ -0,0 +1,205 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use strum_macros::{Display, EnumString};
+use utoipa::ToSchema;
+
+use super::model::{ExifInfo, ProbeResponse};
+use crate::{error_handler::ApiError, providers::ProvidersAppState};
+
+// lets 29.97 fps recordings through
+const MIN_FPS: f64 = 29.5;
+const MIN_HEIGHT: i32 = 480;
+// lowercase names of editing and transcoding software that leave their mark in the file metadata
+const REENCODE_MARKERS: &[&str] = &[
+    "handbrake",
+    "premiere",
+    "davinci",
+    "capcut",
+    "shotcut",
+    "vegas",
+    "kdenlive",
+    "imovie",
+    "final cut",
+    "clipchamp",
+    "filmora",
+    "after effects",
+];
+const EXIF_SOFTWARE_KEYS: &[&str] = &[
+    "Software",
+    "Encoder",
+    "CompressorName",
+    "WritingApplication",
+];
+
+/// Something about a raw footage file that a reviewer should look at.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema, Display, EnumString,
+)]
+pub enum RawFootageFlag {
+    /// The file metadata mentions video editing or transcoding software.
+    Reencoded,
+    /// The video has less than 30 frames per second.
+    LowFrameRate,
+    /// The video is less than 480 pixels tall.
+    LowResolution,
+    /// The file has no audio stream.
+    MissingAudio,
+    /// The file was created after the submission was made.
+    CreatedAfterSubmission,
+}
+
+/// Summary of a raw footage file, extracted from its exiftool and ffprobe output.
+#[derive(Debug, Clone, Default)]
+pub struct RawFootageAnalysis {
+    pub duration_seconds: Option<f64>,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub fps: Option<f64>,
+    pub container: Option<String>,
+    pub file_created_at: Option<DateTime<Utc>>,
+    pub has_audio: bool,
+    pub reencoded: bool,
+}
+
+fn codec_type(stream: &JsonValue) -> Option<&str> {
+    stream.get("codec_type").and_then(JsonValue::as_str)
+}
+
+fn parse_frame_rate(stream: &JsonValue, key: &str) -> Option<f64> {
+    let (num, den) = stream.get(key)?.as_str()?.split_once('/')?;
+    let num = num.parse::<f64>().ok()?;
+    let den = den.parse::<f64>().ok()?;
+    (den > 0.0 && num > 0.0).then(|| num / den)
+}
+
+fn tag_values(value: Option<&JsonValue>) -> impl Iterator<Item = &str> {
+    value
+        .and_then(|value| value.get("tags"))
+        .and_then(JsonValue::as_object)
+        .into_iter()
+        .flat_map(|tags| tags.values())
+        .filter_map(JsonValue::as_str)
+}
+
+impl RawFootageAnalysis {
+    pub fn from_probe(probe: &ProbeResponse) -> Result<Self, ApiError> {
+        let Some(ffprobe) = probe.ffprobe.data.as_ref() else {
+            return Err(ApiError::UnprocessableEntity(
+                probe
+                    .ffprobe
+                    .error
+                    .clone()
+                    .unwrap_or_else(|| "The file could not be probed".to_owned()),
+            ));
+        };
+        let exif = probe.exif.data.as_ref().and_then(ExifInfo::from_value);
+
+        let streams = ffprobe
+            .get("streams")
+            .and_then(JsonValue::as_array)
+            .map_or(&[][..], Vec::as_slice);
+        let format = ffprobe.get("format");
+        let video = streams
+            .iter()
+            .find(|stream| codec_type(stream) == Some("video"));
+        let dimension = |key: &str| i32::try_from(video?.get(key)?.as_i64()?).ok();
+
+        let duration_seconds = format
+            .and_then(|format| format.get("duration"))
+            .or_else(|| video?.get("duration"))
+            .and_then(JsonValue::as_str)
+            .and_then(|duration| duration.parse::<f64>().ok());
+
+        let fps = video.and_then(|video| {
+            parse_frame_rate(video, "avg_frame_rate")
+                .or_else(|| parse_frame_rate(video, "r_frame_rate"))
+        });
+
+        let container = exif
+            .as_ref()
+            .and_then(|exif| exif.get_str("FileType"))
+            .or_else(|| {
+                format
+                    .and_then(|format| format.get("format_name"))
+                    .and_then(JsonValue::as_str)
+            })
+            .map(str::to_owned);
+
+        let file_created_at = format
+            .and_then(|format| format.get("tags"))
+            .and_then(|tags| tags.get("creation_time"))
+            .and_then(JsonValue::as_str)
+            .and_then(|created_at| created_at.parse::<DateTime<Utc>>().ok())
+            .or_else(|| {
+                exif.as_ref()
+                    .and_then(|exif| exif.get_str("CreateDate"))
+                    .and_then(|created_at| {
+                        NaiveDateTime::parse_from_str(created_at, "%Y:%m:%d %H:%M:%S").ok()
+                    })
+                    .map(|created_at| created_at.and_utc())
+            });
+
+        let reencoded = tag_values(format)
+            .chain(streams.iter().flat_map(|stream| tag_values(Some(stream))))
+            .chain(
+                EXIF_SOFTWARE_KEYS
+                    .iter()
+                    .filter_map(|key| exif.as_ref()?.get_str(key)),
+            )
+            .map(str::to_lowercase)
+            .any(|value| REENCODE_MARKERS.iter().any(|marker| value.contains(marker)));
+
+        Ok(Self {
+            duration_seconds,
+            width: dimension("width"),
+            height: dimension("height"),
+            fps,
+            container,
+            file_created_at,
+            has_audio: streams
+                .iter()
+                .any(|stream| codec_type(stream) == Some("audio")),
+            reencoded,
+        })
+    }
+
+    pub fn flags(&self, submitted_at: DateTime<Utc>) -> Vec<RawFootageFlag> {
+        let mut flags = Vec::new();
+        if self.reencoded {
+            flags.push(RawFootageFlag::Reencoded);
+        }
+        if self.fps.is_some_and(|fps| fps < MIN_FPS) {
+            flags.push(RawFootageFlag::LowFrameRate);
+        }
+        if self.height.is_some_and(|height| height < MIN_HEIGHT) {
+            flags.push(RawFootageFlag::LowResolution);
+        }
+        if !self.has_audio {
+            flags.push(RawFootageFlag::MissingAudio);
+        }
+        if self
+            .file_created_at
+            .is_some_and(|created_at| created_at > submitted_at)
+        {
+            flags.push(RawFootageFlag::CreatedAfterSubmission);
+        }
+        flags
+    }
+
+    /// Fetches the start of the raw footage through its provider and probes it.
+    pub async fn probe_url(providers: &ProvidersAppState, url: &str) -> Result<Self, ApiError> {
+        let matched = providers.parse_url(url)?;
+        let probe = providers
+            .get_content_location(&matched)
+            .await?
+            .ok_or_else(|| {
+                ApiError::UnprocessableEntity(
+                    "Not supported for this provider yet, or failed to retrieve content location",
+                )
+            })?
+            .probe()
+            .await?;
+        Self::from_probe(&probe)
+    }
+}
@@ -1,4 +1,9 @@
+mod analysis;
 mod model;
 mod routes;
 
+#[cfg(test)]
+pub mod test_utils;
+
+pub use analysis::*;
 pub use routes::{init_routes, ApiDoc};
@@ -44,12 +44,12 @@ impl ProbeField {
     }
 }
 
-struct ExifInfo<'a> {
+pub(super) struct ExifInfo<'a> {
     root: &'a JsonMap<String, JsonValue>,
 }
 
 impl<'a> ExifInfo<'a> {
-    fn from_value(value: &'a JsonValue) -> Option<Self> {
+    pub(super) fn from_value(value: &'a JsonValue) -> Option<Self> {
         let object = match value {
             JsonValue::Array(array) => array.first()?.as_object()?,
             JsonValue::Object(object) => object,
@@ -61,7 +61,7 @@ impl<'a> ExifInfo<'a> {
         Some(ExifInfo { root: object })
     }
 
-    fn get_str(&self, key: &str) -> Option<&str> {
+    pub(super) fn get_str(&self, key: &str) -> Option<&str> {
         self.root.get(key)?.as_str()
     }
 
@@ -91,7 +91,9 @@ impl<'a> ExifInfo<'a> {
 
 impl ContentDataLocation {
     async fn get_ffprobe(path: &Path) -> Result<JsonValue, ApiError> {
-        let mut command = Command::new("/usr/local/bin/ffprobe");
+        let ffprobe_path =
+            std::env::var("FFPROBE_PATH").unwrap_or_else(|_| "/usr/local/bin/ffprobe".to_owned());
+        let mut command = Command::new(ffprobe_path);
         command
             .arg("-v")
             .arg("error")
@@ -130,7 +132,9 @@ impl ContentDataLocation {
     }
 
     async fn get_exiftool(path: &Path) -> Result<JsonValue, ApiError> {
-        let mut command = Command::new("/usr/bin/exiftool");
+        let exiftool_path =
+            std::env::var("EXIFTOOL_PATH").unwrap_or_else(|_| "/usr/bin/exiftool".to_owned());
+        let mut command = Command::new(exiftool_path);
         command
             .arg("-api")
             .arg("LargeFileSupport=1")
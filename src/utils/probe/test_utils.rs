@@ -0,0 +1,38 @@
+#[cfg(test)]
+use {
+    serde_json::Value as JsonValue,
+    std::{fs, os::unix::fs::PermissionsExt as _, path::Path},
+    tempfile::TempDir,
+};
+
+#[cfg(test)]
+fn write_stub_tool(dir: &Path, name: &str, output: &JsonValue) -> String {
+    let path = dir.join(name);
+    fs::write(&path, format!("#!/bin/sh\ncat <<'JSON'\n{output}\nJSON\n"))
+        .expect("Failed to write stub tool");
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+        .expect("Failed to make stub tool executable");
+    path.to_string_lossy().into_owned()
+}
+
+/// Replaces ffprobe and exiftool with scripts printing the given output.
+/// The returned directory holds the scripts and must be kept alive while probing.
+#[cfg(test)]
+pub fn set_probe_tools_env(ffprobe_output: &JsonValue, exiftool_output: &JsonValue) -> TempDir {
+    let dir = TempDir::new().expect("Failed to create stub tool directory");
+    std::env::set_var(
+        "FFPROBE_PATH",
+        write_stub_tool(dir.path(), "ffprobe", ffprobe_output),
+    );
+    std::env::set_var(
+        "EXIFTOOL_PATH",
+        write_stub_tool(dir.path(), "exiftool", exiftool_output),
+    );
+    dir
+}
+
+#[cfg(test)]
+pub fn clear_probe_tools_env() {
+    std::env::remove_var("FFPROBE_PATH");
+    std::env::remove_var("EXIFTOOL_PATH");
+}
@@ -0,0 +1,167 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    error_handler::ApiError,
+    providers::{
+        model::{ProviderId, ProviderUsage},
+        ProvidersAppState,
+    },
+};
+
+#[derive(Deserialize, ToSchema)]
+pub struct UrlDiagnosisQuery {
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, ToSchema)]
+pub enum ProviderCheckOutcome {
+    /// The provider recognized the URL.
+    Matched,
+    /// The URL is hosted somewhere this provider doesn't handle.
+    HostNotAccepted,
+    /// The host belongs to this provider, but the URL isn't in a form it knows.
+    UrlNotRecognized,
+}
+
+/// How a single registered provider handled the URL.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ProviderCheck {
+    pub provider: ProviderId,
+    pub outcome: ProviderCheckOutcome,
+}
+
+/// Result of looking something up through the matched provider.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ProviderLookup {
+    /// Whether the provider returned a result.
+    pub resolved: bool,
+    /// Why the lookup failed. Unresolved lookups without an error are not supported by the provider.
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct UrlDiagnosis {
+    /// URL that was checked.
+    pub url: String,
+    /// Why the URL could not be parsed, if it is malformed.
+    pub error: Option<String>,
+    /// Provider the URL is attributed to.
+    pub provider: Option<ProviderId>,
+    /// ID of the content within the provider.
+    pub content_id: Option<String>,
+    /// URL as it would be stored on a submission.
+    pub normalized_url: Option<String>,
+    /// Which submission fields the provider is allowed for.
+    pub usage: Option<ProviderUsage>,
+    /// Whether the URL would be accepted as a completion video.
+    pub allowed_for_completion: bool,
+    /// How each registered provider handled the URL, sorted by provider.
+    pub checks: Vec<ProviderCheck>,
+    /// Metadata lookup through the matched provider.
+    pub metadata: Option<ProviderLookup>,
+    /// Publish date reported by the provider's metadata.
+    pub published_at: Option<DateTime<Utc>>,
+    /// Lookup of where the file contents can be downloaded from, which probing relies on.
+    pub content_location: Option<ProviderLookup>,
+}
+
+impl ProviderLookup {
+    fn from_result<T>(result: Result<Option<T>, ApiError>) -> (Self, Option<T>) {
+        match result {
+            Ok(value) => (
+                Self {
+                    resolved: value.is_some(),
+                    error: None,
+                },
+                value,
+            ),
+            Err(error) => (
+                Self {
+                    resolved: false,
+                    error: Some(error.error_message),
+                },
+                None,
+            ),
+        }
+    }
+}
+
+impl UrlDiagnosis {
+    pub async fn diagnose(providers: &ProvidersAppState, url: &str) -> Self {
+        let mut diagnosis = Self {
+            url: url.to_owned(),
+            error: None,
+            provider: None,
+            content_id: None,
+            normalized_url: None,
+            usage: None,
+            allowed_for_completion: false,
+            checks: Vec::new(),
+            metadata: None,
+            published_at: None,
+            content_location: None,
+        };
+
+        let parsed = match providers.validate_is_url(url) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                diagnosis.error = Some(error.error_message);
+                return diagnosis;
+            }
+        };
+
+        let host = parsed.host_str().unwrap_or_default();
+        let mut checks = providers
+            .registry
+            .providers()
+            .map(|provider| {
+                let outcome = if !provider.hosts().is_empty() && !provider.hosts().contains(&host) {
+                    ProviderCheckOutcome::HostNotAccepted
+                } else if provider.match_url(&parsed).is_some() {
+                    ProviderCheckOutcome::Matched
+                } else {
+                    ProviderCheckOutcome::UrlNotRecognized
+                };
+                ProviderCheck {
+                    provider: provider.id(),
+                    outcome,
+                }
+            })
+            .collect::<Vec<_>>();
+        checks.sort_by_key(|check| check.provider.to_string());
+        diagnosis.checks = checks;
+
+        let matched = match providers.parse_url(url) {
+            Ok(matched) => matched,
+            Err(error) => {
+                diagnosis.error = Some(error.error_message);
+                return diagnosis;
+            }
+        };
+
+        let usage = providers
+            .registry
+            .get(matched.provider)
+            .map(|provider| provider.usage());
+        diagnosis.allowed_for_completion =
+            usage.is_some_and(|usage| usage.allowed_for_completion());
+        diagnosis.usage = usage;
+
+        let (metadata, content) =
+            ProviderLookup::from_result(providers.fetch_metadata(&matched).await);
+        diagnosis.published_at = content.and_then(|content| content.published_at);
+        diagnosis.metadata = Some(metadata);
+
+        // only whether it resolved is reported, the location itself can carry access tokens
+        let (content_location, _) =
+            ProviderLookup::from_result(providers.get_content_location(&matched).await);
+        diagnosis.content_location = Some(content_location);
+
+        diagnosis.provider = Some(matched.provider);
+        diagnosis.content_id = Some(matched.content_id);
+        diagnosis.normalized_url = Some(matched.normalized_url);
+        diagnosis
+    }
+}
@@ -0,0 +1,58 @@
+use actix_web::{get, web, HttpResponse};
+use std::sync::Arc;
+use utoipa::OpenApi;
+
+use crate::{
+    auth::{Permission, UserAuth},
+    error_handler::ApiError,
+    providers::{
+        model::{ProviderId, ProviderUsage},
+        ProvidersAppState,
+    },
+    utils::providers::{
+        ProviderCheck, ProviderCheckOutcome, ProviderLookup, UrlDiagnosis, UrlDiagnosisQuery,
+    },
+};
+
+#[utoipa::path(
+    get,
+    summary = "[Staff]Diagnose a URL",
+    description = "Explain how the registered providers handle a URL: which one matched or why each one rejected it, its normalized form, what it may be used for and whether its metadata and content location could be resolved.",
+    tag = "Utils",
+    params(
+        ("url" = String, Query, description = "URL to diagnose"),
+    ),
+    responses(
+        (status = 200, body = UrlDiagnosis)
+    ),
+    security(
+        ("access_token" = ["SubmissionReview"]),
+        ("api_key" = ["SubmissionReview"]),
+    ),
+)]
+#[get("/diagnose", wrap = "UserAuth::require(Permission::SubmissionReview)")]
+async fn diagnose(
+    query: web::Query<UrlDiagnosisQuery>,
+    providers_state: web::Data<Arc<ProvidersAppState>>,
+) -> Result<HttpResponse, ApiError> {
+    let diagnosis = UrlDiagnosis::diagnose(providers_state.get_ref(), &query.url).await;
+    Ok(HttpResponse::Ok().json(diagnosis))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    components(schemas(
+        UrlDiagnosis,
+        ProviderCheck,
+        ProviderCheckOutcome,
+        ProviderLookup,
+        ProviderId,
+        ProviderUsage,
+    )),
+    paths(diagnose)
+)]
+pub struct ApiDoc;
+
+pub fn init_routes(config: &mut web::ServiceConfig) {
+    config.service(web::scope("/providers").service(diagnose));
+}
@@ -0,0 +1,117 @@
+#[cfg(test)]
+use {
+    crate::{
+        auth::create_test_token,
+        providers::{metrics::PROVIDER_OPERATIONS, model::ProviderId},
+        test_utils::init_test_app,
+        users::test_utils::{create_test_full_reviewer, create_test_user},
+    },
+    actix_http::StatusCode,
+    actix_web::test::{self, read_body_json},
+    serde_json::Value,
+    url::form_urlencoded::Serializer,
+};
+
+#[cfg(test)]
+fn diagnose_uri(url: &str) -> String {
+    let query = Serializer::new(String::new())
+        .append_pair("url", url)
+        .finish();
+    format!("/utils/providers/diagnose?{query}")
+}
+
+#[cfg(test)]
+fn check_outcome(body: &Value, provider: ProviderId) -> Value {
+    let check = body["checks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|check| check["provider"] == provider.to_string())
+        .unwrap_or_else(|| panic!("{provider} is missing from the checks"));
+    check["outcome"].clone()
+}
+
+#[actix_web::test]
+async fn diagnose_matched_url() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (reviewer, _) = create_test_full_reviewer(&db).await;
+    let token = create_test_token(reviewer, &auth.jwt_encoding_key).unwrap();
+
+    let req = test::TestRequest::get()
+        .uri(&diagnose_uri(
+            "https://www.dropbox.com/s/a1b2c3d4e5/run.mp4?dl=0",
+        ))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: Value = read_body_json(resp).await;
+
+    assert!(body["error"].is_null());
+    assert_eq!(body["provider"], "Dropbox");
+    assert_eq!(body["content_id"], "a1b2c3d4e5");
+    assert_eq!(
+        body["normalized_url"],
+        "https://www.dropbox.com/s/a1b2c3d4e5/run.mp4"
+    );
+    assert_eq!(body["usage"], "RawFootage");
+    assert_eq!(body["allowed_for_completion"], false);
+    // dropbox has no metadata to look up, but its files can be downloaded directly
+    assert_eq!(body["metadata"]["resolved"], false);
+    assert!(body["metadata"]["error"].is_null());
+    assert_eq!(body["content_location"]["resolved"], true);
+    assert_eq!(check_outcome(&body, ProviderId::Dropbox), "Matched");
+    assert_eq!(check_outcome(&body, ProviderId::YouTube), "HostNotAccepted");
+    // the catch-all provider also recognizes it, but dedicated providers take precedence
+    assert_eq!(check_outcome(&body, ProviderId::DirectFile), "Matched");
+}
+
+#[actix_web::test]
+async fn diagnose_unrecognized_url() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (reviewer, _) = create_test_full_reviewer(&db).await;
+    let token = create_test_token(reviewer, &auth.jwt_encoding_key).unwrap();
+    let failures = PROVIDER_OPERATIONS.with_label_values(&["YouTube", "match", "failure"]);
+    let failures_before = failures.get();
+
+    let req = test::TestRequest::get()
+        .uri(&diagnose_uri("https://www.youtube.com/feed/subscriptions"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let body: Value = read_body_json(test::call_service(&app, req).await).await;
+
+    assert!(body["provider"].is_null());
+    assert!(body["error"]
+        .as_str()
+        .unwrap()
+        .contains("does not match any known supported providers"));
+    assert_eq!(
+        check_outcome(&body, ProviderId::YouTube),
+        "UrlNotRecognized"
+    );
+    assert_eq!(check_outcome(&body, ProviderId::Vimeo), "HostNotAccepted");
+    assert!(body.get("metadata").unwrap().is_null());
+    assert!(failures.get() > failures_before);
+
+    let req = test::TestRequest::get()
+        .uri(&diagnose_uri("not a url"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let body: Value = read_body_json(test::call_service(&app, req).await).await;
+    assert!(body["error"].as_str().unwrap().starts_with("Malformed URL"));
+    assert!(body["checks"].as_array().unwrap().is_empty());
+}
+
+#[actix_web::test]
+async fn diagnose_requires_permission() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user, _) = create_test_user(&db, None).await;
+    let token = create_test_token(user, &auth.jwt_encoding_key).unwrap();
+
+    let req = test::TestRequest::get()
+        .uri(&diagnose_uri("https://youtu.be/xvFZjo5PgG0"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
@@ -1,15 +1,20 @@
 use actix_web::web;
 use utoipa::OpenApi;
 
-use crate::utils::probe;
+use crate::utils::{probe, providers};
 
 #[derive(OpenApi)]
 #[openapi(
     nest(
         (path = "/probe", api = probe::ApiDoc),
+        (path = "/providers", api = providers::ApiDoc),
     )
 )]
 pub struct ApiDoc;
 pub fn init_routes(config: &mut web::ServiceConfig) {
-    config.service(web::scope("/utils").configure(probe::init_routes));
+    config.service(
+        web::scope("/utils")
+            .configure(probe::init_routes)
+            .configure(providers::init_routes),
+    );
 }
@@ -0,0 +1,167 @@
+use crate::{
+    app_data::db::DbAppState,
+    error_handler::ApiError,
+    notifications::WebsocketNotification,
+    providers::public_host::{pinned_client, resolve_public_host},
+    webhooks::{Webhook, WebhookAttempt, WebhookDelivery},
+};
+use aws_lc_rs::hmac;
+use chrono::Utc;
+use std::{sync::Arc, time::Duration};
+use url::Url;
+
+/// Header holding the `sha256=<hex>` HMAC signature of `<timestamp>.<body>`.
+pub const SIGNATURE_HEADER: &str = "X-Aredl-Signature";
+/// Header holding the unix timestamp the delivery was signed at.
+pub const TIMESTAMP_HEADER: &str = "X-Aredl-Timestamp";
+/// Header holding the type of the delivered event.
+pub const EVENT_HEADER: &str = "X-Aredl-Event";
+/// Header holding the UUID of the delivery, which stays the same across retries.
+pub const DELIVERY_HEADER: &str = "X-Aredl-Delivery";
+
+/// Maximum amount of deliveries sent in a single run of the delivery job.
+/// Deliveries are sent one after another, so a full batch of timed out attempts has to fit in the claim lease
+/// or another instance could claim and send them again.
+const DELIVERY_BATCH_SIZE: i64 = 20;
+/// Time a single attempt may take, from resolving the endpoint to its response.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+const DELIVERY_USER_AGENT: &str = "AredlBackend/2.0 (+https://api.aredl.net)";
+
+pub fn sign_payload(secret: &str, timestamp: i64, body: &str) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    let tag = hmac::sign(&key, format!("{timestamp}.{body}").as_bytes());
+    let signature = tag
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!("sha256={signature}")
+}
+
+/// Connects to webhook endpoints. Endpoints are only reached on public addresses and their redirects are never followed,
+/// so a webhook can't be used to send requests into the private network.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DeliveryClient {
+    allow_internal_hosts: bool,
+}
+
+impl DeliveryClient {
+    /// Client that also connects to internal addresses, so that deliveries can reach a local mock server.
+    #[cfg(test)]
+    pub fn allowing_internal_hosts() -> Self {
+        Self {
+            allow_internal_hosts: true,
+        }
+    }
+
+    async fn client_for(&self, url: &Url) -> Result<reqwest::Client, ApiError> {
+        // the host is resolved on every attempt, it may point somewhere else than when the webhook was registered
+        let addresses = if self.allow_internal_hosts {
+            Vec::new()
+        } else {
+            resolve_public_host(url).await?
+        };
+        pinned_client(url, &addresses)
+    }
+}
+
+async fn attempt_delivery(
+    client: &DeliveryClient,
+    delivery: &WebhookDelivery,
+    webhook: &Webhook,
+    event: &WebsocketNotification,
+) -> WebhookAttempt {
+    let body = match serde_json::to_string(event) {
+        Ok(body) => body,
+        Err(error) => {
+            return WebhookAttempt {
+                response_status: None,
+                error: Some(format!("Failed to serialize the event: {error}")),
+            }
+        }
+    };
+    let prepared = async {
+        let url = Url::parse(&webhook.url)?;
+        let secret = webhook.signing_secret()?;
+        let http = client.client_for(&url).await?;
+        Ok::<_, ApiError>((url, secret, http))
+    };
+    let (url, secret, http) = match prepared.await {
+        Ok(prepared) => prepared,
+        Err(error) => {
+            return WebhookAttempt {
+                response_status: None,
+                error: Some(format!("Failed to reach the endpoint: {error}")),
+            }
+        }
+    };
+    let timestamp = Utc::now().timestamp();
+    let signature = sign_payload(&secret, timestamp, &body);
+
+    let response = http
+        .post(url)
+        .timeout(DELIVERY_TIMEOUT)
+        .header(reqwest::header::USER_AGENT, DELIVERY_USER_AGENT)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(SIGNATURE_HEADER, signature)
+        .header(TIMESTAMP_HEADER, timestamp.to_string())
+        .header(EVENT_HEADER, &event.notification_type)
+        .header(DELIVERY_HEADER, delivery.id.to_string())
+        .body(body)
+        .send()
+        .await;
+
+    match response {
+        Ok(response) => {
+            let status = response.status();
+            WebhookAttempt {
+                response_status: Some(status.as_u16()),
+                error: (!status.is_success()).then(|| format!("Endpoint responded with {status}")),
+            }
+        }
+        Err(error) => WebhookAttempt {
+            response_status: None,
+            error: Some(format!("Failed to reach the endpoint: {error}")),
+        },
+    }
+}
+
+/// Sends every due webhook delivery and records the outcome. Returns the amount of attempts made.
+pub async fn deliver_due_webhooks(
+    db: &Arc<DbAppState>,
+    client: &DeliveryClient,
+) -> Result<usize, ApiError> {
+    let due = WebhookDelivery::claim_due(&mut db.connection()?, DELIVERY_BATCH_SIZE)?;
+    let attempted = due.len();
+
+    for (delivery, webhook, event) in due {
+        let attempt = tokio::time::timeout(
+            DELIVERY_TIMEOUT,
+            attempt_delivery(client, &delivery, &webhook, &event),
+        )
+        .await
+        .unwrap_or_else(|_| WebhookAttempt {
+            response_status: None,
+            error: Some(String::from("The endpoint did not respond in time")),
+        });
+        if let Some(error) = &attempt.error {
+            tracing::warn!(
+                "Webhook delivery {} to {} failed: {error}",
+                delivery.id,
+                webhook.url
+            );
+        }
+        let recorded = db
+            .connection()
+            .and_then(|mut conn| WebhookDelivery::record_attempt(&mut conn, &delivery, &attempt));
+        if let Err(error) = recorded {
+            tracing::warn!(
+                error = %error.error_message,
+                delivery = %delivery.id,
+                "Failed to record webhook delivery attempt"
+            );
+        }
+    }
+
+    Ok(attempted)
+}
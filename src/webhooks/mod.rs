@@ -0,0 +1,13 @@
+mod delivery;
+mod model;
+mod routes;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(test)]
+pub mod test_utils;
+
+pub use delivery::*;
+pub use model::*;
+pub use routes::{init_routes, ApiDoc};
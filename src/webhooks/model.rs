@@ -0,0 +1,455 @@
+use crate::{
+    app_data::db::DbConnection,
+    error_handler::ApiError,
+    notifications::{WebsocketNotification, WebsocketNotificationType},
+    page_helper::{PageQuery, Paginated},
+    providers::{
+        context::{decrypt_db_token_value, encrypt_db_token_value},
+        public_host::{is_internal_domain, is_public_ip},
+    },
+    schema::{notification_events, webhook_deliveries, webhooks},
+};
+use chrono::{DateTime, Duration, Utc};
+use diesel::{pg::Pg, sql_types::BigInt, AsChangeset, Queryable};
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+use url::{Host, Url};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use diesel::prelude::*;
+
+// webhook secrets are encrypted with the same key as the backend OAuth tokens
+const WEBHOOK_SECRET_AAD: &[u8] = b"webhooks:v1:secret";
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, DbEnum, Clone, Copy, PartialEq, Eq)]
+#[ExistingTypePath = "crate::schema::sql_types::WebhookDeliveryStatus"]
+#[DbValueStyle = "PascalCase"]
+pub enum WebhookDeliveryStatus {
+    /// The delivery is waiting for its next attempt.
+    Pending,
+    /// The endpoint responded with a 2xx status code.
+    Succeeded,
+    /// Every attempt failed, the delivery will not be retried.
+    Failed,
+}
+
+#[derive(Serialize, Selectable, Queryable, Debug, Clone, ToSchema)]
+#[diesel(table_name = webhooks, check_for_backend(Pg))]
+pub struct Webhook {
+    /// Internal UUID of the webhook.
+    pub id: Uuid,
+    /// HTTPS endpoint the events are POSTed to.
+    pub url: String,
+    /// Encrypted secret used to sign the deliveries. Only returned when the webhook is created or its secret is rotated.
+    #[serde(skip)]
+    pub secret: String,
+    /// Event types delivered to this webhook.
+    #[schema(value_type = Vec<WebsocketNotificationType>)]
+    pub event_types: Vec<Option<String>>,
+    /// Whether events are delivered to this webhook. Webhooks are disabled automatically after too many consecutive failed attempts.
+    pub enabled: bool,
+    /// Amount of failed delivery attempts since the last successful one.
+    pub consecutive_failures: i32,
+    /// Internal UUID of the user who registered this webhook.
+    pub created_by: Option<Uuid>,
+    /// Timestamp of when this webhook was registered.
+    pub created_at: DateTime<Utc>,
+    /// Timestamp of when this webhook was last updated.
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct WebhookWithSecret {
+    #[serde(flatten)]
+    pub webhook: Webhook,
+    /// Secret used to sign the deliveries. Store it, it will not be shown again.
+    pub secret: String,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct WebhookCreate {
+    /// HTTPS endpoint the events should be POSTed to.
+    pub url: String,
+    /// Event types to deliver to this webhook.
+    pub event_types: Vec<WebsocketNotificationType>,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct WebhookPatch {
+    /// HTTPS endpoint the events should be POSTed to.
+    pub url: Option<String>,
+    /// Event types to deliver to this webhook.
+    pub event_types: Option<Vec<WebsocketNotificationType>>,
+    /// Whether events should be delivered to this webhook. Re-enabling a webhook resets its failure count.
+    pub enabled: Option<bool>,
+}
+
+#[derive(AsChangeset, Debug)]
+#[diesel(table_name = webhooks)]
+struct WebhookChangeset {
+    url: Option<String>,
+    event_types: Option<Vec<Option<String>>>,
+    enabled: Option<bool>,
+    consecutive_failures: Option<i32>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Selectable, Queryable, Debug, Clone, ToSchema)]
+#[diesel(table_name = webhook_deliveries, check_for_backend(Pg))]
+pub struct WebhookDelivery {
+    /// Internal UUID of the delivery.
+    pub id: Uuid,
+    /// Internal UUID of the webhook this delivery is for.
+    pub webhook_id: Uuid,
+    /// Sequence ID of the delivered event.
+    pub event_id: i64,
+    /// Current status of the delivery.
+    pub status: WebhookDeliveryStatus,
+    /// Amount of attempts made so far.
+    pub attempts: i32,
+    /// Timestamp of the next attempt, if the delivery is still pending.
+    pub next_attempt_at: DateTime<Utc>,
+    /// HTTP status code returned by the endpoint on the last attempt, if it responded.
+    pub response_status: Option<i32>,
+    /// Error of the last failed attempt.
+    pub error: Option<String>,
+    /// Timestamp of when the event was queued for delivery.
+    pub created_at: DateTime<Utc>,
+    /// Timestamp of the last attempt.
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct WebhookDeliveryPage {
+    pub data: Vec<WebhookDelivery>,
+}
+
+/// Result of a single delivery attempt.
+#[derive(Debug)]
+pub struct WebhookAttempt {
+    /// HTTP status code returned by the endpoint, if it responded.
+    pub response_status: Option<u16>,
+    pub error: Option<String>,
+}
+
+impl WebhookAttempt {
+    pub fn is_success(&self) -> bool {
+        self.response_status
+            .is_some_and(|status| (200..300).contains(&status))
+    }
+}
+
+fn validate_url(url: &str) -> Result<String, ApiError> {
+    let parsed = Url::parse(url.trim())
+        .map_err(|_err| ApiError::BadRequest("The webhook URL is not a valid URL."))?;
+    if parsed.scheme() != "https" || parsed.host_str().is_none() {
+        return Err(ApiError::BadRequest("Webhook URLs must use HTTPS."));
+    }
+    let internal = match parsed.host() {
+        Some(Host::Domain(domain)) => is_internal_domain(domain),
+        Some(Host::Ipv4(ip)) => !is_public_ip(ip.into()),
+        Some(Host::Ipv6(ip)) => !is_public_ip(ip.into()),
+        None => true,
+    };
+    if internal {
+        return Err(ApiError::BadRequest(
+            "Webhook URLs must point to a public host.",
+        ));
+    }
+    Ok(parsed.to_string())
+}
+
+fn event_types_to_db(
+    event_types: &[WebsocketNotificationType],
+) -> Result<Vec<Option<String>>, ApiError> {
+    if event_types.is_empty() {
+        return Err(ApiError::BadRequest(
+            "A webhook must subscribe to at least one event type.",
+        ));
+    }
+    let mut event_types = event_types
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    event_types.sort_unstable();
+    event_types.dedup();
+    Ok(event_types.into_iter().map(Some).collect())
+}
+
+fn generate_secret() -> String {
+    rand::random::<[u8; 32]>()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+impl Webhook {
+    /// Maximum amount of consecutive failed attempts before a webhook gets disabled.
+    pub const DISABLE_AFTER_FAILURES: i32 = 15;
+
+    /// Decrypts the secret used to sign the deliveries. Secrets stored before they were encrypted are returned as is.
+    pub fn signing_secret(&self) -> Result<String, ApiError> {
+        decrypt_db_token_value(&self.secret, WEBHOOK_SECRET_AAD)
+    }
+
+    pub fn find_all(conn: &mut DbConnection) -> Result<Vec<Self>, ApiError> {
+        let webhooks = webhooks::table
+            .order(webhooks::created_at.asc())
+            .select(Webhook::as_select())
+            .load::<Webhook>(conn)?;
+        Ok(webhooks)
+    }
+
+    pub fn create(
+        conn: &mut DbConnection,
+        webhook: &WebhookCreate,
+        created_by: Uuid,
+    ) -> Result<WebhookWithSecret, ApiError> {
+        let url = validate_url(&webhook.url)?;
+        let event_types = event_types_to_db(&webhook.event_types)?;
+        let secret = generate_secret();
+
+        let webhook = diesel::insert_into(webhooks::table)
+            .values((
+                webhooks::url.eq(url),
+                webhooks::secret.eq(encrypt_db_token_value(&secret, WEBHOOK_SECRET_AAD)?),
+                webhooks::event_types.eq(event_types),
+                webhooks::created_by.eq(created_by),
+            ))
+            .returning(Webhook::as_select())
+            .get_result::<Webhook>(conn)?;
+
+        Ok(WebhookWithSecret { webhook, secret })
+    }
+
+    pub fn patch(
+        conn: &mut DbConnection,
+        id: Uuid,
+        patch: &WebhookPatch,
+    ) -> Result<Self, ApiError> {
+        let changeset = WebhookChangeset {
+            url: patch.url.as_deref().map(validate_url).transpose()?,
+            event_types: patch
+                .event_types
+                .as_deref()
+                .map(event_types_to_db)
+                .transpose()?,
+            enabled: patch.enabled,
+            consecutive_failures: patch.enabled.filter(|enabled| *enabled).map(|_| 0),
+            updated_at: Utc::now(),
+        };
+
+        let updated = diesel::update(webhooks::table.filter(webhooks::id.eq(id)))
+            .set(&changeset)
+            .returning(Webhook::as_select())
+            .get_result::<Webhook>(conn)?;
+        Ok(updated)
+    }
+
+    pub fn rotate_secret(conn: &mut DbConnection, id: Uuid) -> Result<WebhookWithSecret, ApiError> {
+        let secret = generate_secret();
+        let webhook = diesel::update(webhooks::table.filter(webhooks::id.eq(id)))
+            .set((
+                webhooks::secret.eq(encrypt_db_token_value(&secret, WEBHOOK_SECRET_AAD)?),
+                webhooks::updated_at.eq(Utc::now()),
+            ))
+            .returning(Webhook::as_select())
+            .get_result::<Webhook>(conn)?;
+        Ok(WebhookWithSecret { webhook, secret })
+    }
+
+    pub fn delete(conn: &mut DbConnection, id: Uuid) -> Result<Self, ApiError> {
+        let deleted = diesel::delete(webhooks::table.filter(webhooks::id.eq(id)))
+            .returning(Webhook::as_select())
+            .get_result::<Webhook>(conn)?;
+        Ok(deleted)
+    }
+
+    /// Queues a delivery of the event for every enabled webhook subscribed to its type.
+    pub fn enqueue_deliveries(
+        conn: &mut DbConnection,
+        notification: &WebsocketNotification,
+    ) -> Result<usize, ApiError> {
+        let subscribed = webhooks::table
+            .filter(webhooks::enabled.eq(true))
+            .filter(
+                webhooks::event_types.contains(vec![Some(notification.notification_type.clone())]),
+            )
+            .select((webhooks::id, notification.id.into_sql::<BigInt>()));
+
+        let queued = diesel::insert_into(webhook_deliveries::table)
+            .values(subscribed)
+            .into_columns((webhook_deliveries::webhook_id, webhook_deliveries::event_id))
+            .execute(conn)?;
+        Ok(queued)
+    }
+}
+
+impl WebhookDelivery {
+    /// Maximum amount of attempts before a delivery is marked as failed.
+    pub const MAX_ATTEMPTS: i32 = 8;
+    /// Delay before the first retry, doubled after every failed attempt.
+    pub const RETRY_BASE_DELAY_SECS: i64 = 30;
+    /// How long a claimed delivery is reserved for the worker that claimed it, it must outlast a full delivery batch.
+    pub const CLAIM_LEASE_SECS: i64 = 300;
+
+    pub fn retry_delay(attempts: i32) -> Duration {
+        let exponent = u32::try_from(attempts.saturating_sub(1).clamp(0, 16)).unwrap_or(0);
+        Duration::seconds(Self::RETRY_BASE_DELAY_SECS.saturating_mul(1_i64 << exponent))
+    }
+
+    /// Claims the deliveries that are due, so that concurrent workers do not send them twice.
+    pub fn claim_due(
+        conn: &mut DbConnection,
+        limit: i64,
+    ) -> Result<Vec<(WebhookDelivery, Webhook, WebsocketNotification)>, ApiError> {
+        conn.transaction(|connection| {
+            let now = Utc::now();
+            let ids = webhook_deliveries::table
+                .filter(webhook_deliveries::status.eq(WebhookDeliveryStatus::Pending))
+                .filter(webhook_deliveries::next_attempt_at.le(now))
+                .filter(
+                    webhook_deliveries::webhook_id.eq_any(
+                        webhooks::table
+                            .filter(webhooks::enabled.eq(true))
+                            .select(webhooks::id),
+                    ),
+                )
+                .order(webhook_deliveries::next_attempt_at.asc())
+                .limit(limit)
+                .select(webhook_deliveries::id)
+                .for_update()
+                .skip_locked()
+                .load::<Uuid>(connection)?;
+
+            if ids.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            diesel::update(webhook_deliveries::table.filter(webhook_deliveries::id.eq_any(&ids)))
+                .set(
+                    webhook_deliveries::next_attempt_at
+                        .eq(now + Duration::seconds(Self::CLAIM_LEASE_SECS)),
+                )
+                .execute(connection)?;
+
+            let claimed = webhook_deliveries::table
+                .inner_join(webhooks::table)
+                .inner_join(notification_events::table)
+                .filter(webhook_deliveries::id.eq_any(&ids))
+                .order(webhook_deliveries::event_id.asc())
+                .select((
+                    WebhookDelivery::as_select(),
+                    Webhook::as_select(),
+                    WebsocketNotification::as_select(),
+                ))
+                .load::<(WebhookDelivery, Webhook, WebsocketNotification)>(connection)?;
+            Ok(claimed)
+        })
+    }
+
+    /// Records the outcome of an attempt, scheduling a retry or disabling the webhook if needed.
+    pub fn record_attempt(
+        conn: &mut DbConnection,
+        delivery: &WebhookDelivery,
+        attempt: &WebhookAttempt,
+    ) -> Result<Self, ApiError> {
+        conn.transaction(|connection| {
+            let now = Utc::now();
+            let attempts = delivery.attempts + 1;
+            let response_status = attempt.response_status.map(i32::from);
+
+            if attempt.is_success() {
+                diesel::update(webhooks::table.filter(webhooks::id.eq(delivery.webhook_id)))
+                    .set(webhooks::consecutive_failures.eq(0))
+                    .execute(connection)?;
+
+                let updated = diesel::update(
+                    webhook_deliveries::table.filter(webhook_deliveries::id.eq(delivery.id)),
+                )
+                .set((
+                    webhook_deliveries::status.eq(WebhookDeliveryStatus::Succeeded),
+                    webhook_deliveries::attempts.eq(attempts),
+                    webhook_deliveries::response_status.eq(response_status),
+                    webhook_deliveries::error.eq(None::<String>),
+                    webhook_deliveries::updated_at.eq(now),
+                ))
+                .returning(WebhookDelivery::as_select())
+                .get_result::<WebhookDelivery>(connection)?;
+                return Ok(updated);
+            }
+
+            let status = if attempts >= Self::MAX_ATTEMPTS {
+                WebhookDeliveryStatus::Failed
+            } else {
+                WebhookDeliveryStatus::Pending
+            };
+
+            let updated = diesel::update(
+                webhook_deliveries::table.filter(webhook_deliveries::id.eq(delivery.id)),
+            )
+            .set((
+                webhook_deliveries::status.eq(status),
+                webhook_deliveries::attempts.eq(attempts),
+                webhook_deliveries::response_status.eq(response_status),
+                webhook_deliveries::error.eq(&attempt.error),
+                webhook_deliveries::next_attempt_at.eq(now + Self::retry_delay(attempts)),
+                webhook_deliveries::updated_at.eq(now),
+            ))
+            .returning(WebhookDelivery::as_select())
+            .get_result::<WebhookDelivery>(connection)?;
+
+            let failures =
+                diesel::update(webhooks::table.filter(webhooks::id.eq(delivery.webhook_id)))
+                    .set(webhooks::consecutive_failures.eq(webhooks::consecutive_failures + 1))
+                    .returning(webhooks::consecutive_failures)
+                    .get_result::<i32>(connection)?;
+
+            if failures >= Webhook::DISABLE_AFTER_FAILURES {
+                tracing::warn!(
+                    "Disabling webhook {} after {failures} consecutive failed deliveries",
+                    delivery.webhook_id
+                );
+                diesel::update(webhooks::table.filter(webhooks::id.eq(delivery.webhook_id)))
+                    .set((webhooks::enabled.eq(false), webhooks::updated_at.eq(now)))
+                    .execute(connection)?;
+            }
+
+            Ok(updated)
+        })
+    }
+}
+
+impl WebhookDeliveryPage {
+    pub fn find<const D: i64>(
+        conn: &mut DbConnection,
+        webhook_id: Uuid,
+        page_query: PageQuery<D>,
+    ) -> Result<Paginated<Self>, ApiError> {
+        // make sure the webhook exists, so that unknown ids return a 404 instead of an empty page
+        webhooks::table
+            .filter(webhooks::id.eq(webhook_id))
+            .select(webhooks::id)
+            .first::<Uuid>(conn)?;
+
+        let total = webhook_deliveries::table
+            .filter(webhook_deliveries::webhook_id.eq(webhook_id))
+            .count()
+            .get_result::<i64>(conn)?;
+
+        let deliveries = webhook_deliveries::table
+            .filter(webhook_deliveries::webhook_id.eq(webhook_id))
+            .order(webhook_deliveries::created_at.desc())
+            .limit(page_query.per_page())
+            .offset(page_query.offset())
+            .select(WebhookDelivery::as_select())
+            .load::<WebhookDelivery>(conn)?;
+
+        Ok(Paginated::from_data(
+            page_query,
+            total,
+            WebhookDeliveryPage { data: deliveries },
+        ))
+    }
+}
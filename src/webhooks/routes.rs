@@ -0,0 +1,224 @@
+use crate::{
+    app_data::db::DbAppState,
+    auth::{Authenticated, Permission, UserAuth},
+    error_handler::ApiError,
+    page_helper::{PageQuery, Paginated},
+    webhooks::{
+        Webhook, WebhookCreate, WebhookDelivery, WebhookDeliveryPage, WebhookDeliveryStatus,
+        WebhookPatch, WebhookWithSecret,
+    },
+};
+use actix_web::{delete, get, patch, post, web, HttpResponse};
+use std::sync::Arc;
+use tracing_actix_web::RootSpan;
+use utoipa::OpenApi;
+use uuid::Uuid;
+
+#[utoipa::path(
+    get,
+    summary = "[Staff]List webhooks",
+    description = "Lists every registered outgoing webhook. Secrets are never included.",
+    tag = "Webhooks",
+    responses(
+        (status = 200, body = Vec<Webhook>)
+    ),
+    security(
+        ("access_token" = ["WebhookManage"]),
+        ("api_key" = ["WebhookManage"]),
+    ),
+)]
+#[get("", wrap = "UserAuth::require(Permission::WebhookManage)")]
+async fn find_all_webhooks(db: web::Data<Arc<DbAppState>>) -> Result<HttpResponse, ApiError> {
+    let webhooks = web::block(move || Webhook::find_all(&mut db.connection()?)).await??;
+    Ok(HttpResponse::Ok().json(webhooks))
+}
+
+#[utoipa::path(
+    post,
+    summary = "[Staff]Register a webhook",
+    description = "Registers a new HTTPS endpoint that will receive a POST request for every event of the selected types. The event types are the same as the ones emitted on the notifications websocket, and the body is the same JSON object.\n\nEvery request is signed: the `X-Aredl-Signature` header holds `sha256=` followed by the hex encoded HMAC-SHA256 of `<X-Aredl-Timestamp>.<body>`, keyed with the webhook secret. The secret is only returned by this endpoint and when rotating it.\n\nFailed deliveries are retried with an exponential backoff, and the webhook is disabled automatically after too many consecutive failures.",
+    tag = "Webhooks",
+    request_body = WebhookCreate,
+    responses(
+        (status = 200, body = WebhookWithSecret)
+    ),
+    security(
+        ("access_token" = ["WebhookManage"]),
+        ("api_key" = ["WebhookManage"]),
+    ),
+)]
+#[post("", wrap = "UserAuth::require(Permission::WebhookManage)")]
+async fn create_webhook(
+    db: web::Data<Arc<DbAppState>>,
+    body: web::Json<WebhookCreate>,
+    authenticated: Authenticated,
+    root_span: RootSpan,
+) -> Result<HttpResponse, ApiError> {
+    root_span.record("body", tracing::field::debug(&body));
+    let webhook = web::block(move || {
+        Webhook::create(
+            &mut db.connection()?,
+            &body.into_inner(),
+            authenticated.user_id,
+        )
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(webhook))
+}
+
+#[utoipa::path(
+    patch,
+    summary = "[Staff]Edit a webhook",
+    description = "Edits a webhook URL or event types, or enables/disables it.",
+    tag = "Webhooks",
+    request_body = WebhookPatch,
+    params(
+        ("id" = Uuid, description = "The internal UUID of the webhook"),
+    ),
+    responses(
+        (status = 200, body = Webhook)
+    ),
+    security(
+        ("access_token" = ["WebhookManage"]),
+        ("api_key" = ["WebhookManage"]),
+    ),
+)]
+#[patch("/{id}", wrap = "UserAuth::require(Permission::WebhookManage)")]
+async fn patch_webhook(
+    db: web::Data<Arc<DbAppState>>,
+    id: web::Path<Uuid>,
+    body: web::Json<WebhookPatch>,
+    root_span: RootSpan,
+) -> Result<HttpResponse, ApiError> {
+    root_span.record("body", tracing::field::debug(&body));
+    let webhook = web::block(move || {
+        Webhook::patch(&mut db.connection()?, id.into_inner(), &body.into_inner())
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(webhook))
+}
+
+#[utoipa::path(
+    delete,
+    summary = "[Staff]Delete a webhook",
+    description = "Deletes a webhook along with its delivery log.",
+    tag = "Webhooks",
+    params(
+        ("id" = Uuid, description = "The internal UUID of the webhook"),
+    ),
+    responses(
+        (status = 200, body = Webhook)
+    ),
+    security(
+        ("access_token" = ["WebhookManage"]),
+        ("api_key" = ["WebhookManage"]),
+    ),
+)]
+#[delete("/{id}", wrap = "UserAuth::require(Permission::WebhookManage)")]
+async fn delete_webhook(
+    db: web::Data<Arc<DbAppState>>,
+    id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let webhook =
+        web::block(move || Webhook::delete(&mut db.connection()?, id.into_inner())).await??;
+    Ok(HttpResponse::Ok().json(webhook))
+}
+
+#[utoipa::path(
+    post,
+    summary = "[Staff]Rotate a webhook secret",
+    description = "Generates a new signing secret for a webhook. The previous secret stops being used immediately.",
+    tag = "Webhooks",
+    params(
+        ("id" = Uuid, description = "The internal UUID of the webhook"),
+    ),
+    responses(
+        (status = 200, body = WebhookWithSecret)
+    ),
+    security(
+        ("access_token" = ["WebhookManage"]),
+        ("api_key" = ["WebhookManage"]),
+    ),
+)]
+#[post("/{id}/secret", wrap = "UserAuth::require(Permission::WebhookManage)")]
+async fn rotate_webhook_secret(
+    db: web::Data<Arc<DbAppState>>,
+    id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let webhook =
+        web::block(move || Webhook::rotate_secret(&mut db.connection()?, id.into_inner()))
+            .await??;
+    Ok(HttpResponse::Ok().json(webhook))
+}
+
+#[utoipa::path(
+    get,
+    summary = "[Staff]List webhook deliveries",
+    description = "Paginated delivery log of a webhook, most recent first.",
+    tag = "Webhooks",
+    params(
+        ("id" = Uuid, description = "The internal UUID of the webhook"),
+        ("page" = Option<i64>, Query, description = "The page number to fetch"),
+        ("per_page" = Option<i64>, Query, description = "The number of items per page"),
+    ),
+    responses(
+        (status = 200, body = Paginated<WebhookDeliveryPage>)
+    ),
+    security(
+        ("access_token" = ["WebhookManage"]),
+        ("api_key" = ["WebhookManage"]),
+    ),
+)]
+#[get(
+    "/{id}/deliveries",
+    wrap = "UserAuth::require(Permission::WebhookManage)"
+)]
+async fn find_webhook_deliveries(
+    db: web::Data<Arc<DbAppState>>,
+    id: web::Path<Uuid>,
+    page_query: web::Query<PageQuery<50>>,
+) -> Result<HttpResponse, ApiError> {
+    let deliveries = web::block(move || {
+        WebhookDeliveryPage::find(
+            &mut db.connection()?,
+            id.into_inner(),
+            page_query.into_inner(),
+        )
+    })
+    .await??;
+    Ok(HttpResponse::Ok().json(deliveries))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    components(schemas(
+        Webhook,
+        WebhookWithSecret,
+        WebhookCreate,
+        WebhookPatch,
+        WebhookDelivery,
+        WebhookDeliveryStatus,
+        WebhookDeliveryPage
+    )),
+    paths(
+        find_all_webhooks,
+        create_webhook,
+        patch_webhook,
+        delete_webhook,
+        rotate_webhook_secret,
+        find_webhook_deliveries,
+    )
+)]
+pub struct ApiDoc;
+
+pub fn init_routes(config: &mut web::ServiceConfig) {
+    config.service(
+        web::scope("/webhooks")
+            .service(find_all_webhooks)
+            .service(create_webhook)
+            .service(patch_webhook)
+            .service(delete_webhook)
+            .service(rotate_webhook_secret)
+            .service(find_webhook_deliveries),
+    );
+}
@@ -0,0 +1,74 @@
+#[cfg(test)]
+use {
+    crate::{
+        app_data::db::DbAppState,
+        schema::{webhook_deliveries, webhooks},
+        webhooks::{WebhookDelivery, WebhookDeliveryStatus},
+    },
+    diesel::prelude::*,
+    std::sync::Arc,
+    uuid::Uuid,
+};
+
+#[cfg(test)]
+pub const TEST_WEBHOOK_SECRET: &str = "test-webhook-secret";
+
+/// Registers a webhook directly in the database, bypassing the HTTPS requirement so that it can point to a mock server.
+#[cfg(test)]
+pub async fn create_test_webhook(db: &Arc<DbAppState>, url: &str, event_types: &[&str]) -> Uuid {
+    diesel::insert_into(webhooks::table)
+        .values((
+            webhooks::url.eq(url),
+            webhooks::secret.eq(TEST_WEBHOOK_SECRET),
+            webhooks::event_types.eq(event_types
+                .iter()
+                .map(|event_type| Some((*event_type).to_owned()))
+                .collect::<Vec<_>>()),
+        ))
+        .returning(webhooks::id)
+        .get_result::<Uuid>(&mut db.connection().unwrap())
+        .expect("Failed to create test webhook")
+}
+
+#[cfg(test)]
+pub async fn get_test_webhook_deliveries(
+    db: &Arc<DbAppState>,
+    webhook_id: Uuid,
+) -> Vec<WebhookDelivery> {
+    webhook_deliveries::table
+        .filter(webhook_deliveries::webhook_id.eq(webhook_id))
+        .order(webhook_deliveries::created_at.asc())
+        .select(WebhookDelivery::as_select())
+        .load::<WebhookDelivery>(&mut db.connection().unwrap())
+        .expect("Failed to load test webhook deliveries")
+}
+
+/// Makes every pending delivery of a webhook due immediately, as if its retry delay had elapsed.
+#[cfg(test)]
+pub async fn make_test_webhook_deliveries_due(db: &Arc<DbAppState>, webhook_id: Uuid) {
+    diesel::update(
+        webhook_deliveries::table
+            .filter(webhook_deliveries::webhook_id.eq(webhook_id))
+            .filter(webhook_deliveries::status.eq(WebhookDeliveryStatus::Pending)),
+    )
+    .set(webhook_deliveries::next_attempt_at.eq(chrono::Utc::now()))
+    .execute(&mut db.connection().unwrap())
+    .expect("Failed to update test webhook deliveries");
+}
+
+#[cfg(test)]
+pub async fn set_test_webhook_failures(db: &Arc<DbAppState>, webhook_id: Uuid, failures: i32) {
+    diesel::update(webhooks::table.filter(webhooks::id.eq(webhook_id)))
+        .set(webhooks::consecutive_failures.eq(failures))
+        .execute(&mut db.connection().unwrap())
+        .expect("Failed to update test webhook");
+}
+
+#[cfg(test)]
+pub async fn is_test_webhook_enabled(db: &Arc<DbAppState>, webhook_id: Uuid) -> bool {
+    webhooks::table
+        .filter(webhooks::id.eq(webhook_id))
+        .select(webhooks::enabled)
+        .first::<bool>(&mut db.connection().unwrap())
+        .expect("Failed to load test webhook")
+}
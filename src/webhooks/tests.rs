@@ -0,0 +1,408 @@
+#[cfg(test)]
+use {
+    crate::{
+        auth::{create_test_token, Permission},
+        notifications::{NotificationList, WebsocketNotification, WebsocketNotificationType},
+        providers::test_utils::set_token_encryption_env,
+        schema::webhooks,
+        test_utils::*,
+        users::test_utils::create_test_user,
+        webhooks::{
+            deliver_due_webhooks, sign_payload,
+            test_utils::{
+                create_test_webhook, get_test_webhook_deliveries, is_test_webhook_enabled,
+                make_test_webhook_deliveries_due, set_test_webhook_failures, TEST_WEBHOOK_SECRET,
+            },
+            DeliveryClient, Webhook, WebhookDelivery, WebhookDeliveryStatus, DELIVERY_HEADER,
+            EVENT_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+        },
+    },
+    actix_http::StatusCode,
+    actix_web::test::{self, read_body_json},
+    diesel::prelude::*,
+    httpmock::{prelude::*, HttpMockRequest},
+    serde_json::json,
+    serial_test::serial,
+    uuid::Uuid,
+};
+
+#[cfg(test)]
+fn header_value(req: &HttpMockRequest, name: &str) -> Option<String> {
+    req.headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_owned)
+}
+
+#[actix_web::test]
+async fn webhooks_require_permission() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, None).await;
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+
+    let req = test::TestRequest::get()
+        .uri("/webhooks")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+#[serial]
+async fn create_and_manage_webhook() {
+    set_token_encryption_env();
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, Some(Permission::WebhookManage)).await;
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+
+    let req = test::TestRequest::post()
+        .uri("/webhooks")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({
+            "url": "https://example.com/hooks/aredl",
+            "event_types": ["SUBMISSION_ACCEPTED", "SHIFT_COMPLETED", "SUBMISSION_ACCEPTED"]
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let created: serde_json::Value = read_body_json(resp).await;
+    assert_eq!(created["url"], "https://example.com/hooks/aredl");
+    assert_eq!(
+        created["event_types"],
+        json!(["SHIFT_COMPLETED", "SUBMISSION_ACCEPTED"])
+    );
+    assert_eq!(created["enabled"], true);
+    assert_eq!(created["secret"].as_str().unwrap().len(), 64);
+    let webhook_id = created["id"].as_str().unwrap().to_owned();
+
+    // the secret is only stored encrypted
+    let stored = webhooks::table
+        .filter(webhooks::id.eq(Uuid::parse_str(&webhook_id).unwrap()))
+        .select(Webhook::as_select())
+        .first::<Webhook>(&mut db.connection().unwrap())
+        .unwrap();
+    assert_ne!(stored.secret, created["secret"].as_str().unwrap());
+    assert_eq!(
+        stored.signing_secret().unwrap(),
+        created["secret"].as_str().unwrap()
+    );
+
+    let req = test::TestRequest::get()
+        .uri("/webhooks")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let webhooks: serde_json::Value = read_body_json(resp).await;
+    let listed = webhooks
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|webhook| webhook["id"] == webhook_id.as_str())
+        .expect("Created webhook is not listed");
+    assert!(listed.get("secret").is_none());
+
+    let req = test::TestRequest::patch()
+        .uri(&format!("/webhooks/{webhook_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({ "event_types": ["LEVEL_PLACED"], "enabled": false }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let patched: serde_json::Value = read_body_json(resp).await;
+    assert_eq!(patched["event_types"], json!(["LEVEL_PLACED"]));
+    assert_eq!(patched["enabled"], false);
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/webhooks/{webhook_id}/secret"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let rotated: serde_json::Value = read_body_json(resp).await;
+    assert_ne!(rotated["secret"], created["secret"]);
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/webhooks/{webhook_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/webhooks/{webhook_id}/deliveries"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn create_webhook_validation() {
+    let (app, db, auth, _) = init_test_app().await;
+    let (user_id, _) = create_test_user(&db, Some(Permission::WebhookManage)).await;
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+
+    for (body, expected) in [
+        (
+            json!({ "url": "http://example.com/hook", "event_types": ["SUBMISSION_CREATED"] }),
+            StatusCode::BAD_REQUEST,
+        ),
+        (
+            json!({ "url": "not a url", "event_types": ["SUBMISSION_CREATED"] }),
+            StatusCode::BAD_REQUEST,
+        ),
+        (
+            json!({ "url": "https://localhost/hook", "event_types": ["SUBMISSION_CREATED"] }),
+            StatusCode::BAD_REQUEST,
+        ),
+        (
+            json!({ "url": "https://10.0.0.5/hook", "event_types": ["SUBMISSION_CREATED"] }),
+            StatusCode::BAD_REQUEST,
+        ),
+        (
+            json!({ "url": "https://example.com/hook", "event_types": [] }),
+            StatusCode::BAD_REQUEST,
+        ),
+        (
+            json!({ "url": "https://example.com/hook", "event_types": ["NOT_AN_EVENT"] }),
+            StatusCode::BAD_REQUEST,
+        ),
+    ] {
+        let req = test::TestRequest::post()
+            .uri("/webhooks")
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .set_json(&body)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), expected, "body: {body}");
+    }
+}
+
+#[actix_web::test]
+async fn webhook_delivery_is_signed() {
+    let (_app, db, _auth, notify_tx) = init_test_app().await;
+    let server = MockServer::start_async().await;
+    let webhook_id = create_test_webhook(&db, &server.url("/hook"), &["SUBMISSION_ACCEPTED"]).await;
+    let ignored_id = create_test_webhook(&db, &server.url("/ignored"), &["SHIFTS_CREATED"]).await;
+
+    let hook_mock = server
+        .mock_async(|when, then| {
+            when.method(POST)
+                .path("/hook")
+                .header("content-type", "application/json")
+                .header(EVENT_HEADER, "SUBMISSION_ACCEPTED")
+                .header_exists(DELIVERY_HEADER)
+                .is_true(|req| {
+                    let (Some(timestamp), Some(signature)) = (
+                        header_value(req, TIMESTAMP_HEADER),
+                        header_value(req, SIGNATURE_HEADER),
+                    ) else {
+                        return false;
+                    };
+                    let Ok(timestamp) = timestamp.parse::<i64>() else {
+                        return false;
+                    };
+                    let body = String::from_utf8_lossy(req.body_ref());
+                    signature == sign_payload(TEST_WEBHOOK_SECRET, timestamp, &body)
+                        && body.contains("\"notification_type\":\"SUBMISSION_ACCEPTED\"")
+                });
+            then.status(204);
+        })
+        .await;
+
+    WebsocketNotification::send(
+        &mut db.connection().unwrap(),
+        &notify_tx,
+        WebsocketNotificationType::SubmissionAccepted,
+        Some(NotificationList::Aredl),
+        &json!({ "hello": 1 }),
+    );
+
+    assert!(get_test_webhook_deliveries(&db, ignored_id)
+        .await
+        .is_empty());
+    let queued = get_test_webhook_deliveries(&db, webhook_id).await;
+    assert_eq!(queued.len(), 1);
+    assert_eq!(queued[0].status, WebhookDeliveryStatus::Pending);
+
+    let client = DeliveryClient::allowing_internal_hosts();
+    deliver_due_webhooks(&db, &client).await.unwrap();
+
+    hook_mock.assert_async().await;
+    let delivered = get_test_webhook_deliveries(&db, webhook_id).await;
+    assert_eq!(delivered[0].status, WebhookDeliveryStatus::Succeeded);
+    assert_eq!(delivered[0].attempts, 1);
+    assert_eq!(delivered[0].response_status, Some(204));
+
+    // delivered events are not sent again
+    deliver_due_webhooks(&db, &client).await.unwrap();
+    hook_mock.assert_calls_async(1).await;
+}
+
+#[actix_web::test]
+async fn webhook_delivery_retries_with_backoff() {
+    let (app, db, auth, notify_tx) = init_test_app().await;
+    let server = MockServer::start_async().await;
+    let webhook_id = create_test_webhook(&db, &server.url("/hook"), &["SUBMISSION_DENIED"]).await;
+
+    let failing_mock = server
+        .mock_async(|when, then| {
+            when.method(POST).path("/hook");
+            then.status(500);
+        })
+        .await;
+
+    WebsocketNotification::send(
+        &mut db.connection().unwrap(),
+        &notify_tx,
+        WebsocketNotificationType::SubmissionDenied,
+        Some(NotificationList::Arepl),
+        &json!({}),
+    );
+
+    let client = DeliveryClient::allowing_internal_hosts();
+    deliver_due_webhooks(&db, &client).await.unwrap();
+    failing_mock.assert_calls_async(1).await;
+
+    let deliveries = get_test_webhook_deliveries(&db, webhook_id).await;
+    assert_eq!(deliveries[0].status, WebhookDeliveryStatus::Pending);
+    assert_eq!(deliveries[0].attempts, 1);
+    assert_eq!(deliveries[0].response_status, Some(500));
+    assert!(deliveries[0].next_attempt_at > chrono::Utc::now());
+
+    // not retried before the backoff elapsed
+    deliver_due_webhooks(&db, &client).await.unwrap();
+    failing_mock.assert_calls_async(1).await;
+    failing_mock.delete_async().await;
+
+    let ok_mock = server
+        .mock_async(|when, then| {
+            when.method(POST).path("/hook");
+            then.status(200);
+        })
+        .await;
+    make_test_webhook_deliveries_due(&db, webhook_id).await;
+    deliver_due_webhooks(&db, &client).await.unwrap();
+    ok_mock.assert_async().await;
+
+    let deliveries = get_test_webhook_deliveries(&db, webhook_id).await;
+    assert_eq!(deliveries[0].status, WebhookDeliveryStatus::Succeeded);
+    assert_eq!(deliveries[0].attempts, 2);
+    assert_eq!(deliveries[0].error, None);
+
+    let (user_id, _) = create_test_user(&db, Some(Permission::WebhookManage)).await;
+    let token = create_test_token(user_id, &auth.jwt_encoding_key).unwrap();
+    let req = test::TestRequest::get()
+        .uri(&format!("/webhooks/{webhook_id}/deliveries"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status is {}", resp.status());
+    let body: serde_json::Value = read_body_json(resp).await;
+    assert_eq!(body["count"], 1);
+    assert_eq!(body["data"][0]["status"], "Succeeded");
+    assert_eq!(body["data"][0]["attempts"], 2);
+}
+
+#[actix_web::test]
+async fn webhook_disabled_after_repeated_failures() {
+    let (_app, db, _auth, notify_tx) = init_test_app().await;
+    let server = MockServer::start_async().await;
+    let webhook_id = create_test_webhook(&db, &server.url("/hook"), &["SHIFTS_CREATED"]).await;
+    set_test_webhook_failures(&db, webhook_id, Webhook::DISABLE_AFTER_FAILURES - 1).await;
+
+    server
+        .mock_async(|when, then| {
+            when.method(POST).path("/hook");
+            then.status(503);
+        })
+        .await;
+
+    WebsocketNotification::send(
+        &mut db.connection().unwrap(),
+        &notify_tx,
+        WebsocketNotificationType::ShiftsCreated,
+        None,
+        &json!([]),
+    );
+
+    deliver_due_webhooks(&db, &DeliveryClient::allowing_internal_hosts())
+        .await
+        .unwrap();
+    assert!(!is_test_webhook_enabled(&db, webhook_id).await);
+
+    // disabled webhooks do not get new deliveries queued
+    WebsocketNotification::send(
+        &mut db.connection().unwrap(),
+        &notify_tx,
+        WebsocketNotificationType::ShiftsCreated,
+        None,
+        &json!([]),
+    );
+    assert_eq!(get_test_webhook_deliveries(&db, webhook_id).await.len(), 1);
+}
+
+#[actix_web::test]
+async fn webhook_delivery_stays_on_public_hosts() {
+    let (_app, db, _auth, notify_tx) = init_test_app().await;
+    let server = MockServer::start_async().await;
+    let internal_id = create_test_webhook(&db, &server.url("/internal"), &["LEVEL_PLACED"]).await;
+    let redirect_id = create_test_webhook(&db, &server.url("/redirect"), &["LEVEL_REMOVED"]).await;
+
+    let internal_mock = server
+        .mock_async(|when, then| {
+            when.method(POST).path("/internal");
+            then.status(204);
+        })
+        .await;
+    let redirect_mock = server
+        .mock_async(|when, then| {
+            when.method(POST).path("/redirect");
+            then.status(307).header("Location", server.url("/internal"));
+        })
+        .await;
+
+    for notification_type in [
+        WebsocketNotificationType::LevelPlaced,
+        WebsocketNotificationType::LevelRemoved,
+    ] {
+        WebsocketNotification::send(
+            &mut db.connection().unwrap(),
+            &notify_tx,
+            notification_type,
+            Some(NotificationList::Aredl),
+            &json!({}),
+        );
+    }
+
+    // the mock server listens on a loopback address, which webhooks are never sent to
+    deliver_due_webhooks(&db, &DeliveryClient::default())
+        .await
+        .unwrap();
+    internal_mock.assert_calls_async(0).await;
+    redirect_mock.assert_calls_async(0).await;
+    let deliveries = get_test_webhook_deliveries(&db, internal_id).await;
+    assert_eq!(deliveries[0].status, WebhookDeliveryStatus::Pending);
+    assert_eq!(deliveries[0].response_status, None);
+    assert!(deliveries[0].error.is_some());
+
+    // redirects are not followed
+    make_test_webhook_deliveries_due(&db, redirect_id).await;
+    deliver_due_webhooks(&db, &DeliveryClient::allowing_internal_hosts())
+        .await
+        .unwrap();
+    redirect_mock.assert_calls_async(1).await;
+    internal_mock.assert_calls_async(0).await;
+    let deliveries = get_test_webhook_deliveries(&db, redirect_id).await;
+    assert_eq!(deliveries[0].status, WebhookDeliveryStatus::Pending);
+    assert_eq!(deliveries[0].response_status, Some(307));
+}
+
+#[actix_web::test]
+async fn webhook_retry_delay_grows_exponentially() {
+    assert_eq!(WebhookDelivery::retry_delay(1).num_seconds(), 30);
+    assert_eq!(WebhookDelivery::retry_delay(2).num_seconds(), 60);
+    assert_eq!(WebhookDelivery::retry_delay(4).num_seconds(), 240);
+}